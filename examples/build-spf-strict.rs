@@ -2,8 +2,7 @@ use decon_spf::mechanism::{Kind, Mechanism, ParsedMechanism, Qualifier};
 use decon_spf::Spf;
 
 fn main() {
-    let mut spf1 = Spf::new();
-    spf1.set_v1();
+    let mut spf1 = Spf::new_v1();
     let ip_m_1 = ParsedMechanism::new("+ip4:203.32.160.0/24");
     let ip_m_2 = ParsedMechanism::new("+ip4:203.32.166.0/24");
     if let Ok(ip1) = ip_m_1 {
@@ -29,8 +28,7 @@ fn main() {
         "v=spf1 mx ip4:203.32.160.0/24 ip4:203.32.166.0/24"
     );
 
-    let mut spf2 = Spf::new();
-    spf2.set_v1();
+    let mut spf2 = Spf::new_v1();
     let ip = "203.32.166.0/24".parse().unwrap();
     spf2.append_ip_mechanism(Mechanism::ip(Qualifier::Pass, ip));
 
@@ -41,7 +39,7 @@ fn main() {
             spf2.append_mechanism(m);
         }
         Err(e) => {
-            println!("Error creating Mechanism: \"{}\"", e.to_string());
+            println!("Error creating Mechanism: \"{}\"", e);
         }
     };
     println!("Add mx to spf2");
@@ -51,8 +49,7 @@ fn main() {
     spf2.clear_mechanism(Kind::MX);
     println!("Altered spf 2: >{}<", spf2);
 
-    let mut spf3 = Spf::new();
-    spf3.set_v2_pra();
+    let mut spf3 = Spf::new_v2_pra();
     spf3.append_mechanism(Mechanism::a(Qualifier::Pass));
     spf3.append_mechanism(Mechanism::all(Qualifier::Neutral));
 
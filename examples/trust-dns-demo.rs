@@ -22,7 +22,7 @@ fn main() {
 
     let txt_response = resolver.txt_lookup(query);
 
-    let spf_record = display_txt(&query, &txt_response);
+    let spf_record = display_txt(query, &txt_response);
     println!("\nDecontructing SPF Record");
     println!("Debug Output!");
     println!("{:?}", spf_record);
@@ -72,15 +72,13 @@ fn display_txt(query: &str, txt_response: &ResolveResult<TxtLookup>) -> Spf {
     match txt_response {
         Err(_) => println!("No TXT Records."),
         Ok(txt_response) => {
-            let mut i = 1;
             println!("List of TXT records found for {}", &query);
-            for record in txt_response.iter() {
-                println!("TXT Record {}:", i);
+            for (i, record) in txt_response.iter().enumerate() {
+                println!("TXT Record {}:", i + 1);
                 println!("{}", &record.to_string());
                 if record.to_string().starts_with("v=spf1") {
                     spf_record = record.to_string().parse().unwrap();
                 }
-                i = i + 1;
             }
         }
     }
@@ -0,0 +1,186 @@
+//! The `Received-SPF` header which records the result of an evaluation. RFC 7208 section 9.1
+use crate::evaluate::SpfResult;
+use std::net::IpAddr;
+
+/// The result of an evaluation with the details needed to write a `Received-SPF` header.
+/// See RFC 7208 section 9.1
+///
+/// The `problem` key is written for a `temperror` or `permerror` result, giving its reason.
+/// Every other key is only written when it is set.
+///
+/// # Example:
+///```rust
+/// use decon_spf::evaluate::{SpfResult, SpfResultHeader};
+/// let header = SpfResultHeader::new(SpfResult::None, "192.0.2.1".parse().unwrap())
+///     .receiver("mybox.example.org")
+///     .envelope_from("myname@example.com");
+/// assert_eq!(
+///     header.to_received_spf_header(),
+///     "Received-SPF: none (mybox.example.org: domain of myname@example.com does not \
+///      provide an SPF record) receiver=mybox.example.org; client-ip=192.0.2.1; \
+///      envelope-from=\"myname@example.com\";"
+/// );
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpfResultHeader {
+    result: SpfResult,
+    client_ip: IpAddr,
+    envelope_from: Option<String>,
+    helo: Option<String>,
+    receiver: Option<String>,
+    identity: Option<String>,
+    mechanism: Option<String>,
+}
+
+impl SpfResultHeader {
+    /// Create the header for `result`, found for the client `client_ip`.
+    pub fn new(result: SpfResult, client_ip: IpAddr) -> Self {
+        SpfResultHeader {
+            result,
+            client_ip,
+            envelope_from: None,
+            helo: None,
+            receiver: None,
+            identity: None,
+            mechanism: None,
+        }
+    }
+    /// Set the `MAIL FROM` of the message.
+    pub fn envelope_from(mut self, envelope_from: &str) -> Self {
+        self.envelope_from = Some(envelope_from.to_string());
+        self
+    }
+    /// Set the name the client gave in `HELO` or `EHLO`.
+    pub fn helo(mut self, helo: &str) -> Self {
+        self.helo = Some(helo.to_string());
+        self
+    }
+    /// Set the name of the host which performed the check.
+    pub fn receiver(mut self, receiver: &str) -> Self {
+        self.receiver = Some(receiver.to_string());
+        self
+    }
+    /// Set the identity which was checked, `mailfrom` or `helo`.
+    pub fn identity(mut self, identity: &str) -> Self {
+        self.identity = Some(identity.to_string());
+        self
+    }
+    /// Set the mechanism which matched. See [`SpfResult::mechanism()`](SpfResult::mechanism)
+    pub fn mechanism(mut self, mechanism: &str) -> Self {
+        self.mechanism = Some(mechanism.to_string());
+        self
+    }
+    /// Returns the result.
+    pub fn result(&self) -> &SpfResult {
+        &self.result
+    }
+    /// Returns the ip address of the client.
+    pub fn client_ip(&self) -> IpAddr {
+        self.client_ip
+    }
+    /// Write the `Received-SPF` header: the result, a comment explaining it and the
+    /// key-value pairs which are set, in the order `receiver`, `identity`, `client-ip`,
+    /// `envelope-from`, `helo`, `mechanism` and `problem`.
+    ///
+    /// A value which is not a dot-atom is written as a quoted-string. Control characters,
+    /// which may not appear in a header, are left out. The header is written on a single
+    /// line, without folding or the final CRLF.
+    pub fn to_received_spf_header(&self) -> String {
+        let mut header = format!("Received-SPF: {} ({})", self.result, self.comment());
+        let problem = match &self.result {
+            SpfResult::TempError(reason) | SpfResult::PermError(reason) => Some(reason.clone()),
+            _ => None,
+        };
+        let pairs = [
+            ("receiver", self.receiver.clone()),
+            ("identity", self.identity.clone()),
+            ("client-ip", Some(self.client_ip.to_string())),
+            ("envelope-from", self.envelope_from.clone()),
+            ("helo", self.helo.clone()),
+            ("mechanism", self.mechanism.clone()),
+            ("problem", problem),
+        ];
+        for (key, value) in pairs.iter() {
+            if let Some(value) = value {
+                header.push_str(&format!(" {}={};", key, quote(value)));
+            }
+        }
+        header
+    }
+    // The comment of RFC 7208 section 9.1 describing the result, without its parentheses.
+    fn comment(&self) -> String {
+        let receiver = self
+            .receiver
+            .as_ref()
+            .map_or(String::new(), |r| format!("{}: ", r));
+        let sender = self
+            .envelope_from
+            .as_deref()
+            .filter(|sender| !sender.is_empty())
+            .or(self.helo.as_deref());
+        let domain = sender.map_or("the domain".to_string(), |s| format!("domain of {}", s));
+        let ip = self.client_ip;
+        let text = match self.result {
+            SpfResult::Pass(_) => format!("{} designates {} as permitted sender", domain, ip),
+            SpfResult::Fail(_) => {
+                format!("{} does not designate {} as permitted sender", domain, ip)
+            }
+            SpfResult::SoftFail(_) => format!(
+                "{} does not designate {} as permitted sender",
+                domain.replacen("domain of", "domain of transitioning", 1),
+                ip
+            ),
+            SpfResult::Neutral(_) => {
+                format!("{} is neither permitted nor denied by {}", ip, domain)
+            }
+            SpfResult::None => format!("{} does not provide an SPF record", domain),
+            SpfResult::TempError(_) => {
+                format!("temporary error while checking {} for {}", domain, ip)
+            }
+            SpfResult::PermError(_) => {
+                format!("permanent error in the SPF record of {}", domain)
+            }
+        };
+        escape_comment(&format!("{}{}", receiver, text))
+    }
+}
+
+// Characters which may be written in a dot-atom. RFC 5322 section 3.2.3
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c)
+}
+
+fn is_dot_atom(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .split('.')
+            .all(|atom| !atom.is_empty() && atom.chars().all(is_atext))
+}
+
+// Write `value` as a dot-atom when it is one, otherwise as a quoted-string.
+fn quote(value: &str) -> String {
+    if is_dot_atom(value) {
+        return value.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in value.chars().filter(|c| !c.is_control()) {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Escape the characters which may not appear unescaped in a comment. RFC 5322 section 3.2.2
+fn escape_comment(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars().filter(|c| !c.is_control()) {
+        if matches!(c, '(' | ')' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,57 @@
+//! [`SpfResolver`](SpfResolver) for the synchronous resolver of `hickory-resolver`.
+use crate::evaluate::resolver::{LookupError, SpfResolver};
+use hickory_resolver::error::{ResolveError, ResolveErrorKind};
+use hickory_resolver::Resolver;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "hickory-resolver")))]
+impl SpfResolver for Resolver {
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+        answers(self.txt_lookup(name), |records| {
+            records
+                .iter()
+                .map(|txt| {
+                    txt.txt_data()
+                        .iter()
+                        .map(|chunk| String::from_utf8_lossy(chunk))
+                        .collect()
+                })
+                .collect()
+        })
+    }
+    fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+        answers(self.ipv4_lookup(name), |records| {
+            records.iter().map(|a| a.0).collect()
+        })
+    }
+    fn lookup_aaaa(&self, name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+        answers(self.ipv6_lookup(name), |records| {
+            records.iter().map(|aaaa| aaaa.0).collect()
+        })
+    }
+    fn lookup_mx(&self, name: &str) -> Result<Vec<String>, LookupError> {
+        answers(self.mx_lookup(name), |records| {
+            records.iter().map(|mx| mx.exchange().to_utf8()).collect()
+        })
+    }
+    fn lookup_ptr(&self, ip: IpAddr) -> Result<Vec<String>, LookupError> {
+        answers(self.reverse_lookup(ip), |records| {
+            records.iter().map(|ptr| ptr.to_utf8()).collect()
+        })
+    }
+}
+
+// The records of a lookup. A name which does not exist, or has no records of the type, has
+// none.
+fn answers<L, T>(
+    lookup: Result<L, ResolveError>,
+    records: impl FnOnce(L) -> Vec<T>,
+) -> Result<Vec<T>, LookupError> {
+    match lookup {
+        Ok(lookup) => Ok(records(lookup)),
+        Err(err) => match err.kind() {
+            ResolveErrorKind::NoRecordsFound { .. } => Ok(vec![]),
+            _ => Err(LookupError::new(&err.to_string())),
+        },
+    }
+}
@@ -0,0 +1,80 @@
+//! The lookups made by the evaluator, whether the resolver is synchronous or not.
+#[cfg(feature = "async")]
+use crate::evaluate::resolver::AsyncSpfResolver;
+use crate::evaluate::resolver::{LookupError, SpfResolver};
+use std::future::{ready, Future};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+pub(crate) type LookupFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<Vec<T>, LookupError>> + Send + 'a>>;
+
+pub(crate) trait Lookups: Sync {
+    fn txt<'a>(&'a self, name: &'a str) -> LookupFuture<'a, String>;
+    fn a<'a>(&'a self, name: &'a str) -> LookupFuture<'a, Ipv4Addr>;
+    fn aaaa<'a>(&'a self, name: &'a str) -> LookupFuture<'a, Ipv6Addr>;
+    fn mx<'a>(&'a self, name: &'a str) -> LookupFuture<'a, String>;
+    fn ptr<'a>(&'a self, ip: IpAddr) -> LookupFuture<'a, String>;
+}
+
+// The lookups of an `SpfResolver`, which are complete before their future is polled.
+pub(crate) struct Blocking<'r>(pub(crate) &'r dyn SpfResolver);
+
+impl Lookups for Blocking<'_> {
+    fn txt<'a>(&'a self, name: &'a str) -> LookupFuture<'a, String> {
+        Box::pin(ready(self.0.lookup_txt(name)))
+    }
+    fn a<'a>(&'a self, name: &'a str) -> LookupFuture<'a, Ipv4Addr> {
+        Box::pin(ready(self.0.lookup_a(name)))
+    }
+    fn aaaa<'a>(&'a self, name: &'a str) -> LookupFuture<'a, Ipv6Addr> {
+        Box::pin(ready(self.0.lookup_aaaa(name)))
+    }
+    fn mx<'a>(&'a self, name: &'a str) -> LookupFuture<'a, String> {
+        Box::pin(ready(self.0.lookup_mx(name)))
+    }
+    fn ptr<'a>(&'a self, ip: IpAddr) -> LookupFuture<'a, String> {
+        Box::pin(ready(self.0.lookup_ptr(ip)))
+    }
+}
+
+#[cfg(feature = "async")]
+pub(crate) struct NonBlocking<'r>(pub(crate) &'r dyn AsyncSpfResolver);
+
+#[cfg(feature = "async")]
+impl Lookups for NonBlocking<'_> {
+    fn txt<'a>(&'a self, name: &'a str) -> LookupFuture<'a, String> {
+        self.0.lookup_txt(name)
+    }
+    fn a<'a>(&'a self, name: &'a str) -> LookupFuture<'a, Ipv4Addr> {
+        self.0.lookup_a(name)
+    }
+    fn aaaa<'a>(&'a self, name: &'a str) -> LookupFuture<'a, Ipv6Addr> {
+        self.0.lookup_aaaa(name)
+    }
+    fn mx<'a>(&'a self, name: &'a str) -> LookupFuture<'a, String> {
+        self.0.lookup_mx(name)
+    }
+    fn ptr<'a>(&'a self, ip: IpAddr) -> LookupFuture<'a, String> {
+        self.0.lookup_ptr(ip)
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+// Run an evaluation using `Blocking` lookups. Nothing it awaits is pending, so it completes
+// on the first poll.
+pub(crate) fn complete<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut context = Context::from_waker(&waker);
+    match Box::pin(future).as_mut().poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("a blocking lookup was pending"),
+    }
+}
@@ -0,0 +1,432 @@
+//! Evaluation of Spf records, answering whether a client may send mail for a domain.
+//! See RFC 7208 sections 4 and 5.
+//!
+//! DNS lookups are made through an [`SpfResolver`](SpfResolver) supplied by the caller. This
+//! crate does not make DNS lookups itself. With the `async` feature, an
+//! [`AsyncSpfResolver`](AsyncSpfResolver) can be used with
+//! [`check_host_async()`](check_host_async) instead.
+mod header;
+#[cfg(feature = "hickory-resolver")]
+mod hickory;
+mod lookups;
+mod resolver;
+mod result;
+mod tests;
+
+pub use crate::evaluate::header::SpfResultHeader;
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use crate::evaluate::resolver::AsyncSpfResolver;
+pub use crate::evaluate::resolver::{LookupError, SpfResolver};
+pub use crate::evaluate::result::SpfResult;
+/// The attribute used to implement [`AsyncSpfResolver`](AsyncSpfResolver).
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use async_trait::async_trait;
+
+#[cfg(feature = "async")]
+use crate::evaluate::lookups::NonBlocking;
+use crate::evaluate::lookups::{complete, Blocking, Lookups};
+use crate::mechanism::{Kind, MacroContext, Mechanism, ParsedMechanism};
+use crate::spf::{ParseOptions, Spf};
+use ipnetwork::IpNetwork;
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+
+/// The most DNS lookups an evaluation may cause. RFC 7208 section 4.6.4
+const MAX_LOOKUPS: usize = 10;
+/// The most lookups which may find no records. RFC 7208 section 4.6.4
+const MAX_VOID_LOOKUPS: usize = 2;
+/// The most host names of an `mx` or `ptr` which are used. RFC 7208 section 4.6.4
+const MAX_HOST_NAMES: usize = 10;
+
+/// Evaluate the Spf record of the sender for a message received from the client `ip`.
+/// This is the `check_host()` function of RFC 7208 section 4.
+///
+/// The domain checked is that of `mail_from`. An empty `mail_from` is taken to be
+/// `postmaster@<helo>`, which checks the domain of `helo`.
+///
+/// The mechanisms are evaluated in the order they are written in the record, not the
+/// order of [`Spf::iter()`](crate::Spf::iter). Domain-specs have their macros expanded.
+/// `exp=` is not evaluated.
+///
+/// # Example:
+///```rust
+/// use decon_spf::evaluate::{check_host, LookupError, SpfResolver, SpfResult};
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+///
+/// struct Resolver;
+///
+/// impl SpfResolver for Resolver {
+///     fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+///         match name {
+///             "example.com" => Ok(vec!["v=spf1 -ip4:192.0.2.13 ip4:192.0.2.0/24 ~all".into()]),
+///             _ => Ok(vec![]),
+///         }
+///     }
+///     fn lookup_a(&self, _name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     fn lookup_aaaa(&self, _name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     fn lookup_mx(&self, _name: &str) -> Result<Vec<String>, LookupError> {
+///         Ok(vec![])
+///     }
+///     fn lookup_ptr(&self, _ip: IpAddr) -> Result<Vec<String>, LookupError> {
+///         Ok(vec![])
+///     }
+/// }
+///
+/// let ip = "192.0.2.10".parse().unwrap();
+/// let result = check_host(&Resolver, ip, "mail.example.com", "user@example.com");
+/// assert_eq!(result.is_pass(), true);
+/// assert_eq!(result.mechanism().unwrap().to_string(), "ip4:192.0.2.0/24");
+///
+/// let ip = "192.0.2.13".parse().unwrap();
+/// let result = check_host(&Resolver, ip, "mail.example.com", "user@example.com");
+/// assert_eq!(result.to_string(), "fail");
+///
+/// let ip = "198.51.100.1".parse().unwrap();
+/// assert_eq!(check_host(&Resolver, ip, "", "user@example.com").is_softfail(), true);
+/// assert_eq!(check_host(&Resolver, ip, "", "user@example.org"), SpfResult::None);
+///```
+pub fn check_host(
+    resolver: &dyn SpfResolver,
+    ip: IpAddr,
+    helo: &str,
+    mail_from: &str,
+) -> SpfResult {
+    complete(evaluate(&Blocking(resolver), ip, helo, mail_from))
+}
+
+/// Evaluate the Spf record of the sender for a message received from the client `ip`, making
+/// DNS lookups through an [`AsyncSpfResolver`](AsyncSpfResolver). This is the same evaluation
+/// as [`check_host()`](check_host).
+///
+/// Lookups are made one at a time, in the order the record is evaluated, as whether a later
+/// mechanism is looked up at all depends on the result of the earlier ones.
+///
+/// # Example:
+///```rust
+/// use decon_spf::evaluate::{async_trait, check_host_async, AsyncSpfResolver, LookupError};
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+///
+/// struct Resolver;
+///
+/// #[async_trait]
+/// impl AsyncSpfResolver for Resolver {
+///     async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+///         match name {
+///             "example.com" => Ok(vec!["v=spf1 ip4:192.0.2.0/24 -all".into()]),
+///             _ => Ok(vec![]),
+///         }
+///     }
+///     async fn lookup_a(&self, _name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     async fn lookup_aaaa(&self, _name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     async fn lookup_mx(&self, _name: &str) -> Result<Vec<String>, LookupError> {
+///         Ok(vec![])
+///     }
+///     async fn lookup_ptr(&self, _ip: IpAddr) -> Result<Vec<String>, LookupError> {
+///         Ok(vec![])
+///     }
+/// }
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let ip = "192.0.2.10".parse().unwrap();
+/// let result = runtime.block_on(check_host_async(&Resolver, ip, "", "user@example.com"));
+/// assert_eq!(result.is_pass(), true);
+///```
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn check_host_async(
+    resolver: &dyn AsyncSpfResolver,
+    ip: IpAddr,
+    helo: &str,
+    mail_from: &str,
+) -> SpfResult {
+    evaluate(&NonBlocking(resolver), ip, helo, mail_from).await
+}
+
+async fn evaluate(lookups: &dyn Lookups, ip: IpAddr, helo: &str, mail_from: &str) -> SpfResult {
+    // An IPv4-mapped ip6 address is checked as the ip4 address it holds.
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    };
+    let context = MacroContext::new(mail_from, ip, helo);
+    let domain = context.sender_domain().to_string();
+    let mut evaluator = Evaluator {
+        resolver: lookups,
+        ip,
+        context,
+        lookups: 0,
+        void_lookups: 0,
+    };
+    evaluator.check_host(&domain).await
+}
+
+struct Evaluator<'a> {
+    resolver: &'a dyn Lookups,
+    ip: IpAddr,
+    context: MacroContext,
+    lookups: usize,
+    void_lookups: usize,
+}
+
+// The mechanism matched, did not match, or evaluation stopped with the result.
+type Matched = Result<bool, SpfResult>;
+
+// The result of an evaluation which has not finished. It is boxed as `include:` and
+// `redirect=` evaluate another record.
+type Evaluation<'b> = Pin<Box<dyn Future<Output = SpfResult> + Send + 'b>>;
+
+impl Evaluator<'_> {
+    fn check_host<'b>(&'b mut self, domain: &'b str) -> Evaluation<'b> {
+        Box::pin(async move { self.check_record(domain).await })
+    }
+    async fn check_record(&mut self, domain: &str) -> SpfResult {
+        if !is_valid_domain(domain) {
+            return SpfResult::None;
+        }
+        let record = match self.record(domain).await {
+            Ok(Some(record)) => record,
+            Ok(None) => return SpfResult::None,
+            Err(result) => return result,
+        };
+        let context = self.context.clone().domain(domain);
+        let mut terms = Vec::new();
+        for term in record.source().split_whitespace().skip(1) {
+            match term.parse::<ParsedMechanism>() {
+                Ok(mechanism) => terms.push(mechanism),
+                // An unknown modifier is ignored.
+                Err(_) if is_modifier(term) => {}
+                Err(e) => return SpfResult::PermError(e.to_string()),
+            }
+        }
+        let (modifiers, mechanisms): (Vec<_>, Vec<_>) =
+            terms.into_iter().partition(|m| m.kind().is_modifier());
+        for kind in [Kind::Redirect, Kind::Exp] {
+            if modifiers.iter().filter(|m| m.kind() == &kind).count() > 1 {
+                let reason = format!("{} may occur only once.", kind.as_str());
+                return SpfResult::PermError(reason);
+            }
+        }
+        for mechanism in mechanisms {
+            match self.matches(&mechanism, &context).await {
+                Ok(true) => return SpfResult::matched(mechanism),
+                Ok(false) => {}
+                Err(result) => return result,
+            }
+        }
+        let redirect = modifiers.into_iter().find_map(|m| match m {
+            ParsedMechanism::TXT(m) if m.kind().is_redirect() => Some(m),
+            _ => None,
+        });
+        match redirect {
+            Some(redirect) => self.redirect(&redirect, &context).await,
+            None => SpfResult::Neutral(None),
+        }
+    }
+    // The single Spf record of `domain`.
+    async fn record(&mut self, domain: &str) -> Result<Option<Spf>, SpfResult> {
+        let records = self
+            .resolver
+            .txt(domain)
+            .await
+            .map_err(|e| SpfResult::TempError(e.to_string()))?;
+        let mut records = records.iter().filter(|record| is_spf1(record));
+        let record = match (records.next(), records.next()) {
+            (None, _) => return Ok(None),
+            (Some(record), None) => record,
+            (Some(_), Some(_)) => {
+                let reason = format!("{} has more than one Spf record.", domain);
+                return Err(SpfResult::PermError(reason));
+            }
+        };
+        // Only a syntax error is a PermError. Terms after the first `all` are valid, but are
+        // never evaluated. Unknown terms are rejected as each term is parsed in `check_record`.
+        let options = ParseOptions::new();
+        Spf::parse(record, &options, usize::MAX)
+            .map(Some)
+            .map_err(|e| SpfResult::PermError(e.to_string()))
+    }
+    async fn matches(&mut self, mechanism: &ParsedMechanism, context: &MacroContext) -> Matched {
+        let mechanism = match mechanism {
+            ParsedMechanism::IP(network) => return Ok(network.as_network().contains(self.ip)),
+            ParsedMechanism::TXT(mechanism) => mechanism,
+        };
+        if mechanism.kind().is_all() {
+            return Ok(true);
+        }
+        self.count_lookup()?;
+        let target = expand(mechanism, context)?;
+        match mechanism.kind() {
+            Kind::Include => match self.check_host(&target).await {
+                SpfResult::Pass(_) => Ok(true),
+                SpfResult::Fail(_) | SpfResult::SoftFail(_) | SpfResult::Neutral(_) => Ok(false),
+                SpfResult::None => Err(SpfResult::PermError(format!(
+                    "{} has no Spf record to include.",
+                    target
+                ))),
+                result => Err(result),
+            },
+            Kind::A => {
+                let addresses = self.addresses(&target).await?;
+                self.void_if_empty(&addresses)?;
+                Ok(self.in_cidr(mechanism, &addresses))
+            }
+            Kind::MX => {
+                let hosts = self
+                    .resolver
+                    .mx(&target)
+                    .await
+                    .map_err(|e| SpfResult::TempError(e.to_string()))?;
+                self.void_if_empty(&hosts)?;
+                if hosts.len() > MAX_HOST_NAMES {
+                    let reason = format!("{} has more than 10 mail exchangers.", target);
+                    return Err(SpfResult::PermError(reason));
+                }
+                for host in hosts {
+                    if self.in_cidr(mechanism, &self.addresses(&host).await?) {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Kind::Ptr => Ok(self.validated_names(&target).await),
+            Kind::Exists => {
+                let addresses = self
+                    .resolver
+                    .a(&target)
+                    .await
+                    .map_err(|e| SpfResult::TempError(e.to_string()))?;
+                self.void_if_empty(&addresses)?;
+                Ok(!addresses.is_empty())
+            }
+            _ => Ok(false),
+        }
+    }
+    async fn redirect(
+        &mut self,
+        redirect: &Mechanism<String>,
+        context: &MacroContext,
+    ) -> SpfResult {
+        if let Err(result) = self.count_lookup() {
+            return result;
+        }
+        let target = match expand(redirect, context) {
+            Ok(target) => target,
+            Err(result) => return result,
+        };
+        match self.check_host(&target).await {
+            SpfResult::None => {
+                SpfResult::PermError(format!("{} has no Spf record to redirect to.", target))
+            }
+            result => result,
+        }
+    }
+    fn count_lookup(&mut self) -> Result<(), SpfResult> {
+        self.lookups += 1;
+        if self.lookups > MAX_LOOKUPS {
+            return Err(SpfResult::PermError("Too many DNS lookups.".to_string()));
+        }
+        Ok(())
+    }
+    fn void_if_empty<T>(&mut self, records: &[T]) -> Result<(), SpfResult> {
+        if records.is_empty() {
+            self.void_lookups += 1;
+            if self.void_lookups > MAX_VOID_LOOKUPS {
+                return Err(SpfResult::PermError(
+                    "Too many void DNS lookups.".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+    // The addresses of `name` in the family of the client ip.
+    async fn addresses(&self, name: &str) -> Result<Vec<IpAddr>, SpfResult> {
+        let addresses = match self.ip {
+            IpAddr::V4(_) => self
+                .resolver
+                .a(name)
+                .await
+                .map(|a| a.into_iter().map(IpAddr::V4).collect()),
+            IpAddr::V6(_) => self
+                .resolver
+                .aaaa(name)
+                .await
+                .map(|aaaa| aaaa.into_iter().map(IpAddr::V6).collect()),
+        };
+        addresses.map_err(|e| SpfResult::TempError(e.to_string()))
+    }
+    // `true` if the client ip is within the cidr length of `a` or `mx` of an address.
+    fn in_cidr(&self, mechanism: &Mechanism<String>, addresses: &[IpAddr]) -> bool {
+        let (ip4, ip6) = mechanism.cidr().unwrap_or_default();
+        let prefix = match self.ip {
+            IpAddr::V4(_) => ip4.unwrap_or(32),
+            IpAddr::V6(_) => ip6.unwrap_or(128),
+        };
+        addresses.iter().any(|address| {
+            IpNetwork::new(*address, prefix).is_ok_and(|network| network.contains(self.ip))
+        })
+    }
+    // `true` if a host name of the client ip, whose addresses include the client ip, is
+    // `target` or a subdomain of it. A failed lookup does not match. RFC 7208 section 5.5
+    async fn validated_names(&self, target: &str) -> bool {
+        let names = self.resolver.ptr(self.ip).await.unwrap_or_default();
+        let subdomain = format!(".{}", target.to_ascii_lowercase());
+        for name in names.iter().take(MAX_HOST_NAMES) {
+            let name = name.trim_end_matches('.').to_ascii_lowercase();
+            if !(name == target.to_ascii_lowercase() || name.ends_with(&subdomain)) {
+                continue;
+            }
+            let addresses = self.addresses(&name).await;
+            if addresses.is_ok_and(|addresses| addresses.contains(&self.ip)) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// The domain name a mechanism refers to.
+fn expand(mechanism: &Mechanism<String>, context: &MacroContext) -> Result<String, SpfResult> {
+    mechanism
+        .expand_macros(context)
+        .map_err(|e| SpfResult::PermError(e.to_string()))
+}
+
+// Returns `true` if `record` is an Spf version 1 record. RFC 7208 section 4.5
+pub(crate) fn is_spf1(record: &str) -> bool {
+    let version = record.split(' ').next().unwrap_or_default();
+    version.eq_ignore_ascii_case("v=spf1")
+}
+
+// Returns `true` if `term` has the form of a modifier, `name=value`. RFC 7208 section 12
+fn is_modifier(term: &str) -> bool {
+    match term.split_once('=') {
+        Some((name, _)) => {
+            name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        }
+        None => false,
+    }
+}
+
+// A domain which can not be looked up results in `none`. RFC 7208 section 4.3
+fn is_valid_domain(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.');
+    domain.len() <= 253
+        && domain.contains('.')
+        && domain
+            .split('.')
+            .all(|label| !label.is_empty() && label.len() <= 63)
+}
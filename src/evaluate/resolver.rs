@@ -0,0 +1,137 @@
+//! The DNS lookups needed to evaluate an Spf record.
+#[cfg(feature = "async")]
+use async_trait::async_trait;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Performs the DNS lookups needed by [`check_host()`](crate::evaluate::check_host).
+///
+/// Each lookup returns every record found. An empty `Vec` means that the name does not exist
+/// or has no records of the type. An [`LookupError`](LookupError) means that the lookup
+/// failed, such as a timeout, and results in
+/// [`SpfResult::TempError`](crate::evaluate::SpfResult::TempError).
+///
+/// A resolver applies its own timeouts, returning a `LookupError` when one expires.
+///
+/// # Example:
+///```rust
+/// use decon_spf::evaluate::{LookupError, SpfResolver};
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+///
+/// // A resolver which only knows the Spf record of example.com
+/// struct Fixed;
+///
+/// impl SpfResolver for Fixed {
+///     fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+///         match name {
+///             "example.com" => Ok(vec!["v=spf1 ip4:192.0.2.0/24 -all".to_string()]),
+///             _ => Ok(vec![]),
+///         }
+///     }
+///     fn lookup_a(&self, _name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     fn lookup_aaaa(&self, _name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     fn lookup_mx(&self, _name: &str) -> Result<Vec<String>, LookupError> {
+///         Ok(vec![])
+///     }
+///     fn lookup_ptr(&self, _ip: IpAddr) -> Result<Vec<String>, LookupError> {
+///         Err(LookupError::new("PTR lookups are not supported"))
+///     }
+/// }
+/// assert_eq!(Fixed.lookup_txt("example.com").unwrap().len(), 1);
+///```
+pub trait SpfResolver: Sync {
+    /// Returns the TXT records of `name`. The character-strings of each record are joined
+    /// into a single `String`.
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError>;
+    /// Returns the IPv4 addresses of `name`.
+    fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, LookupError>;
+    /// Returns the IPv6 addresses of `name`.
+    fn lookup_aaaa(&self, name: &str) -> Result<Vec<Ipv6Addr>, LookupError>;
+    /// Returns the host names of the mail exchangers of `name`.
+    fn lookup_mx(&self, name: &str) -> Result<Vec<String>, LookupError>;
+    /// Returns the host names of `ip`.
+    fn lookup_ptr(&self, ip: IpAddr) -> Result<Vec<String>, LookupError>;
+}
+
+/// Performs the DNS lookups needed by
+/// [`check_host_async()`](crate::evaluate::check_host_async). This is the asynchronous form of
+/// [`SpfResolver`](SpfResolver), and its lookups have the same meaning.
+///
+/// Implementations use the [`async_trait`](crate::evaluate::async_trait) attribute.
+///
+/// # Example:
+///```rust
+/// use decon_spf::evaluate::{async_trait, AsyncSpfResolver, LookupError};
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+///
+/// struct Fixed;
+///
+/// #[async_trait]
+/// impl AsyncSpfResolver for Fixed {
+///     async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+///         match name {
+///             "example.com" => Ok(vec!["v=spf1 ip4:192.0.2.0/24 -all".to_string()]),
+///             _ => Ok(vec![]),
+///         }
+///     }
+///     async fn lookup_a(&self, _name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     async fn lookup_aaaa(&self, _name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+///         Ok(vec![])
+///     }
+///     async fn lookup_mx(&self, _name: &str) -> Result<Vec<String>, LookupError> {
+///         Ok(vec![])
+///     }
+///     async fn lookup_ptr(&self, _ip: IpAddr) -> Result<Vec<String>, LookupError> {
+///         Err(LookupError::new("PTR lookups are not supported"))
+///     }
+/// }
+///```
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[async_trait]
+pub trait AsyncSpfResolver: Send + Sync {
+    /// Returns the TXT records of `name`. The character-strings of each record are joined
+    /// into a single `String`.
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError>;
+    /// Returns the IPv4 addresses of `name`.
+    async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, LookupError>;
+    /// Returns the IPv6 addresses of `name`.
+    async fn lookup_aaaa(&self, name: &str) -> Result<Vec<Ipv6Addr>, LookupError>;
+    /// Returns the host names of the mail exchangers of `name`.
+    async fn lookup_mx(&self, name: &str) -> Result<Vec<String>, LookupError>;
+    /// Returns the host names of `ip`.
+    async fn lookup_ptr(&self, ip: IpAddr) -> Result<Vec<String>, LookupError>;
+}
+
+/// A DNS lookup which failed, rather than finding no records. See
+/// [`SpfResolver`](SpfResolver)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupError {
+    message: String,
+}
+
+impl LookupError {
+    /// Create a new error describing why the lookup failed.
+    pub fn new(message: &str) -> Self {
+        LookupError {
+            message: message.to_string(),
+        }
+    }
+    /// Returns why the lookup failed.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for LookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DNS lookup failed: {}", self.message)
+    }
+}
+
+impl std::error::Error for LookupError {}
@@ -0,0 +1,90 @@
+//! The result of evaluating an Spf record.
+use crate::mechanism::{ParsedMechanism, Qualifier};
+
+/// The result of [`check_host()`](crate::evaluate::check_host). See RFC 7208 section 2.6
+///
+/// A result given by a mechanism holds that mechanism. When an `include:` matches, this is
+/// the `include:` itself. When `redirect=` is followed, this is the mechanism of the record
+/// redirected to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpfResult {
+    /// The client is authorised to use the domain.
+    Pass(ParsedMechanism),
+    /// The client is not authorised to use the domain.
+    Fail(ParsedMechanism),
+    /// The client is probably not authorised to use the domain.
+    SoftFail(ParsedMechanism),
+    /// The domain makes no assertion about the client. There is no mechanism when no
+    /// mechanism matched.
+    Neutral(Option<ParsedMechanism>),
+    /// The domain has no Spf record, or is not a valid domain.
+    None,
+    /// A DNS lookup failed. The reason is given.
+    TempError(String),
+    /// The record could not be correctly interpreted. The reason is given.
+    PermError(String),
+}
+
+impl SpfResult {
+    // The result of a mechanism which matched.
+    pub(crate) fn matched(mechanism: ParsedMechanism) -> Self {
+        match mechanism.qualifier() {
+            Qualifier::Pass => SpfResult::Pass(mechanism),
+            Qualifier::Fail => SpfResult::Fail(mechanism),
+            Qualifier::SoftFail => SpfResult::SoftFail(mechanism),
+            Qualifier::Neutral => SpfResult::Neutral(Some(mechanism)),
+        }
+    }
+    /// Returns the mechanism which gave the result, if any.
+    pub fn mechanism(&self) -> Option<&ParsedMechanism> {
+        match self {
+            SpfResult::Pass(m) | SpfResult::Fail(m) | SpfResult::SoftFail(m) => Some(m),
+            SpfResult::Neutral(m) => m.as_ref(),
+            _ => None,
+        }
+    }
+    /// Returns `true` if the result is `pass`
+    pub fn is_pass(&self) -> bool {
+        matches!(self, SpfResult::Pass(_))
+    }
+    /// Returns `true` if the result is `fail`
+    pub fn is_fail(&self) -> bool {
+        matches!(self, SpfResult::Fail(_))
+    }
+    /// Returns `true` if the result is `softfail`
+    pub fn is_softfail(&self) -> bool {
+        matches!(self, SpfResult::SoftFail(_))
+    }
+    /// Returns `true` if the result is `neutral`
+    pub fn is_neutral(&self) -> bool {
+        matches!(self, SpfResult::Neutral(_))
+    }
+    /// Returns `true` if the result is `none`
+    pub fn is_none(&self) -> bool {
+        matches!(self, SpfResult::None)
+    }
+    /// Returns `true` if the result is `temperror`
+    pub fn is_temperror(&self) -> bool {
+        matches!(self, SpfResult::TempError(_))
+    }
+    /// Returns `true` if the result is `permerror`
+    pub fn is_permerror(&self) -> bool {
+        matches!(self, SpfResult::PermError(_))
+    }
+}
+
+/// Writes the name of the result as used in a `Received-SPF` header. e.g. `softfail`
+impl std::fmt::Display for SpfResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SpfResult::Pass(_) => "pass",
+            SpfResult::Fail(_) => "fail",
+            SpfResult::SoftFail(_) => "softfail",
+            SpfResult::Neutral(_) => "neutral",
+            SpfResult::None => "none",
+            SpfResult::TempError(_) => "temperror",
+            SpfResult::PermError(_) => "permerror",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod received {
+
+    use crate::evaluate::tests::MemoryResolver;
+    use crate::evaluate::{check_host, SpfResult, SpfResultHeader};
+    use crate::mechanism::ParsedMechanism;
+
+    // A header as printed in RFC 7208, unfolded onto a single line.
+    fn unfold(header: &str) -> String {
+        header.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+    fn mechanism(s: &str) -> ParsedMechanism {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn rfc_pass_example() {
+        let header = SpfResultHeader::new(
+            SpfResult::Pass(mechanism("ip4:192.0.2.1")),
+            "192.0.2.1".parse().unwrap(),
+        )
+        .receiver("mybox.example.org")
+        .envelope_from("myname@example.com")
+        .helo("foo.example.com");
+        assert_eq!(
+            header.to_received_spf_header(),
+            unfold(
+                "Received-SPF: pass (mybox.example.org: domain of
+                 myname@example.com designates 192.0.2.1 as permitted sender)
+                    receiver=mybox.example.org; client-ip=192.0.2.1;
+                    envelope-from=\"myname@example.com\"; helo=foo.example.com;"
+            )
+        );
+    }
+    #[test]
+    fn rfc_fail_example() {
+        let header = SpfResultHeader::new(
+            SpfResult::Fail(mechanism("-all")),
+            "192.0.2.1".parse().unwrap(),
+        )
+        .receiver("mybox.example.org")
+        .identity("mailfrom")
+        .envelope_from("myname@example.com");
+        assert_eq!(
+            header.to_received_spf_header(),
+            unfold(
+                "Received-SPF: fail (mybox.example.org: domain of
+                                  myname@example.com does not designate
+                                  192.0.2.1 as permitted sender)
+                                  receiver=mybox.example.org; identity=mailfrom;
+                                  client-ip=192.0.2.1; envelope-from=\"myname@example.com\";"
+            )
+        );
+    }
+    #[test]
+    fn each_result() {
+        let ip = "2001:db8::1".parse().unwrap();
+        let header = |result| {
+            SpfResultHeader::new(result, ip)
+                .envelope_from("user@example.com")
+                .to_received_spf_header()
+        };
+        assert_eq!(
+            header(SpfResult::SoftFail(mechanism("~all"))),
+            "Received-SPF: softfail (domain of transitioning user@example.com does not \
+             designate 2001:db8::1 as permitted sender) client-ip=\"2001:db8::1\"; \
+             envelope-from=\"user@example.com\";"
+        );
+        assert_eq!(
+            header(SpfResult::Neutral(None)),
+            "Received-SPF: neutral (2001:db8::1 is neither permitted nor denied by domain of \
+             user@example.com) client-ip=\"2001:db8::1\"; envelope-from=\"user@example.com\";"
+        );
+        assert_eq!(
+            header(SpfResult::TempError("DNS timeout".to_string())),
+            "Received-SPF: temperror (temporary error while checking domain of \
+             user@example.com for 2001:db8::1) client-ip=\"2001:db8::1\"; \
+             envelope-from=\"user@example.com\"; problem=\"DNS timeout\";"
+        );
+        assert_eq!(
+            header(SpfResult::PermError("Too many DNS lookups".to_string())),
+            "Received-SPF: permerror (permanent error in the SPF record of domain of \
+             user@example.com) client-ip=\"2001:db8::1\"; envelope-from=\"user@example.com\"; \
+             problem=\"Too many DNS lookups\";"
+        );
+    }
+    #[test]
+    fn odd_characters() {
+        let header = SpfResultHeader::new(SpfResult::None, "192.0.2.1".parse().unwrap())
+            .envelope_from("\"john (jr) smith\"@example.com\r\n")
+            .helo("[192.0.2.1]");
+        assert_eq!(
+            header.to_received_spf_header(),
+            r#"Received-SPF: none (domain of "john \(jr\) smith"@example.com does not provide an SPF record) client-ip=192.0.2.1; envelope-from="\"john (jr) smith\"@example.com"; helo="[192.0.2.1]";"#
+        );
+        let header = SpfResultHeader::new(SpfResult::None, "192.0.2.1".parse().unwrap())
+            .envelope_from("back\\slash@example.com");
+        assert_eq!(
+            header.to_received_spf_header(),
+            r#"Received-SPF: none (domain of back\\slash@example.com does not provide an SPF record) client-ip=192.0.2.1; envelope-from="back\\slash@example.com";"#
+        );
+    }
+    #[test]
+    fn without_sender() {
+        let header = SpfResultHeader::new(SpfResult::None, "192.0.2.1".parse().unwrap());
+        assert_eq!(
+            header.to_received_spf_header(),
+            "Received-SPF: none (the domain does not provide an SPF record) client-ip=192.0.2.1;"
+        );
+        let header = header.envelope_from("").helo("mail.example.com");
+        assert_eq!(
+            header.to_received_spf_header(),
+            "Received-SPF: none (domain of mail.example.com does not provide an SPF record) \
+             client-ip=192.0.2.1; envelope-from=\"\"; helo=mail.example.com;"
+        );
+    }
+    #[test]
+    fn from_check_host() {
+        let resolver = MemoryResolver::new().txt("example.com", "v=spf1 ip4:192.0.2.0/24 -all");
+        let ip = "192.0.2.10".parse().unwrap();
+        let result = check_host(&resolver, ip, "mail.example.com", "user@example.com");
+        let matched = result.mechanism().unwrap().to_string();
+        let header = SpfResultHeader::new(result, ip)
+            .identity("mailfrom")
+            .envelope_from("user@example.com")
+            .mechanism(&matched);
+        assert_eq!(
+            header.to_received_spf_header(),
+            "Received-SPF: pass (domain of user@example.com designates 192.0.2.10 as \
+             permitted sender) identity=mailfrom; client-ip=192.0.2.10; \
+             envelope-from=\"user@example.com\"; mechanism=\"ip4:192.0.2.0/24\";"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod nested {
+
+    use crate::evaluate::tests::MemoryResolver;
+    use crate::evaluate::{check_host, SpfResult};
+
+    fn check(resolver: &MemoryResolver, ip: &str) -> SpfResult {
+        check_host(resolver, ip.parse().unwrap(), "", "user@example.com")
+    }
+    fn resolver() -> MemoryResolver {
+        MemoryResolver::new()
+            .txt("example.com", "v=spf1 include:_spf.example.net ~all")
+            .txt(
+                "_spf.example.net",
+                "v=spf1 ip4:192.0.2.0/24 -ip4:198.51.100.0/24 ?all",
+            )
+    }
+
+    #[test]
+    fn include_pass() {
+        let result = check(&resolver(), "192.0.2.1");
+        assert_eq!(result.is_pass(), true);
+        assert_eq!(
+            result.mechanism().unwrap().to_string(),
+            "include:_spf.example.net"
+        );
+    }
+    #[test]
+    fn include_not_pass_does_not_match() {
+        // A fail or neutral of the included record only stops the include from matching.
+        assert_eq!(check(&resolver(), "198.51.100.1").is_softfail(), true);
+        assert_eq!(check(&resolver(), "203.0.113.1").is_softfail(), true);
+    }
+    #[test]
+    fn include_qualifier() {
+        let resolver = resolver().txt("example.org", "v=spf1 -include:_spf.example.net");
+        let ip = "192.0.2.1".parse().unwrap();
+        assert_eq!(
+            check_host(&resolver, ip, "", "user@example.org").is_fail(),
+            true
+        );
+    }
+    #[test]
+    fn include_without_record() {
+        let resolver = MemoryResolver::new().txt("example.com", "v=spf1 include:example.net -all");
+        assert_eq!(check(&resolver, "192.0.2.1").is_permerror(), true);
+    }
+    #[test]
+    fn include_temperror() {
+        let resolver = resolver().failing("_spf.example.net");
+        assert_eq!(check(&resolver, "192.0.2.1").is_temperror(), true);
+    }
+    #[test]
+    fn redirect() {
+        let resolver = MemoryResolver::new()
+            .txt(
+                "example.com",
+                "v=spf1 ip4:203.0.113.1 redirect=_spf.example.net",
+            )
+            .txt("_spf.example.net", "v=spf1 ip4:192.0.2.0/24 -all");
+        assert_eq!(check(&resolver, "203.0.113.1").is_pass(), true);
+        let result = check(&resolver, "192.0.2.1");
+        assert_eq!(result.mechanism().unwrap().to_string(), "ip4:192.0.2.0/24");
+        assert_eq!(check(&resolver, "198.51.100.1").is_fail(), true);
+    }
+    #[test]
+    fn redirect_ignored_with_all() {
+        let resolver = MemoryResolver::new()
+            .txt("example.com", "v=spf1 redirect=_spf.example.net ~all")
+            .txt("_spf.example.net", "v=spf1 -all");
+        assert_eq!(check(&resolver, "192.0.2.1").is_softfail(), true);
+    }
+    #[test]
+    fn redirect_without_record() {
+        let resolver = MemoryResolver::new().txt("example.com", "v=spf1 redirect=example.net");
+        assert_eq!(check(&resolver, "192.0.2.1").is_permerror(), true);
+    }
+    #[test]
+    fn include_cycle() {
+        let resolver = MemoryResolver::new()
+            .txt("example.com", "v=spf1 include:example.net -all")
+            .txt("example.net", "v=spf1 include:example.com -all");
+        assert_eq!(check(&resolver, "192.0.2.1").is_permerror(), true);
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod errors {
+
+    use crate::evaluate::tests::MemoryResolver;
+    use crate::evaluate::{check_host, SpfResult};
+
+    fn check(resolver: &MemoryResolver) -> SpfResult {
+        check_host(
+            resolver,
+            "192.0.2.1".parse().unwrap(),
+            "",
+            "user@example.com",
+        )
+    }
+    // A record with `count` `a:` mechanisms, each with an address.
+    fn lookups(count: usize) -> MemoryResolver {
+        let mut record = "v=spf1".to_string();
+        let mut resolver = MemoryResolver::new();
+        for n in 0..count {
+            let name = format!("host{}.example.com", n);
+            record.push_str(&format!(" a:{}", name));
+            resolver = resolver.a(&name, "198.51.100.1");
+        }
+        record.push_str(" -all");
+        resolver.txt("example.com", &record)
+    }
+
+    #[test]
+    fn ten_lookups() {
+        assert_eq!(check(&lookups(10)).is_fail(), true);
+    }
+    #[test]
+    fn eleven_lookups() {
+        assert_eq!(check(&lookups(11)).is_permerror(), true);
+    }
+    #[test]
+    fn lookups_counted_through_include() {
+        let resolver = MemoryResolver::new()
+            .txt("example.com", "v=spf1 include:example.net -all")
+            .txt("example.net", "v=spf1 a a a a a a a a a a -all")
+            .a("example.net", "198.51.100.1");
+        assert_eq!(check(&resolver).is_permerror(), true);
+    }
+    #[test]
+    fn void_lookups() {
+        let resolver =
+            MemoryResolver::new().txt("example.com", "v=spf1 a:a.example.com a:b.example.com -all");
+        assert_eq!(check(&resolver).is_fail(), true);
+        let resolver = MemoryResolver::new().txt(
+            "example.com",
+            "v=spf1 a:a.example.com a:b.example.com exists:c.example.com -all",
+        );
+        assert_eq!(check(&resolver).is_permerror(), true);
+    }
+    #[test]
+    fn ip_and_all_not_counted() {
+        let mut record = "v=spf1".to_string();
+        for n in 1..=20 {
+            record.push_str(&format!(" ip4:198.51.100.{}", n));
+        }
+        record.push_str(" ip4:192.0.2.1 -all");
+        let resolver = MemoryResolver::new().txt("example.com", &record);
+        assert_eq!(check(&resolver).is_pass(), true);
+    }
+    #[test]
+    fn multiple_records() {
+        let resolver = MemoryResolver::new()
+            .txt("example.com", "v=spf1 -all")
+            .txt("example.com", "v=spf1 +all");
+        assert_eq!(check(&resolver).is_permerror(), true);
+    }
+    #[test]
+    fn other_txt_records_ignored() {
+        let resolver = MemoryResolver::new()
+            .txt("example.com", "v=spf10 +all")
+            .txt("example.com", "v=spf1 -all");
+        assert_eq!(check(&resolver).is_fail(), true);
+    }
+    #[test]
+    fn invalid_record() {
+        let resolver = MemoryResolver::new().txt("example.com", "v=spf1 ip4:192.0.2.300 -all");
+        assert_eq!(check(&resolver).is_permerror(), true);
+    }
+    #[test]
+    fn duplicate_redirect() {
+        let resolver = MemoryResolver::new()
+            .txt(
+                "example.com",
+                "v=spf1 +all redirect=a.example.com redirect=b.example.com",
+            )
+            .txt("a.example.com", "v=spf1 -all");
+        assert_eq!(check(&resolver).is_permerror(), true);
+    }
+    #[test]
+    fn txt_lookup_failure() {
+        let resolver = MemoryResolver::new().failing("example.com");
+        assert_eq!(
+            check(&resolver),
+            SpfResult::TempError("DNS lookup failed: timed out".to_string())
+        );
+    }
+    #[test]
+    fn too_many_mx_hosts() {
+        let mut resolver = MemoryResolver::new().txt("example.com", "v=spf1 mx -all");
+        for n in 0..11 {
+            resolver = resolver.mx("example.com", &format!("mx{}.example.com", n));
+        }
+        assert_eq!(check(&resolver).is_permerror(), true);
+    }
+}
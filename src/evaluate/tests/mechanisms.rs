@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod matching {
+
+    use crate::evaluate::tests::MemoryResolver;
+    use crate::evaluate::{check_host, SpfResult};
+
+    // The result of checking `ip` against the record of example.com, and the mechanism which
+    // matched.
+    fn check(resolver: MemoryResolver, ip: &str) -> (String, Option<String>) {
+        let result = check_host(&resolver, ip.parse().unwrap(), "", "user@example.com");
+        (
+            result.to_string(),
+            result.mechanism().map(|m| m.to_string()),
+        )
+    }
+    fn record(record: &str) -> MemoryResolver {
+        MemoryResolver::new().txt("example.com", record)
+    }
+    fn result(name: &str, mechanism: &str) -> (String, Option<String>) {
+        (name.to_string(), Some(mechanism.to_string()))
+    }
+
+    #[test]
+    fn ip4_and_ip6() {
+        let resolver = || record("v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all");
+        assert_eq!(
+            check(resolver(), "192.0.2.1"),
+            result("pass", "ip4:192.0.2.0/24")
+        );
+        assert_eq!(
+            check(resolver(), "2001:db8::1"),
+            result("pass", "ip6:2001:db8::/32")
+        );
+        assert_eq!(check(resolver(), "198.51.100.1"), result("fail", "-all"));
+    }
+    #[test]
+    fn ipv4_mapped_address() {
+        let resolver = record("v=spf1 ip4:192.0.2.0/24 -all");
+        assert_eq!(
+            check(resolver, "::ffff:192.0.2.1"),
+            result("pass", "ip4:192.0.2.0/24")
+        );
+    }
+    #[test]
+    fn qualifiers() {
+        let resolver = || record("v=spf1 -ip4:192.0.2.1 ~ip4:192.0.2.2 ?ip4:192.0.2.3 +all");
+        assert_eq!(
+            check(resolver(), "192.0.2.1"),
+            result("fail", "-ip4:192.0.2.1")
+        );
+        assert_eq!(
+            check(resolver(), "192.0.2.2"),
+            result("softfail", "~ip4:192.0.2.2")
+        );
+        assert_eq!(
+            check(resolver(), "192.0.2.3"),
+            result("neutral", "?ip4:192.0.2.3")
+        );
+        assert_eq!(check(resolver(), "192.0.2.4"), result("pass", "all"));
+    }
+    #[test]
+    fn no_match_is_neutral() {
+        let resolver = record("v=spf1 ip4:192.0.2.0/24");
+        assert_eq!(
+            check(resolver, "198.51.100.1"),
+            ("neutral".to_string(), None)
+        );
+    }
+    #[test]
+    fn source_order() {
+        // iter() would evaluate `a` before `ip4:`. Evaluation follows the record.
+        let resolver = record("v=spf1 -ip4:192.0.2.1 a -all").a("example.com", "192.0.2.1");
+        assert_eq!(
+            check(resolver, "192.0.2.1"),
+            result("fail", "-ip4:192.0.2.1")
+        );
+    }
+    #[test]
+    fn mechanism_after_all() {
+        let resolver = record("v=spf1 -all a").a("example.com", "192.0.2.1");
+        assert_eq!(check(resolver, "192.0.2.1"), result("fail", "-all"));
+    }
+    #[test]
+    fn first_of_two_all() {
+        let resolver = record("v=spf1 ~all -all");
+        assert_eq!(check(resolver, "192.0.2.1"), result("softfail", "~all"));
+    }
+    #[test]
+    fn unknown_term_after_all() {
+        let resolver = record("v=spf1 -all foo:bar");
+        assert_eq!(check(resolver, "192.0.2.1").0, "permerror");
+    }
+    #[test]
+    fn a_with_cidr() {
+        let resolver = || {
+            record("v=spf1 a a:mail.example.com/24 -all")
+                .a("example.com", "192.0.2.10")
+                .a("mail.example.com", "198.51.100.10")
+        };
+        assert_eq!(check(resolver(), "192.0.2.10"), result("pass", "a"));
+        assert_eq!(check(resolver(), "192.0.2.11"), result("fail", "-all"));
+        assert_eq!(
+            check(resolver(), "198.51.100.99"),
+            result("pass", "a:mail.example.com/24")
+        );
+    }
+    #[test]
+    fn a_ipv6() {
+        let resolver = record("v=spf1 a//64 -all").aaaa("example.com", "2001:db8::1");
+        assert_eq!(check(resolver, "2001:db8::ff"), result("pass", "a//64"));
+    }
+    #[test]
+    fn mx() {
+        let resolver = || {
+            record("v=spf1 mx -all")
+                .mx("example.com", "mx1.example.com")
+                .mx("example.com", "mx2.example.com")
+                .a("mx1.example.com", "192.0.2.1")
+                .a("mx2.example.com", "192.0.2.2")
+        };
+        assert_eq!(check(resolver(), "192.0.2.2"), result("pass", "mx"));
+        assert_eq!(check(resolver(), "192.0.2.3"), result("fail", "-all"));
+    }
+    #[test]
+    fn ptr() {
+        let resolver = || {
+            record("v=spf1 ptr -all")
+                .ptr("192.0.2.1", "mail.example.com")
+                .a("mail.example.com", "192.0.2.1")
+                .ptr("192.0.2.2", "mail.example.com")
+                .ptr("192.0.2.3", "mail.example.org")
+                .a("mail.example.org", "192.0.2.3")
+        };
+        assert_eq!(check(resolver(), "192.0.2.1"), result("pass", "ptr"));
+        // The name does not resolve back to the client ip.
+        assert_eq!(check(resolver(), "192.0.2.2"), result("fail", "-all"));
+        assert_eq!(check(resolver(), "192.0.2.3"), result("fail", "-all"));
+    }
+    #[test]
+    #[cfg(not(feature = "strict-dns"))]
+    fn exists_with_macros() {
+        let resolver = || {
+            record("v=spf1 exists:%{ir}.%{l}._spf.%{d} -all")
+                .a("1.2.0.192.user._spf.example.com", "127.0.0.2")
+        };
+        assert_eq!(
+            check(resolver(), "192.0.2.1"),
+            result("pass", "exists:%{ir}.%{l}._spf.%{d}")
+        );
+        assert_eq!(check(resolver(), "192.0.2.2"), result("fail", "-all"));
+    }
+    #[test]
+    fn unknown_modifier_ignored() {
+        let resolver = record("v=spf1 moo=cow ip4:192.0.2.1 -all");
+        assert_eq!(
+            check(resolver, "192.0.2.1"),
+            result("pass", "ip4:192.0.2.1")
+        );
+    }
+    #[test]
+    fn empty_mail_from_uses_helo() {
+        let resolver = MemoryResolver::new()
+            .txt("mail.example.org", "v=spf1 ip4:192.0.2.1 -all")
+            .txt("example.com", "v=spf1 -all");
+        let ip = "192.0.2.1".parse().unwrap();
+        assert_eq!(
+            check_host(&resolver, ip, "mail.example.org", "").is_pass(),
+            true
+        );
+        assert_eq!(
+            check_host(&resolver, ip, "mail.example.org", "user@example.com").is_fail(),
+            true
+        );
+    }
+    #[test]
+    fn no_record() {
+        let resolver = MemoryResolver::new().txt("example.org", "google-site-verification=x");
+        let ip = "192.0.2.1".parse().unwrap();
+        assert_eq!(
+            check_host(&resolver, ip, "", "user@example.org"),
+            SpfResult::None
+        );
+        assert_eq!(
+            check_host(&resolver, ip, "", "user@localhost"),
+            SpfResult::None
+        );
+    }
+}
@@ -0,0 +1,101 @@
+mod header;
+mod include;
+mod limits;
+mod mechanisms;
+mod nonblocking;
+
+#[cfg(test)]
+use crate::evaluate::{LookupError, SpfResolver};
+#[cfg(test)]
+use std::collections::{HashMap, HashSet};
+#[cfg(test)]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+// A resolver which answers from records inserted by the test. A name without records of a
+// type has none. A lookup of a name set to fail returns a `LookupError`.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryResolver {
+    txt: HashMap<String, Vec<String>>,
+    a: HashMap<String, Vec<Ipv4Addr>>,
+    aaaa: HashMap<String, Vec<Ipv6Addr>>,
+    mx: HashMap<String, Vec<String>>,
+    ptr: HashMap<IpAddr, Vec<String>>,
+    failing: HashSet<String>,
+}
+
+#[cfg(test)]
+impl MemoryResolver {
+    pub(crate) fn new() -> Self {
+        MemoryResolver::default()
+    }
+    pub(crate) fn txt(mut self, name: &str, record: &str) -> Self {
+        self.txt
+            .entry(name.to_ascii_lowercase())
+            .or_default()
+            .push(record.to_string());
+        self
+    }
+    pub(crate) fn a(mut self, name: &str, address: &str) -> Self {
+        self.a
+            .entry(name.to_ascii_lowercase())
+            .or_default()
+            .push(address.parse().unwrap());
+        self
+    }
+    pub(crate) fn aaaa(mut self, name: &str, address: &str) -> Self {
+        self.aaaa
+            .entry(name.to_ascii_lowercase())
+            .or_default()
+            .push(address.parse().unwrap());
+        self
+    }
+    pub(crate) fn mx(mut self, name: &str, host: &str) -> Self {
+        self.mx
+            .entry(name.to_ascii_lowercase())
+            .or_default()
+            .push(host.to_string());
+        self
+    }
+    pub(crate) fn ptr(mut self, address: &str, name: &str) -> Self {
+        self.ptr
+            .entry(address.parse().unwrap())
+            .or_default()
+            .push(name.to_string());
+        self
+    }
+    pub(crate) fn failing(mut self, name: &str) -> Self {
+        self.failing.insert(name.to_ascii_lowercase());
+        self
+    }
+    fn get<T: Clone>(
+        &self,
+        records: &HashMap<String, Vec<T>>,
+        name: &str,
+    ) -> Result<Vec<T>, LookupError> {
+        let name = name.trim_end_matches('.').to_ascii_lowercase();
+        if self.failing.contains(&name) {
+            return Err(LookupError::new("timed out"));
+        }
+        Ok(records.get(&name).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+impl SpfResolver for MemoryResolver {
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+        self.get(&self.txt, name)
+    }
+    fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+        self.get(&self.a, name)
+    }
+    fn lookup_aaaa(&self, name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+        self.get(&self.aaaa, name)
+    }
+    fn lookup_mx(&self, name: &str) -> Result<Vec<String>, LookupError> {
+        self.get(&self.mx, name)
+    }
+    fn lookup_ptr(&self, ip: IpAddr) -> Result<Vec<String>, LookupError> {
+        Ok(self.ptr.get(&ip).cloned().unwrap_or_default())
+    }
+}
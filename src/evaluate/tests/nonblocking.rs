@@ -0,0 +1,121 @@
+#[cfg(all(test, feature = "async"))]
+mod asynchronous {
+
+    use crate::evaluate::tests::MemoryResolver;
+    use crate::evaluate::{
+        async_trait, check_host, check_host_async, AsyncSpfResolver, LookupError, SpfResolver,
+    };
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // Answers from a `MemoryResolver`, yielding to the runtime before each answer and counting
+    // the lookups.
+    struct Delayed {
+        records: MemoryResolver,
+        lookups: AtomicUsize,
+    }
+
+    impl Delayed {
+        fn new(records: MemoryResolver) -> Self {
+            Delayed {
+                records,
+                lookups: AtomicUsize::new(0),
+            }
+        }
+        async fn wait(&self) {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[async_trait]
+    impl AsyncSpfResolver for Delayed {
+        async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+            self.wait().await;
+            self.records.lookup_txt(name)
+        }
+        async fn lookup_a(&self, name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+            self.wait().await;
+            self.records.lookup_a(name)
+        }
+        async fn lookup_aaaa(&self, name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+            self.wait().await;
+            self.records.lookup_aaaa(name)
+        }
+        async fn lookup_mx(&self, name: &str) -> Result<Vec<String>, LookupError> {
+            self.wait().await;
+            self.records.lookup_mx(name)
+        }
+        async fn lookup_ptr(&self, ip: IpAddr) -> Result<Vec<String>, LookupError> {
+            self.wait().await;
+            self.records.lookup_ptr(ip)
+        }
+    }
+
+    fn records() -> MemoryResolver {
+        MemoryResolver::new()
+            .txt(
+                "example.com",
+                "v=spf1 -ip4:192.0.2.13 include:_spf.example.net mx redirect=example.org",
+            )
+            .txt("_spf.example.net", "v=spf1 ip4:192.0.2.0/24 -all")
+            .txt("example.org", "v=spf1 ip6:2001:db8::/32 ~all")
+            .mx("example.com", "mx.example.com")
+            .a("mx.example.com", "198.51.100.1")
+            .failing("example.net")
+    }
+
+    #[tokio::test]
+    async fn same_results_as_blocking() {
+        let resolver = Delayed::new(records());
+        for ip in [
+            "192.0.2.13",
+            "192.0.2.1",
+            "198.51.100.1",
+            "2001:db8::1",
+            "203.0.113.1",
+        ] {
+            let ip = ip.parse().unwrap();
+            for sender in ["user@example.com", "user@example.net", "user@example.edu"] {
+                assert_eq!(
+                    check_host_async(&resolver, ip, "", sender).await,
+                    check_host(&resolver.records, ip, "", sender)
+                );
+            }
+        }
+    }
+    #[tokio::test]
+    async fn lookups_made() {
+        let resolver = Delayed::new(records());
+        let ip = "198.51.100.1".parse().unwrap();
+        let result = check_host_async(&resolver, ip, "", "user@example.com").await;
+        assert_eq!(result.is_pass(), true);
+        assert_eq!(result.mechanism().unwrap().to_string(), "mx");
+        // example.com, _spf.example.net, the mx of example.com and the a of mx.example.com
+        assert_eq!(resolver.lookups.load(Ordering::SeqCst), 4);
+    }
+    #[tokio::test]
+    async fn lookup_limit() {
+        let mut record = "v=spf1".to_string();
+        for _ in 0..11 {
+            record.push_str(" a");
+        }
+        let records = MemoryResolver::new()
+            .txt("example.com", &record)
+            .a("example.com", "198.51.100.1");
+        let ip = "192.0.2.1".parse().unwrap();
+        let resolver = Delayed::new(records);
+        let result = check_host_async(&resolver, ip, "", "user@example.com").await;
+        assert_eq!(result.is_permerror(), true);
+    }
+    #[tokio::test]
+    async fn spawned() {
+        let resolver = Arc::new(Delayed::new(records()));
+        let task = tokio::spawn(async move {
+            let ip = "192.0.2.1".parse().unwrap();
+            check_host_async(resolver.as_ref(), ip, "", "user@example.com").await
+        });
+        assert_eq!(task.await.unwrap().is_pass(), true);
+    }
+}
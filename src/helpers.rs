@@ -1,7 +1,8 @@
-use crate::mechanism::{Kind, Mechanism, Qualifier};
+use crate::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
 use ipnetwork::IpNetwork;
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::num::ParseIntError;
 
 // Provide domain host validation check.
@@ -12,18 +13,19 @@ use addr::parse_dns_name;
 pub(crate) const MAX_SPF_STRING_LENGTH: usize = 255;
 
 // List of Regular Expressions used to parse Spf Mechanisms.
-// Note: This Regex has errors. Needs to be reworked.
-// Tends to match any string starting with 'a'
+// `A` and `MX` accept an optional domain followed by an optional dual cidr suffix.
+// a, a/24, a//64, a/24//64, a:example.com, a:example.com/24//64
 pub(crate) const MECHANISM_A_PATTERN: &str =
-    r"(?i)^(?P<qualifier>[+?~-])?a(?:$|[^a-z.])(?P<mechanism>[a-z0-9].*|\d{1,3})?$";
+    r"(?i)^(?P<qualifier>[+?~-])?a(?::(?P<domain>[^/]+))?(?P<cidr>(?:/\d{1,3})?(?://\d{1,3})?)$";
 pub(crate) const MECHANISM_MX_PATTERN: &str =
-    r"(?i)^(?P<qualifier>[+?~-])?mx(?:$|[^a-z.])(?P<mechanism>[a-z0-9].*|\d{1,3})?$";
-pub(crate) const MECHANISM_PTR_PATTERN: &str = r"(?i)^(?P<qualifier>[+?~-])?ptr(?:$|[^a-z./])(?P<mechanism>(?:[[:word:]]+\.)*[[:word:]]+
-?)?$";
+    r"(?i)^(?P<qualifier>[+?~-])?mx(?::(?P<domain>[^/]+))?(?P<cidr>(?:/\d{1,3})?(?://\d{1,3})?)$";
+pub(crate) const MECHANISM_PTR_PATTERN: &str =
+    r"(?i)^(?P<qualifier>[+?~-])?ptr(?::(?P<mechanism>(?:[[:word:]]+\.)*[[:word:]]+\.?))?$";
 pub(crate) const MECHANISM_EXISTS_PATTERN: &str =
     r"(?i)^(?P<qualifier>[+?~-])?exists:(?P<mechanism>(?:%|\w).*)";
 // All Regex is currently not being used.
-pub(crate) const MECHANISM_ALL_PATTERN: &str = r"^(?P<qualifier>[+?~-])?all(?P<mechanism>\s)?$";
+#[allow(dead_code)]
+pub(crate) const MECHANISM_ALL_PATTERN: &str = r"(?i)^(?P<qualifier>[+?~-])?all(?P<mechanism>\s)?$";
 // Create a new mechanism for a matched regular expression.
 pub(crate) fn capture_matches(string: &str, kind: Kind) -> Option<Mechanism<String>> {
     lazy_static! {
@@ -33,6 +35,14 @@ pub(crate) fn capture_matches(string: &str, kind: Kind) -> Option<Mechanism<Stri
         static ref EXISTS_RE: Regex = Regex::new(MECHANISM_EXISTS_PATTERN).unwrap();
         static ref ALL_RE: Regex = Regex::new(MECHANISM_ALL_PATTERN).unwrap();
     }
+    // A domain-spec may end with a single dot but never contains an empty label.
+    if string.contains("..") {
+        return None;
+    }
+    // A domain-spec containing macros is kept as written.
+    if has_macros(string) {
+        return capture_macro_domain_spec(string, kind);
+    }
     let caps = match kind {
         Kind::A => A_RE.captures(string),
         Kind::MX => MX_RE.captures(string),
@@ -52,13 +62,28 @@ pub(crate) fn capture_matches(string: &str, kind: Kind) -> Option<Mechanism<Stri
                 qualifier_char = qualifier.as_str().chars().next().unwrap();
                 qualifier_result = char_to_qualifier(qualifier_char);
             }
-            //if caps.name("mechanism").is_some() {
-            if let Some(mechanism_value) = caps.name("mechanism") {
+            if kind.is_a() || kind.is_mx() {
+                // The regex has already checked the form of the domain and cidr suffix.
+                // Store them together so that the rrdata is reproduced exactly.
+                let mut rrdata = String::new();
+                if let Some(domain) = caps.name("domain") {
+                    rrdata.push_str(domain.as_str());
+                }
+                if let Some(cidr) = caps.name("cidr") {
+                    rrdata.push_str(cidr.as_str());
+                }
+                if rrdata.is_empty() {
+                    mechanism = Mechanism::generic_inclusive(kind, qualifier_result, None);
+                } else {
+                    mechanism = Mechanism::generic_inclusive(kind, qualifier_result, Some(rrdata));
+                }
+            } else if let Some(mechanism_value) = caps.name("mechanism") {
                 let mut new_mechanism: String = String::new();
                 mechanism_string = mechanism_value.as_str().to_string();
                 // Check if we got a match on a number. No more than 3 digits.
                 // Regex matches [ domain, domain/num, num] so we handle the "num" case here
-                if mechanism_string.len() < 4 {
+                // The domain-spec of `exists` is always taken as is.
+                if !kind.is_exists() && mechanism_string.len() < 4 {
                     let num: Result<i32, ParseIntError> = mechanism_string.parse();
                     // Check that we can convert this to an integer. If we can. Its ok.
                     // convert mechanism_string from "num" to "/num"
@@ -86,6 +111,74 @@ pub(crate) fn capture_matches(string: &str, kind: Kind) -> Option<Mechanism<Stri
     }
 }
 
+// Create a new mechanism from a term whose domain-spec contains macros. The value after the
+// `name:` separator is stored verbatim.
+fn capture_macro_domain_spec(string: &str, kind: Kind) -> Option<Mechanism<String>> {
+    let qualifier = return_and_remove_qualifier(string, kind.as_str().chars().next().unwrap()).0;
+    let separator = if kind.is_exists() { "" } else { ":" };
+    let name = format!("{}{}", kind.as_str(), separator);
+    let rrdata = strip_prefix_ignore_case(strip_qualifier(string), &name)?;
+    // A domain-spec starting with `/` could not be told apart from a cidr suffix.
+    if rrdata.is_empty() || (!kind.is_exists() && rrdata.starts_with('/')) {
+        return None;
+    }
+    Some(Mechanism::generic_inclusive(
+        kind,
+        qualifier,
+        Some(rrdata.to_string()),
+    ))
+}
+
+// Returns `true` if `s` contains a macro expansion or escape. e.g. `%{d}`, `%%`
+pub(crate) fn has_macros(s: &str) -> bool {
+    s.contains('%')
+}
+// Returns the part of `s` which follows its last macro expansion. A macro may contain `/` as
+// a delimiter, which must not be mistaken for a cidr suffix. e.g. `%{l/}.example.com/24`
+pub(crate) fn after_macros(s: &str) -> &str {
+    match s.rfind('}') {
+        Some(end) => &s[end + 1..],
+        None => s,
+    }
+}
+#[test]
+fn macros() {
+    assert_eq!(has_macros("%{ir}.%{v}._spf.%{d2}"), true);
+    assert_eq!(has_macros("_spf.example.com"), false);
+    assert_eq!(after_macros("%{l/}.example.com/24"), ".example.com/24");
+    assert_eq!(after_macros("example.com/24"), "example.com/24");
+}
+// Lowercase a domain-spec, leaving the contents of each `%{...}` macro unchanged. The case of
+// a macro letter selects whether its value is url escaped.
+pub(crate) fn lowercase_domain_spec(s: &str) -> String {
+    let mut lowercase = String::with_capacity(s.len());
+    let mut in_macro = false;
+    let mut previous = None;
+    for c in s.chars() {
+        if in_macro {
+            in_macro = c != '}';
+            lowercase.push(c);
+        } else {
+            in_macro = c == '{' && previous == Some('%');
+            lowercase.push(c.to_ascii_lowercase());
+        }
+        previous = Some(c);
+    }
+    lowercase
+}
+#[test]
+fn lowercase_macros_unchanged() {
+    assert_eq!(
+        lowercase_domain_spec("_SPF.Example.COM"),
+        "_spf.example.com"
+    );
+    assert_eq!(
+        lowercase_domain_spec("%{IR}.%{v}._SPF.%{D2}/24"),
+        "%{IR}.%{v}._spf.%{D2}/24"
+    );
+    assert_eq!(lowercase_domain_spec("%%X.%{L}"), "%%x.%{L}");
+}
+
 pub(crate) fn spf_check_whitespace(s: &str) -> bool {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"\s{2,}").unwrap();
@@ -94,6 +187,81 @@ pub(crate) fn spf_check_whitespace(s: &str) -> bool {
     RE.is_match(s) || ENDING_SPACE.is_match(s)
 }
 
+// Returns the byte offset of each run of whitespace which is not a single separator between
+// two terms. This includes leading and trailing whitespace.
+pub(crate) fn extra_whitespace_positions(s: &str) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut run: Option<(usize, usize)> = None;
+    for (i, c) in s.char_indices() {
+        if c.is_whitespace() {
+            match run {
+                Some((start, len)) => run = Some((start, len + 1)),
+                None => run = Some((i, 1)),
+            }
+        } else if let Some((start, len)) = run.take() {
+            if start == 0 || len > 1 {
+                positions.push(start);
+            }
+        }
+    }
+    if let Some((start, _)) = run {
+        positions.push(start);
+    }
+    positions
+}
+#[test]
+fn extra_whitespace() {
+    assert_eq!(
+        extra_whitespace_positions("v=spf1 a -all"),
+        Vec::<usize>::new()
+    );
+    assert_eq!(
+        extra_whitespace_positions(" v=spf1  a -all "),
+        vec![0, 7, 15]
+    );
+    assert_eq!(extra_whitespace_positions("v=spf1 \t a"), vec![6]);
+}
+
+// Returns `true` if `name` conforms to the RFC 7208 modifier name grammar.
+// name = ALPHA *( ALPHA / DIGIT / "-" / "_" / "." )
+pub(crate) fn is_modifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        }
+        _ => false,
+    }
+}
+// Split a `name=value` term into its parts if `name` is a valid modifier name.
+pub(crate) fn split_unknown_modifier(record: &str) -> Option<(&str, &str)> {
+    let (name, value) = record.split_once('=')?;
+    if is_modifier_name(name) {
+        Some((name, value))
+    } else {
+        None
+    }
+}
+#[test]
+fn modifier_name_valid() {
+    assert_eq!(is_modifier_name("t"), true);
+    assert_eq!(is_modifier_name("x-vendor_tag.v2"), true);
+}
+#[test]
+fn modifier_name_invalid() {
+    assert_eq!(is_modifier_name(""), false);
+    assert_eq!(is_modifier_name("1t"), false);
+    assert_eq!(is_modifier_name("-t"), false);
+    assert_eq!(is_modifier_name("include:t"), false);
+}
+#[test]
+fn split_modifier() {
+    assert_eq!(split_unknown_modifier("t=y"), Some(("t", "y")));
+    assert_eq!(split_unknown_modifier("t=a=b"), Some(("t", "a=b")));
+    assert_eq!(split_unknown_modifier("include:a=b"), None);
+    assert_eq!(split_unknown_modifier("a"), None);
+}
+
 pub(crate) fn char_to_qualifier(c: char) -> Qualifier {
     match c {
         '+' => Qualifier::Pass,
@@ -104,6 +272,26 @@ pub(crate) fn char_to_qualifier(c: char) -> Qualifier {
     }
 }
 
+// The length of `value` as written by its `Display`, without keeping the string.
+pub(crate) fn display_len(value: &dyn std::fmt::Display) -> usize {
+    struct Counter(usize);
+    impl std::fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            self.0 += s.len();
+            Ok(())
+        }
+    }
+    let mut counter = Counter(0);
+    // Writing to a `Counter` can not fail.
+    let _ = std::fmt::write(&mut counter, format_args!("{}", value));
+    counter.0
+}
+#[test]
+fn length_of_display() {
+    assert_eq!(display_len(&"v=spf1"), 6);
+    assert_eq!(display_len(&Mechanism::all(Qualifier::Fail)), 4);
+}
+
 // builds a string representation of of the mechanisms stored in the Vec<Mechanism<String>>
 pub(crate) fn build_spf_str(str: &[Mechanism<String>]) -> String {
     let mut partial_spf = String::new();
@@ -131,7 +319,7 @@ pub(crate) fn return_and_remove_qualifier(record: &str, c: char) -> (Qualifier,
     // Returns a tuple of (qualifier, &str)
     // &str will have had the qualifier character removed if it existed. The &str will be unchanged
     // if the qualifier was not present
-    if c != record.chars().next().unwrap() {
+    if !record.chars().next().unwrap().eq_ignore_ascii_case(&c) {
         // qualifier exists. return tuple of qualifier and `record` with qualifier removed.
         (
             char_to_qualifier(record.chars().next().unwrap()),
@@ -182,8 +370,12 @@ pub(crate) fn dns_is_valid(name: &str) -> bool {
     // These can not be and do not need to be tested. They are always valid.
     if name == "a" || name == "mx" || name == "ptr" || name == "all" || name.starts_with('/') {
         true
+    } else if name.contains("..") {
+        // Empty labels are never valid.
+        false
     } else {
-        match parse_dns_name(name) {
+        // A single trailing dot only anchors a fully qualified name.
+        match parse_dns_name(name.strip_suffix('.').unwrap_or(name)) {
             Err(_) => false,
             Ok(dns) => dns.has_known_suffix(),
         }
@@ -214,6 +406,13 @@ fn valid_mx() {
 fn valid_ptr() {
     assert_eq!(dns_is_valid("ptr"), true);
 }
+#[cfg(feature = "warn-dns")]
+#[test]
+fn trailing_dot() {
+    assert_eq!(dns_is_valid("_spf.example.com."), true);
+    assert_eq!(dns_is_valid("example.com.."), false);
+    assert_eq!(dns_is_valid("example..com"), false);
+}
 
 #[test]
 fn return_and_remove_qualifier_no_qualifier() {
@@ -250,6 +449,144 @@ fn return_and_remove_qualifier_neutral() {
     assert_eq!(Qualifier::Neutral, c);
     assert_eq!("prefix", new_str);
 }
+#[test]
+fn return_and_remove_qualifier_uppercase() {
+    let source = "Include:example.com";
+    let (c, new_str) = return_and_remove_qualifier(source, 'i');
+    assert_eq!(Qualifier::Pass, c);
+    assert_eq!(source, new_str);
+}
+// Check if `term` is exactly an Spf version string. `v=spf1` or `spf2.0/<scope>[,<scope>]`
+// Each scope follows the same `name` grammar as a modifier name.
+pub(crate) fn is_version_term(term: &str) -> bool {
+    if term.eq_ignore_ascii_case("v=spf1") {
+        return true;
+    }
+    match strip_prefix_ignore_case(term, "spf2.0/") {
+        Some(scopes) => scopes.split(',').all(is_modifier_name),
+        None => false,
+    }
+}
+#[test]
+fn version_term() {
+    assert_eq!(is_version_term("v=spf1"), true);
+    assert_eq!(is_version_term("V=SPF1"), true);
+    assert_eq!(is_version_term("spf2.0/pra"), true);
+    assert_eq!(is_version_term("spf2.0/mfrom,pra"), true);
+    assert_eq!(is_version_term("include:v=spf1.example.com"), false);
+}
+// Check if `term` was intended as a version but is malformed. e.g. `v=spf10`, `spf1`
+pub(crate) fn is_malformed_version(term: &str) -> bool {
+    !is_version_term(term)
+        && (strip_prefix_ignore_case(term, "v=").is_some()
+            || strip_prefix_ignore_case(term, "spf").is_some())
+}
+#[test]
+fn malformed_version() {
+    assert_eq!(is_malformed_version("v=spf2"), true);
+    assert_eq!(is_malformed_version("v=spf1.0"), true);
+    assert_eq!(is_malformed_version("spf1"), true);
+    assert_eq!(is_malformed_version("SPF2.0/"), true);
+    assert_eq!(is_malformed_version("v=spf1"), false);
+    assert_eq!(is_malformed_version("a"), false);
+}
+#[test]
+fn version_term_near_miss() {
+    assert_eq!(is_version_term("v=spf1a"), false);
+    assert_eq!(is_version_term("v=spf10"), false);
+    assert_eq!(is_version_term("xv=spf1"), false);
+    assert_eq!(is_version_term("spf2.0"), false);
+    assert_eq!(is_version_term("spf2.0/"), false);
+    assert_eq!(is_version_term("spf2.0/pra,"), false);
+    assert_eq!(is_version_term("spf2.0/pra,,mfrom"), false);
+    assert_eq!(is_version_term("spf2.0pra"), false);
+}
+// Returns the kind of modifier if `term` is a `redirect=` or `exp=` modifier with a qualifier.
+pub(crate) fn qualified_modifier(term: &str) -> Option<Kind> {
+    let unqualified = strip_qualifier(term);
+    if unqualified.len() == term.len() {
+        return None;
+    }
+    [Kind::Redirect, Kind::Exp]
+        .iter()
+        .find(|kind| strip_prefix_ignore_case(unqualified, kind.as_str()).is_some())
+        .copied()
+}
+#[test]
+fn qualified_modifiers() {
+    assert_eq!(
+        qualified_modifier("+redirect=_spf.example.com"),
+        Some(Kind::Redirect)
+    );
+    assert_eq!(
+        qualified_modifier("~EXP=explain.example.com"),
+        Some(Kind::Exp)
+    );
+    assert_eq!(qualified_modifier("redirect=_spf.example.com"), None);
+    assert_eq!(qualified_modifier("-include:_spf.example.com"), None);
+}
+// Check if `term` is the `all` mechanism, allowing for a leading qualifier.
+pub(crate) fn is_all_term(term: &str) -> bool {
+    strip_qualifier(term).eq_ignore_ascii_case("all")
+}
+#[test]
+fn all_term() {
+    assert_eq!(is_all_term("all"), true);
+    assert_eq!(is_all_term("?ALL"), true);
+    assert_eq!(is_all_term("ball"), false);
+    assert_eq!(is_all_term("-all:example.com"), false);
+}
+// Check if `term` is an `ip4:` or `ip6:` mechanism, allowing for a leading qualifier.
+pub(crate) fn is_ip_term(term: &str) -> bool {
+    let term = strip_qualifier(term);
+    strip_prefix_ignore_case(term, "ip4:").is_some()
+        || strip_prefix_ignore_case(term, "ip6:").is_some()
+}
+#[test]
+fn ip_term() {
+    assert_eq!(is_ip_term("~ip6:2001:db8::/32"), true);
+    assert_eq!(is_ip_term("IP4:192.0.2.1"), true);
+    assert_eq!(is_ip_term("include:ip4:example.com"), false);
+}
+// Remove a leading qualifier character (+,-,~,?) if one is present.
+pub(crate) fn strip_qualifier(record: &str) -> &str {
+    record.strip_prefix(['+', '-', '~', '?']).unwrap_or(record)
+}
+#[test]
+fn test_strip_qualifier() {
+    assert_eq!(
+        strip_qualifier("-redirect=example.com"),
+        "redirect=example.com"
+    );
+    assert_eq!(
+        strip_qualifier("redirect=example.com"),
+        "redirect=example.com"
+    );
+}
+// Case insensitive version of `str::strip_prefix`. Mechanism and modifier names are
+// case-insensitive, the remainder of `s` is returned unchanged.
+pub(crate) fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    match s.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&s[prefix.len()..]),
+        _ => None,
+    }
+}
+#[test]
+fn strip_prefix_ignore_case_match() {
+    assert_eq!(
+        strip_prefix_ignore_case("IP4:1.2.3.4", "ip4:"),
+        Some("1.2.3.4")
+    );
+    assert_eq!(
+        strip_prefix_ignore_case("Exp=%{D}.Example.com", "exp="),
+        Some("%{D}.Example.com")
+    );
+}
+#[test]
+fn strip_prefix_ignore_case_no_match() {
+    assert_eq!(strip_prefix_ignore_case("ip6:", "ip4:"), None);
+    assert_eq!(strip_prefix_ignore_case("ip", "ip4:"), None);
+}
 #[doc(hidden)]
 pub(crate) fn remove_qualifier(record: &str) -> &str {
     // Remove leading (+,-,~,?) character and return an updated str
@@ -263,3 +600,318 @@ fn test_remove_qualifier() {
     let result = remove_qualifier(test_str);
     assert_eq!(result, "bc");
 }
+// Returns the prefix length of `raw` when `raw` is an address of the family of `kind`
+// followed by a prefix length which is out of range for that family.
+// e.g. `10.0.0.0/33` as `IpV4` returns `Some("33")`
+pub(crate) fn invalid_cidr_length<'a>(raw: &'a str, kind: &Kind) -> Option<&'a str> {
+    let (address, length) = raw.split_once('/')?;
+    let max = if kind.is_ip_v4() {
+        address.parse::<Ipv4Addr>().ok()?;
+        32
+    } else {
+        address.parse::<Ipv6Addr>().ok()?;
+        128
+    };
+    match length.parse::<u8>() {
+        Ok(length) if length <= max => None,
+        _ => Some(length),
+    }
+}
+#[test]
+fn cidr_length() {
+    assert_eq!(invalid_cidr_length("10.0.0.0/33", &Kind::IpV4), Some("33"));
+    assert_eq!(invalid_cidr_length("10.0.0.0/-1", &Kind::IpV4), Some("-1"));
+    assert_eq!(invalid_cidr_length("10.0.0.0/32", &Kind::IpV4), None);
+    assert_eq!(
+        invalid_cidr_length("2001:db8::/129", &Kind::IpV6),
+        Some("129")
+    );
+    assert_eq!(invalid_cidr_length("2001:db8::/36", &Kind::IpV4), None);
+    assert_eq!(invalid_cidr_length("10.0.0.0", &Kind::IpV4), None);
+}
+// Split the rrdata of an `a` or `mx` into the domain-spec and any cidr suffix. The suffix starts
+// with `/`. A `/` within a macro is part of the domain-spec. e.g. `%{l/}.example.com/24`
+pub(crate) fn split_cidr(rrdata: &str) -> (&str, &str) {
+    let start = rrdata.len() - after_macros(rrdata).len();
+    match rrdata[start..].find('/') {
+        Some(cidr) => rrdata.split_at(start + cidr),
+        None => (rrdata, ""),
+    }
+}
+#[test]
+fn split_domain_and_cidr() {
+    assert_eq!(split_cidr("example.com/24//64"), ("example.com", "/24//64"));
+    assert_eq!(split_cidr("//64"), ("", "//64"));
+    assert_eq!(split_cidr("example.com"), ("example.com", ""));
+    assert_eq!(
+        split_cidr("%{l/}.example.com/24"),
+        ("%{l/}.example.com", "/24")
+    );
+}
+// Returns `true` if `term` is an `a` or `mx` mechanism whose dual cidr suffix is malformed, or
+// whose `:` is followed by `/` instead of a domain-spec.
+// e.g. `a/24//`, `mx//`, `a:example.com/24/`, `mx:/24` or `a:/%{d}`
+pub(crate) fn malformed_dual_cidr(term: &str) -> bool {
+    lazy_static! {
+        static ref DUAL_CIDR_RE: Regex = Regex::new(r"^(?:/\d{1,3})?(?://\d{1,3})?$").unwrap();
+    }
+    let unqualified = strip_qualifier(term);
+    let rest = match strip_prefix_ignore_case(unqualified, "a")
+        .or_else(|| strip_prefix_ignore_case(unqualified, "mx"))
+    {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let (domain, cidr) = match rest.strip_prefix(':') {
+        // Written without the domain-spec the term would read as a cidr suffix.
+        Some(domain_spec) if domain_spec.starts_with('/') => return true,
+        Some(domain_spec) => split_cidr(domain_spec),
+        None if rest.starts_with('/') => ("", rest),
+        None => return false,
+    };
+    !cidr.is_empty()
+        && ((rest.starts_with(':') && domain.is_empty()) || !DUAL_CIDR_RE.is_match(cidr))
+}
+#[test]
+fn malformed_dual_cidr_suffix() {
+    for term in [
+        "a/24//",
+        "mx//",
+        "a:foo/24/",
+        "mx:/24",
+        "-a///64",
+        "a:/x",
+        "MX/24/64",
+    ] {
+        assert_eq!(malformed_dual_cidr(term), true, "{}", term);
+    }
+    for term in [
+        "a",
+        "a/24//64",
+        "mx//64",
+        "a:foo/24",
+        "all",
+        "mx:example.com",
+        "a:%{l/}.x",
+    ] {
+        assert_eq!(malformed_dual_cidr(term), false, "{}", term);
+    }
+}
+// Returns the family and value of the first prefix length of a dual cidr suffix at the end
+// of `rrdata` which is out of range. e.g. `example.com/24//129` returns `(IpV6, "129")`
+pub(crate) fn invalid_dual_cidr_length(rrdata: &str) -> Option<(Kind, &str)> {
+    let rrdata = after_macros(rrdata);
+    let (rest, ip6_length) = match rrdata.find("//") {
+        Some(i) => (&rrdata[..i], Some(&rrdata[i + 2..])),
+        None => (rrdata, None),
+    };
+    if let Some(i) = rest.find('/') {
+        let ip4_length = &rest[i + 1..];
+        if !matches!(ip4_length.parse::<u8>(), Ok(length) if length <= 32) {
+            return Some((Kind::IpV4, ip4_length));
+        }
+    }
+    match ip6_length {
+        Some(length) if !matches!(length.parse::<u8>(), Ok(l) if l <= 128) => {
+            Some((Kind::IpV6, length))
+        }
+        _ => None,
+    }
+}
+#[test]
+fn dual_cidr_length() {
+    assert_eq!(invalid_dual_cidr_length("example.com/24//64"), None);
+    assert_eq!(invalid_dual_cidr_length("//128"), None);
+    assert_eq!(invalid_dual_cidr_length("/33"), Some((Kind::IpV4, "33")));
+    assert_eq!(
+        invalid_dual_cidr_length("example.com/24//129"),
+        Some((Kind::IpV6, "129"))
+    );
+    assert_eq!(invalid_dual_cidr_length("example.com"), None);
+}
+// Check that `ip` is of the family named by `kind`. An `ip4:` term must hold an IPv4
+// network and an `ip6:` term an IPv6 network.
+pub(crate) fn check_ip_family(kind: &Kind, ip: &IpNetwork) -> Result<(), MechanismError> {
+    if kind.is_ip_v4() && !ip.is_ipv4() {
+        Err(MechanismError::NotIP4Network(ip.to_string()))
+    } else if kind.is_ip_v6() && !ip.is_ipv6() {
+        Err(MechanismError::NotIP6Network(ip.to_string()))
+    } else {
+        Ok(())
+    }
+}
+#[test]
+fn ip_family() {
+    let ip4: IpNetwork = "203.0.113.0/24".parse().unwrap();
+    let ip6: IpNetwork = "2001:db8::/32".parse().unwrap();
+    assert_eq!(check_ip_family(&Kind::IpV4, &ip4), Ok(()));
+    assert_eq!(check_ip_family(&Kind::IpV6, &ip6), Ok(()));
+    assert_eq!(
+        check_ip_family(&Kind::IpV4, &ip6),
+        Err(MechanismError::NotIP4Network("2001:db8::/32".to_string()))
+    );
+    assert_eq!(
+        check_ip_family(&Kind::IpV6, &ip4),
+        Err(MechanismError::NotIP6Network("203.0.113.0/24".to_string()))
+    );
+}
+// Returns `true` if `term` is one of the mechanisms which may be followed by a domain-spec
+// or network. That is every mechanism other than `all`.
+pub(crate) fn is_mechanism_term(term: &str) -> bool {
+    let term = strip_qualifier(term);
+    let name = match term.find([':', '/']) {
+        Some(end) => &term[..end],
+        None => term,
+    };
+    ["a", "mx", "ptr", "ip4", "ip6", "include", "exists"]
+        .iter()
+        .any(|mechanism| name.eq_ignore_ascii_case(mechanism))
+}
+#[test]
+fn mechanism_term() {
+    assert_eq!(is_mechanism_term("include:_spf.example.com"), true);
+    assert_eq!(is_mechanism_term("-MX/24"), true);
+    assert_eq!(is_mechanism_term("~ip4:203.0.113.0/24"), true);
+    assert_eq!(is_mechanism_term("all"), false);
+    assert_eq!(is_mechanism_term("redirect=_spf.example.com"), false);
+    assert_eq!(is_mechanism_term("exp=explain.example.com"), false);
+    assert_eq!(is_mechanism_term("bogusmech"), false);
+}
+// Returns the qualifier and name of a term shaped like a mechanism, `[qualifier] name [":" value]`,
+// when the name is not a known mechanism or modifier. e.g. `-foobar`, `~spoof:example.com`
+pub(crate) fn unknown_mechanism_name(term: &str) -> Option<&str> {
+    let unqualified = strip_qualifier(term);
+    let end = unqualified.find(':').unwrap_or(unqualified.len());
+    let name = &unqualified[..end];
+    let mut chars = name.chars();
+    if !chars.next()?.is_ascii_alphabetic()
+        || !chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return None;
+    }
+    let known = [
+        "all", "include", "a", "mx", "ptr", "ip4", "ip6", "exists", "redirect", "exp",
+    ];
+    if known.iter().any(|known| name.eq_ignore_ascii_case(known)) {
+        return None;
+    }
+    Some(&term[..term.len() - unqualified.len() + end])
+}
+#[test]
+fn unknown_mechanism() {
+    assert_eq!(unknown_mechanism_name("-foobar"), Some("-foobar"));
+    assert_eq!(unknown_mechanism_name("~spoof:example.com"), Some("~spoof"));
+    assert_eq!(
+        unknown_mechanism_name("inclde:_spf.example.com"),
+        Some("inclde")
+    );
+    assert_eq!(unknown_mechanism_name("mxx"), Some("mxx"));
+    assert_eq!(unknown_mechanism_name("-MX"), None);
+    assert_eq!(unknown_mechanism_name("redirect:_spf.example.com"), None);
+    assert_eq!(unknown_mechanism_name("a/24//"), None);
+    assert_eq!(unknown_mechanism_name("abc.com"), None);
+    assert_eq!(unknown_mechanism_name(","), None);
+    assert_eq!(unknown_mechanism_name("1t=y"), None);
+}
+// Returns the domain-spec of an `a`, `mx`, `ptr`, `include` or `exists` term if it contains
+// an empty label. e.g. `include:example..com` or `a:.example.com`
+pub(crate) fn domain_with_empty_label(term: &str) -> Option<&str> {
+    if !is_mechanism_term(term) || is_ip_term(term) {
+        return None;
+    }
+    let (_, value) = strip_qualifier(term).split_once(':')?;
+    let domain = value.split('/').next().unwrap_or(value);
+    if domain.contains("..") || domain.starts_with('.') {
+        Some(domain)
+    } else {
+        None
+    }
+}
+#[test]
+fn empty_label() {
+    assert_eq!(
+        domain_with_empty_label("include:example..com"),
+        Some("example..com")
+    );
+    assert_eq!(
+        domain_with_empty_label("-a:.example.com/24"),
+        Some(".example.com")
+    );
+    assert_eq!(domain_with_empty_label("mx:mail.example.org."), None);
+    assert_eq!(domain_with_empty_label("redirect=example..com"), None);
+    assert_eq!(domain_with_empty_label("ip6:2001:db8::/32"), None);
+}
+// Returns the first problem with `domain` as the domain-spec of a `kind` term, as the
+// offending label and a description. A label may begin with an underscore. e.g. `_spf`
+// A domain-spec with macros is not checked as its labels are only known once expanded.
+pub(crate) fn domain_spec_problem(domain: &str, kind: &Kind) -> Option<(String, String)> {
+    if has_macros(domain) {
+        return None;
+    }
+    let name = domain.strip_suffix('.').unwrap_or(domain);
+    if name.parse::<std::net::IpAddr>().is_ok() {
+        return Some((
+            name.to_string(),
+            "is an IP address, not a domain".to_string(),
+        ));
+    }
+    if name.len() > 253 {
+        return Some((name.to_string(), "is longer than 253 octets".to_string()));
+    }
+    if name.split('.').any(str::is_empty) {
+        return Some((name.to_string(), "has an empty label".to_string()));
+    }
+    for label in name.split('.') {
+        let problem = if label.len() > 63 {
+            "is longer than 63 octets"
+        } else if !label
+            .strip_prefix('_')
+            .unwrap_or(label)
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            "contains a character other than a letter, digit or hyphen"
+        } else if label.starts_with('-') || label.ends_with('-') {
+            "begins or ends with a hyphen"
+        } else {
+            continue;
+        };
+        return Some((label.to_string(), problem.to_string()));
+    }
+    let needs_two = matches!(
+        kind,
+        Kind::Include | Kind::Redirect | Kind::Exists | Kind::Exp
+    );
+    if needs_two && !name.contains('.') {
+        return Some((
+            name.to_string(),
+            "must have at least two labels".to_string(),
+        ));
+    }
+    None
+}
+#[test]
+fn domain_spec() {
+    let problem = |domain, kind| domain_spec_problem(domain, &kind).map(|(label, _)| label);
+    assert_eq!(problem("_spf.example.com.", Kind::Include), None);
+    assert_eq!(problem("%{i}.not valid!", Kind::Exists), None);
+    assert_eq!(problem("mail", Kind::A), None);
+    assert_eq!(problem("mail", Kind::Include), Some("mail".to_string()));
+    assert_eq!(problem("192.0.2.1", Kind::A), Some("192.0.2.1".to_string()));
+    assert_eq!(
+        problem("bad!.example.com", Kind::MX),
+        Some("bad!".to_string())
+    );
+    assert_eq!(
+        problem("a_b.example.com", Kind::MX),
+        Some("a_b".to_string())
+    );
+    assert_eq!(
+        problem("-mx.example.com", Kind::MX),
+        Some("-mx".to_string())
+    );
+    assert_eq!(
+        problem("example..com", Kind::Include),
+        Some("example..com".to_string())
+    );
+}
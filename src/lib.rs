@@ -1,6 +1,7 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
 //! This crate is responsible for providing tools to access and modify information about spf records.  
 //! Provides methods for building spf records programmatically.  
 //!
@@ -11,6 +12,7 @@
 //!
 //! # Abilities:
 //! - Check and Set Spf record version. See: [`Spf Versions`](spf::Spf::set_v1)
+//! - Create an Spf record with its version set. See: [`Spf::new_v1()`](spf::Spf::new_v1)
 //! - Check and Create Spf Mechanism/Modifiers:
 //!     - [`Mechanism`](mechanism::Mechanism)
 //!     - [`Mechanism Qualifier`](mechanism::Mechanism::is_pass)
@@ -22,12 +24,57 @@
 //! - `strict-dns` (Disabled by default.)  
 //!   This enables syntactical checking of Domain Names.
 //!     - When enabled it changes the behavour of `FromStr` for `Mechanism<String>` and
-//! `ParsedMechanism`. By default `Mechanism<String>`'s `rrdata` is not checked.
+//!       `ParsedMechanism`. By default `Mechanism<String>`'s `rrdata` is not checked.
+//! - `async` (Disabled by default.)
+//!     - Adds [`AsyncSpfResolver`](evaluate::AsyncSpfResolver) and
+//!       [`check_host_async()`](evaluate::check_host_async).
+//! - `hickory-resolver` (Disabled by default.)
+//!     - Adds [`Spf::from_domain()`](spf::Spf::from_domain) and implements
+//!       [`SpfResolver`](evaluate::SpfResolver) for the synchronous `hickory_resolver::Resolver`.
+//! - `serde` (Disabled by default.)
+//!     - Implements `serde::Serialize` for [`SpfSummary`](spf::SpfSummary).
 //!
+pub mod evaluate;
 mod helpers;
 pub mod mechanism;
 mod spf;
 
 //use crate::mechanism::Mechanism;
+pub use crate::spf::Disposition;
+pub use crate::spf::DomainLookups;
+pub use crate::spf::ExpandError;
+pub use crate::spf::Explanation;
+pub use crate::spf::Finding;
+pub use crate::spf::FindingCode;
+pub use crate::spf::FlattenError;
+pub use crate::spf::IpOverlap;
+pub use crate::spf::IpRelation;
+pub use crate::spf::IpSpace;
+pub use crate::spf::IpSpaceStats;
+pub use crate::spf::LookupItem;
+pub use crate::spf::LookupReport;
+pub use crate::spf::ParseError;
+pub use crate::spf::ParseOptions;
+pub use crate::spf::ResolutionMap;
+pub use crate::spf::Scope;
+pub use crate::spf::Severity;
+pub use crate::spf::SortStrategy;
 pub use crate::spf::Spf;
+pub use crate::spf::SpfBuilder;
+pub use crate::spf::SpfDiff;
 pub use crate::spf::SpfError;
+pub use crate::spf::SpfFetchError;
+pub use crate::spf::SpfGraph;
+pub use crate::spf::SpfIntoIter;
+pub use crate::spf::SpfIter;
+pub use crate::spf::SpfRfcStandard;
+pub use crate::spf::SpfSource;
+pub use crate::spf::SpfSummary;
+pub use crate::spf::SpfTree;
+pub use crate::spf::SpfTreeIter;
+pub use crate::spf::SpfTreeNetworks;
+pub use crate::spf::SubsetResult;
+pub use crate::spf::ValidationOptions;
+pub use crate::spf::ValidationReport;
+pub use crate::spf::Version;
+pub use crate::spf::Warning;
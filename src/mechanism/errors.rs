@@ -1,3 +1,5 @@
+use crate::mechanism::Kind;
+
 /// Error message when unable to construct a new Mechanism.
 #[derive(Debug, PartialEq)]
 pub enum MechanismError {
@@ -15,6 +17,30 @@ pub enum MechanismError {
     NotStringMechanism,
     /// Indicates that the host record is not valid. Does not conform to RFC1123
     InvalidDomainHost(String),
+    /// Indicates that the provided value is not a valid [`Qualifier`](crate::mechanism::Qualifier)
+    InvalidQualifier(String),
+    /// Indicates that the prefix length of an ip4 network is not between 0 and 32.
+    InvalidIPv4CidrLength(String),
+    /// Indicates that the prefix length of an ip6 network is not between 0 and 128.
+    InvalidIPv6CidrLength(String),
+    /// Indicates that the dual cidr suffix of an `a` or `mx` term is not well formed. Holds the
+    /// term. e.g. `a/24//` or `mx:/24`
+    InvalidDualCidr(String),
+    /// Indicates that the term is shaped like a mechanism, but the name is not a known
+    /// mechanism. Holds the qualifier, if any, and the name. e.g. `~spoof`
+    UnknownMechanismName(String),
+    /// Indicates that an `ip4:` or `ip6:` term was parsed as a `Mechanism<String>`. These
+    /// terms must be parsed as a `Mechanism<IpNetwork>`.
+    IpNetworkTerm(String),
+    /// Indicates that an `ip4:` or `ip6:` term has nothing after the prefix.
+    EmptyIpValue(String),
+    /// Indicates that a qualifier was placed in front of a modifier. e.g. `+redirect=`
+    QualifierNotAllowedForModifier(Kind),
+    /// Indicates that a value was given to a mechanism which takes none. e.g. `all`
+    RrdataNotAllowed(Kind),
+    /// Indicates that no value was given to a mechanism or modifier which requires one.
+    /// e.g. `include:`
+    RrdataRequired(Kind),
 }
 
 impl std::fmt::Display for MechanismError {
@@ -41,6 +67,36 @@ impl std::fmt::Display for MechanismError {
             MechanismError::InvalidDomainHost(host) => {
                 write!(f, "Invalid DNS string: {}", host)
             }
+            MechanismError::InvalidQualifier(value) => {
+                write!(f, "{} is not a valid qualifier", value)
+            }
+            MechanismError::InvalidIPv4CidrLength(length) => {
+                write!(f, "{} is not a valid ip4 cidr length", length)
+            }
+            MechanismError::InvalidIPv6CidrLength(length) => {
+                write!(f, "{} is not a valid ip6 cidr length", length)
+            }
+            MechanismError::InvalidDualCidr(term) => {
+                write!(f, "{} does not have a valid dual cidr suffix", term)
+            }
+            MechanismError::UnknownMechanismName(name) => {
+                write!(f, "{} is not a known mechanism", name)
+            }
+            MechanismError::IpNetworkTerm(term) => {
+                write!(f, "{} must be parsed as Mechanism<IpNetwork>", term)
+            }
+            MechanismError::EmptyIpValue(term) => {
+                write!(f, "{} has no ip network", term)
+            }
+            MechanismError::QualifierNotAllowedForModifier(kind) => {
+                write!(f, "Modifier {} may not have a qualifier", kind.as_str())
+            }
+            MechanismError::RrdataNotAllowed(kind) => {
+                write!(f, "{} may not have a value", kind.as_str())
+            }
+            MechanismError::RrdataRequired(kind) => {
+                write!(f, "{} requires a value", kind.as_str())
+            }
         }
     }
 }
@@ -65,10 +121,113 @@ impl MechanismError {
     }
     /// Return `true` if it the string can not be parsed to an IpNetwork
     /// # Example:
-    /// "ip4:203.32.160.0/33" would give this error. This applies to IP6 networks.
+    /// "ip4:203.32.160.256/24" would give this error. This applies to IP6 networks.
     pub fn is_invalid_ip(&self) -> bool {
         matches!(self, Self::InvalidIPNetwork(_))
     }
+    /// Return `true` if the value is not a valid qualifier.
+    pub fn is_invalid_qualifier(&self) -> bool {
+        matches!(self, Self::InvalidQualifier(_))
+    }
+    /// Return `true` if the prefix length of an ip4 network is out of range.
+    /// # Example:
+    /// "ip4:203.32.160.0/33" would give this error.
+    pub fn is_invalid_ipv4_cidr_length(&self) -> bool {
+        matches!(self, Self::InvalidIPv4CidrLength(_))
+    }
+    /// Return `true` if the prefix length of an ip6 network is out of range.
+    /// # Example:
+    /// "ip6:2001:4860:4000::/129" would give this error.
+    pub fn is_invalid_ipv6_cidr_length(&self) -> bool {
+        matches!(self, Self::InvalidIPv6CidrLength(_))
+    }
+    /// Return `true` if the dual cidr suffix of an `a` or `mx` term is not well formed.
+    /// # Example:
+    /// "a:example.com/24/" would give this error.
+    pub fn is_invalid_dual_cidr(&self) -> bool {
+        matches!(self, Self::InvalidDualCidr(_))
+    }
+    /// Return `true` if the term looks like a mechanism but its name is not known.
+    /// # Example:
+    /// "~spoof:example.com" would give this error.
+    pub fn is_unknown_mechanism_name(&self) -> bool {
+        matches!(self, Self::UnknownMechanismName(_))
+    }
+    /// Return `true` if an `ip4:` or `ip6:` term was parsed as a `Mechanism<String>`.
+    /// # Example:
+    /// "ip4:203.32.160.0/24".parse::<Mechanism<String>>() would give this error.
+    pub fn is_ip_network_term(&self) -> bool {
+        matches!(self, Self::IpNetworkTerm(_))
+    }
+    /// Return `true` if an `ip4:` or `ip6:` term has no network.
+    /// # Example:
+    /// "ip4:" would give this error.
+    pub fn is_empty_ip_value(&self) -> bool {
+        matches!(self, Self::EmptyIpValue(_))
+    }
+    /// Return `true` if a modifier was given a qualifier.
+    /// # Example:
+    /// "~exp=explain.example.com" would give this error.
+    pub fn is_qualifier_not_allowed_for_modifier(&self) -> bool {
+        matches!(self, Self::QualifierNotAllowedForModifier(_))
+    }
+    /// Return `true` if a value was given to a mechanism which takes none.
+    /// # Example:
+    /// Setting the rrdata of `all` would give this error.
+    pub fn is_rrdata_not_allowed(&self) -> bool {
+        matches!(self, Self::RrdataNotAllowed(_))
+    }
+    /// Return `true` if no value was given to a mechanism or modifier which requires one.
+    /// # Example:
+    /// Removing the rrdata of `include:` would give this error.
+    pub fn is_rrdata_required(&self) -> bool {
+        matches!(self, Self::RrdataRequired(_))
+    }
 }
 
 impl std::error::Error for MechanismError {}
+
+/// Error returned when the macros of a domain-spec can not be expanded.
+/// See [`MacroContext`](crate::mechanism::MacroContext)
+#[derive(Debug, PartialEq)]
+pub enum MacroError {
+    /// The macro-string is not well formed. e.g. `%{x}`, `%{d0}` or `%{d` without `}`
+    InvalidMacroString(String),
+    /// The macro letter, `c`, `r` or `t`, may only be used in an explanation string.
+    ExplanationOnlyMacro(char),
+    /// The Mechanism has no domain name to expand. e.g. `all`
+    NoDomainSpec(Kind),
+}
+
+impl std::fmt::Display for MacroError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MacroError::InvalidMacroString(mesg) => {
+                write!(f, "{} is not a valid macro-string", mesg)
+            }
+            MacroError::ExplanationOnlyMacro(letter) => {
+                write!(f, "Macro {} may only be used in an explanation", letter)
+            }
+            MacroError::NoDomainSpec(kind) => {
+                write!(f, "{} has no domain-spec", kind.as_str())
+            }
+        }
+    }
+}
+
+impl MacroError {
+    /// Returns `true` if the macro-string is not well formed.
+    pub fn is_invalid_macro_string(&self) -> bool {
+        matches!(self, Self::InvalidMacroString(_))
+    }
+    /// Returns `true` if an explanation only macro was used in a domain-spec.
+    pub fn is_explanation_only_macro(&self) -> bool {
+        matches!(self, Self::ExplanationOnlyMacro(_))
+    }
+    /// Returns `true` if the Mechanism has no domain-spec.
+    pub fn is_no_domain_spec(&self) -> bool {
+        matches!(self, Self::NoDomainSpec(_))
+    }
+}
+
+impl std::error::Error for MacroError {}
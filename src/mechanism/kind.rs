@@ -4,7 +4,11 @@
 //! small as to not require any distinction in the current code base.
 //!
 /// Defines the possible mechanisms.
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// More kinds may be added in a future release, so a `match` on `Kind` outside this crate
+/// needs a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
 pub enum Kind {
     /// Represents a *Modifier* of type redirect=  
     /// If this is present, the *All* mechanism should not be present.  
@@ -17,6 +21,7 @@ pub enum Kind {
     /// a:example.com  
     /// a:example.com/24  
     /// ```
+    #[default]
     A,
     /// Represents a Mechanism of type *MX*  
     /// Possible values follow the same loyout as for [`A`](Kind::A)
@@ -47,6 +52,11 @@ pub enum Kind {
     Exists,
     /// Represents a Mechanism of type *All*
     All,
+    /// Represents a *Modifier* of type exp=
+    /// ```text
+    /// exp=<domain>
+    /// ```
+    Exp,
 }
 
 impl Kind {
@@ -104,6 +114,14 @@ impl Kind {
     pub fn is_all(&self) -> bool {
         matches!(self, Self::All)
     }
+    /// Returns `true` if the modifier is [`Exp`](Kind::Exp).
+    pub fn is_exp(&self) -> bool {
+        matches!(self, Self::Exp)
+    }
+    /// Returns `true` if this is a *Modifier*. ([`Redirect`](Kind::Redirect) or [`Exp`](Kind::Exp))
+    pub fn is_modifier(&self) -> bool {
+        matches!(self, Self::Redirect) || matches!(self, Self::Exp)
+    }
     /// Returns a reference to the str for kind enums.
     ///
     /// # Examples:
@@ -129,6 +147,7 @@ impl Kind {
             Kind::Ptr => "ptr",
             Kind::Exists => "exists:",
             Kind::All => "all",
+            Kind::Exp => "exp=",
         }
     }
 }
@@ -145,16 +164,11 @@ impl std::fmt::Display for Kind {
             Kind::Ptr => write!(f, "ptr"),
             Kind::Exists => write!(f, "exists:"),
             Kind::All => write!(f, "all"),
+            Kind::Exp => write!(f, "exp="),
         }
     }
 }
 
-impl Default for Kind {
-    fn default() -> Self {
-        Self::A
-    }
-}
-
 #[test]
 fn a() {
     let a = Kind::A;
@@ -200,3 +214,14 @@ fn all() {
     let a = Kind::All;
     assert_eq!(a.to_string(), "all");
 }
+#[test]
+fn exp() {
+    let a = Kind::Exp;
+    assert_eq!(a.to_string(), "exp=");
+}
+#[test]
+fn is_modifier() {
+    assert_eq!(Kind::Redirect.is_modifier(), true);
+    assert_eq!(Kind::Exp.is_modifier(), true);
+    assert_eq!(Kind::Include.is_modifier(), false);
+}
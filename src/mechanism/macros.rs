@@ -0,0 +1,371 @@
+//! Expansion of the macros found within a domain-spec. See RFC 7208 section 7.
+use crate::mechanism::MacroError;
+use std::net::IpAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The maximum length of a domain name produced by expanding a domain-spec.
+const MAX_DOMAIN_NAME_LENGTH: usize = 253;
+/// Characters which may be used to split a macro value.
+const DELIMITERS: &str = ".-+,/_=";
+
+/// Describes the message being checked. Supplies the values substituted for each macro
+/// letter when a domain-spec is expanded.
+///
+/// | Letter | Value                                                  |
+/// |--------|--------------------------------------------------------|
+/// | `s`    | sender                                                 |
+/// | `l`    | local-part of the sender                               |
+/// | `o`    | domain of the sender                                   |
+/// | `d`    | domain being checked. Defaults to the sender domain.   |
+/// | `i`    | client ip, as dotted quads or dotted nibbles           |
+/// | `p`    | validated domain name of the client ip, or `unknown`   |
+/// | `v`    | `in-addr` for an IPv4 client, `ip6` for IPv6           |
+/// | `h`    | HELO or EHLO domain                                    |
+/// | `c`    | client ip, readable form. Explanations only.           |
+/// | `r`    | receiving host, or `unknown`. Explanations only.       |
+/// | `t`    | timestamp in seconds. Explanations only.               |
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::{MacroContext, Mechanism};
+/// use decon_spf::Spf;
+/// let context = MacroContext::new(
+///     "strong-bad@email.example.com",
+///     "192.0.2.3".parse().unwrap(),
+///     "mx.example.org",
+/// );
+/// let spf: Spf = "v=spf1 exists:%{ir}.%{v}._spf.%{d2} -all".parse().unwrap();
+/// let exists = &spf.exists().unwrap()[0];
+/// assert_eq!(
+///     exists.expand_macros(&context).unwrap(),
+///     "3.2.0.192.in-addr._spf.example.com"
+/// );
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroContext {
+    sender: String,
+    local_part: String,
+    sender_domain: String,
+    domain: String,
+    ip: IpAddr,
+    helo: String,
+    validated_domain: Option<String>,
+    receiver: Option<String>,
+    timestamp: u64,
+}
+
+impl MacroContext {
+    /// Create a new context for a message from `sender`, received from the client `ip`
+    /// which introduced itself as `helo`.
+    ///
+    /// A sender without a local-part uses `postmaster`. An empty sender is taken to be
+    /// `postmaster@<helo>`.
+    pub fn new(sender: &str, ip: IpAddr, helo: &str) -> Self {
+        let sender = if sender.is_empty() { helo } else { sender };
+        let (local_part, sender_domain) = match sender.rsplit_once('@') {
+            Some((local_part, domain)) if !local_part.is_empty() => (local_part, domain),
+            Some((_, domain)) => ("postmaster", domain),
+            None => ("postmaster", sender),
+        };
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        MacroContext {
+            sender: format!("{}@{}", local_part, sender_domain),
+            local_part: local_part.to_string(),
+            sender_domain: sender_domain.to_string(),
+            domain: sender_domain.to_string(),
+            ip,
+            helo: helo.to_string(),
+            validated_domain: None,
+            receiver: None,
+            timestamp,
+        }
+    }
+    /// Set the domain being checked. This changes while following `include` and `redirect`.
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = domain.to_string();
+        self
+    }
+    /// Set the validated domain name of the client ip, used by `%{p}`.
+    /// Without it `%{p}` expands to `unknown`.
+    pub fn validated_domain(mut self, validated_domain: &str) -> Self {
+        self.validated_domain = Some(validated_domain.to_string());
+        self
+    }
+    /// Set the name of the host performing the check, used by `%{r}`.
+    pub fn receiver(mut self, receiver: &str) -> Self {
+        self.receiver = Some(receiver.to_string());
+        self
+    }
+    /// Set the timestamp, in seconds since the epoch, used by `%{t}`. Defaults to the time
+    /// the context was created.
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+    /// Expand the macros of an explanation string. This is the TXT record found at the
+    /// target of an `exp=` modifier. Unlike a domain-spec it may use `%{c}`, `%{r}` and `%{t}`.
+    pub fn expand_explanation(&self, explain_string: &str) -> Result<String, MacroError> {
+        self.expand(explain_string, true)
+    }
+    // The domain of the sender, which is the first domain checked.
+    pub(crate) fn sender_domain(&self) -> &str {
+        &self.sender_domain
+    }
+    // Expand a domain-spec into the domain name which would be queried.
+    pub(crate) fn expand_domain_spec(&self, domain_spec: &str) -> Result<String, MacroError> {
+        let expanded = self.expand(domain_spec, false)?;
+        Ok(truncate_domain_name(&expanded).to_string())
+    }
+
+    fn expand(&self, macro_string: &str, explanation: bool) -> Result<String, MacroError> {
+        let invalid = || MacroError::InvalidMacroString(macro_string.to_string());
+        let mut expanded = String::new();
+        let mut rest = macro_string;
+        while let Some(start) = rest.find('%') {
+            expanded.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            match after.chars().next() {
+                Some('%') => expanded.push('%'),
+                Some('_') => expanded.push(' '),
+                Some('-') => expanded.push_str("%20"),
+                Some('{') => {
+                    let end = after.find('}').ok_or_else(invalid)?;
+                    expanded.push_str(&self.expand_macro(&after[1..end], explanation, invalid)?);
+                    rest = &after[end + 1..];
+                    continue;
+                }
+                _ => return Err(invalid()),
+            }
+            rest = &after[1..];
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+    // Expand the body of a single `%{...}` macro. e.g. `ir`, `l1r-`
+    fn expand_macro(
+        &self,
+        body: &str,
+        explanation: bool,
+        invalid: impl Fn() -> MacroError,
+    ) -> Result<String, MacroError> {
+        let letter = body.chars().next().ok_or_else(&invalid)?;
+        let value = match letter.to_ascii_lowercase() {
+            's' => self.sender.clone(),
+            'l' => self.local_part.clone(),
+            'o' => self.sender_domain.clone(),
+            'd' => self.domain.clone(),
+            'i' => dotted_ip(&self.ip),
+            'p' => self
+                .validated_domain
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            'v' if self.ip.is_ipv4() => "in-addr".to_string(),
+            'v' => "ip6".to_string(),
+            'h' => self.helo.clone(),
+            'c' | 'r' | 't' if !explanation => {
+                return Err(MacroError::ExplanationOnlyMacro(letter));
+            }
+            'c' => self.ip.to_string(),
+            'r' => self
+                .receiver
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            't' => self.timestamp.to_string(),
+            _ => return Err(invalid()),
+        };
+        // Transformers: an optional count of parts to keep, then an optional `r` to reverse.
+        let transformers = &body[letter.len_utf8()..];
+        let digits = transformers
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(transformers.len());
+        let keep = match &transformers[..digits] {
+            "" => None,
+            count => match count.parse::<usize>() {
+                Ok(count) if count > 0 => Some(count),
+                _ => return Err(invalid()),
+            },
+        };
+        let mut delimiters = &transformers[digits..];
+        let reverse = delimiters.starts_with(['r', 'R']);
+        if reverse {
+            delimiters = &delimiters[1..];
+        }
+        if !delimiters.chars().all(|c| DELIMITERS.contains(c)) {
+            return Err(invalid());
+        }
+        let delimiters = if delimiters.is_empty() {
+            "."
+        } else {
+            delimiters
+        };
+        let mut parts: Vec<&str> = value.split(|c| delimiters.contains(c)).collect();
+        if reverse {
+            parts.reverse();
+        }
+        if let Some(keep) = keep {
+            if keep < parts.len() {
+                parts.drain(..parts.len() - keep);
+            }
+        }
+        let value = parts.join(".");
+        if letter.is_ascii_uppercase() {
+            Ok(url_escape(&value))
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+// Check `macro_string` against the macro-string ABNF of RFC 7208 section 7.1, returning the
+// byte offset and a description of each violation. `c`, `r` and `t` are only permitted when
+// `explanation` is `true`.
+pub(crate) fn macro_syntax_errors(macro_string: &str, explanation: bool) -> Vec<(usize, String)> {
+    let mut errors = Vec::new();
+    let mut position = 0;
+    while let Some(c) = macro_string[position..].chars().next() {
+        let start = position;
+        position += c.len_utf8();
+        if c != '%' {
+            if !('!'..='~').contains(&c) {
+                errors.push((start, format!("{:?} is not permitted", c)));
+            }
+            continue;
+        }
+        match macro_string[position..].chars().next() {
+            Some('%' | '_' | '-') => position += 1,
+            Some('{') => {
+                let body = &macro_string[position + 1..];
+                let end = match body.find('}') {
+                    Some(end) => end,
+                    None => {
+                        errors.push((start, "'%{' is not closed by '}'".to_string()));
+                        break;
+                    }
+                };
+                if let Some(error) = macro_body_error(&body[..end], explanation) {
+                    errors.push((start, error));
+                }
+                position += end + 2;
+            }
+            Some(next) => errors.push((
+                start,
+                format!(
+                    "'%' is followed by '{}' rather than '{{', '%', '_' or '-'",
+                    next
+                ),
+            )),
+            None => errors.push((start, "'%' ends the macro-string".to_string())),
+        }
+    }
+    errors
+}
+// Check the body of a single `%{...}` macro. e.g. `ir`, `l1r-`
+fn macro_body_error(body: &str, explanation: bool) -> Option<String> {
+    let letter = match body.chars().next() {
+        Some(letter) => letter,
+        None => return Some("'%{}' has no macro letter".to_string()),
+    };
+    match letter.to_ascii_lowercase() {
+        's' | 'l' | 'o' | 'd' | 'i' | 'p' | 'v' | 'h' => {}
+        'c' | 'r' | 't' if explanation => {}
+        'c' | 'r' | 't' => return Some(MacroError::ExplanationOnlyMacro(letter).to_string()),
+        _ => return Some(format!("'{}' is not a macro letter", letter)),
+    }
+    let transformers = &body[letter.len_utf8()..];
+    let digits = transformers
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(transformers.len());
+    if transformers[..digits].parse::<usize>() == Ok(0) {
+        return Some("A transformer must keep at least one part".to_string());
+    }
+    let delimiters = &transformers[digits..];
+    let delimiters = delimiters.strip_prefix(['r', 'R']).unwrap_or(delimiters);
+    delimiters
+        .chars()
+        .find(|c| !DELIMITERS.contains(*c))
+        .map(|c| format!("'{}' is not a delimiter", c))
+}
+#[test]
+fn macro_syntax() {
+    assert_eq!(macro_syntax_errors("%{ir}.%{v}._spf.%{d2}", false), vec![]);
+    assert_eq!(macro_syntax_errors("%%%_%-%{L1r-}", false), vec![]);
+    assert_eq!(macro_syntax_errors("%{t}", true), vec![]);
+    let offsets = |s| -> Vec<usize> {
+        macro_syntax_errors(s, false)
+            .into_iter()
+            .map(|(offset, _)| offset)
+            .collect()
+    };
+    assert_eq!(offsets("%{x}.%(d).%{d0}.%{t}"), vec![0, 5, 10, 16]);
+    assert_eq!(offsets("example.com%"), vec![11]);
+    assert_eq!(offsets("%{i}.%{d"), vec![5]);
+    assert_eq!(offsets("%{d2r!}"), vec![0]);
+}
+
+// The client ip as dotted quads for IPv4, or dotted nibbles for IPv6.
+fn dotted_ip(ip: &IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => ip.to_string(),
+        IpAddr::V6(ip) => ip
+            .octets()
+            .iter()
+            .map(|octet| format!("{:x}.{:x}", octet >> 4, octet & 0xf))
+            .collect::<Vec<String>>()
+            .join("."),
+    }
+}
+
+// Escape every character which is not unreserved. Used by uppercase macro letters.
+fn url_escape(value: &str) -> String {
+    let mut escaped = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    escaped
+}
+
+// Remove labels from the left of `name` until it is no longer than 253 characters.
+fn truncate_domain_name(mut name: &str) -> &str {
+    while name.len() > MAX_DOMAIN_NAME_LENGTH {
+        match name.find('.') {
+            Some(dot) => name = &name[dot + 1..],
+            None => break,
+        }
+    }
+    name
+}
+
+#[test]
+fn dotted_ip6() {
+    let ip: IpAddr = "2001:db8::cb01".parse().unwrap();
+    assert_eq!(
+        dotted_ip(&ip),
+        "2.0.0.1.0.d.b.8.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.c.b.0.1"
+    );
+}
+#[test]
+fn escape() {
+    assert_eq!(
+        url_escape("strong-bad@email.example.com"),
+        "strong-bad%40email.example.com"
+    );
+    assert_eq!(url_escape("a b/c"), "a%20b%2Fc");
+}
+#[test]
+fn truncate() {
+    let label = "a".repeat(63);
+    let name = format!("x.{}.{}.{}.{}", label, label, label, label);
+    assert_eq!(name.len(), 257);
+    // Removing `x.` leaves 255 characters, so a further label is removed.
+    assert_eq!(
+        truncate_domain_name(&name),
+        format!("{}.{}.{}", label, label, label)
+    );
+}
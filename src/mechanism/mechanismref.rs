@@ -0,0 +1,57 @@
+use crate::mechanism::{Kind, Mechanism, Qualifier};
+use ipnetwork::IpNetwork;
+
+/// A reference to a mechanism or modifier held by an [`Spf`](crate::Spf). This will either
+/// borrow a `Mechanism<String>` or a `Mechanism<IpNetwork>`. See
+/// [`Spf::iter()`](crate::Spf::iter)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MechanismRef<'a> {
+    /// This variant borrows a Mechanism containing a String
+    TXT(&'a Mechanism<String>),
+    /// This variant borrows a Mechanism containing an IpNetwork
+    IP(&'a Mechanism<IpNetwork>),
+}
+
+impl std::fmt::Display for MechanismRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            MechanismRef::TXT(m) => write!(f, "{}", m),
+            MechanismRef::IP(m) => write!(f, "{}", m),
+        }
+    }
+}
+
+impl<'a> MechanismRef<'a> {
+    /// Returns a reference to the [`Kind`](Kind) of the mechanism.
+    pub fn kind(&self) -> &'a Kind {
+        match *self {
+            MechanismRef::TXT(m) => m.kind(),
+            MechanismRef::IP(m) => m.kind(),
+        }
+    }
+    /// Returns a reference to the [`Qualifier`](Qualifier) of the mechanism.
+    pub fn qualifier(&self) -> &'a Qualifier {
+        match *self {
+            MechanismRef::TXT(m) => m.qualifier(),
+            MechanismRef::IP(m) => m.qualifier(),
+        }
+    }
+    /// Returns `true` if the mechanism is an `ip4:` or `ip6:` mechanism.
+    pub fn is_network(&self) -> bool {
+        matches!(self, MechanismRef::IP(_))
+    }
+    /// Returns the `Mechanism<String>`, or `None` for an `ip4:` or `ip6:` mechanism.
+    pub fn txt(&self) -> Option<&'a Mechanism<String>> {
+        match *self {
+            MechanismRef::TXT(m) => Some(m),
+            MechanismRef::IP(_) => None,
+        }
+    }
+    /// Returns the `Mechanism<IpNetwork>`, or `None` if it is not an `ip4:` or `ip6:` mechanism.
+    pub fn network(&self) -> Option<&'a Mechanism<IpNetwork>> {
+        match *self {
+            MechanismRef::TXT(_) => None,
+            MechanismRef::IP(m) => Some(m),
+        }
+    }
+}
@@ -6,26 +6,31 @@
 //! The module also contains a number of ways to create the `Mechanism` instances.
 //! - [`ParsedMechanism`](parsedmechanism::ParsedMechanism)
 //!     - This provides a unified method for parsing any mechanism string. It will either contain a `Mechanism<String>`
-//! or a `Mechanism<IpNetwork>` if the string is succesfully parsed.
+//!       or a `Mechanism<IpNetwork>` if the string is succesfully parsed.
 //! - Both `Mechanism<String>` and `Mechanism<IpNetwork>` have the `FromStr` trait implemented. Allowing for the strings
-//! to be `parsed()`
+//!   to be `parsed()`
 //! - The `Mechanism` struct also has a number of specfic methods which can be used to create related mechanisms; which are
-//! used with the `FromStr` trait.
+//!   used with the `FromStr` trait.
 //!
 mod errors;
 mod kind;
+mod macros;
+mod mechanismref;
 mod parsedmechanism;
 mod qualifier;
 mod tests;
 
-pub use crate::mechanism::errors::MechanismError;
+pub use crate::mechanism::errors::{MacroError, MechanismError};
 pub use crate::mechanism::kind::Kind;
+pub use crate::mechanism::macros::MacroContext;
+pub use crate::mechanism::mechanismref::MechanismRef;
 pub use crate::mechanism::parsedmechanism::ParsedMechanism;
 pub use crate::mechanism::qualifier::Qualifier;
 
 use crate::helpers;
 use ipnetwork::{IpNetwork, IpNetworkError};
 
+use std::hash::{Hash, Hasher};
 use std::{convert::TryFrom, str::FromStr};
 
 /// Stores its [`Kind`](Kind), [`Qualifier`](Qualifier), and its `Value`
@@ -34,6 +39,95 @@ pub struct Mechanism<T> {
     kind: Kind,
     qualifier: Qualifier,
     rrdata: Option<T>,
+    // The name as it was written in the source, only set when it differs from the
+    // lowercase form and case is being preserved.
+    name: Option<String>,
+    // `true` when a `Pass` qualifier was written as an explicit `+` and should be reproduced.
+    explicit_pass: bool,
+    // `true` when an ip network was written without a prefix length. The prefix length is then
+    // left out when building a string.
+    implicit_prefix: bool,
+}
+
+/// Two mechanisms are equal when their [`Kind`](Kind), [`Qualifier`](Qualifier) and `rrdata`
+/// are equal. How the mechanism was written, such as the case of its name or an explicit `+`,
+/// is not compared.
+///
+/// The domain-spec is compared ignoring case. The contents of a macro, such as `%{d}`, are
+/// compared as written, as the case of a macro letter changes its expansion.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::{Mechanism, Qualifier};
+/// let include: Mechanism<String> = "+Include:_SPF.Example.com".parse().unwrap();
+/// assert_eq!(include, Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap());
+///```
+impl PartialEq for Mechanism<String> {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.qualifier == other.qualifier
+            && match (&self.rrdata, &other.rrdata) {
+                (Some(rrdata), Some(other)) => {
+                    helpers::lowercase_domain_spec(rrdata) == helpers::lowercase_domain_spec(other)
+                }
+                (rrdata, other) => rrdata == other,
+            }
+    }
+}
+
+impl Eq for Mechanism<String> {}
+
+/// Hashes the [`Kind`](Kind), [`Qualifier`](Qualifier) and `rrdata`, with the domain-spec
+/// lowercased as for equality. Mechanisms which are equal have the same hash.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::Mechanism;
+/// use std::collections::HashSet;
+/// let mut set: HashSet<Mechanism<String>> = HashSet::new();
+/// set.insert("a:Example.com".parse().unwrap());
+/// assert_eq!(set.contains(&"+a:example.com".parse().unwrap()), true);
+/// assert_eq!(set.contains(&"-a:example.com".parse().unwrap()), false);
+///```
+impl Hash for Mechanism<String> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.qualifier.hash(state);
+        self.rrdata
+            .as_deref()
+            .map(helpers::lowercase_domain_spec)
+            .hash(state);
+    }
+}
+
+/// Two ip mechanisms are equal when their [`Kind`](Kind), [`Qualifier`](Qualifier) and network
+/// are equal. A network written without a prefix length is equal to the same network written
+/// with `/32` or `/128`.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::Mechanism;
+/// use ipnetwork::IpNetwork;
+/// let host: Mechanism<IpNetwork> = "ip4:203.0.113.1".parse().unwrap();
+/// assert_eq!(host, "+ip4:203.0.113.1/32".parse().unwrap());
+/// assert_ne!(host, "-ip4:203.0.113.1".parse().unwrap());
+///```
+impl PartialEq for Mechanism<IpNetwork> {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.qualifier == other.qualifier && self.rrdata == other.rrdata
+    }
+}
+
+impl Eq for Mechanism<IpNetwork> {}
+
+/// Hashes the [`Kind`](Kind), [`Qualifier`](Qualifier) and network. Mechanisms which are equal
+/// have the same hash.
+impl Hash for Mechanism<IpNetwork> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.qualifier.hash(state);
+        self.rrdata.hash(state);
+    }
 }
 
 /// Create a Mechanism<String> from the provided string.
@@ -61,30 +155,47 @@ impl FromStr for Mechanism<String> {
         if s.ends_with(':') || s.ends_with('/') {
             return Err(MechanismError::InvalidMechanismFormat(s.to_string()));
         };
-        if s.contains("ip4:") || s.contains("ip6:") {
-            return Err(MechanismError::InvalidMechanismFormat(s.to_string()));
+        if helpers::malformed_dual_cidr(s) {
+            return Err(MechanismError::InvalidDualCidr(s.to_string()));
+        }
+        if helpers::is_ip_term(s) {
+            return Err(MechanismError::IpNetworkTerm(s.to_string()));
+        }
+        if let Some(domain) = helpers::domain_with_empty_label(s) {
+            return Err(MechanismError::InvalidDomainHost(domain.to_string()));
+        }
+        if let Some(kind) = helpers::qualified_modifier(s) {
+            return Err(MechanismError::QualifierNotAllowedForModifier(kind));
         }
         let mut m: Option<Mechanism<String>> = None;
 
-        if s.contains("redirect=") {
-            let mut items = s.rsplit('=');
-            if let Some(rrdata) = items.next() {
+        if let Some(rrdata) = helpers::strip_prefix_ignore_case(s, "redirect=") {
+            // The target is everything after `redirect=` and may itself contain `=`.
+            if !rrdata.is_empty() {
                 m = Some(Mechanism::generic_inclusive(
                     Kind::Redirect,
                     Qualifier::Pass,
                     Some(rrdata.to_string()),
                 ));
             }
-        } else if s.contains("include:") {
-            let qualifier_and_modified_str = helpers::return_and_remove_qualifier(s, 'i');
-            if let Some(rrdata) = s.rsplit(':').next() {
+        } else if let Some(rrdata) = helpers::strip_prefix_ignore_case(s, "exp=") {
+            if !rrdata.is_empty() {
                 m = Some(Mechanism::generic_inclusive(
-                    Kind::Include,
-                    qualifier_and_modified_str.0,
+                    Kind::Exp,
+                    Qualifier::Pass,
                     Some(rrdata.to_string()),
                 ));
             }
-        } else if s.ends_with("all") && (s.len() == 3 || s.len() == 4) {
+        } else if let Some(rrdata) =
+            helpers::strip_prefix_ignore_case(helpers::strip_qualifier(s), "include:")
+        {
+            // The domain-spec is everything after `include:` and may itself contain `:`.
+            m = Some(Mechanism::generic_inclusive(
+                Kind::Include,
+                helpers::return_and_remove_qualifier(s, 'i').0,
+                Some(rrdata.to_string()),
+            ));
+        } else if helpers::is_all_term(s) {
             m = Some(Mechanism::all(
                 helpers::return_and_remove_qualifier(s, 'a').0,
             ));
@@ -95,11 +206,16 @@ impl FromStr for Mechanism<String> {
         } else if let Some(ptr_mechanism) = helpers::capture_matches(s, Kind::Ptr) {
             m = Some(ptr_mechanism);
         } else if let Some(exists_mechanism) = helpers::capture_matches(s, Kind::Exists) {
-            if !exists_mechanism.raw().contains('/') {
+            if !helpers::after_macros(&exists_mechanism.raw()).contains('/') {
                 m = Some(exists_mechanism);
             }
         }
         if let Some(value) = m {
+            if value.kind().is_a() || value.kind().is_mx() {
+                if let Some((kind, length)) = helpers::invalid_dual_cidr_length(&value.raw()) {
+                    return Err(cidr_length_error(kind, length));
+                }
+            }
             #[cfg(feature = "strict-dns")]
             {
                 if !helpers::dns_is_valid(helpers::get_domain_before_slash(&value.raw())) {
@@ -108,10 +224,22 @@ impl FromStr for Mechanism<String> {
             }
             return Ok(value);
         }
+        if let Some(name) = helpers::unknown_mechanism_name(s) {
+            return Err(MechanismError::UnknownMechanismName(name.to_string()));
+        }
         Err(MechanismError::InvalidMechanismFormat(s.to_string()))
     }
 }
 
+// The error for a prefix length which is out of range for the family of `kind`.
+fn cidr_length_error(kind: Kind, length: &str) -> MechanismError {
+    if kind.is_ip_v4() {
+        MechanismError::InvalidIPv4CidrLength(length.to_string())
+    } else {
+        MechanismError::InvalidIPv6CidrLength(length.to_string())
+    }
+}
+
 impl TryFrom<&str> for Mechanism<String> {
     type Error = MechanismError;
 
@@ -133,7 +261,7 @@ impl TryFrom<&str> for Mechanism<String> {
 /// assert_eq!(ip6.kind().is_ip_v6(), true);
 ///
 /// let bad_ip4: Result<Mechanism<IpNetwork>, MechanismError> = "ip4:203.32.160.0/33".parse();
-/// assert_eq!(bad_ip4.unwrap_err().to_string(), "invalid address: 203.32.160.0/33");
+/// assert_eq!(bad_ip4.unwrap_err().to_string(), "33 is not a valid ip4 cidr length");
 ///
 /// let ip6_but_ip4: Result<Mechanism<IpNetwork>, MechanismError> = "ip6:203.32.160.0/24".parse();
 /// let err = ip6_but_ip4.unwrap_err();
@@ -144,46 +272,37 @@ impl FromStr for Mechanism<IpNetwork> {
     type Err = MechanismError;
 
     fn from_str(s: &str) -> Result<Mechanism<IpNetwork>, Self::Err> {
-        if s.contains("ip4:") || s.contains("ip6:") {
-            let mut kind = Kind::IpV4;
-            let mut raw_ip: Option<&str> = None;
-            let qualifier_and_modified_str = helpers::return_and_remove_qualifier(s, 'i');
-            if qualifier_and_modified_str.1.contains("ip4") {
-                kind = Kind::IpV4;
-                raw_ip = qualifier_and_modified_str.1.strip_prefix("ip4:");
-            } else if qualifier_and_modified_str.1.contains("ip6") {
-                kind = Kind::IpV6;
-                raw_ip = qualifier_and_modified_str.1.strip_prefix("ip6:")
-            };
-            // Consider changing this to if let Some() = {}
-            let parsed = raw_ip.unwrap().parse();
-            if let Ok(parsed_ip) = parsed {
-                let ip: IpNetwork = parsed_ip;
-                if ip.is_ipv4() && kind.is_ip_v4() {
-                    return Ok(Mechanism::generic_inclusive(
-                        kind,
-                        qualifier_and_modified_str.0,
-                        Some(ip),
-                    ));
-                } else if ip.is_ipv4() && !kind.is_ip_v4() {
-                    return Err(MechanismError::NotIP6Network(ip.to_string()));
-                } else if ip.is_ipv6() && kind.is_ip_v6() {
-                    return Ok(Mechanism::generic_inclusive(
-                        kind,
-                        qualifier_and_modified_str.0,
-                        Some(ip),
-                    ));
-                } else if ip.is_ipv6() && !kind.is_ip_v6() {
-                    return Err(MechanismError::NotIP4Network(ip.to_string()));
-                };
-            } else {
-                return Err(MechanismError::InvalidIPNetwork(
-                    parsed.unwrap_err().to_string(),
-                ));
-            };
-        }
         // Catch all. This is not an ip4 or ip6 spf string.
-        Err(MechanismError::InvalidMechanismFormat(s.to_string()))
+        let invalid_format = || MechanismError::InvalidMechanismFormat(s.to_string());
+        if !helpers::is_ip_term(s) {
+            return Err(invalid_format());
+        }
+        let (qualifier, term) = helpers::return_and_remove_qualifier(s, 'i');
+        let (kind, raw_ip) = if let Some(raw_ip) = helpers::strip_prefix_ignore_case(term, "ip4:") {
+            (Kind::IpV4, raw_ip)
+        } else if let Some(raw_ip) = helpers::strip_prefix_ignore_case(term, "ip6:") {
+            (Kind::IpV6, raw_ip)
+        } else {
+            return Err(invalid_format());
+        };
+        if raw_ip.is_empty() {
+            return Err(MechanismError::EmptyIpValue(s.to_string()));
+        }
+        if raw_ip.contains(char::is_whitespace) {
+            return Err(invalid_format());
+        }
+        match raw_ip.parse::<IpNetwork>() {
+            Ok(ip) => {
+                helpers::check_ip_family(&kind, &ip)?;
+                let mut m = Mechanism::generic_inclusive(kind, qualifier, Some(ip));
+                m.preserve_implicit_prefix(raw_ip);
+                Ok(m)
+            }
+            Err(error) => match helpers::invalid_cidr_length(raw_ip, &kind) {
+                Some(length) => Err(cidr_length_error(kind, length)),
+                None => Err(MechanismError::InvalidIPNetwork(error.to_string())),
+            },
+        }
     }
 }
 
@@ -204,14 +323,62 @@ impl<T> Mechanism<T> {
             kind,
             qualifier,
             rrdata: mechanism,
+            name: None,
+            explicit_pass: false,
+            implicit_prefix: false,
         }
     }
+    // Unchecked. For a `Mechanism<String>`, `try_new()` checks the kind against the rrdata.
     #[doc(hidden)]
     pub fn new(kind: Kind, qualifier: Qualifier) -> Self {
         Self {
             kind,
             qualifier,
             rrdata: None,
+            name: None,
+            explicit_pass: false,
+            implicit_prefix: false,
+        }
+    }
+    // Retain the name of the mechanism as it was written in `term` when it is not in
+    // lowercase. The name is then used in place of `Kind::as_str()` when building a string.
+    pub(crate) fn preserve_name_case(&mut self, term: &str) {
+        let term = helpers::strip_qualifier(term);
+        let name = self.kind.as_str();
+        match term.get(..name.len()) {
+            Some(written) if written != name && written.eq_ignore_ascii_case(name) => {
+                self.name = Some(written.to_string());
+            }
+            _ => {}
+        }
+    }
+    // Remember that the `Pass` qualifier of a mechanism was written as an explicit `+` in `term`.
+    // Modifiers take no qualifier and are never marked.
+    pub(crate) fn preserve_explicit_pass(&mut self, term: &str) {
+        self.explicit_pass =
+            self.qualifier == Qualifier::Pass && !self.kind.is_modifier() && term.starts_with('+');
+    }
+    // Forget how the mechanism was written, so its name is written in lowercase and a `Pass`
+    // qualifier is not written.
+    pub(crate) fn clear_presentation(&mut self) {
+        self.name = None;
+        self.explicit_pass = false;
+    }
+    // The qualifier as it should appear in a string. Modifiers never have a qualifier.
+    fn qualifier_str(&self) -> &str {
+        if self.kind.is_modifier() {
+            ""
+        } else if self.explicit_pass {
+            "+"
+        } else {
+            self.qualifier.as_str()
+        }
+    }
+    // The mechanism name as it should appear in a string.
+    fn name_str(&self) -> &str {
+        match &self.name {
+            Some(name) => name.as_str(),
+            None => self.kind.as_str(),
         }
     }
     /// Check mechanism is pass
@@ -238,6 +405,37 @@ impl<T> Mechanism<T> {
     pub fn qualifier(&self) -> &Qualifier {
         &self.qualifier
     }
+    /// Set the Mechanism's Qualifier. A `Pass` qualifier is not written when building a
+    /// string, unless the mechanism was parsed from an explicit `+` and its qualifier is
+    /// still `Pass`. Modifiers never write a qualifier.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// let mut all: Mechanism<String> = "-all".parse().unwrap();
+    /// all.set_qualifier(Qualifier::SoftFail);
+    /// assert_eq!(all.to_string(), "~all");
+    /// all.set_qualifier(Qualifier::Pass);
+    /// assert_eq!(all.to_string(), "all");
+    ///```
+    pub fn set_qualifier(&mut self, qualifier: Qualifier) {
+        self.qualifier = qualifier;
+        self.explicit_pass &= qualifier == Qualifier::Pass;
+    }
+    /// Returns the Mechanism with its Qualifier set to `qualifier`. See
+    /// [`set_qualifier()`](Mechanism::set_qualifier)
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use ipnetwork::IpNetwork;
+    /// let ip4: Mechanism<IpNetwork> = "ip4:203.0.113.0/24".parse().unwrap();
+    /// assert_eq!(ip4.with_qualifier(Qualifier::Fail).to_string(), "-ip4:203.0.113.0/24");
+    ///```
+    pub fn with_qualifier(mut self, qualifier: Qualifier) -> Self {
+        self.set_qualifier(qualifier);
+        self
+    }
     /// Returns a reference to the Mechanism's Value.  
     /// This could return a `String`, `IpNetwork`, or `None`
     pub fn mechanism(&self) -> &Option<T> {
@@ -245,16 +443,226 @@ impl<T> Mechanism<T> {
     }
 }
 
+impl<T> Mechanism<T>
+where
+    Mechanism<T>: std::fmt::Display,
+{
+    /// Returns the number of characters the mechanism adds to a record, including the space
+    /// which separates it from the term before. This is one more than the length of
+    /// `to_string()`, and includes the qualifier when it is written.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use ipnetwork::IpNetwork;
+    /// let include = Mechanism::include(Qualifier::SoftFail, "_spf.example.com").unwrap();
+    /// assert_eq!(include.serialized_len(), " ~include:_spf.example.com".len());
+    /// let ip4: Mechanism<IpNetwork> = "ip4:192.0.2.0/24".parse().unwrap();
+    /// assert_eq!(ip4.serialized_len(), 17);
+    ///```
+    pub fn serialized_len(&self) -> usize {
+        1 + helpers::display_len(self)
+    }
+}
+
 impl Mechanism<String> {
+    /// Returns `true` if the domain-spec of the mechanism contains macros.
+    ///
+    /// A domain-spec containing macros is stored and rebuilt exactly as written.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 exists:%{ir}.%{l1r+-}._spf.%{d} -all".parse().unwrap();
+    /// let m = &spf.exists().unwrap()[0];
+    /// assert_eq!(m.has_macros(), true);
+    /// assert_eq!(m.raw(), "%{ir}.%{l1r+-}._spf.%{d}");
+    /// assert_eq!(m.to_string(), "exists:%{ir}.%{l1r+-}._spf.%{d}");
+    ///```
+    pub fn has_macros(&self) -> bool {
+        match &self.rrdata {
+            Some(rrdata) => helpers::has_macros(rrdata),
+            None => false,
+        }
+    }
+    // Each violation of the macro-string syntax within the domain-spec, as its byte offset
+    // within `raw()` and a description.
+    pub(crate) fn macro_syntax_errors(&self) -> Vec<(usize, String)> {
+        match &self.rrdata {
+            Some(rrdata) if helpers::has_macros(rrdata) => {
+                macros::macro_syntax_errors(rrdata, false)
+            }
+            _ => Vec::new(),
+        }
+    }
+    // Lowercase the domain-spec, leaving any macros unchanged.
+    pub(crate) fn lowercase_rrdata(&mut self) {
+        if let Some(rrdata) = &self.rrdata {
+            self.rrdata = Some(helpers::lowercase_domain_spec(rrdata));
+        }
+    }
+    /// Returns the domain-spec of the mechanism without any cidr suffix. Returns `None` when
+    /// there is no domain-spec, such as for `all`, `mx` or `a/24`.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::Mechanism;
+    /// let mx: Mechanism<String> = "mx:example.com/24".parse().unwrap();
+    /// assert_eq!(mx.domain(), Some("example.com"));
+    /// let mx: Mechanism<String> = "mx/24".parse().unwrap();
+    /// assert_eq!(mx.domain(), None);
+    ///```
+    pub fn domain(&self) -> Option<&str> {
+        let rrdata = self.rrdata.as_deref()?;
+        let domain = match self.kind {
+            Kind::A | Kind::MX => helpers::split_cidr(rrdata).0,
+            _ => rrdata,
+        };
+        if domain.is_empty() {
+            None
+        } else {
+            Some(domain)
+        }
+    }
+    /// Returns the ip4 and ip6 prefix lengths of an `a` or `mx` mechanism. Returns `None` when
+    /// there is no cidr suffix, or for any other kind of mechanism.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::Mechanism;
+    /// let mx: Mechanism<String> = "mx:example.com/24//64".parse().unwrap();
+    /// assert_eq!(mx.cidr(), Some((Some(24), Some(64))));
+    /// let a: Mechanism<String> = "a//64".parse().unwrap();
+    /// assert_eq!(a.cidr(), Some((None, Some(64))));
+    /// let a: Mechanism<String> = "a:example.com".parse().unwrap();
+    /// assert_eq!(a.cidr(), None);
+    ///```
+    pub fn cidr(&self) -> Option<(Option<u8>, Option<u8>)> {
+        if !self.kind.is_a() && !self.kind.is_mx() {
+            return None;
+        }
+        let (_, suffix) = helpers::split_cidr(self.rrdata.as_deref()?);
+        if suffix.is_empty() {
+            return None;
+        }
+        let (ip4, ip6) = match suffix.find("//") {
+            Some(i) => (&suffix[..i], Some(&suffix[i + 2..])),
+            None => (suffix, None),
+        };
+        let ip4 = ip4.strip_prefix('/').and_then(|length| length.parse().ok());
+        let ip6 = ip6.and_then(|length| length.parse().ok());
+        Some((ip4, ip6))
+    }
+    /// Expand the macros of the domain-spec, returning the domain name which would be
+    /// queried for the message described by `context`.
+    ///
+    /// The cidr suffix of `a` and `mx` is not part of the domain name. Without a domain-spec
+    /// `a`, `mx` and `ptr` use the domain being checked. `all` has no domain name.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::{MacroContext, Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let context = MacroContext::new("strong-bad@email.example.com",
+    ///                                 "192.0.2.3".parse().unwrap(),
+    ///                                 "mx.example.org");
+    /// let spf: Spf = "v=spf1 a:%{d2}/24 -all".parse().unwrap();
+    /// let a = &spf.a().unwrap()[0];
+    /// assert_eq!(a.expand_macros(&context).unwrap(), "example.com");
+    /// let mx = Mechanism::mx(Qualifier::Pass);
+    /// assert_eq!(mx.expand_macros(&context).unwrap(), "email.example.com");
+    ///```
+    pub fn expand_macros(&self, context: &MacroContext) -> Result<String, MacroError> {
+        let rrdata = self.rrdata.as_deref().unwrap_or_default();
+        let domain_spec = match self.kind {
+            Kind::All => return Err(MacroError::NoDomainSpec(self.kind)),
+            Kind::A | Kind::MX => helpers::split_cidr(rrdata).0,
+            _ => rrdata,
+        };
+        if domain_spec.is_empty() {
+            context.expand_domain_spec("%{d}")
+        } else {
+            context.expand_domain_spec(domain_spec)
+        }
+    }
+    /// Create a new Mechanism of any [`Kind`](Kind) which can be held as a string, checking
+    /// that `rrdata` is given only where the kind allows it.
+    ///
+    /// `rrdata` is **required** for `Include`, `Exists`, `Redirect` and `Exp`, **optional** for
+    /// `A`, `MX` and `Ptr` and **not allowed** for `All`.
+    ///
+    /// # Errors:
+    /// - [`IpNetworkTerm`](MechanismError::IpNetworkTerm) for `IpV4` and `IpV6`. These are
+    ///   held as a `Mechanism<IpNetwork>`.
+    /// - [`QualifierNotAllowedForModifier`](MechanismError::QualifierNotAllowedForModifier)
+    ///   if `qualifier` is not `Pass` for `Redirect` or `Exp`.
+    /// - Any error from [`set_rrdata()`](Mechanism::set_rrdata).
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
+    /// let include =
+    ///     Mechanism::try_new(Kind::Include, Qualifier::SoftFail, Some("_spf.example.com".into()));
+    /// assert_eq!(include.unwrap().to_string(), "~include:_spf.example.com");
+    /// assert_eq!(
+    ///     Mechanism::try_new(Kind::All, Qualifier::Pass, Some("junk".into())).unwrap_err(),
+    ///     MechanismError::RrdataNotAllowed(Kind::All)
+    /// );
+    /// assert_eq!(
+    ///     Mechanism::try_new(Kind::Include, Qualifier::Pass, None).unwrap_err(),
+    ///     MechanismError::RrdataRequired(Kind::Include)
+    /// );
+    ///```
+    pub fn try_new(
+        kind: Kind,
+        qualifier: Qualifier,
+        rrdata: Option<String>,
+    ) -> Result<Self, MechanismError> {
+        match kind {
+            Kind::IpV4 | Kind::IpV6 => {
+                let term = format!("{}{}", kind, rrdata.as_deref().unwrap_or_default());
+                return Err(MechanismError::IpNetworkTerm(term));
+            }
+            Kind::Redirect | Kind::Exp if qualifier != Qualifier::Pass => {
+                return Err(MechanismError::QualifierNotAllowedForModifier(kind));
+            }
+            _ => {}
+        }
+        let mut mechanism = Self::new(kind, qualifier);
+        mechanism.set_rrdata(rrdata)?;
+        Ok(mechanism)
+    }
     /// Create a new Mechanism struct of `Redirect`
+    ///
+    /// Modifiers take no qualifier. `qualifier` is ignored and the modifier is always created
+    /// as [`Qualifier::Pass`].
     #[deprecated(note = "This will be depreciated in 0.3.0. Please use `redirect()` instead")]
-    pub fn new_redirect(qualifier: Qualifier, mechanism: String) -> Self {
-        Mechanism::generic_inclusive(Kind::Redirect, qualifier, Some(mechanism))
+    pub fn new_redirect(_qualifier: Qualifier, mechanism: String) -> Self {
+        Mechanism::generic_inclusive(Kind::Redirect, Qualifier::Pass, Some(mechanism))
     }
 
     /// Create a new Mechanism struct of `Redirect`
-    pub fn redirect(qualifier: Qualifier, rrdata: &str) -> Result<Self, MechanismError> {
-        Ok(Mechanism::new(Kind::Redirect, qualifier).with_rrdata(rrdata)?)
+    ///
+    /// Modifiers take no qualifier. `qualifier` is ignored and the modifier is always created
+    /// as [`Qualifier::Pass`].
+    ///
+    /// # Example:
+    /// ```rust
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// let redirect = Mechanism::redirect(Qualifier::Fail, "_spf.example.com").unwrap();
+    /// assert_eq!(redirect.is_pass(), true);
+    /// assert_eq!(redirect.to_string(), "redirect=_spf.example.com");
+    /// ```
+    pub fn redirect(_qualifier: Qualifier, rrdata: &str) -> Result<Self, MechanismError> {
+        Mechanism::try_new(Kind::Redirect, Qualifier::Pass, Some(rrdata.to_string()))
+    }
+    /// Create a new Mechanism struct of `Exp`
+    ///
+    /// # Example:
+    /// ```rust
+    /// use decon_spf::mechanism::Mechanism;
+    /// let exp = Mechanism::exp("explain._spf.example.com").unwrap();
+    /// assert_eq!(exp.kind().is_exp(), true);
+    /// assert_eq!(exp.to_string(), "exp=explain._spf.example.com");
+    /// ```
+    pub fn exp(rrdata: &str) -> Result<Self, MechanismError> {
+        Mechanism::try_new(Kind::Exp, Qualifier::Pass, Some(rrdata.to_string()))
     }
     /// Create a new Mechanism struct of `A` with no string value.
     #[deprecated(note = "This will be depreciated in 0.3.0. Please use `a()` instead")]
@@ -363,7 +771,7 @@ impl Mechanism<String> {
     /// assert_eq!(include2.to_string(), "~include:example.com")
     /// ```
     pub fn include(qualifier: Qualifier, rrdata: &str) -> Result<Self, MechanismError> {
-        Ok(Mechanism::new(Kind::Include, qualifier).with_rrdata(rrdata)?)
+        Mechanism::try_new(Kind::Include, qualifier, Some(rrdata.to_string()))
     }
 
     /// Create a new Mechanism struct of `Ptr` with no value
@@ -406,7 +814,7 @@ impl Mechanism<String> {
 
     /// Create a new Mechanism struct of `Exists`
     pub fn exists(qualifier: Qualifier, rrdata: &str) -> Result<Self, MechanismError> {
-        Ok(Mechanism::new(Kind::Exists, qualifier).with_rrdata(rrdata)?)
+        Mechanism::try_new(Kind::Exists, qualifier, Some(rrdata.to_string()))
     }
     /// Set the rrdata for Mechanism
     /// # Note: This is only applicable for Mechanisms of `A`, `MX` and `Ptr`.  
@@ -415,16 +823,7 @@ impl Mechanism<String> {
     /// See: [`a`](Mechanism<String>::a) for an example.
     pub fn with_rrdata(mut self, rrdata: &str) -> Result<Self, MechanismError> {
         #[cfg(feature = "strict-dns")]
-        {
-            match self.kind() {
-                Kind::A | Kind::MX | Kind::Include | Kind::Ptr | Kind::Exists => {
-                    if !helpers::dns_is_valid(helpers::get_domain_before_slash(&rrdata)) {
-                        return Err(MechanismError::InvalidDomainHost(rrdata.to_string()));
-                    };
-                }
-                _ => {}
-            };
-        }
+        self.check_domain(rrdata)?;
         match self.kind() {
             // Ensure that `All` is always None even if with_rrdata() is called
             Kind::All => self.rrdata = None,
@@ -432,6 +831,60 @@ impl Mechanism<String> {
         }
         Ok(self)
     }
+    /// Replace the rrdata of the Mechanism, keeping its [`Kind`](Kind) and
+    /// [`Qualifier`](Qualifier).
+    ///
+    /// # Errors:
+    /// - [`RrdataNotAllowed`](MechanismError::RrdataNotAllowed) if `rrdata` is `Some` for `All`.
+    /// - [`RrdataRequired`](MechanismError::RrdataRequired) if `rrdata` is `None` for
+    ///   `Include`, `Exists`, `Redirect` or `Exp`.
+    /// - [`InvalidDomainHost`](MechanismError::InvalidDomainHost) if `rrdata` is empty or
+    ///   contains whitespace. With the `strict-dns` feature, also if the domain is not valid.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::{Mechanism, MechanismError, Kind};
+    /// let mut include: Mechanism<String> = "~include:old.example.net".parse().unwrap();
+    /// include.set_rrdata(Some("new.example.net".to_string())).unwrap();
+    /// assert_eq!(include.to_string(), "~include:new.example.net");
+    /// assert_eq!(
+    ///     include.set_rrdata(None).unwrap_err(),
+    ///     MechanismError::RrdataRequired(Kind::Include)
+    /// );
+    ///```
+    pub fn set_rrdata(&mut self, rrdata: Option<String>) -> Result<(), MechanismError> {
+        match &rrdata {
+            Some(_) if self.kind == Kind::All => {
+                return Err(MechanismError::RrdataNotAllowed(self.kind));
+            }
+            Some(value) if value.is_empty() || value.contains(char::is_whitespace) => {
+                return Err(MechanismError::InvalidDomainHost(value.to_string()));
+            }
+            #[cfg(feature = "strict-dns")]
+            Some(value) => self.check_domain(value)?,
+            None if matches!(
+                self.kind,
+                Kind::Include | Kind::Exists | Kind::Redirect | Kind::Exp
+            ) =>
+            {
+                return Err(MechanismError::RrdataRequired(self.kind));
+            }
+            _ => {}
+        }
+        self.rrdata = rrdata;
+        Ok(())
+    }
+    #[cfg(feature = "strict-dns")]
+    fn check_domain(&self, rrdata: &str) -> Result<(), MechanismError> {
+        match self.kind() {
+            Kind::A | Kind::MX | Kind::Include | Kind::Ptr | Kind::Exists
+                if !helpers::dns_is_valid(helpers::get_domain_before_slash(rrdata)) =>
+            {
+                Err(MechanismError::InvalidDomainHost(rrdata.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
     /// Create a new Mechanism struct of `All`
     #[deprecated(note = "This will  be depreciated in 0.3.0. Please use `all()` instead")]
     pub fn new_all(qualifier: Qualifier) -> Self {
@@ -455,38 +908,31 @@ impl Mechanism<String> {
     ///                                                          String::from("example.com"));
     /// assert_eq!(mechanism_a_string.raw(), "example.com");
     pub fn raw(&self) -> String {
-        if self.rrdata.is_none() {
-            self.kind().to_string()
-        } else {
-            self.rrdata.as_ref().unwrap().to_string()
+        match &self.rrdata {
+            None => self.kind().to_string(),
+            Some(rrdata) => rrdata.to_string(),
         }
     }
 
     fn build_string(&self) -> String {
         let mut mechanism_str = String::new();
         let tmp_mechanism_str;
-        if self.qualifier != Qualifier::Pass {
-            mechanism_str.push_str(self.qualifier.as_str());
-        };
-        mechanism_str.push_str(self.kind().as_str());
+        mechanism_str.push_str(self.qualifier_str());
+        mechanism_str.push_str(self.name_str());
         if let Some(ref rrdata) = self.rrdata {
             tmp_mechanism_str = rrdata.as_str();
         } else {
             tmp_mechanism_str = "";
         }
         match self.kind {
-            Kind::A | Kind::MX => {
-                // This must be starting with 'domain.com' So prepend ':'
-                if !tmp_mechanism_str.is_empty() && !tmp_mechanism_str.starts_with('/') {
-                    mechanism_str.push(':')
-                }
-            }
-            Kind::Ptr => {
-                // This Ptr has a domain. Prepend ':'
-                if !tmp_mechanism_str.is_empty() {
-                    mechanism_str.push(':')
-                }
+            // This must be starting with 'domain.com' So prepend ':'
+            Kind::A | Kind::MX
+                if !tmp_mechanism_str.is_empty() && !tmp_mechanism_str.starts_with('/') =>
+            {
+                mechanism_str.push(':')
             }
+            // This Ptr has a domain. Prepend ':'
+            Kind::Ptr if !tmp_mechanism_str.is_empty() => mechanism_str.push(':'),
             // Do nothing in all other cases.
             _ => {}
         }
@@ -496,7 +942,6 @@ impl Mechanism<String> {
 }
 
 /// Provide to_string for Mechanism<String>
-
 impl std::fmt::Display for Mechanism<String> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.build_string())
@@ -525,7 +970,7 @@ impl Mechanism<IpNetwork> {
     /// }
     ///```
     pub fn ip_from_string(string: &str) -> Result<Mechanism<IpNetwork>, MechanismError> {
-        Ok(Mechanism::<IpNetwork>::from_str(string)?)
+        Mechanism::<IpNetwork>::from_str(string)
     }
 
     /// Create a new V4 or V6 Mechanism<IpNetwork>  
@@ -606,12 +1051,51 @@ impl Mechanism<IpNetwork> {
         self.rrdata.unwrap().to_string()
     }
 
+    // Remember that the network was written in `raw` without a prefix length.
+    pub(crate) fn preserve_implicit_prefix(&mut self, raw: &str) {
+        self.implicit_prefix = !raw.contains('/');
+    }
+
+    // Write a single host without its prefix length, and any other network with it.
+    pub(crate) fn omit_host_prefix(&mut self) {
+        let network = self.as_network();
+        let host = if network.is_ipv4() { 32 } else { 128 };
+        self.implicit_prefix = network.prefix() == host;
+    }
+
     fn build_string(&self) -> String {
         let mut ip_mechanism_str = String::new();
-        if self.qualifier != Qualifier::Pass {
-            ip_mechanism_str.push_str(self.qualifier.as_str());
-        };
-        ip_mechanism_str.push_str(self.kind().as_str());
+        ip_mechanism_str.push_str(self.qualifier_str());
+        ip_mechanism_str.push_str(self.name_str());
+        let network = self.rrdata.unwrap();
+        if self.implicit_prefix {
+            ip_mechanism_str.push_str(network.ip().to_string().as_str());
+        } else {
+            ip_mechanism_str.push_str(network.to_string().as_str());
+        }
+        ip_mechanism_str
+    }
+
+    /// Returns the string representation of the mechanism, always including the prefix length.
+    ///
+    /// A network written without a prefix length, such as `ip4:203.0.113.4`, is rebuilt by
+    /// `to_string()` as it was written. `to_string_with_prefix()` includes the implied `/32` or
+    /// `/128`.
+    ///
+    /// # Example
+    ///
+    ///```
+    /// use decon_spf::mechanism::Mechanism;
+    /// use ipnetwork::IpNetwork;
+    /// let ip4: Mechanism<IpNetwork> = "ip4:203.0.113.4".parse().unwrap();
+    /// assert_eq!(ip4.to_string(), "ip4:203.0.113.4");
+    /// assert_eq!(ip4.to_string_with_prefix(), "ip4:203.0.113.4/32");
+    /// assert_eq!(ip4.as_network().prefix(), 32);
+    ///```
+    pub fn to_string_with_prefix(&self) -> String {
+        let mut ip_mechanism_str = String::new();
+        ip_mechanism_str.push_str(self.qualifier.as_str());
+        ip_mechanism_str.push_str(self.kind.as_str());
         ip_mechanism_str.push_str(self.rrdata.unwrap().to_string().as_str());
         ip_mechanism_str
     }
@@ -620,6 +1104,70 @@ impl Mechanism<IpNetwork> {
     pub fn as_network(&self) -> &IpNetwork {
         self.rrdata.as_ref().unwrap()
     }
+    /// Replace the network of the Mechanism, keeping its [`Qualifier`](Qualifier). The
+    /// [`Kind`](Kind) is changed to `IpV4` or `IpV6` to match the address family of `rrdata`.
+    /// The prefix length is always written.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::Mechanism;
+    /// use ipnetwork::IpNetwork;
+    /// let mut ip: Mechanism<IpNetwork> = "-ip4:203.0.113.0/24".parse().unwrap();
+    /// ip.set_rrdata("2001:db8::/32".parse().unwrap());
+    /// assert_eq!(ip.kind().is_ip_v6(), true);
+    /// assert_eq!(ip.to_string(), "-ip6:2001:db8::/32");
+    ///```
+    pub fn set_rrdata(&mut self, rrdata: IpNetwork) {
+        self.kind = if rrdata.is_ipv4() {
+            Kind::IpV4
+        } else {
+            Kind::IpV6
+        };
+        self.rrdata = Some(rrdata);
+        self.implicit_prefix = false;
+    }
+
+    /// Returns `false` if the address has bits set below the prefix length.
+    /// e.g. `ip4:192.168.1.17/24` matches all of `192.168.1.0/24`.
+    ///
+    /// # Example
+    ///
+    ///```
+    /// use decon_spf::mechanism::Mechanism;
+    /// use ipnetwork::IpNetwork;
+    /// let ip4: Mechanism<IpNetwork> = "ip4:192.168.1.17/24".parse().unwrap();
+    /// assert_eq!(ip4.is_normalized(), false);
+    /// let ip4: Mechanism<IpNetwork> = "ip4:192.168.1.0/24".parse().unwrap();
+    /// assert_eq!(ip4.is_normalized(), true);
+    ///```
+    pub fn is_normalized(&self) -> bool {
+        let network = self.as_network();
+        network.ip() == network.network()
+    }
+
+    /// Returns a copy of the mechanism with any bits below the prefix length cleared, so its
+    /// address is the network address which is actually matched.
+    ///
+    /// Not to be confused with [`to_string_with_prefix()`](Mechanism::to_string_with_prefix),
+    /// which only adds the implied prefix length to the string representation.
+    ///
+    /// # Example
+    ///
+    ///```
+    /// use decon_spf::mechanism::Mechanism;
+    /// use ipnetwork::IpNetwork;
+    /// let ip4: Mechanism<IpNetwork> = "-ip4:192.168.1.17/24".parse().unwrap();
+    /// let network = ip4.normalized();
+    /// assert_eq!(network.is_normalized(), true);
+    /// assert_eq!(network.to_string(), "-ip4:192.168.1.0/24");
+    ///```
+    pub fn normalized(&self) -> Mechanism<IpNetwork> {
+        let network = self.as_network();
+        let mut normalized = self.clone();
+        // The prefix is taken from a valid network, so it is always in range.
+        normalized.rrdata = IpNetwork::new(network.network(), network.prefix()).ok();
+        normalized
+    }
 }
 
 /// Provide to_string for Mechanism<IpNetwork>
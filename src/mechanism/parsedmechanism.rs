@@ -1,3 +1,4 @@
+use crate::helpers;
 use crate::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
 use ipnetwork::IpNetwork;
 
@@ -5,7 +6,7 @@ use std::{convert::TryFrom, str::FromStr};
 
 /// Stores the result of a successful parsing of a Mechanism String.  
 /// This will either contain a `Mechanism<String>` or `Mechanism<IpNetwork>`
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ParsedMechanism {
     /// This variant represents a Mechanism containing a String  
     TXT(Mechanism<String>),
@@ -24,10 +25,16 @@ impl std::fmt::Display for ParsedMechanism {
 
 /// Implement `from_str` for ParsedMechanism.  
 /// Provides the ability to parse any supported `Spf Mechanisms`. See [`Kind`](Kind)
+///
+/// Terms starting with `ip4:` or `ip6:`, after any qualifier, are parsed as a
+/// `Mechanism<IpNetwork>`. All other terms are parsed as a `Mechanism<String>`.
 /// # Examples:
 ///```rust
-/// use decon_spf::mechanism::{ParsedMechanism, MechanismError};
+/// use decon_spf::mechanism::{Kind, ParsedMechanism, MechanismError};
 /// let mechanism_a: ParsedMechanism = "a:test.com/24".parse().unwrap();
+/// let mechanism_ip6 = "~ip6:2001:db8::/32".parse::<ParsedMechanism>().unwrap();
+/// assert_eq!(mechanism_ip6.kind(), &Kind::IpV6);
+/// assert_eq!(mechanism_ip6.qualifier().is_softfail(), true);
 /// let mechanism_mx = "mx:example.com".parse::<ParsedMechanism>().unwrap();
 /// let mechanism_ip4 = "ip4:203.32.160.10/24".parse::<ParsedMechanism>().unwrap();
 ///
@@ -45,7 +52,7 @@ impl FromStr for ParsedMechanism {
     type Err = MechanismError;
 
     fn from_str(s: &str) -> Result<ParsedMechanism, Self::Err> {
-        if s.contains("ip4:") || s.contains("ip6:") {
+        if helpers::is_ip_term(s) {
             Ok(ParsedMechanism::IP(Mechanism::<IpNetwork>::from_str(s)?))
         } else {
             Ok(ParsedMechanism::TXT(Mechanism::<String>::from_str(s)?))
@@ -74,7 +81,7 @@ impl ParsedMechanism {
     ///            MechanismError::InvalidMechanismFormat("ab.com".to_string()));
     ///```
     pub fn new(s: &str) -> Result<ParsedMechanism, MechanismError> {
-        if s.contains("ip4:") || s.contains("ip6:") {
+        if helpers::is_ip_term(s) {
             Ok(ParsedMechanism::IP(Mechanism::<IpNetwork>::from_str(s)?))
         } else {
             Ok(ParsedMechanism::TXT(Mechanism::<String>::from_str(s)?))
@@ -110,22 +117,22 @@ impl ParsedMechanism {
             ParsedMechanism::TXT(_) => unreachable!(),
         }
     }
-    #[allow(dead_code)]
-    fn kind(&self) -> &Kind {
+    /// Returns a reference to the [`Kind`](Kind) of the parsed mechanism.
+    pub fn kind(&self) -> &Kind {
         match *self {
             ParsedMechanism::TXT(ref m) => m.kind(),
             ParsedMechanism::IP(ref m) => m.kind(),
         }
     }
-    #[allow(dead_code)]
-    fn qualifier(&self) -> &Qualifier {
+    /// Returns a reference to the [`Qualifier`](Qualifier) of the parsed mechanism.
+    pub fn qualifier(&self) -> &Qualifier {
         match *self {
             ParsedMechanism::TXT(ref m) => m.qualifier(),
             ParsedMechanism::IP(ref m) => m.qualifier(),
         }
     }
-    #[allow(dead_code)]
-    fn raw(&self) -> String {
+    /// Returns the value of the parsed mechanism. See [`Mechanism::raw()`](Mechanism::raw)
+    pub fn raw(&self) -> String {
         match *self {
             ParsedMechanism::TXT(ref m) => m.raw(),
             ParsedMechanism::IP(ref m) => m.raw(),
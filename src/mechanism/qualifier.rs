@@ -1,10 +1,13 @@
 //! An enumeration of possible qualifiers that are used in Mechanism record.
+use crate::mechanism::MechanismError;
+use std::{convert::TryFrom, str::FromStr};
 
 /// Declaration for possible `Qualifier` of a given Mechanism
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Qualifier {
     /// This is the default value for a qualifier if the value is not present in the SPF record.
     /// It is denoted by '+' (Plus Sign)
+    #[default]
     Pass,
     /// It is denoted by '-' (Minus Sign)
     Fail,
@@ -14,9 +17,53 @@ pub enum Qualifier {
     Neutral,
 }
 
-impl Default for Qualifier {
-    fn default() -> Self {
-        Self::Pass
+/// Create a Qualifier from either its symbol or its name.
+///
+/// The symbols are `+`, `-`, `~` and `?`. An empty string is [`Pass`](Qualifier::Pass).
+/// The names `pass`, `fail`, `softfail` and `neutral` are matched case-insensitively.
+///
+/// # Examples:
+///```rust
+/// # use decon_spf::mechanism::Qualifier;
+/// assert_eq!("~".parse::<Qualifier>().unwrap(), Qualifier::SoftFail);
+/// assert_eq!("SoftFail".parse::<Qualifier>().unwrap(), Qualifier::SoftFail);
+/// assert_eq!("".parse::<Qualifier>().unwrap(), Qualifier::Pass);
+/// assert_eq!("reject".parse::<Qualifier>().is_err(), true);
+///```
+impl FromStr for Qualifier {
+    type Err = MechanismError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "" | "+" | "pass" => Ok(Qualifier::Pass),
+            "-" | "fail" => Ok(Qualifier::Fail),
+            "~" | "softfail" => Ok(Qualifier::SoftFail),
+            "?" | "neutral" => Ok(Qualifier::Neutral),
+            _ => Err(MechanismError::InvalidQualifier(s.to_string())),
+        }
+    }
+}
+
+/// Create a Qualifier from its symbol. `+`, `-`, `~` or `?`
+///
+/// # Examples:
+///```rust
+/// # use decon_spf::mechanism::Qualifier;
+/// use std::convert::TryFrom;
+/// assert_eq!(Qualifier::try_from('-').unwrap(), Qualifier::Fail);
+/// assert_eq!(Qualifier::try_from('x').is_err(), true);
+///```
+impl TryFrom<char> for Qualifier {
+    type Error = MechanismError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '+' => Ok(Qualifier::Pass),
+            '-' => Ok(Qualifier::Fail),
+            '~' => Ok(Qualifier::SoftFail),
+            '?' => Ok(Qualifier::Neutral),
+            _ => Err(MechanismError::InvalidQualifier(c.to_string())),
+        }
     }
 }
 
@@ -101,3 +148,39 @@ fn as_neutral() {
     let q = Qualifier::Neutral;
     assert_eq!(q.as_str(), "?");
 }
+#[test]
+fn from_str_round_trip() {
+    for q in [
+        Qualifier::Pass,
+        Qualifier::Fail,
+        Qualifier::SoftFail,
+        Qualifier::Neutral,
+    ] {
+        assert_eq!(q.as_str().parse::<Qualifier>().unwrap(), q);
+        assert_eq!(q.as_char().to_string().parse::<Qualifier>().unwrap(), q);
+        assert_eq!(Qualifier::try_from(q.as_char()).unwrap(), q);
+    }
+}
+#[test]
+fn from_str_words() {
+    assert_eq!("pass".parse::<Qualifier>().unwrap(), Qualifier::Pass);
+    assert_eq!("FAIL".parse::<Qualifier>().unwrap(), Qualifier::Fail);
+    assert_eq!(
+        "SoftFail".parse::<Qualifier>().unwrap(),
+        Qualifier::SoftFail
+    );
+    assert_eq!("Neutral".parse::<Qualifier>().unwrap(), Qualifier::Neutral);
+}
+#[test]
+fn from_str_invalid() {
+    let err = "soft".parse::<Qualifier>().unwrap_err();
+    assert_eq!(err, MechanismError::InvalidQualifier("soft".to_string()));
+    assert_eq!(err.is_invalid_qualifier(), true);
+    assert_eq!(err.to_string(), "soft is not a valid qualifier");
+    assert_eq!("+-".parse::<Qualifier>().is_err(), true);
+}
+#[test]
+fn try_from_char_invalid() {
+    let err = Qualifier::try_from('a').unwrap_err();
+    assert_eq!(err, MechanismError::InvalidQualifier("a".to_string()));
+}
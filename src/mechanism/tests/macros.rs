@@ -0,0 +1,178 @@
+#[cfg(test)]
+use crate::mechanism::{Kind, MacroContext, MacroError, Mechanism, Qualifier};
+#[cfg(test)]
+use crate::Spf;
+
+// The context used by the examples of RFC 7208 section 7.4
+#[cfg(test)]
+fn rfc_context() -> MacroContext {
+    MacroContext::new(
+        "strong-bad@email.example.com",
+        "192.0.2.3".parse().unwrap(),
+        "mx.example.org",
+    )
+}
+#[cfg(test)]
+fn expand(domain_spec: &str, context: &MacroContext) -> Result<String, MacroError> {
+    let spf: Spf = format!("v=spf1 exists:{}", domain_spec).parse().unwrap();
+    spf.exists().unwrap()[0].expand_macros(context)
+}
+
+#[test]
+fn rfc_macro_examples() {
+    let context = rfc_context();
+    for (domain_spec, expansion) in [
+        ("%{s}", "strong-bad@email.example.com"),
+        ("%{o}", "email.example.com"),
+        ("%{d}", "email.example.com"),
+        ("%{d4}", "email.example.com"),
+        ("%{d3}", "email.example.com"),
+        ("%{d2}", "example.com"),
+        ("%{d1}", "com"),
+        ("%{dr}", "com.example.email"),
+        ("%{d2r}", "example.email"),
+        ("%{l}", "strong-bad"),
+        ("%{l-}", "strong.bad"),
+        ("%{lr}", "strong-bad"),
+        ("%{lr-}", "bad.strong"),
+        ("%{l1r-}", "strong"),
+    ] {
+        assert_eq!(expand(domain_spec, &context).unwrap(), expansion);
+    }
+}
+#[test]
+fn rfc_macro_string_examples() {
+    let context = rfc_context();
+    for (domain_spec, expansion) in [
+        (
+            "%{ir}.%{v}._spf.%{d2}",
+            "3.2.0.192.in-addr._spf.example.com",
+        ),
+        ("%{lr-}.lp._spf.%{d2}", "bad.strong.lp._spf.example.com"),
+        (
+            "%{lr-}.lp.%{ir}.%{v}._spf.%{d2}",
+            "bad.strong.lp.3.2.0.192.in-addr._spf.example.com",
+        ),
+        (
+            "%{ir}.%{v}.%{l1r-}.lp._spf.%{d2}",
+            "3.2.0.192.in-addr.strong.lp._spf.example.com",
+        ),
+        (
+            "%{d2}.trusted-domains.example.net",
+            "example.com.trusted-domains.example.net",
+        ),
+    ] {
+        assert_eq!(expand(domain_spec, &context).unwrap(), expansion);
+    }
+}
+#[test]
+fn rfc_ip6_example() {
+    let context = MacroContext::new(
+        "strong-bad@email.example.com",
+        "2001:db8::cb01".parse().unwrap(),
+        "mx.example.org",
+    );
+    assert_eq!(
+        expand("%{ir}.%{v}._spf.%{d2}", &context).unwrap(),
+        "1.0.b.c.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6._spf.example.com"
+    );
+}
+#[test]
+fn literals_and_delimiters() {
+    let context = rfc_context();
+    assert_eq!(
+        context.expand_explanation("%{l}%%%_%-done").unwrap(),
+        "strong-bad% %20done"
+    );
+    assert_eq!(
+        expand("%{ir}.%{l1r+-}._spf.%{d}", &context).unwrap(),
+        "3.2.0.192.strong._spf.email.example.com"
+    );
+}
+#[test]
+fn uppercase_is_url_escaped() {
+    let context = rfc_context();
+    assert_eq!(
+        context.expand_explanation("%{S}").unwrap(),
+        "strong-bad%40email.example.com"
+    );
+}
+#[test]
+fn sender_without_local_part() {
+    let context = MacroContext::new("email.example.com", "192.0.2.3".parse().unwrap(), "mx");
+    assert_eq!(
+        expand("%{l}.%{o}", &context).unwrap(),
+        "postmaster.email.example.com"
+    );
+    assert_eq!(
+        expand("%{s}", &context).unwrap(),
+        "postmaster@email.example.com"
+    );
+}
+#[test]
+fn domain_and_validated_domain() {
+    let context = rfc_context().domain("example.net");
+    assert_eq!(expand("%{d}", &context).unwrap(), "example.net");
+    assert_eq!(expand("%{p}", &context).unwrap(), "unknown");
+    let context = context.validated_domain("mx.example.org");
+    assert_eq!(expand("%{p}", &context).unwrap(), "mx.example.org");
+}
+#[test]
+fn explanation_only_macros() {
+    let context = rfc_context().receiver("mail.example.net").timestamp(1234);
+    assert_eq!(
+        expand("%{t}.example.com", &context).unwrap_err(),
+        MacroError::ExplanationOnlyMacro('t')
+    );
+    assert_eq!(
+        context
+            .expand_explanation("%{c} rejected by %{r} at %{t}")
+            .unwrap(),
+        "192.0.2.3 rejected by mail.example.net at 1234"
+    );
+}
+#[test]
+fn invalid_macro_strings() {
+    let context = rfc_context();
+    for domain_spec in ["%{x}.example.com", "%{d0}", "%{d", "%{d2!}", "%a"] {
+        let err = context.expand_explanation(domain_spec).unwrap_err();
+        assert_eq!(err.is_invalid_macro_string(), true);
+        assert_eq!(
+            err.to_string(),
+            format!("{} is not a valid macro-string", domain_spec)
+        );
+    }
+}
+#[test]
+fn mechanisms_without_domain_spec() {
+    let context = rfc_context();
+    let spf: Spf = "v=spf1 a/24 mx -all".parse().unwrap();
+    assert_eq!(
+        spf.a().unwrap()[0].expand_macros(&context).unwrap(),
+        "email.example.com"
+    );
+    assert_eq!(
+        spf.mx().unwrap()[0].expand_macros(&context).unwrap(),
+        "email.example.com"
+    );
+    let err = Mechanism::all(Qualifier::Fail)
+        .expand_macros(&context)
+        .unwrap_err();
+    assert_eq!(err, MacroError::NoDomainSpec(Kind::All));
+    assert_eq!(err.is_no_domain_spec(), true);
+}
+#[test]
+fn domain_without_macros() {
+    let context = rfc_context();
+    let spf: Spf = "v=spf1 include:_spf.example.com a:example.org/24//64"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        spf.includes().unwrap()[0].expand_macros(&context).unwrap(),
+        "_spf.example.com"
+    );
+    assert_eq!(
+        spf.a().unwrap()[0].expand_macros(&context).unwrap(),
+        "example.org"
+    );
+}
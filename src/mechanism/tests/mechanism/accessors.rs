@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod domain {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+
+    #[test]
+    fn mx_forms() {
+        for (input, domain) in [
+            ("mx", None),
+            ("mx/24", None),
+            ("mx//64", None),
+            ("mx:example.com", Some("example.com")),
+            ("-mx:example.com/24", Some("example.com")),
+            ("mx:example.com/24//64", Some("example.com")),
+        ] {
+            let m: Mechanism<String> = input.parse().unwrap();
+            assert_eq!(m.domain(), domain);
+        }
+    }
+    #[test]
+    fn a_forms() {
+        let m: Mechanism<String> = "a".parse().unwrap();
+        assert_eq!(m.domain(), None);
+        let m: Mechanism<String> = "~a:example.com//64".parse().unwrap();
+        assert_eq!(m.domain(), Some("example.com"));
+    }
+    #[test]
+    fn other_kinds() {
+        let m: Mechanism<String> = "include:_spf.example.com".parse().unwrap();
+        assert_eq!(m.domain(), Some("_spf.example.com"));
+        let m: Mechanism<String> = "exists:example.com".parse().unwrap();
+        assert_eq!(m.domain(), Some("example.com"));
+        let m: Mechanism<String> = "ptr:example.com".parse().unwrap();
+        assert_eq!(m.domain(), Some("example.com"));
+        let m: Mechanism<String> = "ptr".parse().unwrap();
+        assert_eq!(m.domain(), None);
+        assert_eq!(Mechanism::all(Qualifier::Fail).domain(), None);
+    }
+    #[test]
+    #[cfg(not(feature = "strict-dns"))]
+    fn macro_with_slash_delimiter() {
+        let m: Mechanism<String> = "a:%{l/}.example.com/24".parse().unwrap();
+        assert_eq!(m.domain(), Some("%{l/}.example.com"));
+        assert_eq!(m.cidr(), Some((Some(24), None)));
+    }
+}
+#[cfg(test)]
+mod cidr {
+
+    use crate::mechanism::Mechanism;
+
+    #[test]
+    fn mx_forms() {
+        for (input, cidr) in [
+            ("mx", None),
+            ("mx:example.com", None),
+            ("mx/24", Some((Some(24), None))),
+            ("mx//64", Some((None, Some(64)))),
+            ("mx/30//96", Some((Some(30), Some(96)))),
+            ("mx:example.com/24", Some((Some(24), None))),
+            ("?mx:example.com/24//64", Some((Some(24), Some(64)))),
+        ] {
+            let m: Mechanism<String> = input.parse().unwrap();
+            assert_eq!(m.cidr(), cidr);
+        }
+    }
+    #[test]
+    fn a_forms() {
+        let m: Mechanism<String> = "a/32".parse().unwrap();
+        assert_eq!(m.cidr(), Some((Some(32), None)));
+        let m: Mechanism<String> = "a:example.com//128".parse().unwrap();
+        assert_eq!(m.cidr(), Some((None, Some(128))));
+    }
+    #[test]
+    fn not_legal() {
+        for input in [
+            "include:_spf.example.com",
+            "exists:example.com",
+            "ptr:example.com",
+            "ptr",
+            "-all",
+        ] {
+            let m: Mechanism<String> = input.parse().unwrap();
+            assert_eq!(m.cidr(), None);
+        }
+    }
+}
@@ -8,9 +8,7 @@ use crate::mechanism::Mechanism;
 #[test]
 fn test_match_on_a_only() {
     let string = "a";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::A);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::A);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_pass(), true);
@@ -20,9 +18,7 @@ fn test_match_on_a_only() {
 #[test]
 fn test_match_on_a_colon() {
     let string = "-a:example.com";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::A);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::A);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_fail(), true);
@@ -32,9 +28,7 @@ fn test_match_on_a_colon() {
 #[test]
 fn test_match_on_a_slash() {
     let string = "~a/24";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::A);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::A);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_softfail(), true);
@@ -44,12 +38,26 @@ fn test_match_on_a_slash() {
 #[test]
 fn test_match_on_a_colon_slash() {
     let string = "+a:example.com/24";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::A);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::A);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_pass(), true);
     assert_eq!(test.raw(), "example.com/24");
     assert_eq!(test.to_string(), "a:example.com/24");
 }
+#[test]
+fn test_match_on_a_dual_cidr() {
+    let string = "a/24//64";
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::A);
+
+    let test = option_test.unwrap();
+    assert_eq!(test.raw(), "/24//64");
+    assert_eq!(test.to_string(), "a/24//64");
+}
+#[test]
+fn test_match_on_a_malformed_dual_cidr() {
+    let string = "a/24//";
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::A);
+
+    assert_eq!(option_test.is_none(), true);
+}
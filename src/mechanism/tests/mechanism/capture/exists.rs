@@ -8,12 +8,28 @@ use crate::mechanism::Mechanism;
 #[test]
 fn basic() {
     let string = "exists:a.example.com";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::Exists);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::Exists);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_pass(), true);
     assert_eq!(test.raw(), "a.example.com");
     assert_eq!(test.to_string(), "exists:a.example.com");
 }
+#[test]
+fn test_match_on_exists_short_domain() {
+    let string = "exists:a.b";
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::Exists);
+
+    let test = option_test.unwrap();
+    assert_eq!(test.raw(), "a.b");
+    assert_eq!(test.to_string(), "exists:a.b");
+}
+#[test]
+fn test_match_on_exists_macro_with_colon() {
+    let string = "exists:%{l}:%{i}._spf.%{d}";
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::Exists);
+
+    let test = option_test.unwrap();
+    assert_eq!(test.raw(), "%{l}:%{i}._spf.%{d}");
+    assert_eq!(test.to_string(), string);
+}
@@ -8,9 +8,7 @@ use crate::mechanism::Mechanism;
 #[test]
 fn match_on_mx_only() {
     let string = "mx";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::MX);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::MX);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_pass(), true);
@@ -20,9 +18,7 @@ fn match_on_mx_only() {
 #[test]
 fn match_on_mx_colon() {
     let string = "-mx:example.com";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::MX);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::MX);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_fail(), true);
@@ -32,9 +28,7 @@ fn match_on_mx_colon() {
 #[test]
 fn match_on_mx_slash() {
     let string = "~mx/24";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::MX);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::MX);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_softfail(), true);
@@ -44,9 +38,7 @@ fn match_on_mx_slash() {
 #[test]
 fn match_on_mx_colon_slash() {
     let string = "+mx:example.com/24";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::MX);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::MX);
 
     let test = option_test.unwrap();
     assert_eq!(test.is_pass(), true);
@@ -8,9 +8,7 @@ use crate::mechanism::Mechanism;
 #[test]
 fn match_on_ptr() {
     let string = "ptr";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::Ptr);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::Ptr);
     assert!(option_test.is_some());
 
     let test = option_test.unwrap();
@@ -21,9 +19,7 @@ fn match_on_ptr() {
 #[test]
 fn match_on_ptr_colon() {
     let string = "ptr:example.com";
-    let option_test: Option<Mechanism<String>>;
-
-    option_test = helpers::capture_matches(&string, Kind::Ptr);
+    let option_test: Option<Mechanism<String>> = helpers::capture_matches(string, Kind::Ptr);
     assert!(option_test.is_some());
 
     let test = option_test.unwrap();
@@ -15,14 +15,11 @@ mod a {
     fn a_with_rrdata() {
         let string = String::from("example.com");
         let a_mechanism = Mechanism::a(Qualifier::Fail).with_rrdata(&string);
-        match a_mechanism {
-            Ok(m) => {
-                assert_eq!(m.is_fail(), true);
-                assert_eq!(m.kind(), &Kind::A);
-                assert_eq!(m.raw(), string);
-                assert_eq!(m.to_string(), "-a:example.com");
-            }
-            Err(_) => {}
+        if let Ok(m) = a_mechanism {
+            assert_eq!(m.is_fail(), true);
+            assert_eq!(m.kind(), &Kind::A);
+            assert_eq!(m.raw(), string);
+            assert_eq!(m.to_string(), "-a:example.com");
         }
     }
     #[test]
@@ -77,13 +74,10 @@ mod mx {
     #[test]
     fn mx_with_rrdata_match() {
         let mx = Mechanism::mx(Qualifier::Neutral).with_rrdata("example.com");
-        match mx {
-            Ok(mx) => {
-                assert_eq!(mx.is_neutral(), true);
-                assert_eq!(mx.raw(), "example.com");
-                assert_eq!(mx.to_string(), "?mx:example.com");
-            }
-            Err(_) => {}
+        if let Ok(mx) = mx {
+            assert_eq!(mx.is_neutral(), true);
+            assert_eq!(mx.raw(), "example.com");
+            assert_eq!(mx.to_string(), "?mx:example.com");
         }
     }
     #[test]
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod string {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+
+    #[test]
+    fn presentation_is_ignored() {
+        let include: Mechanism<String> = "+INCLUDE:_spf.example.com".parse().unwrap();
+        assert_eq!(
+            include,
+            Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap()
+        );
+    }
+    #[test]
+    fn domain_case_is_ignored() {
+        let a: Mechanism<String> = "a:Mail.Example.com/24".parse().unwrap();
+        let b: Mechanism<String> = "a:mail.example.com/24".parse().unwrap();
+        assert_eq!(a, b);
+    }
+    #[test]
+    fn macro_case_is_compared() {
+        let a: Mechanism<String> = "exists:%{i}.%{D}.example.com".parse().unwrap();
+        let b: Mechanism<String> = "exists:%{i}.%{D}.EXAMPLE.com".parse().unwrap();
+        let c: Mechanism<String> = "exists:%{i}.%{d}.example.com".parse().unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+    #[test]
+    fn qualifier_is_compared() {
+        assert_ne!(
+            Mechanism::all(Qualifier::Fail),
+            Mechanism::all(Qualifier::SoftFail)
+        );
+    }
+    #[test]
+    fn kind_is_compared() {
+        let a: Mechanism<String> = "a:example.com".parse().unwrap();
+        let mx: Mechanism<String> = "mx:example.com".parse().unwrap();
+        assert_ne!(a, mx);
+    }
+    #[test]
+    fn rrdata_is_compared() {
+        let a: Mechanism<String> = "a".parse().unwrap();
+        let a_domain: Mechanism<String> = "a:example.com".parse().unwrap();
+        let a_cidr: Mechanism<String> = "a/24".parse().unwrap();
+        assert_ne!(a, a_domain);
+        assert_ne!(a, a_cidr);
+    }
+}
+#[cfg(test)]
+mod ip {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use ipnetwork::IpNetwork;
+
+    #[test]
+    fn implicit_prefix() {
+        let host: Mechanism<IpNetwork> = "ip4:203.0.113.1".parse().unwrap();
+        let explicit: Mechanism<IpNetwork> = "+ip4:203.0.113.1/32".parse().unwrap();
+        assert_eq!(host, explicit);
+        let host: Mechanism<IpNetwork> = "ip6:2001:db8::1".parse().unwrap();
+        assert_eq!(host, "ip6:2001:db8::1/128".parse().unwrap());
+    }
+    #[test]
+    fn by_value() {
+        let built = Mechanism::ip(Qualifier::Fail, "2001:db8::/32".parse().unwrap());
+        let parsed: Mechanism<IpNetwork> = "-ip6:2001:DB8:0::/32".parse().unwrap();
+        assert_eq!(built, parsed);
+    }
+    #[test]
+    fn differ() {
+        let network: Mechanism<IpNetwork> = "ip4:203.0.113.0/24".parse().unwrap();
+        assert_ne!(network, "ip4:203.0.113.0/25".parse().unwrap());
+        assert_ne!(network, "~ip4:203.0.113.0/24".parse().unwrap());
+        assert_ne!(network, "ip4:198.51.100.0/24".parse().unwrap());
+    }
+}
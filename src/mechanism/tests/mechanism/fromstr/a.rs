@@ -65,3 +65,79 @@ fn basic_pass_slash() {
     assert_eq!(m.raw(), "/24");
     assert_eq!(m.to_string(), input);
 }
+#[test]
+fn dual_cidr() {
+    let input = "a:example.com/24//64";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_a(), true);
+    assert_eq!(m.raw(), "example.com/24//64");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn dual_cidr_without_domain() {
+    let input = "-a/24//64";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.qualifier().is_fail(), true);
+    assert_eq!(m.raw(), "/24//64");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn ip6_cidr_only() {
+    let input = "a//64";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_a(), true);
+    assert_eq!(m.raw(), "//64");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn malformed_dual_cidr() {
+    use crate::mechanism::MechanismError;
+
+    for input in ["a/24//", "a:example.com//"] {
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(
+            err,
+            MechanismError::InvalidMechanismFormat(input.to_string())
+        );
+    }
+    for input in ["a/24/64", "a///64", "a:example.com/24/64", "a:/24"] {
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(err, MechanismError::InvalidDualCidr(input.to_string()));
+        assert_eq!(err.is_invalid_dual_cidr(), true);
+    }
+}
+#[test]
+fn out_of_range_dual_cidr() {
+    use crate::mechanism::MechanismError;
+
+    let err = "a:example.com/33".parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(err, MechanismError::InvalidIPv4CidrLength("33".to_string()));
+    let err = "a/24//129".parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(
+        err,
+        MechanismError::InvalidIPv6CidrLength("129".to_string())
+    );
+    assert_eq!(err.is_invalid_ipv6_cidr_length(), true);
+}
+#[test]
+fn fail_rrdata_with_slash() {
+    let input = "-a:example.com/24";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_a(), true);
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.raw(), "example.com/24");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn question_mark_slash() {
+    let input = "?a/24";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_a(), true);
+    assert_eq!(m.is_neutral(), true);
+    assert_eq!(m.raw(), "/24");
+}
@@ -1,5 +1,5 @@
 #[cfg(test)]
-use crate::mechanism::Mechanism;
+use crate::mechanism::{Mechanism, MechanismError, Qualifier};
 
 #[test]
 fn default() {
@@ -37,3 +37,25 @@ fn fail() {
     assert_eq!(m.raw(), "all");
     assert_eq!(m.to_string(), input);
 }
+#[test]
+fn question_mark() {
+    let input = "?all";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_all(), true);
+    assert_eq!(m.qualifier(), &Qualifier::Neutral);
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn name_ending_in_all() {
+    let err = "ball".parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(
+        err,
+        MechanismError::UnknownMechanismName("ball".to_string())
+    );
+    let err = "-xall".parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(
+        err,
+        MechanismError::UnknownMechanismName("-xall".to_string())
+    );
+}
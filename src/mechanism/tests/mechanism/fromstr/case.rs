@@ -0,0 +1,56 @@
+#[cfg(test)]
+use crate::mechanism::{Mechanism, ParsedMechanism};
+#[cfg(test)]
+use ipnetwork::IpNetwork;
+
+#[test]
+fn uppercase_include() {
+    let m: Mechanism<String> = "-INCLUDE:_spf.Example.com".parse().unwrap();
+    assert_eq!(m.kind().is_include(), true);
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.raw(), "_spf.Example.com");
+    assert_eq!(m.to_string(), "-include:_spf.Example.com");
+}
+#[test]
+fn uppercase_redirect() {
+    let m: Mechanism<String> = "REDIRECT=_spf.example.com".parse().unwrap();
+    assert_eq!(m.kind().is_redirect(), true);
+    assert_eq!(m.to_string(), "redirect=_spf.example.com");
+}
+#[test]
+fn mixed_case_exp() {
+    let m: Mechanism<String> = "Exp=%{L}.explain.example.com".parse().unwrap();
+    assert_eq!(m.kind().is_exp(), true);
+    assert_eq!(m.raw(), "%{L}.explain.example.com");
+}
+#[test]
+fn uppercase_all() {
+    let m: Mechanism<String> = "~ALL".parse().unwrap();
+    assert_eq!(m.kind().is_all(), true);
+    assert_eq!(m.is_softfail(), true);
+    assert_eq!(m.to_string(), "~all");
+}
+#[test]
+fn uppercase_mx() {
+    let m: Mechanism<String> = "MX:Example.com/24".parse().unwrap();
+    assert_eq!(m.kind().is_mx(), true);
+    assert_eq!(m.to_string(), "mx:Example.com/24");
+}
+#[test]
+fn uppercase_ip4() {
+    let m: Mechanism<IpNetwork> = "-IP4:10.0.0.0/8".parse().unwrap();
+    assert_eq!(m.kind().is_ip_v4(), true);
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.to_string(), "-ip4:10.0.0.0/8");
+}
+#[test]
+fn uppercase_ip6_parsed_mechanism() {
+    let m: ParsedMechanism = "Ip6:2001:4860:4000::/36".parse().unwrap();
+    assert_eq!(m.network().kind().is_ip_v6(), true);
+}
+#[test]
+fn uppercase_ip4_as_string_is_invalid() {
+    let m: Result<Mechanism<String>, _> = "IP4:10.0.0.0/8".parse();
+    assert_eq!(m.is_err(), true);
+    assert_eq!(m.unwrap_err().is_ip_network_term(), true);
+}
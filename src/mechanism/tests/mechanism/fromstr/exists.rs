@@ -35,3 +35,29 @@ fn neutral() {
     assert_eq!(m.raw(), "example.com");
     assert_eq!(m.to_string(), input);
 }
+#[test]
+#[cfg(not(feature = "strict-dns"))]
+fn exists_macro_with_slash_delimiter() {
+    let input = "exists:%{l/}._spf.%{d}";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.raw(), "%{l/}._spf.%{d}");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn fail() {
+    let input = "-exists:example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_exists(), true);
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn question_mark() {
+    let input = "?exists:example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.is_neutral(), true);
+    assert_eq!(m.raw(), "example.com");
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+use crate::mechanism::{Kind, Mechanism, MechanismError};
+
+#[test]
+fn default() {
+    let input = "exp=explain._spf.example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_exp(), true);
+    assert_eq!(m.raw(), "explain._spf.example.com");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn qualifier_is_not_allowed() {
+    for input in [
+        "+exp=explain._spf.example.com",
+        "-exp=explain._spf.example.com",
+        "~exp=explain._spf.example.com",
+        "?exp=explain._spf.example.com",
+    ] {
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(
+            err,
+            MechanismError::QualifierNotAllowedForModifier(Kind::Exp)
+        );
+        assert_eq!(err.to_string(), "Modifier exp= may not have a qualifier");
+    }
+}
+#[test]
+fn empty() {
+    let err = "exp=".parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(
+        err,
+        MechanismError::InvalidMechanismFormat("exp=".to_string())
+    );
+}
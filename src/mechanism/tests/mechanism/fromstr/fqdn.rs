@@ -0,0 +1,37 @@
+#[cfg(test)]
+use crate::mechanism::{Mechanism, MechanismError};
+
+#[test]
+fn trailing_dot_round_trip() {
+    for input in [
+        "a:mail.example.org.",
+        "-a:mail.example.org./24//64",
+        "mx:example.com.",
+        "~mx:example.com./30",
+        "ptr:example.com.",
+        "include:_spf.example.com.",
+        "exists:%{i}._spf.example.com.",
+    ] {
+        let m: Mechanism<String> = input.parse().unwrap();
+        assert_eq!(m.to_string(), input);
+    }
+}
+#[test]
+fn trailing_dot_raw() {
+    let m: Mechanism<String> = "ptr:example.com.".parse().unwrap();
+    assert_eq!(m.kind().is_ptr(), true);
+    assert_eq!(m.raw(), "example.com.");
+}
+#[test]
+fn empty_label() {
+    for (input, domain) in [
+        ("a:example..com", "example..com"),
+        ("mx:example.com../24", "example.com.."),
+        ("ptr:example..com", "example..com"),
+        ("include:_spf..example.com", "_spf..example.com"),
+        ("exists:%{i}..example.com", "%{i}..example.com"),
+    ] {
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(err, MechanismError::InvalidDomainHost(domain.to_string()));
+    }
+}
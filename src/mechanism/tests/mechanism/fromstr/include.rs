@@ -28,3 +28,20 @@ fn neutral() {
     assert_eq!(m.raw(), "example.com");
     assert_eq!(m.to_string(), "~include:example.com");
 }
+#[test]
+#[cfg(not(feature = "strict-dns"))]
+fn macro_with_colon() {
+    let input = "include:%{ir}.%{v}._spf.%{d2}";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_include(), true);
+    assert_eq!(m.raw(), "%{ir}.%{v}._spf.%{d2}");
+    assert_eq!(m.to_string(), input);
+
+    let input = "-include:ip:%{i}.include.example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.raw(), "ip:%{i}.include.example.com");
+    assert_eq!(m.to_string(), input);
+}
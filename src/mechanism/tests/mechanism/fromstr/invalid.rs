@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 mod general {
 
     use crate::mechanism::{Mechanism, MechanismError};
@@ -108,4 +107,54 @@ mod general {
             MechanismError::InvalidMechanismFormat(input.to_string())
         );
     }
+    #[test]
+    fn unknown_mechanism_name() {
+        let input = "~spoof:example.com";
+
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(err.is_unknown_mechanism_name(), true);
+        assert_eq!(
+            err,
+            MechanismError::UnknownMechanismName("~spoof".to_string())
+        );
+        assert_eq!(err.to_string(), "~spoof is not a known mechanism");
+    }
+    #[test]
+    fn unknown_mechanism_name_without_value() {
+        let err = "-foobar".parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(
+            err,
+            MechanismError::UnknownMechanismName("-foobar".to_string())
+        );
+    }
+}
+#[cfg(test)]
+mod ip_network {
+
+    use crate::mechanism::{Mechanism, MechanismError};
+
+    #[test]
+    fn ip4_as_string() {
+        let input = "ip4:203.32.160.0/24";
+
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(err.is_ip_network_term(), true);
+        assert_eq!(err, MechanismError::IpNetworkTerm(input.to_string()));
+        assert_eq!(
+            err.to_string(),
+            "ip4:203.32.160.0/24 must be parsed as Mechanism<IpNetwork>"
+        );
+    }
+    #[test]
+    fn ip6_as_string() {
+        let input = "-ip6:2001:4860:4000::/36";
+
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(err, MechanismError::IpNetworkTerm(input.to_string()));
+    }
+    #[test]
+    fn include_of_ip_named_domain() {
+        let m: Mechanism<String> = "include:ip4.example.com".parse().unwrap();
+        assert_eq!(m.kind().is_include(), true);
+    }
 }
@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 mod valid_ip4 {
 
     use crate::mechanism::Mechanism;
@@ -35,6 +34,23 @@ mod valid_ip4 {
         assert_eq!(m.mechanism().unwrap().to_string(), "203.32.160.0/24");
         assert_eq!(m.to_string(), input);
     }
+    #[test]
+    fn bare_ip_str() {
+        let input = "~ip4:203.0.113.4";
+
+        let m: Mechanism<IpNetwork> = input.parse().unwrap();
+        assert_eq!(m.as_network().prefix(), 32);
+        assert_eq!(m.to_string(), input);
+        assert_eq!(m.to_string_with_prefix(), "~ip4:203.0.113.4/32");
+    }
+    #[test]
+    fn explicit_full_prefix_is_kept() {
+        let input = "ip4:203.0.113.4/32";
+
+        let m: Mechanism<IpNetwork> = input.parse().unwrap();
+        assert_eq!(m.to_string(), input);
+        assert_eq!(m.to_string_with_prefix(), input);
+    }
 }
 
 #[cfg(test)]
@@ -60,12 +76,28 @@ mod invalid_ip4 {
 
         let m: Result<Mechanism<IpNetwork>, MechanismError> = input.parse();
         assert_eq!(m.is_err(), true);
+        let err = m.unwrap_err();
+        assert_eq!(err.is_invalid_ipv4_cidr_length(), true);
+        assert_eq!(err.to_string(), "33 is not a valid ip4 cidr length");
+    }
+    #[test]
+    fn mechanism_negative_cidr() {
+        let input = "ip4:203.32.160.0/-1";
+
+        let m: Result<Mechanism<IpNetwork>, MechanismError> = input.parse();
         assert_eq!(
-            m.unwrap_err().to_string(),
-            "invalid address: 203.32.160.0/33"
+            m.unwrap_err(),
+            MechanismError::InvalidIPv4CidrLength("-1".to_string())
         );
     }
     #[test]
+    fn mechanism_invalid_address() {
+        let input = "ip4:203.32.160.256/24";
+
+        let m: Result<Mechanism<IpNetwork>, MechanismError> = input.parse();
+        assert_eq!(m.unwrap_err().is_invalid_ip(), true);
+    }
+    #[test]
     fn mechanism_ip6_as_ip4_malformed() {
         let input = "ip4:2001:4860:4000::/36";
 
@@ -112,6 +144,15 @@ mod valid_ip6 {
         assert_eq!(m.kind().is_ip_v6(), true);
         assert_eq!(m.to_string(), input);
     }
+    #[test]
+    fn bare_ip_str() {
+        let input = "ip6:2001:db8::1";
+
+        let m: Mechanism<IpNetwork> = input.parse().unwrap();
+        assert_eq!(m.as_network().prefix(), 128);
+        assert_eq!(m.to_string(), input);
+        assert_eq!(m.to_string_with_prefix(), "ip6:2001:db8::1/128");
+    }
 }
 
 #[cfg(test)]
@@ -138,10 +179,9 @@ mod invalid_ip6 {
 
         let m: Result<Mechanism<IpNetwork>, MechanismError> = input.parse();
         assert_eq!(m.is_err(), true);
-        assert_eq!(
-            m.unwrap_err().to_string(),
-            "invalid address: 2001:4860:4000::/129"
-        );
+        let err = m.unwrap_err();
+        assert_eq!(err.is_invalid_ipv6_cidr_length(), true);
+        assert_eq!(err.to_string(), "129 is not a valid ip6 cidr length");
     }
     #[test]
     fn mechanism_ip4_as_ip6_malformed() {
@@ -155,3 +195,103 @@ mod invalid_ip6 {
         );
     }
 }
+
+#[cfg(test)]
+mod malformed {
+
+    use crate::mechanism::{Mechanism, MechanismError};
+    use ipnetwork::IpNetwork;
+
+    #[test]
+    fn empty_value() {
+        for input in ["ip4:", "-ip6:", "IP4:"] {
+            let err = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
+            assert_eq!(err.is_empty_ip_value(), true);
+            assert_eq!(err, MechanismError::EmptyIpValue(input.to_string()));
+        }
+        let err = "ip4:".parse::<Mechanism<IpNetwork>>().unwrap_err();
+        assert_eq!(err.to_string(), "ip4: has no ip network");
+    }
+    #[test]
+    fn embedded_whitespace() {
+        let input = "ip4:203.32.160.0 /24";
+
+        let err = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
+        assert_eq!(
+            err,
+            MechanismError::InvalidMechanismFormat(input.to_string())
+        );
+    }
+    #[test]
+    fn no_colon() {
+        for input in ["ip4", "~ip6", "ip4203.32.160.0/24"] {
+            let err = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
+            assert_eq!(
+                err,
+                MechanismError::InvalidMechanismFormat(input.to_string())
+            );
+        }
+    }
+    #[test]
+    fn ip_prefix_in_the_middle() {
+        for input in ["exists:ip4:foo", "include:ip6:example.com", "a:ip4:"] {
+            let err = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
+            assert_eq!(err.is_invalid_format(), true);
+        }
+    }
+    #[test]
+    fn empty_and_qualifier_only() {
+        for input in ["", "-", "+ip"] {
+            let err = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
+            assert_eq!(err.is_invalid_format(), true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod host_bits {
+
+    use crate::mechanism::Mechanism;
+    use ipnetwork::IpNetwork;
+
+    #[test]
+    fn edge_prefixes() {
+        for (input, normalized) in [
+            ("ip4:192.0.2.1/0", "ip4:0.0.0.0/0"),
+            ("ip4:192.0.2.1/31", "ip4:192.0.2.0/31"),
+            ("ip6:2001:db8::1/0", "ip6:::/0"),
+            ("ip6:2001:db8::1/127", "ip6:2001:db8::/127"),
+        ] {
+            let m: Mechanism<IpNetwork> = input.parse().unwrap();
+            assert_eq!(m.is_normalized(), false);
+            assert_eq!(m.normalized().is_normalized(), true);
+            assert_eq!(m.normalized().to_string(), normalized);
+        }
+    }
+    #[test]
+    fn already_normalized() {
+        for input in [
+            "ip4:0.0.0.0/0",
+            "ip4:192.0.2.0/31",
+            "ip4:192.0.2.1/32",
+            "ip4:192.0.2.1",
+            "ip6:::/0",
+            "ip6:2001:db8::/127",
+            "ip6:2001:db8::1/128",
+            "ip6:2001:db8::1",
+        ] {
+            let m: Mechanism<IpNetwork> = input.parse().unwrap();
+            assert_eq!(m.is_normalized(), true);
+            assert_eq!(m.normalized().to_string(), input);
+        }
+    }
+    #[test]
+    fn keeps_qualifier() {
+        let m: Mechanism<IpNetwork> = "~ip4:192.168.1.17/24".parse().unwrap();
+        let normalized = m.normalized();
+        assert_eq!(normalized.qualifier().is_softfail(), true);
+        assert_eq!(normalized.raw(), "192.168.1.0/24");
+        // The original is unchanged.
+        assert_eq!(m.raw(), "192.168.1.17/24");
+    }
+}
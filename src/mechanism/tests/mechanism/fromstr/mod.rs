@@ -1,6 +1,9 @@
 mod a;
 mod all;
+mod case;
 mod exists;
+mod exp;
+mod fqdn;
 mod include;
 mod invalid;
 mod ipnetwork;
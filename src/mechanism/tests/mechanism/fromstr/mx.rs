@@ -44,3 +44,65 @@ fn mx_slash() {
     assert_eq!(m.raw(), "/24");
     assert_eq!(m.to_string(), input);
 }
+#[test]
+fn mx_dual_cidr() {
+    let input = "mx/30//96";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_mx(), true);
+    assert_eq!(m.raw(), "/30//96");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn mx_rrdata_ip6_cidr() {
+    let input = "mx:example.com//64";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.raw(), "example.com//64");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn mx_rrdata_dual_cidr() {
+    let input = "?mx:example.com/24//64";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.qualifier().is_neutral(), true);
+    assert_eq!(m.raw(), "example.com/24//64");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn mx_malformed_dual_cidr() {
+    let input = "mx/24//";
+    let err = input.parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(err.is_invalid_format(), true);
+    let err = "mx:/24".parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(err.is_invalid_dual_cidr(), true);
+}
+#[test]
+fn mx_fail() {
+    let input = "-mx";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_mx(), true);
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.raw(), "mx");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn mx_neutral_slash() {
+    let input = "?mx/24";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_mx(), true);
+    assert_eq!(m.is_neutral(), true);
+    assert_eq!(m.raw(), "/24");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn mx_softfail_rrdata() {
+    let input = "~mx:example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_mx(), true);
+    assert_eq!(m.is_softfail(), true);
+}
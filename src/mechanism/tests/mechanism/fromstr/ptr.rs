@@ -36,3 +36,40 @@ fn default_rrdata() {
     assert_eq!(m.raw(), "example.com");
     assert_eq!(m.to_string(), input);
 }
+#[test]
+#[cfg(not(feature = "strict-dns"))]
+fn ptr_with_macros() {
+    let input = "-ptr:%{ir}.%{v}._spf.%{d2}";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.has_macros(), true);
+    assert_eq!(m.raw(), "%{ir}.%{v}._spf.%{d2}");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn fail() {
+    let input = "-ptr";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_ptr(), true);
+    assert_eq!(m.is_fail(), true);
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn question_mark_rrdata() {
+    let input = "?ptr:example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_ptr(), true);
+    assert_eq!(m.is_neutral(), true);
+    assert_eq!(m.raw(), "example.com");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn ptr_slash() {
+    let input = "ptr/24";
+
+    let err = input.parse::<Mechanism<String>>().unwrap_err();
+    assert_eq!(err.is_invalid_format(), true);
+}
@@ -1,5 +1,5 @@
 #[cfg(test)]
-use crate::mechanism::Mechanism;
+use crate::mechanism::{Kind, Mechanism, MechanismError};
 
 #[test]
 fn default() {
@@ -10,3 +10,56 @@ fn default() {
     assert_eq!(m.raw(), "_spf.example.com");
     assert_eq!(m.to_string(), input);
 }
+#[test]
+fn macro_target() {
+    let input = "redirect=%{l1r-}.%{d}._spf.example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_redirect(), true);
+    assert_eq!(m.raw(), "%{l1r-}.%{d}._spf.example.com");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn uppercase() {
+    let input = "REDIRECT=_spf.example.com";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.kind().is_redirect(), true);
+    assert_eq!(m.raw(), "_spf.example.com");
+    assert_eq!(m.to_string(), "redirect=_spf.example.com");
+}
+#[test]
+#[cfg(not(feature = "strict-dns"))]
+fn target_contains_equals() {
+    let input = "redirect=%{d}=something";
+
+    let m: Mechanism<String> = input.parse().unwrap();
+    assert_eq!(m.raw(), "%{d}=something");
+    assert_eq!(m.to_string(), input);
+}
+#[test]
+fn qualifier_is_not_allowed() {
+    for input in [
+        "+redirect=_spf.example.com",
+        "-redirect=_spf.example.com",
+        "~redirect=_spf.example.com",
+        "?redirect=_spf.example.com",
+    ] {
+        let err = input.parse::<Mechanism<String>>().unwrap_err();
+        assert_eq!(err.is_qualifier_not_allowed_for_modifier(), true);
+        assert_eq!(
+            err,
+            MechanismError::QualifierNotAllowedForModifier(Kind::Redirect)
+        );
+    }
+}
+#[test]
+fn empty_target() {
+    let input = "redirect=";
+
+    let m: Result<Mechanism<String>, MechanismError> = input.parse();
+    assert_eq!(
+        m.unwrap_err(),
+        MechanismError::InvalidMechanismFormat(input.to_string())
+    );
+}
@@ -73,3 +73,18 @@ mod exists {
         assert_eq!(err.to_string(), "Invalid DNS string: example.xx");
     }
 }
+#[cfg(feature = "strict-dns")]
+#[cfg(test)]
+mod redirect {
+    use crate::mechanism::Mechanism;
+    use crate::mechanism::MechanismError;
+    #[test]
+    #[cfg(feature = "strict-dns")]
+    fn redirect_rrdata_contains_equals() {
+        let input = "redirect=%{d}=something";
+
+        let m: Result<Mechanism<String>, MechanismError> = input.parse();
+        let err = m.unwrap_err();
+        assert_eq!(err.to_string(), "Invalid DNS string: %{d}=something");
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod string {
+
+    use crate::mechanism::Mechanism;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    const CORPUS: [&str; 24] = [
+        "a",
+        "+a",
+        "-a",
+        "a/24",
+        "a//64",
+        "a:example.com",
+        "A:Example.com",
+        "+a:EXAMPLE.com",
+        "a:example.com/24",
+        "mx",
+        "MX",
+        "mx:Mail.Example.com",
+        "mx:mail.example.com",
+        "?mx:mail.example.com",
+        "include:_spf.example.com",
+        "Include:_SPF.example.com",
+        "~include:_spf.example.com",
+        "exists:%{i}.%{d}.example.com",
+        "exists:%{i}.%{d}.Example.com",
+        "exists:%{i}.%{D}.example.com",
+        "ptr",
+        "all",
+        "-all",
+        "redirect=_spf.example.com",
+    ];
+
+    fn hash(mechanism: &Mechanism<String>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        mechanism.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn equal_implies_same_hash() {
+        let corpus: Vec<Mechanism<String>> = CORPUS.iter().map(|m| m.parse().unwrap()).collect();
+        let mut equal_pairs = 0;
+        for a in &corpus {
+            for b in &corpus {
+                if a == b {
+                    equal_pairs += 1;
+                    assert_eq!(hash(a), hash(b), "{} and {}", a, b);
+                }
+            }
+        }
+        // Each mechanism is equal to itself, and some to a differently written form.
+        assert_eq!(equal_pairs > corpus.len(), true);
+    }
+    #[test]
+    fn case_folded() {
+        let upper: Mechanism<String> = "a:Example.com".parse().unwrap();
+        let lower: Mechanism<String> = "a:example.com".parse().unwrap();
+        assert_eq!(hash(&upper), hash(&lower));
+    }
+    #[test]
+    fn hash_set() {
+        let set: HashSet<Mechanism<String>> = CORPUS.iter().map(|m| m.parse().unwrap()).collect();
+        // "+a", "+a:EXAMPLE.com", "A:Example.com", "MX", "mx:Mail.Example.com",
+        // "Include:_SPF.example.com" and "exists:%{i}.%{d}.Example.com" are duplicates.
+        assert_eq!(set.len(), CORPUS.len() - 7);
+        assert_eq!(
+            set.contains(&"exists:%{i}.%{D}.example.com".parse().unwrap()),
+            true
+        );
+    }
+}
+#[cfg(test)]
+mod ip {
+
+    use crate::mechanism::Mechanism;
+    use ipnetwork::IpNetwork;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+
+    const CORPUS: [&str; 10] = [
+        "ip4:203.0.113.1",
+        "ip4:203.0.113.1/32",
+        "+ip4:203.0.113.1",
+        "-ip4:203.0.113.1",
+        "ip4:203.0.113.0/24",
+        "ip4:203.0.113.0/25",
+        "ip6:2001:db8::1",
+        "ip6:2001:DB8::1/128",
+        "ip6:2001:db8::/32",
+        "~ip6:2001:db8::/32",
+    ];
+
+    fn hash(mechanism: &Mechanism<IpNetwork>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        mechanism.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn equal_implies_same_hash() {
+        let corpus: Vec<Mechanism<IpNetwork>> = CORPUS.iter().map(|m| m.parse().unwrap()).collect();
+        for a in &corpus {
+            for b in &corpus {
+                if a == b {
+                    assert_eq!(hash(a), hash(b), "{} and {}", a, b);
+                }
+            }
+        }
+    }
+    #[test]
+    fn hash_map() {
+        let mut domains: HashMap<Mechanism<IpNetwork>, Vec<&str>> = HashMap::new();
+        for (mechanism, domain) in [
+            ("ip4:203.0.113.1", "example.com"),
+            ("ip4:203.0.113.1/32", "example.net"),
+            ("ip4:203.0.113.0/24", "example.org"),
+        ] {
+            domains
+                .entry(mechanism.parse().unwrap())
+                .or_default()
+                .push(domain);
+        }
+        assert_eq!(domains.len(), 2);
+        let host: Mechanism<IpNetwork> = "+ip4:203.0.113.1".parse().unwrap();
+        assert_eq!(domains[&host], vec!["example.com", "example.net"]);
+    }
+}
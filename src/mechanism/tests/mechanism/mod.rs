@@ -1,4 +1,10 @@
+mod accessors;
 mod build;
 mod capture;
 mod create;
+mod eq;
 mod fromstr;
+mod hash;
+mod new;
+mod qualifier;
+mod rrdata;
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod try_new {
+
+    use crate::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
+
+    fn some(rrdata: &str) -> Option<String> {
+        Some(rrdata.to_string())
+    }
+
+    #[test]
+    fn optional_rrdata() {
+        for kind in [Kind::A, Kind::MX, Kind::Ptr] {
+            let m = Mechanism::try_new(kind, Qualifier::Fail, None).unwrap();
+            assert_eq!(m.kind(), &kind);
+            assert_eq!(m.to_string(), format!("-{}", kind));
+            let m = Mechanism::try_new(kind, Qualifier::Fail, some("example.com")).unwrap();
+            assert_eq!(m.to_string(), format!("-{}:example.com", kind));
+        }
+        let a = Mechanism::try_new(Kind::A, Qualifier::Pass, some("/24")).unwrap();
+        assert_eq!(a.to_string(), "a/24");
+    }
+    #[test]
+    fn required_rrdata() {
+        for kind in [Kind::Include, Kind::Exists, Kind::Redirect, Kind::Exp] {
+            let m = Mechanism::try_new(kind, Qualifier::Pass, some("example.com")).unwrap();
+            assert_eq!(m.kind(), &kind);
+            assert_eq!(m.to_string(), format!("{}example.com", kind));
+            assert_eq!(
+                Mechanism::try_new(kind, Qualifier::Pass, None).unwrap_err(),
+                MechanismError::RrdataRequired(kind)
+            );
+        }
+    }
+    #[test]
+    fn all() {
+        let all = Mechanism::try_new(Kind::All, Qualifier::SoftFail, None).unwrap();
+        assert_eq!(all.to_string(), "~all");
+        assert_eq!(
+            Mechanism::try_new(Kind::All, Qualifier::Pass, some("junk")).unwrap_err(),
+            MechanismError::RrdataNotAllowed(Kind::All)
+        );
+    }
+    #[test]
+    fn ip_kinds() {
+        let err = Mechanism::try_new(Kind::IpV4, Qualifier::Pass, some("203.0.113.0/24"));
+        assert_eq!(
+            err.unwrap_err(),
+            MechanismError::IpNetworkTerm("ip4:203.0.113.0/24".to_string())
+        );
+        let err = Mechanism::try_new(Kind::IpV6, Qualifier::Pass, None);
+        assert_eq!(err.unwrap_err().is_ip_network_term(), true);
+    }
+    #[test]
+    fn modifier_qualifier() {
+        for kind in [Kind::Redirect, Kind::Exp] {
+            assert_eq!(
+                Mechanism::try_new(kind, Qualifier::Fail, some("example.com")).unwrap_err(),
+                MechanismError::QualifierNotAllowedForModifier(kind)
+            );
+        }
+    }
+    #[test]
+    fn empty_rrdata() {
+        for kind in [Kind::A, Kind::Include, Kind::Redirect] {
+            assert_eq!(
+                Mechanism::try_new(kind, Qualifier::Pass, some("")).unwrap_err(),
+                MechanismError::InvalidDomainHost(String::new())
+            );
+        }
+        assert_eq!(
+            Mechanism::try_new(Kind::Exists, Qualifier::Pass, some("a b.example.com")).unwrap_err(),
+            MechanismError::InvalidDomainHost("a b.example.com".to_string())
+        );
+    }
+    #[test]
+    fn matches_parsed() {
+        let built = Mechanism::try_new(Kind::Include, Qualifier::Neutral, some("_spf.example.com"))
+            .unwrap();
+        let parsed: Mechanism<String> = "?include:_spf.example.com".parse().unwrap();
+        assert_eq!(built, parsed);
+    }
+    #[test]
+    fn constructors() {
+        assert_eq!(
+            Mechanism::include(Qualifier::Pass, "").unwrap_err(),
+            MechanismError::InvalidDomainHost(String::new())
+        );
+        assert_eq!(
+            Mechanism::exp(" ").unwrap_err(),
+            MechanismError::InvalidDomainHost(" ".to_string())
+        );
+    }
+}
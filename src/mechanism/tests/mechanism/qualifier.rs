@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod set_qualifier {
+
+    use crate::mechanism::{Mechanism, ParsedMechanism, Qualifier};
+    use crate::spf::{ParseOptions, Spf};
+    use ipnetwork::IpNetwork;
+
+    #[test]
+    fn each_qualifier() {
+        for (qualifier, expected) in [
+            (Qualifier::Pass, "mx:example.com"),
+            (Qualifier::Fail, "-mx:example.com"),
+            (Qualifier::SoftFail, "~mx:example.com"),
+            (Qualifier::Neutral, "?mx:example.com"),
+        ] {
+            let mut mx: Mechanism<String> = "?mx:example.com".parse().unwrap();
+            mx.set_qualifier(qualifier);
+            assert_eq!(mx.qualifier(), &qualifier);
+            assert_eq!(mx.to_string(), expected);
+        }
+    }
+    #[test]
+    fn to_implicit_pass() {
+        let mut a: Mechanism<String> = "-a".parse().unwrap();
+        a.set_qualifier(Qualifier::Pass);
+        assert_eq!(a.is_pass(), true);
+        assert_eq!(a.to_string(), "a");
+        let ip6: Mechanism<IpNetwork> = "~ip6:2001:db8::/32".parse().unwrap();
+        assert_eq!(
+            ip6.with_qualifier(Qualifier::Pass).to_string(),
+            "ip6:2001:db8::/32"
+        );
+    }
+    #[test]
+    fn explicit_pass() {
+        let spf: Spf = Spf::from_str_with_options(
+            "v=spf1 +a +mx -all",
+            &ParseOptions::new().preserve_explicit_pass(true),
+        )
+        .unwrap();
+        let a = spf.a().unwrap()[0].clone();
+        assert_eq!(a.to_string(), "+a");
+        // Setting the same qualifier keeps the `+`.
+        assert_eq!(a.clone().with_qualifier(Qualifier::Pass).to_string(), "+a");
+        // Once changed, a `Pass` qualifier is no longer written.
+        let a = a
+            .with_qualifier(Qualifier::Fail)
+            .with_qualifier(Qualifier::Pass);
+        assert_eq!(a.to_string(), "a");
+    }
+    #[test]
+    fn modifier() {
+        let redirect = Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap();
+        assert_eq!(
+            redirect.with_qualifier(Qualifier::Fail).to_string(),
+            "redirect=_spf.example.com"
+        );
+    }
+    #[test]
+    fn downgrade_record() {
+        let spf: Spf = "v=spf1 -a mx -include:_spf.example.com -ip4:203.0.113.0/24 -all"
+            .parse()
+            .unwrap();
+        let mut downgraded = Spf::new();
+        downgraded.set_v1();
+        for mechanism in spf {
+            match mechanism {
+                ParsedMechanism::TXT(m) if m.is_fail() => {
+                    downgraded.append_mechanism(m.with_qualifier(Qualifier::SoftFail))
+                }
+                ParsedMechanism::TXT(m) => downgraded.append_mechanism(m),
+                ParsedMechanism::IP(m) if m.is_fail() => {
+                    downgraded.append_ip_mechanism(m.with_qualifier(Qualifier::SoftFail))
+                }
+                ParsedMechanism::IP(m) => downgraded.append_ip_mechanism(m),
+            }
+        }
+        assert_eq!(
+            downgraded.to_string(),
+            "v=spf1 ~a mx ~include:_spf.example.com ~ip4:203.0.113.0/24 ~all"
+        );
+    }
+}
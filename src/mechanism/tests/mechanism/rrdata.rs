@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod string {
+
+    use crate::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
+
+    #[test]
+    fn domain_swap() {
+        let mut include: Mechanism<String> = "~include:old.example.net".parse().unwrap();
+        include
+            .set_rrdata(Some("new.example.net".to_string()))
+            .unwrap();
+        assert_eq!(include.qualifier(), &Qualifier::SoftFail);
+        assert_eq!(include.raw(), "new.example.net");
+        assert_eq!(include.to_string(), "~include:new.example.net");
+        let reparsed: Mechanism<String> = include.to_string().parse().unwrap();
+        assert_eq!(reparsed, include);
+    }
+    #[test]
+    fn optional_rrdata() {
+        let mut a: Mechanism<String> = "-a:example.com/24".parse().unwrap();
+        a.set_rrdata(None).unwrap();
+        assert_eq!(a.to_string(), "-a");
+        a.set_rrdata(Some("/28".to_string())).unwrap();
+        assert_eq!(a.to_string(), "-a/28");
+        let mut mx = Mechanism::mx(Qualifier::Pass);
+        mx.set_rrdata(Some("mail.example.com".to_string())).unwrap();
+        assert_eq!(mx.to_string(), "mx:mail.example.com");
+        let mut ptr: Mechanism<String> = "ptr:example.com".parse().unwrap();
+        ptr.set_rrdata(None).unwrap();
+        assert_eq!(ptr.to_string(), "ptr");
+    }
+    #[test]
+    fn all_takes_no_value() {
+        let mut all = Mechanism::all(Qualifier::Fail);
+        let err = all.set_rrdata(Some("example.com".to_string())).unwrap_err();
+        assert_eq!(err, MechanismError::RrdataNotAllowed(Kind::All));
+        assert_eq!(err.is_rrdata_not_allowed(), true);
+        assert_eq!(err.to_string(), "all may not have a value");
+        assert_eq!(all.to_string(), "-all");
+        all.set_rrdata(None).unwrap();
+    }
+    #[test]
+    fn value_required() {
+        for term in [
+            "include:_spf.example.com",
+            "exists:%{i}.example.com",
+            "redirect=_spf.example.com",
+            "exp=explain.example.com",
+        ] {
+            let mut mechanism: Mechanism<String> = term.parse().unwrap();
+            let err = mechanism.set_rrdata(None).unwrap_err();
+            assert_eq!(err, MechanismError::RrdataRequired(*mechanism.kind()));
+            assert_eq!(err.is_rrdata_required(), true);
+            // The mechanism is unchanged.
+            assert_eq!(mechanism.to_string(), term);
+        }
+        let err = Mechanism::include(Qualifier::Pass, "_spf.example.com")
+            .unwrap()
+            .set_rrdata(None)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "include: requires a value");
+    }
+    #[test]
+    fn whitespace() {
+        let mut include = Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap();
+        for value in [
+            "new.example.net -all",
+            " new.example.net",
+            "new.\texample.net",
+            "",
+        ] {
+            assert_eq!(
+                include.set_rrdata(Some(value.to_string())).unwrap_err(),
+                MechanismError::InvalidDomainHost(value.to_string())
+            );
+        }
+        assert_eq!(include.to_string(), "include:_spf.example.com");
+    }
+    #[test]
+    #[cfg(feature = "strict-dns")]
+    fn strict_dns() {
+        let mut include = Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap();
+        assert_eq!(
+            include
+                .set_rrdata(Some("example..com".to_string()))
+                .unwrap_err(),
+            MechanismError::InvalidDomainHost("example..com".to_string())
+        );
+    }
+}
+#[cfg(test)]
+mod ip {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use ipnetwork::IpNetwork;
+
+    #[test]
+    fn same_family() {
+        let mut ip: Mechanism<IpNetwork> = "~ip4:203.0.113.0/24".parse().unwrap();
+        ip.set_rrdata("198.51.100.0/24".parse().unwrap());
+        assert_eq!(ip.kind(), &Kind::IpV4);
+        assert_eq!(ip.qualifier(), &Qualifier::SoftFail);
+        assert_eq!(ip.to_string(), "~ip4:198.51.100.0/24");
+    }
+    #[test]
+    fn family_changes() {
+        let mut ip: Mechanism<IpNetwork> = "ip4:203.0.113.0/24".parse().unwrap();
+        ip.set_rrdata("2001:db8::/32".parse().unwrap());
+        assert_eq!(ip.kind(), &Kind::IpV6);
+        assert_eq!(ip.to_string(), "ip6:2001:db8::/32");
+        ip.set_rrdata("203.0.113.0/24".parse().unwrap());
+        assert_eq!(ip.kind(), &Kind::IpV4);
+        assert_eq!(ip.to_string(), "ip4:203.0.113.0/24");
+    }
+    #[test]
+    fn implicit_prefix_is_not_kept() {
+        let mut ip: Mechanism<IpNetwork> = "ip4:203.0.113.1".parse().unwrap();
+        assert_eq!(ip.to_string(), "ip4:203.0.113.1");
+        ip.set_rrdata("203.0.113.2".parse().unwrap());
+        assert_eq!(ip.to_string(), "ip4:203.0.113.2/32");
+    }
+}
@@ -1,2 +1,3 @@
+mod macros;
 mod mechanism;
 mod parsedmechanism;
@@ -50,14 +50,8 @@ mod test_a {
         let m: Result<ParsedMechanism, MechanismError> = "ab".parse();
         assert_eq!(m.is_err(), true);
         let err = m.unwrap_err();
-        assert_eq!(
-            err,
-            MechanismError::InvalidMechanismFormat("ab".to_string())
-        );
-        assert_eq!(
-            err.to_string(),
-            "ab does not conform to any Mechanism format"
-        );
+        assert_eq!(err, MechanismError::UnknownMechanismName("ab".to_string()));
+        assert_eq!(err.to_string(), "ab is not a known mechanism");
     }
 }
 
@@ -138,3 +132,52 @@ mod exists_invalid {
         assert_eq!(err.to_string(), "Invalid DNS string: example.xx");
     }
 }
+#[cfg(test)]
+mod dispatch {
+    use crate::mechanism::{Kind, MechanismError, ParsedMechanism, Qualifier};
+    #[test]
+    fn ip6_with_qualifier() {
+        let m: ParsedMechanism = "~ip6:2001:db8::/32".parse().unwrap();
+        assert_eq!(m.is_network(), true);
+        assert_eq!(m.kind(), &Kind::IpV6);
+        assert_eq!(m.qualifier(), &Qualifier::SoftFail);
+        assert_eq!(m.raw(), "2001:db8::/32");
+        assert_eq!(m.to_string(), "~ip6:2001:db8::/32");
+    }
+    #[test]
+    fn exists_macro() {
+        let m: ParsedMechanism = "-exists:%{i}.rbl.example.org".parse().unwrap();
+        assert_eq!(m.is_network(), false);
+        assert_eq!(m.kind(), &Kind::Exists);
+        assert_eq!(m.qualifier(), &Qualifier::Fail);
+        assert_eq!(m.raw(), "%{i}.rbl.example.org");
+    }
+    #[test]
+    fn mx_cidr() {
+        let m: ParsedMechanism = "mx/24".parse().unwrap();
+        assert_eq!(m.kind(), &Kind::MX);
+        assert_eq!(m.qualifier(), &Qualifier::Pass);
+        assert_eq!(m.to_string(), "mx/24");
+    }
+    #[test]
+    fn redirect() {
+        let m: ParsedMechanism = "redirect=_spf.example.com".parse().unwrap();
+        assert_eq!(m.kind(), &Kind::Redirect);
+        assert_eq!(m.raw(), "_spf.example.com");
+    }
+    #[test]
+    fn include_of_ip_named_domain() {
+        let m: ParsedMechanism = "include:ip4:example.com".parse().unwrap();
+        assert_eq!(m.is_network(), false);
+        assert_eq!(m.kind(), &Kind::Include);
+        assert_eq!(m.raw(), "ip4:example.com");
+    }
+    #[test]
+    fn garbage() {
+        let m: Result<ParsedMechanism, MechanismError> = "not-a-term".parse();
+        assert_eq!(
+            m.unwrap_err(),
+            MechanismError::UnknownMechanismName("not-a-term".to_string())
+        );
+    }
+}
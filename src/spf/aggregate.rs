@@ -0,0 +1,141 @@
+//! Aggregation of `ip4:` and `ip6:` networks into the fewest networks covering the same
+//! addresses.
+use crate::mechanism::Mechanism;
+use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+// Aggregate each run of consecutive networks with the same qualifier. Networks are evaluated
+// in order, so networks are never moved past a network with a different qualifier. A run which
+// can not be made shorter is kept as it is.
+pub(crate) fn aggregate_networks(networks: &[Mechanism<IpNetwork>]) -> Vec<Mechanism<IpNetwork>> {
+    let mut aggregated = Vec::with_capacity(networks.len());
+    let mut start = 0;
+    while start < networks.len() {
+        let qualifier = networks[start].qualifier();
+        let end = networks[start..]
+            .iter()
+            .position(|m| m.qualifier() != qualifier)
+            .map_or(networks.len(), |len| start + len);
+        aggregated.extend(aggregate_run(&networks[start..end]));
+        start = end;
+    }
+    aggregated
+}
+
+// Networks which all have the same qualifier and family.
+fn aggregate_run(run: &[Mechanism<IpNetwork>]) -> Vec<Mechanism<IpNetwork>> {
+    let merged = merge(run.iter().map(|m| range(m.as_network())).collect());
+    let is_ipv4 = run[0].as_network().is_ipv4();
+    let cidrs: Vec<IpNetwork> = merged
+        .into_iter()
+        .flat_map(|(first, last)| cidrs(first, last, is_ipv4))
+        .collect();
+    if cidrs.len() >= run.len() {
+        return run.to_vec();
+    }
+    cidrs
+        .into_iter()
+        .map(|cidr| {
+            // Keep a network which is unchanged, as it may be written without a prefix.
+            run.iter()
+                .find(|m| m.as_network() == &cidr)
+                .cloned()
+                .unwrap_or_else(|| Mechanism::ip(*run[0].qualifier(), cidr))
+        })
+        .collect()
+}
+
+// The ranges which cover the same addresses as `ranges`, in order, without overlapping or
+// touching.
+pub(crate) fn merge(mut ranges: Vec<(u128, u128)>) -> Vec<(u128, u128)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u128, u128)> = Vec::new();
+    for (first, last) in ranges {
+        match merged.last_mut() {
+            Some((_, end)) if first <= end.saturating_add(1) => *end = (*end).max(last),
+            _ => merged.push((first, last)),
+        }
+    }
+    merged
+}
+
+// The number of bits in an address of the family.
+fn address_bits(is_ipv4: bool) -> u32 {
+    if is_ipv4 {
+        32
+    } else {
+        128
+    }
+}
+
+// The host part of a network with `host_bits` bits.
+fn host_mask(host_bits: u32) -> u128 {
+    match host_bits {
+        0 => 0,
+        128 => u128::MAX,
+        bits => (1 << bits) - 1,
+    }
+}
+
+// The first and last address of `network`.
+pub(crate) fn range(network: &IpNetwork) -> (u128, u128) {
+    let (address, bits) = match network {
+        IpNetwork::V4(n) => (u128::from(u32::from(n.network())), 32),
+        IpNetwork::V6(n) => (u128::from(n.network()), 128),
+    };
+    let mask = host_mask(bits - u32::from(network.prefix()));
+    (address & !mask, address | mask)
+}
+
+// The fewest networks which cover every address from `first` to `last`.
+pub(crate) fn cidrs(mut first: u128, last: u128, is_ipv4: bool) -> Vec<IpNetwork> {
+    let bits = address_bits(is_ipv4);
+    let mut cidrs = Vec::new();
+    loop {
+        let mut host_bits = first.trailing_zeros().min(bits);
+        while first | host_mask(host_bits) > last {
+            host_bits -= 1;
+        }
+        let prefix = (bits - host_bits) as u8;
+        cidrs.push(if is_ipv4 {
+            IpNetwork::V4(Ipv4Network::new(Ipv4Addr::from(first as u32), prefix).unwrap())
+        } else {
+            IpNetwork::V6(Ipv6Network::new(Ipv6Addr::from(first), prefix).unwrap())
+        });
+        let end = first | host_mask(host_bits);
+        if end >= last {
+            return cidrs;
+        }
+        first = end + 1;
+    }
+}
+
+#[test]
+fn range_to_cidrs() {
+    let first = u128::from(u32::from(Ipv4Addr::new(192, 0, 2, 0)));
+    let last = u128::from(u32::from(Ipv4Addr::new(192, 0, 2, 255)));
+    assert_eq!(
+        cidrs(first, last, true),
+        vec!["192.0.2.0/24".parse::<IpNetwork>().unwrap()]
+    );
+    let networks: Vec<String> = cidrs(first + 1, last - 1, true)
+        .iter()
+        .map(IpNetwork::to_string)
+        .collect();
+    assert_eq!(networks.len(), 14);
+    assert_eq!(networks[0], "192.0.2.1/32");
+    assert_eq!(networks[7], "192.0.2.128/26");
+    assert_eq!(networks[13], "192.0.2.254/32");
+}
+#[test]
+fn whole_address_space() {
+    assert_eq!(
+        cidrs(0, u128::from(u32::MAX), true),
+        vec!["0.0.0.0/0".parse::<IpNetwork>().unwrap()]
+    );
+    assert_eq!(
+        cidrs(0, u128::MAX, false),
+        vec!["::/0".parse::<IpNetwork>().unwrap()]
+    );
+    assert_eq!(range(&"::/0".parse().unwrap()), (0, u128::MAX));
+}
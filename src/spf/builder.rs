@@ -0,0 +1,170 @@
+//! A chainable builder for constructing an Spf record programmatically.
+use crate::mechanism::{Mechanism, MechanismError, Qualifier};
+use crate::spf::{Spf, SpfError};
+use ipnetwork::IpNetwork;
+
+/// Builds an [`Spf`](crate::Spf) from a chain of method calls.
+///
+/// Each method appends as with [`Spf::try_append_mechanism`](crate::Spf::try_append_mechanism).
+/// The first error is kept and returned by [`build()`](SpfBuilder::build), so misuse such as
+/// calling `all()` twice is not silently ignored.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::Qualifier;
+/// use decon_spf::SpfBuilder;
+/// let spf = SpfBuilder::new()
+///     .v1()
+///     .a(Qualifier::Pass, None)
+///     .mx(Qualifier::Pass, Some("mail.example.com"))
+///     .ip("203.0.113.0/24")
+///     .ip("2001:db8::/32")
+///     .include(Qualifier::Pass, "_spf.google.com")
+///     .include(Qualifier::Pass, "spf.protection.outlook.com")
+///     .all(Qualifier::SoftFail)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     spf.to_string(),
+///     "v=spf1 a mx:mail.example.com include:_spf.google.com include:spf.protection.outlook.com \
+///      ip4:203.0.113.0/24 ip6:2001:db8::/32 ~all"
+/// );
+/// assert_eq!(spf.is_valid(), true);
+///```
+#[derive(Debug, Default)]
+pub struct SpfBuilder {
+    spf: Spf,
+    error: Option<SpfError>,
+}
+
+impl SpfBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        SpfBuilder::default()
+    }
+    // Keep the first error. Later errors are a consequence of the first or would be hidden by it.
+    fn record(mut self, result: Result<(), SpfError>) -> Self {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+        self
+    }
+    fn mechanism(mut self, mechanism: Result<Mechanism<String>, MechanismError>) -> Self {
+        let result = mechanism
+            .map_err(SpfError::InvalidMechanism)
+            .and_then(|mechanism| self.spf.try_append_mechanism(mechanism));
+        self.record(result)
+    }
+    /// Set the version to `v=spf1`
+    pub fn v1(mut self) -> Self {
+        self.spf.set_v1();
+        self
+    }
+    /// Set the version to `spf2.0/pra`
+    pub fn v2_pra(mut self) -> Self {
+        self.spf.set_v2_pra();
+        self
+    }
+    /// Set the version to `spf2.0/mfrom`
+    pub fn v2_mfrom(mut self) -> Self {
+        self.spf.set_v2_mfrom();
+        self
+    }
+    /// Set the version to `spf2.0/mfrom,pra`
+    pub fn v2_mfrom_pra(mut self) -> Self {
+        self.spf.set_v2_mfrom_pra();
+        self
+    }
+    /// Set the longest the record may be written. See
+    /// [`Spf::set_max_length`](crate::Spf::set_max_length)
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.spf.set_max_length(max_length);
+        self
+    }
+    /// Append an `a` mechanism, optionally with a domain-spec and cidr suffix.
+    pub fn a(self, qualifier: Qualifier, domain: Option<&str>) -> Self {
+        let a = Mechanism::a(qualifier);
+        match domain {
+            Some(domain) => self.mechanism(a.with_rrdata(domain)),
+            None => self.mechanism(Ok(a)),
+        }
+    }
+    /// Append an `mx` mechanism, optionally with a domain-spec and cidr suffix.
+    pub fn mx(self, qualifier: Qualifier, domain: Option<&str>) -> Self {
+        let mx = Mechanism::mx(qualifier);
+        match domain {
+            Some(domain) => self.mechanism(mx.with_rrdata(domain)),
+            None => self.mechanism(Ok(mx)),
+        }
+    }
+    /// Append an `include:` mechanism.
+    pub fn include(self, qualifier: Qualifier, domain: &str) -> Self {
+        self.mechanism(Mechanism::include(qualifier, domain))
+    }
+    /// Append an `exists:` mechanism.
+    pub fn exists(self, qualifier: Qualifier, domain: &str) -> Self {
+        self.mechanism(Mechanism::exists(qualifier, domain))
+    }
+    /// Append an `ip4:` or `ip6:` mechanism with a `Pass` qualifier. The family is taken
+    /// from `network`. A network without a prefix length is written without one.
+    pub fn ip(mut self, network: &str) -> Self {
+        match network.parse::<IpNetwork>() {
+            Ok(ip) => {
+                let mut mechanism = Mechanism::ip(Qualifier::Pass, ip);
+                mechanism.preserve_implicit_prefix(network);
+                let result = self.spf.try_append_ip_mechanism(mechanism);
+                self.record(result)
+            }
+            Err(error) => self.record(Err(SpfError::InvalidIPAddr(error))),
+        }
+    }
+    /// Append the `all` mechanism.
+    pub fn all(self, qualifier: Qualifier) -> Self {
+        self.mechanism(Ok(Mechanism::all(qualifier)))
+    }
+    /// Append the `redirect=` modifier.
+    pub fn redirect(self, domain: &str) -> Self {
+        self.mechanism(Mechanism::redirect(Qualifier::Pass, domain))
+    }
+    /// Append the `exp=` modifier.
+    pub fn exp(self, domain: &str) -> Self {
+        self.mechanism(Mechanism::exp(domain))
+    }
+    /// Append a modifier which is not `redirect=` or `exp=`. See
+    /// [`Spf::append_modifier`](crate::Spf::append_modifier)
+    pub fn modifier(mut self, name: &str, value: &str) -> Self {
+        let result = self.spf.append_modifier(name, value);
+        self.record(result)
+    }
+    /// Parse and append any single term. See [`Spf::append_str`](crate::Spf::append_str)
+    pub fn term(mut self, term: &str) -> Self {
+        let result = self.spf.append_str(term);
+        self.record(result)
+    }
+    /// Build the Spf, returning the first error found while appending.
+    ///
+    /// # Errors:
+    /// - The first error found while appending. e.g.
+    ///   [`ModifierMayOccurOnlyOnce`](SpfError::ModifierMayOccurOnlyOnce)
+    /// - [`InvalidSource`](SpfError::InvalidSource) if no version was set.
+    /// - [`SourceLengthExceeded`](SpfError::SourceLengthExceeded) if the record is longer
+    ///   than 255 characters, or the length set with [`max_length()`](SpfBuilder::max_length).
+    /// - [`LookupLimitExceeded`](SpfError::LookupLimitExceeded) if the record needs more than
+    ///   10 DNS lookups. See [`Spf::lookup_count()`](crate::Spf::lookup_count)
+    pub fn build(self) -> Result<Spf, SpfError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let mut spf = self.spf;
+        if spf.version_kind().is_none() {
+            return Err(SpfError::InvalidSource);
+        }
+        spf.check_length()?;
+        if spf.lookup_count() > 10 {
+            return Err(SpfError::LookupLimitExceeded);
+        }
+        spf.was_validated = true;
+        spf.is_valid = true;
+        Ok(spf)
+    }
+}
@@ -0,0 +1,121 @@
+//! The differences between two Spf records.
+use crate::helpers;
+use crate::mechanism::Mechanism;
+use crate::spf::Spf;
+use ipnetwork::IpNetwork;
+
+/// The terms which differ between two [`Spf`](crate::Spf) records. See
+/// [`Spf::diff()`](crate::Spf::diff)
+///
+/// Each term is written as in the `to_string()` of its record. The version, mechanisms and
+/// modifiers are all compared as terms. The order of the terms is not compared.
+///
+/// A term whose qualifier has changed, such as `~all` to `-all`, is reported as modified
+/// rather than as both removed and added. So is a term which differs only by case or by an
+/// implied prefix length.
+///
+/// # Example:
+///```rust
+/// use decon_spf::Spf;
+/// let before: Spf = "v=spf1 include:old.example.com mx ~all".parse().unwrap();
+/// let after: Spf = "v=spf1 mx include:new.example.com -all".parse().unwrap();
+/// let diff = before.diff(&after);
+/// assert_eq!(diff.added(), &["include:new.example.com"]);
+/// assert_eq!(diff.removed(), &["include:old.example.com"]);
+/// assert_eq!(diff.modified(), &[("~all".to_string(), "-all".to_string())]);
+/// assert_eq!(
+///     diff.to_string(),
+///     "- include:old.example.com\n+ include:new.example.com\n* ~all -> -all"
+/// );
+///```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpfDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+    modified: Vec<(String, String)>,
+}
+
+impl SpfDiff {
+    pub(crate) fn new(before: &Spf, after: &Spf) -> Self {
+        let mut before = terms(before);
+        let mut after = terms(after);
+        // Terms which are unchanged.
+        before.retain(
+            |(_, term)| match after.iter().position(|(_, t)| t == term) {
+                Some(index) => {
+                    after.remove(index);
+                    false
+                }
+                None => true,
+            },
+        );
+        // Terms which differ only by their qualifier, case or an implied prefix length.
+        let mut modified = Vec::new();
+        before.retain(
+            |(key, term)| match after.iter().position(|(k, _)| k == key) {
+                Some(index) => {
+                    modified.push((term.clone(), after.remove(index).1));
+                    false
+                }
+                None => true,
+            },
+        );
+        SpfDiff {
+            added: after.into_iter().map(|(_, term)| term).collect(),
+            removed: before.into_iter().map(|(_, term)| term).collect(),
+            modified,
+        }
+    }
+    /// Returns the terms which are only present in the second record.
+    pub fn added(&self) -> &[String] {
+        &self.added
+    }
+    /// Returns the terms which are only present in the first record.
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+    /// Returns the terms which have been modified, as the term in the first record and the
+    /// term in the second record.
+    pub fn modified(&self) -> &[(String, String)] {
+        &self.modified
+    }
+    /// Returns `true` if the records hold the same terms.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Lists each removed term prefixed with `- `, each added term prefixed with `+ ` and each
+/// modified term as `* before -> after`, one term per line.
+impl std::fmt::Display for SpfDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .removed
+            .iter()
+            .map(|term| format!("- {}", term))
+            .chain(self.added.iter().map(|term| format!("+ {}", term)))
+            .chain(
+                self.modified
+                    .iter()
+                    .map(|(before, after)| format!("* {} -> {}", before, after)),
+            )
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+// Each term of `spf` with the key used to match it against a term of another record. The key
+// leaves out the qualifier, ignores case and includes any implied prefix length.
+fn terms(spf: &Spf) -> Vec<(String, String)> {
+    spf.to_string()
+        .split_whitespace()
+        .map(|term| {
+            let key = match term.parse::<Mechanism<IpNetwork>>() {
+                Ok(network) => network.to_string_with_prefix(),
+                Err(_) => term.to_string(),
+            };
+            let key = helpers::strip_qualifier(&key).to_ascii_lowercase();
+            (key, term.to_string())
+        })
+        .collect()
+}
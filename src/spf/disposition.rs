@@ -0,0 +1,61 @@
+//! What an Spf record does with a sender which matches none of its mechanisms.
+use crate::mechanism::Qualifier;
+
+/// The result given to a sender which matches none of the mechanisms of an Spf record. See
+/// [`Spf::default_disposition()`](crate::Spf::default_disposition)
+///
+/// # Example:
+///```rust
+/// use decon_spf::{Disposition, Spf};
+/// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ~all".parse().unwrap();
+/// assert_eq!(spf.default_disposition(), Disposition::SoftFail);
+/// assert_eq!(spf.default_disposition().to_string(), "softfail");
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Disposition {
+    /// `+all` or `all`. Every sender is authorised.
+    Pass,
+    /// `-all`
+    Fail,
+    /// `~all`
+    SoftFail,
+    /// `?all`, or a record without `all` or `redirect=`.
+    Neutral,
+    /// `redirect=`. The result is given by the record of the redirect domain.
+    Redirected,
+}
+
+impl Disposition {
+    /// Returns `true` for [`Disposition::Fail`]
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail)
+    }
+    /// Returns `true` for [`Disposition::Redirected`]
+    pub fn is_redirected(&self) -> bool {
+        matches!(self, Self::Redirected)
+    }
+}
+
+impl From<Qualifier> for Disposition {
+    fn from(qualifier: Qualifier) -> Self {
+        match qualifier {
+            Qualifier::Pass => Disposition::Pass,
+            Qualifier::Fail => Disposition::Fail,
+            Qualifier::SoftFail => Disposition::SoftFail,
+            Qualifier::Neutral => Disposition::Neutral,
+        }
+    }
+}
+
+impl std::fmt::Display for Disposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Disposition::Pass => "pass",
+            Disposition::Fail => "fail",
+            Disposition::SoftFail => "softfail",
+            Disposition::Neutral => "neutral",
+            Disposition::Redirected => "redirected",
+        };
+        f.write_str(name)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::mechanism::{Kind, MacroError, MechanismError};
 use ipnetwork::IpNetworkError;
 
 /// A list of expected possible errors for SPF records.
@@ -19,6 +20,153 @@ pub enum SpfError {
     RedirectWithAllMechanism,
     /// Network Address is not valid
     InvalidIPAddr(IpNetworkError),
+    /// The term is not a recognised mechanism, modifier or version. Only returned when parsing
+    /// in strict mode.
+    UnknownTerm(String),
+    /// A term could not be parsed into a valid Mechanism.
+    InvalidMechanism(MechanismError),
+    /// More than one `all` mechanism is present. Positions are the zero based index of each
+    /// term within the record.
+    DuplicateAll {
+        /// Position of the first `all` term.
+        first: usize,
+        /// Position of the repeated `all` term.
+        second: usize,
+    },
+    /// The modifier, `redirect=` or `exp=`, appears more than once.
+    ModifierMayOccurOnlyOnce(Kind),
+    /// The version string is present but is not the first term of the record.
+    VersionNotAtStart {
+        /// Position of the version term.
+        position: usize,
+    },
+    /// The version string appears more than once.
+    DuplicateVersion {
+        /// Position of the repeated version term.
+        position: usize,
+    },
+    /// A TXT character-string is empty.
+    EmptyTxtChunk {
+        /// Index of the empty character-string.
+        index: usize,
+    },
+    /// A mechanism follows the `all` mechanism and can never be evaluated. Only returned when
+    /// parsing in strict mode.
+    MechanismAfterAll {
+        /// Position of the `all` term.
+        all: usize,
+        /// Position of the mechanism which follows `all`.
+        position: usize,
+    },
+    /// The prefix length of an ip4 network is not between 0 and 32.
+    InvalidIPv4CidrLength(String),
+    /// The prefix length of an ip6 network is not between 0 and 128.
+    InvalidIPv6CidrLength(String),
+    /// The first term resembles a version but is not `v=spf1` or `spf2.0/<scope>`.
+    /// e.g. `v=spf10`, `spf1` or `spf2.0/`
+    InvalidVersion(String),
+    /// A scope of an `spf2.0` record is empty, unknown or repeated. e.g. `spf2.0/pra,pra`
+    InvalidScope(String),
+    /// The source is empty or contains only whitespace.
+    EmptyInput,
+    /// There is no mechanism of `kind` at `index`.
+    IndexOutOfBounds {
+        /// The kind of mechanism.
+        kind: Kind,
+        /// The requested index.
+        index: usize,
+        /// The number of mechanisms of `kind`.
+        len: usize,
+    },
+    /// A mechanism which the Spf can hold only once is already present. e.g. `all`
+    MechanismAlreadyPresent(Kind),
+    /// Two records can not be merged as one of them has a mechanism or modifier of this kind.
+    MergeConflict(Kind),
+    /// A mechanism is not of the expected kind.
+    KindMismatch {
+        /// The kind which was expected.
+        expected: Kind,
+        /// The kind of the mechanism provided.
+        found: Kind,
+    },
+    /// A `Mechanism<IpNetwork>` is not an `ip4:` or `ip6:` mechanism, or its kind does not
+    /// match the family of its network.
+    WrongMechanismKind(Kind),
+    /// A modifier name does not follow the grammar of RFC 7208 or is reserved. `redirect`,
+    /// `exp` and `v` are reserved.
+    InvalidModifierName(String),
+    /// An error occurred while parsing a term of the record. See [`ParseError`](ParseError)
+    /// for the term and its position.
+    Parse(Box<ParseError>),
+    /// Records include or redirect to each other. The domains are listed from the first
+    /// `include:` or `redirect=` to the domain which repeats.
+    IncludeCycle(Vec<String>),
+    /// More than one of the TXT records of a domain starts with `v=spf1`. Holds the number of
+    /// Spf records found.
+    MultipleSpfRecords(usize),
+    /// None of the TXT records of a domain starts with `v=spf1`.
+    NoSpfRecordFound,
+    /// The macros of a domain-spec could not be expanded.
+    InvalidMacro(MacroError),
+}
+
+/// An error found while parsing a single term of an Spf record.
+///
+/// The term index is the zero based index of the term within the record, where the version
+/// is at index `0`. The byte offset is the start of the term within the source string.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    term_index: usize,
+    byte_offset: usize,
+    term: String,
+    error: SpfError,
+}
+
+impl ParseError {
+    pub(crate) fn new(term_index: usize, byte_offset: usize, term: &str, error: SpfError) -> Self {
+        ParseError {
+            term_index,
+            byte_offset,
+            term: term.to_string(),
+            error,
+        }
+    }
+    /// Returns the zero based index of the term which failed.
+    pub fn term_index(&self) -> usize {
+        self.term_index
+    }
+    /// Returns the byte offset of the start of the failed term within the source.
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+    /// Returns the text of the term which failed.
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+    /// Returns a reference to the error found in the term.
+    pub fn error(&self) -> &SpfError {
+        &self.error
+    }
+    /// Returns `true` if `other` is the same error found in an identical term.
+    pub(crate) fn is_same_failure(&self, other: &ParseError) -> bool {
+        self.term == other.term && self.error == other.error
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (term {} '{}' at offset {})",
+            self.error, self.term_index, self.term, self.byte_offset
+        )
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
 }
 impl std::fmt::Display for SpfError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -38,6 +186,79 @@ impl std::fmt::Display for SpfError {
                 write!(f, "Redirect with unexpected 'All' Mechanism")
             }
             SpfError::InvalidIPAddr(err) => write!(f, "{}", err),
+            SpfError::UnknownTerm(term) => write!(f, "Unknown term: {}", term),
+            SpfError::InvalidMechanism(err) => write!(f, "{}", err),
+            SpfError::DuplicateAll { first, second } => write!(
+                f,
+                "Duplicate 'all' mechanism at term {}. First seen at term {}.",
+                second, first
+            ),
+            SpfError::ModifierMayOccurOnlyOnce(kind) => {
+                write!(f, "Modifier {} may occur only once.", kind.as_str())
+            }
+            SpfError::VersionNotAtStart { position } => write!(
+                f,
+                "Version must be the first term. Found at term {}.",
+                position
+            ),
+            SpfError::DuplicateVersion { position } => {
+                write!(f, "Duplicate version at term {}.", position)
+            }
+            SpfError::EmptyTxtChunk { index } => {
+                write!(f, "TXT character-string {} is empty.", index)
+            }
+            SpfError::MechanismAfterAll { all, position } => write!(
+                f,
+                "Mechanism at term {} follows 'all' at term {} and can never be evaluated.",
+                position, all
+            ),
+            SpfError::InvalidIPv4CidrLength(length) => {
+                write!(f, "{} is not a valid ip4 cidr length.", length)
+            }
+            SpfError::InvalidIPv6CidrLength(length) => {
+                write!(f, "{} is not a valid ip6 cidr length.", length)
+            }
+            SpfError::InvalidVersion(version) => write!(f, "Invalid version: {}", version),
+            SpfError::InvalidScope(scope) => write!(f, "Invalid spf2.0 scope: '{}'", scope),
+            SpfError::EmptyInput => write!(f, "Source string is empty."),
+            SpfError::IndexOutOfBounds { kind, index, len } => write!(
+                f,
+                "Index {} is out of bounds for {} mechanism(s) of kind {}.",
+                index,
+                len,
+                kind.as_str()
+            ),
+            SpfError::MechanismAlreadyPresent(kind) => {
+                write!(f, "Mechanism {} is already present.", kind.as_str())
+            }
+            SpfError::MergeConflict(kind) => {
+                write!(f, "Records with {} can not be merged.", kind.as_str())
+            }
+            SpfError::KindMismatch { expected, found } => write!(
+                f,
+                "Expected a mechanism of kind {} but found {}.",
+                expected.as_str(),
+                found.as_str()
+            ),
+            SpfError::WrongMechanismKind(kind) => write!(
+                f,
+                "Mechanism of kind {} does not match its ip network.",
+                kind.as_str()
+            ),
+            SpfError::InvalidModifierName(name) => write!(f, "Invalid modifier name: {}", name),
+            SpfError::Parse(err) => write!(f, "{}", err),
+            SpfError::IncludeCycle(path) => {
+                write!(f, "Records include each other: {}", path.join(" -> "))
+            }
+            SpfError::MultipleSpfRecords(count) => {
+                write!(
+                    f,
+                    "Found {} Spf records where only one is permitted.",
+                    count
+                )
+            }
+            SpfError::NoSpfRecordFound => write!(f, "No Spf record was found."),
+            SpfError::InvalidMacro(err) => write!(f, "{}", err),
         }
     }
 }
@@ -48,7 +269,20 @@ impl From<IpNetworkError> for SpfError {
     }
 }
 
-impl std::error::Error for SpfError {}
+impl From<MechanismError> for SpfError {
+    fn from(err: MechanismError) -> Self {
+        SpfError::InvalidMechanism(err)
+    }
+}
+
+impl std::error::Error for SpfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SpfError::Parse(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl SpfError {
     /// Returns `true` if the SpfError is any of those listed [`SpfError`](SpfError).
@@ -60,50 +294,199 @@ impl SpfError {
             || matches!(self, Self::InvalidSPF)
             || matches!(self, Self::RedirectWithAllMechanism)
             || matches!(self, Self::InvalidIPAddr(_))
+            || matches!(self, Self::UnknownTerm(_))
+            || matches!(self, Self::InvalidMechanism(_))
+            || matches!(self, Self::DuplicateAll { .. })
+            || matches!(self, Self::ModifierMayOccurOnlyOnce(_))
+            || matches!(self, Self::VersionNotAtStart { .. })
+            || matches!(self, Self::DuplicateVersion { .. })
+            || matches!(self, Self::EmptyTxtChunk { .. })
+            || matches!(self, Self::MechanismAfterAll { .. })
+            || matches!(self, Self::InvalidIPv4CidrLength(_))
+            || matches!(self, Self::InvalidIPv6CidrLength(_))
+            || matches!(self, Self::InvalidVersion(_))
+            || matches!(self, Self::InvalidScope(_))
+            || matches!(self, Self::EmptyInput)
+            || matches!(self, Self::IndexOutOfBounds { .. })
+            || matches!(self, Self::MechanismAlreadyPresent(_))
+            || matches!(self, Self::MergeConflict(_))
+            || matches!(self, Self::KindMismatch { .. })
+            || matches!(self, Self::WrongMechanismKind(_))
+            || matches!(self, Self::InvalidModifierName(_))
+            || matches!(self, Self::Parse(_))
+            || matches!(self, Self::IncludeCycle(_))
+            || matches!(self, Self::MultipleSpfRecords(_))
+            || matches!(self, Self::NoSpfRecordFound)
+            || matches!(self, Self::InvalidMacro(_))
+    }
+    /// Returns the error without any term position. For [`Parse`](SpfError::Parse) this is
+    /// the error found in the term, otherwise it is `self`.
+    ///
+    /// The `is_*` methods also look through [`Parse`](SpfError::Parse).
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Spf, SpfError};
+    /// let err = Spf::from_str_strict("v=spf1 a bogus. -all").unwrap_err();
+    /// assert_eq!(err.inner(), &SpfError::UnknownTerm("bogus.".to_string()));
+    /// assert_eq!(err.is_unknown_term(), true);
+    /// assert_eq!(err.parse_error().unwrap().term_index(), 2);
+    /// assert_eq!(err.to_string(), "Unknown term: bogus. (term 2 'bogus.' at offset 9)");
+    ///```
+    pub fn inner(&self) -> &SpfError {
+        match self {
+            Self::Parse(err) => err.error(),
+            _ => self,
+        }
+    }
+    /// Returns the [`ParseError`](ParseError), with the failed term and its position, if the
+    /// error was found while parsing a term.
+    pub fn parse_error(&self) -> Option<&ParseError> {
+        match self {
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+    /// Returns `true` if the SpfError was found while parsing a term.
+    pub fn is_parse_error(&self) -> bool {
+        matches!(self, Self::Parse(_))
     }
     /// Returns `true` if the SpfError indicates and Invalid Source error.
     pub fn is_invalid_source(&self) -> bool {
-        matches!(self, Self::InvalidSource)
+        matches!(self.inner(), Self::InvalidSource)
     }
     /// Returns `true` if the SpfError indicates and Invalid Source error.
     pub fn source_is_invalid(&self) -> bool {
-        matches!(self, Self::InvalidSource)
+        matches!(self.inner(), Self::InvalidSource)
     }
     /// Returns `true` if the SpfError indictes source length exceeds 255 characters.
     pub fn is_source_length_exceeded(&self) -> bool {
-        matches!(self, Self::SourceLengthExceeded)
+        matches!(self.inner(), Self::SourceLengthExceeded)
     }
     /// Returns `true` if the SpfError indictes source length exceeds 255 characters.
     pub fn source_length_exceeded(&self) -> bool {
-        matches!(self, Self::SourceLengthExceeded)
+        matches!(self.inner(), Self::SourceLengthExceeded)
     }
     /// Returns `true` if the SpfError indicates SPF contains more than 10 DNS lookups.
     pub fn is_lookup_limit_exceeded(&self) -> bool {
-        matches!(self, Self::LookupLimitExceeded)
+        matches!(self.inner(), Self::LookupLimitExceeded)
     }
     /// Returns `true` if the SpfError indicates SPF contains more than 10 DNS lookups.
     pub fn lookup_limit_exceeded(&self) -> bool {
-        matches!(self, Self::LookupLimitExceeded)
+        matches!(self.inner(), Self::LookupLimitExceeded)
     }
     /// Returns `true` if the SpfError indicates source of Spf has not been parsed.
     pub fn is_has_not_been_parsed(&self) -> bool {
-        matches!(self, Self::HasNotBeenParsed)
+        matches!(self.inner(), Self::HasNotBeenParsed)
     }
     /// Returns `true` if the SpfError indicates source of Spf has not been parsed.
     pub fn has_not_been_parsed(&self) -> bool {
-        matches!(self, Self::HasNotBeenParsed)
+        matches!(self.inner(), Self::HasNotBeenParsed)
     }
     /// Returns `true` if the SpfError indicates this is an invalid Spf Record.
     pub fn is_invalid_spf(&self) -> bool {
-        matches!(self, Self::InvalidSPF)
+        matches!(self.inner(), Self::InvalidSPF)
     }
     /// Returns `true` if the SpfError indicates the presents of `All` Mechanism
     pub fn is_redirect_with_all_mechanism(&self) -> bool {
-        matches!(self, Self::RedirectWithAllMechanism)
+        matches!(self.inner(), Self::RedirectWithAllMechanism)
     }
     /// Returns `true` if the SpfError indicates an Invalid IP Address
     pub fn is_invalid_ip_addr(&self) -> bool {
-        matches!(self, Self::InvalidIPAddr(_))
+        matches!(self.inner(), Self::InvalidIPAddr(_))
+    }
+    /// Returns `true` if the SpfError indicates an unknown term was found in strict mode.
+    pub fn is_unknown_term(&self) -> bool {
+        matches!(self.inner(), Self::UnknownTerm(_))
+    }
+    /// Returns `true` if the SpfError indicates a term could not be parsed into a Mechanism.
+    pub fn is_invalid_mechanism(&self) -> bool {
+        matches!(self.inner(), Self::InvalidMechanism(_))
+    }
+    /// Returns `true` if the SpfError indicates more than one `all` mechanism.
+    pub fn is_duplicate_all(&self) -> bool {
+        matches!(self.inner(), Self::DuplicateAll { .. })
+    }
+    /// Returns `true` if the SpfError indicates a modifier was present more than once.
+    pub fn is_modifier_may_occur_only_once(&self) -> bool {
+        matches!(self.inner(), Self::ModifierMayOccurOnlyOnce(_))
+    }
+    /// Returns `true` if the SpfError indicates the version is not the first term.
+    pub fn is_version_not_at_start(&self) -> bool {
+        matches!(self.inner(), Self::VersionNotAtStart { .. })
+    }
+    /// Returns `true` if the SpfError indicates the version appears more than once.
+    pub fn is_duplicate_version(&self) -> bool {
+        matches!(self.inner(), Self::DuplicateVersion { .. })
+    }
+    /// Returns `true` if the SpfError indicates an empty TXT character-string.
+    pub fn is_empty_txt_chunk(&self) -> bool {
+        matches!(self.inner(), Self::EmptyTxtChunk { .. })
+    }
+    /// Returns `true` if the SpfError indicates a mechanism follows `all`.
+    pub fn is_mechanism_after_all(&self) -> bool {
+        matches!(self.inner(), Self::MechanismAfterAll { .. })
+    }
+    /// Returns `true` if the SpfError indicates an out of range ip4 prefix length.
+    pub fn is_invalid_ipv4_cidr_length(&self) -> bool {
+        matches!(self.inner(), Self::InvalidIPv4CidrLength(_))
+    }
+    /// Returns `true` if the SpfError indicates an out of range ip6 prefix length.
+    pub fn is_invalid_ipv6_cidr_length(&self) -> bool {
+        matches!(self.inner(), Self::InvalidIPv6CidrLength(_))
+    }
+    /// Returns `true` if the SpfError indicates a malformed version.
+    pub fn is_invalid_version(&self) -> bool {
+        matches!(self.inner(), Self::InvalidVersion(_))
+    }
+    /// Returns `true` if the SpfError indicates an empty, unknown or repeated `spf2.0` scope.
+    pub fn is_invalid_scope(&self) -> bool {
+        matches!(self.inner(), Self::InvalidScope(_))
+    }
+    /// Returns `true` if the SpfError indicates the source is empty or only whitespace.
+    pub fn is_empty_input(&self) -> bool {
+        matches!(self.inner(), Self::EmptyInput)
+    }
+    /// Returns `true` if the SpfError indicates there is no mechanism at an index.
+    pub fn is_index_out_of_bounds(&self) -> bool {
+        matches!(self.inner(), Self::IndexOutOfBounds { .. })
+    }
+    /// Returns `true` if the SpfError indicates a mechanism which may only be held once is
+    /// already present.
+    pub fn is_mechanism_already_present(&self) -> bool {
+        matches!(self.inner(), Self::MechanismAlreadyPresent(_))
+    }
+    /// Returns `true` if the SpfError indicates two records could not be merged.
+    pub fn is_merge_conflict(&self) -> bool {
+        matches!(self.inner(), Self::MergeConflict(_))
+    }
+    /// Returns `true` if the SpfError indicates a mechanism of an unexpected kind.
+    pub fn is_kind_mismatch(&self) -> bool {
+        matches!(self.inner(), Self::KindMismatch { .. })
+    }
+    /// Returns `true` if the SpfError indicates an ip mechanism whose kind does not match its
+    /// network.
+    pub fn is_wrong_mechanism_kind(&self) -> bool {
+        matches!(self.inner(), Self::WrongMechanismKind(_))
+    }
+    /// Returns `true` if the SpfError indicates an invalid or reserved modifier name.
+    pub fn is_invalid_modifier_name(&self) -> bool {
+        matches!(self.inner(), Self::InvalidModifierName(_))
+    }
+    /// Returns `true` if the SpfError indicates records which include each other.
+    pub fn is_include_cycle(&self) -> bool {
+        matches!(self.inner(), Self::IncludeCycle(_))
+    }
+    /// Returns `true` if the SpfError indicates more than one Spf record.
+    pub fn is_multiple_spf_records(&self) -> bool {
+        matches!(self.inner(), Self::MultipleSpfRecords(_))
+    }
+    /// Returns `true` if the SpfError indicates that no Spf record was found.
+    pub fn is_no_spf_record_found(&self) -> bool {
+        matches!(self.inner(), Self::NoSpfRecordFound)
+    }
+    /// Returns `true` if the SpfError indicates a macro-string which could not be expanded.
+    pub fn is_invalid_macro(&self) -> bool {
+        matches!(self.inner(), Self::InvalidMacro(_))
     }
 }
 
@@ -150,3 +533,202 @@ fn is_invalid_ip_addr() {
     let err = SpfError::InvalidIPAddr(bad_ip);
     assert_eq!(err.is_invalid_ip_addr(), true)
 }
+#[test]
+fn is_unknown_term() {
+    let err = SpfError::UnknownTerm("bogus".to_string());
+    assert_eq!(err.is_unknown_term(), true);
+    assert_eq!(err.to_string(), "Unknown term: bogus");
+}
+#[test]
+fn is_invalid_mechanism() {
+    let err = SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat(
+        "redirect=".to_string(),
+    ));
+    assert_eq!(err.is_invalid_mechanism(), true);
+    assert_eq!(
+        err.to_string(),
+        "redirect= does not conform to any Mechanism format"
+    );
+}
+#[test]
+fn is_duplicate_all() {
+    let err = SpfError::DuplicateAll {
+        first: 2,
+        second: 3,
+    };
+    assert_eq!(err.is_duplicate_all(), true);
+    assert_eq!(
+        err.to_string(),
+        "Duplicate 'all' mechanism at term 3. First seen at term 2."
+    );
+}
+#[test]
+fn is_modifier_may_occur_only_once() {
+    let err = SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect);
+    assert_eq!(err.is_modifier_may_occur_only_once(), true);
+    assert_eq!(err.to_string(), "Modifier redirect= may occur only once.");
+}
+#[test]
+fn is_version_not_at_start() {
+    let err = SpfError::VersionNotAtStart { position: 2 };
+    assert_eq!(err.is_version_not_at_start(), true);
+    assert_eq!(
+        err.to_string(),
+        "Version must be the first term. Found at term 2."
+    );
+}
+#[test]
+fn is_duplicate_version() {
+    let err = SpfError::DuplicateVersion { position: 3 };
+    assert_eq!(err.is_duplicate_version(), true);
+    assert_eq!(err.to_string(), "Duplicate version at term 3.");
+}
+#[test]
+fn is_empty_txt_chunk() {
+    let err = SpfError::EmptyTxtChunk { index: 1 };
+    assert_eq!(err.is_empty_txt_chunk(), true);
+    assert_eq!(err.to_string(), "TXT character-string 1 is empty.");
+}
+#[test]
+fn is_parse_error() {
+    let err = SpfError::Parse(Box::new(ParseError::new(
+        2,
+        9,
+        "bogus",
+        SpfError::UnknownTerm("bogus".to_string()),
+    )));
+    assert_eq!(err.is_parse_error(), true);
+    assert_eq!(err.is_unknown_term(), true);
+    assert_eq!(err.inner(), &SpfError::UnknownTerm("bogus".to_string()));
+    assert_eq!(
+        err.to_string(),
+        "Unknown term: bogus (term 2 'bogus' at offset 9)"
+    );
+}
+#[test]
+fn is_invalid_ipv4_cidr_length() {
+    let err = SpfError::InvalidIPv4CidrLength("33".to_string());
+    assert_eq!(err.is_invalid_ipv4_cidr_length(), true);
+    assert_eq!(err.to_string(), "33 is not a valid ip4 cidr length.");
+}
+#[test]
+fn is_invalid_ipv6_cidr_length() {
+    let err = SpfError::InvalidIPv6CidrLength("129".to_string());
+    assert_eq!(err.is_invalid_ipv6_cidr_length(), true);
+    assert_eq!(err.to_string(), "129 is not a valid ip6 cidr length.");
+}
+#[test]
+fn is_mechanism_after_all() {
+    let err = SpfError::MechanismAfterAll {
+        all: 1,
+        position: 2,
+    };
+    assert_eq!(err.is_mechanism_after_all(), true);
+    assert_eq!(
+        err.to_string(),
+        "Mechanism at term 2 follows 'all' at term 1 and can never be evaluated."
+    );
+}
+#[test]
+fn is_invalid_version() {
+    let err = SpfError::InvalidVersion("v=spf10".to_string());
+    assert_eq!(err.is_invalid_version(), true);
+    assert_eq!(err.to_string(), "Invalid version: v=spf10");
+}
+#[test]
+fn is_invalid_scope() {
+    let err = SpfError::InvalidScope("helo".to_string());
+    assert_eq!(err.is_invalid_scope(), true);
+    assert_eq!(err.to_string(), "Invalid spf2.0 scope: 'helo'");
+}
+#[test]
+fn is_empty_input() {
+    let err = SpfError::EmptyInput;
+    assert_eq!(err.is_empty_input(), true);
+    assert_eq!(err.to_string(), "Source string is empty.");
+}
+#[test]
+fn is_index_out_of_bounds() {
+    let err = SpfError::IndexOutOfBounds {
+        kind: Kind::IpV4,
+        index: 3,
+        len: 2,
+    };
+    assert_eq!(err.is_index_out_of_bounds(), true);
+    assert_eq!(
+        err.to_string(),
+        "Index 3 is out of bounds for 2 mechanism(s) of kind ip4:."
+    );
+}
+#[test]
+fn is_kind_mismatch() {
+    let err = SpfError::KindMismatch {
+        expected: Kind::Include,
+        found: Kind::A,
+    };
+    assert_eq!(err.is_kind_mismatch(), true);
+    assert_eq!(
+        err.to_string(),
+        "Expected a mechanism of kind include: but found a."
+    );
+}
+#[test]
+fn is_mechanism_already_present() {
+    let err = SpfError::MechanismAlreadyPresent(Kind::All);
+    assert_eq!(err.is_mechanism_already_present(), true);
+    assert_eq!(err.to_string(), "Mechanism all is already present.");
+}
+#[test]
+fn is_merge_conflict() {
+    let err = SpfError::MergeConflict(Kind::Redirect);
+    assert_eq!(err.is_merge_conflict(), true);
+    assert_eq!(err.to_string(), "Records with redirect= can not be merged.");
+}
+#[test]
+fn is_wrong_mechanism_kind() {
+    let err = SpfError::WrongMechanismKind(Kind::A);
+    assert_eq!(err.is_wrong_mechanism_kind(), true);
+    assert_eq!(
+        err.to_string(),
+        "Mechanism of kind a does not match its ip network."
+    );
+}
+#[test]
+fn is_invalid_modifier_name() {
+    let err = SpfError::InvalidModifierName("exp".to_string());
+    assert_eq!(err.is_invalid_modifier_name(), true);
+    assert_eq!(err.to_string(), "Invalid modifier name: exp");
+}
+#[test]
+fn is_include_cycle() {
+    let err = SpfError::IncludeCycle(vec![
+        "a.example.com".to_string(),
+        "a.example.com".to_string(),
+    ]);
+    assert_eq!(err.is_include_cycle(), true);
+    assert_eq!(
+        err.to_string(),
+        "Records include each other: a.example.com -> a.example.com"
+    );
+}
+#[test]
+fn is_multiple_spf_records() {
+    let err = SpfError::MultipleSpfRecords(2);
+    assert_eq!(err.is_multiple_spf_records(), true);
+    assert_eq!(
+        err.to_string(),
+        "Found 2 Spf records where only one is permitted."
+    );
+}
+#[test]
+fn is_no_spf_record_found() {
+    let err = SpfError::NoSpfRecordFound;
+    assert_eq!(err.is_no_spf_record_found(), true);
+    assert_eq!(err.to_string(), "No Spf record was found.");
+}
+#[test]
+fn is_invalid_macro() {
+    let err = SpfError::InvalidMacro(MacroError::InvalidMacroString("%{x}".to_string()));
+    assert_eq!(err.is_invalid_macro(), true);
+    assert_eq!(err.to_string(), "%{x} is not a valid macro-string");
+}
@@ -0,0 +1,260 @@
+//! Expanding the `include:` and `redirect=` terms of an Spf record into a tree of the records
+//! they refer to.
+use crate::evaluate::SpfResolver;
+use crate::mechanism::{Mechanism, MechanismRef};
+use crate::spf::{ResolutionMap, Spf, SpfFetchError};
+use ipnetwork::IpNetwork;
+
+/// Supplies the Spf record of a domain. See [`Spf::expand()`](crate::Spf::expand)
+///
+/// Implemented for a [`ResolutionMap`](crate::ResolutionMap) and for every
+/// [`SpfResolver`](crate::evaluate::SpfResolver).
+pub trait SpfSource {
+    /// Returns the Spf record of `domain`.
+    fn spf(&self, domain: &str) -> Result<Spf, SpfFetchError>;
+}
+
+impl SpfSource for ResolutionMap {
+    fn spf(&self, domain: &str) -> Result<Spf, SpfFetchError> {
+        self.record(domain)
+            .cloned()
+            .ok_or_else(|| SpfFetchError::NoRecord(domain.to_string()))
+    }
+}
+
+impl<R: SpfResolver> SpfSource for R {
+    fn spf(&self, domain: &str) -> Result<Spf, SpfFetchError> {
+        Spf::from_resolver(self, domain)
+    }
+}
+
+impl SpfSource for dyn SpfResolver + '_ {
+    fn spf(&self, domain: &str) -> Result<Spf, SpfFetchError> {
+        Spf::from_resolver(self, domain)
+    }
+}
+
+/// The errors which can prevent an Spf record from being expanded. See
+/// [`Spf::expand()`](crate::Spf::expand)
+///
+/// A path lists the domains from the first `include:` or `redirect=` of the expanded record
+/// to the one which failed.
+#[derive(Debug, PartialEq)]
+pub enum ExpandError {
+    /// The last domain of the path is already in the path.
+    Cycle(Vec<String>),
+    /// The path is longer than the maximum depth.
+    DepthExceeded(Vec<String>),
+    /// The domain-spec of the term has macros, so the domain is only known when a message is
+    /// checked.
+    HasMacros(String),
+    /// The record of the domain could not be found.
+    Fetch {
+        /// The domain whose record was looked for.
+        domain: String,
+        /// Why the record was not found.
+        error: SpfFetchError,
+    },
+}
+
+impl std::fmt::Display for ExpandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpandError::Cycle(path) => {
+                write!(f, "Records include each other: {}", path.join(" -> "))
+            }
+            ExpandError::DepthExceeded(path) => {
+                write!(f, "Records are nested too deeply: {}", path.join(" -> "))
+            }
+            ExpandError::HasMacros(term) => write!(f, "{} has macros.", term),
+            ExpandError::Fetch { domain, error } => {
+                write!(f, "The record of {} was not found: {}", domain, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExpandError {}
+
+impl ExpandError {
+    /// Returns `true` if records include each other.
+    pub fn is_cycle(&self) -> bool {
+        matches!(self, Self::Cycle(_))
+    }
+    /// Returns `true` if records are nested deeper than allowed.
+    pub fn is_depth_exceeded(&self) -> bool {
+        matches!(self, Self::DepthExceeded(_))
+    }
+    /// Returns `true` if a domain-spec has macros.
+    pub fn has_macros(&self) -> bool {
+        matches!(self, Self::HasMacros(_))
+    }
+    /// Returns `true` if the record of a domain was not found.
+    pub fn is_fetch(&self) -> bool {
+        matches!(self, Self::Fetch { .. })
+    }
+    /// Returns the path of the domains which failed, if any.
+    pub fn path(&self) -> Option<&[String]> {
+        match self {
+            Self::Cycle(path) | Self::DepthExceeded(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// An Spf record and the records of its `include:` and `redirect=` terms. See
+/// [`Spf::expand()`](crate::Spf::expand)
+///
+/// # Example:
+///```rust
+/// use decon_spf::{ResolutionMap, Spf};
+/// let mut records = ResolutionMap::new();
+/// records.insert_record("_spf.example.com", "v=spf1 ip4:192.0.2.0/24 -all".parse().unwrap());
+/// let spf: Spf = "v=spf1 include:_spf.example.com ip4:198.51.100.1 -all".parse().unwrap();
+/// let tree = spf.expand(&records, 10).unwrap();
+/// assert_eq!(tree.domain(), None);
+/// assert_eq!(tree.children()[0].domain(), Some("_spf.example.com"));
+/// assert_eq!(tree.children()[0].mechanism().unwrap().to_string(), "include:_spf.example.com");
+/// let networks: Vec<String> = tree.ip_networks().map(|n| n.to_string()).collect();
+/// assert_eq!(networks, vec!["ip4:198.51.100.1", "ip4:192.0.2.0/24"]);
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpfTree {
+    domain: Option<String>,
+    mechanism: Option<Mechanism<String>>,
+    spf: Spf,
+    children: Vec<SpfTree>,
+}
+
+impl SpfTree {
+    /// Returns the domain which publishes the record. This is `None` for the record which
+    /// was expanded.
+    pub fn domain(&self) -> Option<&str> {
+        self.domain.as_deref()
+    }
+    /// Returns the `include:` or `redirect=` of the parent record which refers to this
+    /// record. This is `None` for the record which was expanded.
+    pub fn mechanism(&self) -> Option<&Mechanism<String>> {
+        self.mechanism.as_ref()
+    }
+    /// Returns the record.
+    pub fn spf(&self) -> &Spf {
+        &self.spf
+    }
+    /// Returns the records of the `include:` and `redirect=` terms of the record, in the
+    /// order of [`Spf::iter()`](crate::Spf::iter).
+    pub fn children(&self) -> &[SpfTree] {
+        &self.children
+    }
+    /// Returns `true` if the record has no `include:` or `redirect=`.
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+    /// Returns an iterator over this record and every record below it. A record is given
+    /// before its children.
+    pub fn iter(&self) -> SpfTreeIter<'_> {
+        SpfTreeIter { stack: vec![self] }
+    }
+    /// Returns an iterator over the `ip4:` and `ip6:` mechanisms of every record in the tree,
+    /// in the order of [`iter()`](SpfTree::iter).
+    pub fn ip_networks(&self) -> SpfTreeNetworks<'_> {
+        let inner = self.iter().flat_map(|node| {
+            node.spf.iter().filter_map(|m| match m {
+                MechanismRef::IP(network) => Some(network),
+                MechanismRef::TXT(_) => None,
+            })
+        });
+        SpfTreeNetworks {
+            inner: Box::new(inner),
+        }
+    }
+}
+
+/// An iterator over the records of an [`SpfTree`](SpfTree). See
+/// [`SpfTree::iter()`](SpfTree::iter)
+pub struct SpfTreeIter<'a> {
+    stack: Vec<&'a SpfTree>,
+}
+
+impl<'a> Iterator for SpfTreeIter<'a> {
+    type Item = &'a SpfTree;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
+}
+
+/// An iterator over the `ip4:` and `ip6:` mechanisms of an [`SpfTree`](SpfTree). See
+/// [`SpfTree::ip_networks()`](SpfTree::ip_networks)
+pub struct SpfTreeNetworks<'a> {
+    inner: Box<dyn Iterator<Item = &'a Mechanism<IpNetwork>> + 'a>,
+}
+
+impl<'a> Iterator for SpfTreeNetworks<'a> {
+    type Item = &'a Mechanism<IpNetwork>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+pub(crate) fn expand<S: SpfSource + ?Sized>(
+    spf: &Spf,
+    source: &S,
+    max_depth: usize,
+) -> Result<SpfTree, ExpandError> {
+    let mut path = Vec::new();
+    node(spf.clone(), None, None, source, max_depth, &mut path)
+}
+
+// Expand `spf`. `path` holds the domains of the records above it.
+fn node<S: SpfSource + ?Sized>(
+    spf: Spf,
+    domain: Option<String>,
+    mechanism: Option<Mechanism<String>>,
+    source: &S,
+    max_depth: usize,
+    path: &mut Vec<String>,
+) -> Result<SpfTree, ExpandError> {
+    let mut children = Vec::new();
+    for term in spf.iter() {
+        let term = match term {
+            MechanismRef::TXT(m) if m.kind().is_include() || m.kind().is_redirect() => m,
+            _ => continue,
+        };
+        if term.has_macros() {
+            return Err(ExpandError::HasMacros(term.to_string()));
+        }
+        let child = term.raw().to_ascii_lowercase();
+        let seen = path.contains(&child);
+        path.push(child.clone());
+        if seen {
+            return Err(ExpandError::Cycle(path.clone()));
+        }
+        if path.len() > max_depth {
+            return Err(ExpandError::DepthExceeded(path.clone()));
+        }
+        let record = source.spf(&child).map_err(|error| ExpandError::Fetch {
+            domain: child.clone(),
+            error,
+        })?;
+        let tree = node(
+            record,
+            Some(child),
+            Some(term.clone()),
+            source,
+            max_depth,
+            path,
+        )?;
+        children.push(tree);
+        path.pop();
+    }
+    Ok(SpfTree {
+        domain,
+        mechanism,
+        spf,
+        children,
+    })
+}
@@ -0,0 +1,207 @@
+//! Describing the terms of an Spf record in plain English.
+use crate::mechanism::{Kind, Mechanism, MechanismRef, Qualifier};
+use crate::spf::{LookupItem, Spf};
+use ipnetwork::IpNetwork;
+
+/// A term of an Spf record with a plain English description of what it does. See
+/// [`Spf::explain()`](crate::Spf::explain)
+///
+/// # Example:
+///```rust
+/// use decon_spf::Spf;
+/// let spf: Spf = "v=spf1 include:_spf.google.com ~all".parse().unwrap();
+/// let explained = spf.explain();
+/// assert_eq!(
+///     explained[0].to_string(),
+///     "include:_spf.google.com \u{2014} allow every server that the SPF record of \
+///      _spf.google.com allows (costs 1 DNS lookup)"
+/// );
+/// assert_eq!(
+///     explained[1].to_string(),
+///     "~all \u{2014} mail from any other source should be treated with suspicion (soft fail)"
+/// );
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    term: String,
+    description: String,
+}
+
+impl Explanation {
+    /// Returns the term as it is written in the record.
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+    /// Returns the description of the term, without the term itself.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl std::fmt::Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} \u{2014} {}", self.term, self.description)
+    }
+}
+
+pub(crate) fn explain(spf: &Spf) -> Vec<Explanation> {
+    let mut explained: Vec<Explanation> = spf.lookup_breakdown().iter().map(term).collect();
+    for (name, value) in spf.unknown_modifiers().into_iter().flatten() {
+        explained.push(Explanation {
+            term: format!("{}={}", name, value),
+            description: "an unknown modifier, which is ignored".to_string(),
+        });
+    }
+    explained
+}
+
+fn term(item: &LookupItem) -> Explanation {
+    let mechanism = item.mechanism();
+    let (mut description, mut notes) = match mechanism {
+        MechanismRef::IP(network) => (
+            format!("{} {}", verb(network.qualifier()), network_object(network)),
+            Vec::new(),
+        ),
+        MechanismRef::TXT(mechanism) => txt(mechanism),
+    };
+    if !matches!(mechanism.kind(), Kind::All | Kind::Redirect | Kind::Exp) {
+        if let Some(label) = label(mechanism.qualifier()) {
+            notes.insert(0, label.to_string());
+        }
+    }
+    if let MechanismRef::TXT(m) = mechanism {
+        if m.has_macros() && !m.kind().is_all() {
+            notes.push("the domain is built from each message with macros".to_string());
+        }
+    }
+    if item.costs_lookup() {
+        notes.push("costs 1 DNS lookup".to_string());
+    }
+    if !notes.is_empty() {
+        description = format!("{} ({})", description, notes.join("; "));
+    }
+    Explanation {
+        term: mechanism.to_string(),
+        description,
+    }
+}
+
+// The description of a `Mechanism<String>` with any notes which are particular to its kind.
+fn txt(mechanism: &Mechanism<String>) -> (String, Vec<String>) {
+    let domain = mechanism.domain();
+    let of_domain = domain.map_or("the domain itself".to_string(), str::to_string);
+    let verb = verb(mechanism.qualifier());
+    match mechanism.kind() {
+        Kind::A => (
+            format!(
+                "{} mail from the addresses of {}{}",
+                verb,
+                of_domain,
+                widened(mechanism)
+            ),
+            Vec::new(),
+        ),
+        Kind::MX => (
+            format!(
+                "{} mail from the mail servers (MX) of {}{}",
+                verb,
+                of_domain,
+                widened(mechanism)
+            ),
+            Vec::new(),
+        ),
+        Kind::Include => (
+            format!(
+                "{} every server that the SPF record of {} allows",
+                verb, of_domain
+            ),
+            Vec::new(),
+        ),
+        Kind::Exists => (
+            format!("{} any mail when {} has an address record", verb, of_domain),
+            Vec::new(),
+        ),
+        Kind::Ptr => (
+            format!(
+                "{} mail from servers whose verified host name ends in {}",
+                verb, of_domain
+            ),
+            vec!["discouraged by RFC 7208".to_string()],
+        ),
+        Kind::Redirect => (
+            format!(
+                "use the SPF record of {} for mail which no mechanism matches",
+                of_domain
+            ),
+            Vec::new(),
+        ),
+        Kind::Exp => (
+            format!("explain a failure with the text published at {}", of_domain),
+            Vec::new(),
+        ),
+        Kind::All => (all(mechanism.qualifier()).to_string(), Vec::new()),
+        Kind::IpV4 | Kind::IpV6 => unreachable!(),
+    }
+}
+
+fn all(qualifier: &Qualifier) -> &'static str {
+    match qualifier {
+        Qualifier::Pass => {
+            "mail from any other source is allowed (pass), so anyone may send as \
+             the domain"
+        }
+        Qualifier::Fail => "mail from any other source should be rejected (fail)",
+        Qualifier::SoftFail => {
+            "mail from any other source should be treated with suspicion (soft fail)"
+        }
+        Qualifier::Neutral => "no statement is made about mail from any other source (neutral)",
+    }
+}
+
+fn verb(qualifier: &Qualifier) -> &'static str {
+    match qualifier {
+        Qualifier::Pass => "allow",
+        Qualifier::Fail => "reject",
+        Qualifier::SoftFail => "treat with suspicion",
+        Qualifier::Neutral => "make no statement about",
+    }
+}
+
+// The result of a mechanism which matches, other than pass.
+fn label(qualifier: &Qualifier) -> Option<&'static str> {
+    match qualifier {
+        Qualifier::Pass => None,
+        Qualifier::Fail => Some("fail"),
+        Qualifier::SoftFail => Some("soft fail"),
+        Qualifier::Neutral => Some("neutral"),
+    }
+}
+
+fn network_object(network: &Mechanism<IpNetwork>) -> String {
+    let network = network.as_network();
+    let host = match network {
+        IpNetwork::V4(_) => 32,
+        IpNetwork::V6(_) => 128,
+    };
+    if network.prefix() == host {
+        format!("mail from the address {}", network.ip())
+    } else {
+        format!("mail from any address in {}", network)
+    }
+}
+
+// The networks an `a` or `mx` matches, when a cidr suffix widens its addresses.
+fn widened(mechanism: &Mechanism<String>) -> String {
+    let (ip4, ip6) = match mechanism.cidr() {
+        Some(cidr) => cidr,
+        None => return String::new(),
+    };
+    let mut parts = Vec::new();
+    if let Some(prefix) = ip4 {
+        parts.push(format!("/{} for ip4", prefix));
+    }
+    if let Some(prefix) = ip6 {
+        parts.push(format!("/{} for ip6", prefix));
+    }
+    format!(", widened to {}", parts.join(" and "))
+}
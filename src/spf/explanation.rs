@@ -0,0 +1,29 @@
+//! Resolving the explanation of an `exp=` modifier. RFC 7208 section 6.2
+use crate::evaluate::SpfResolver;
+use crate::mechanism::MacroContext;
+use crate::spf::{Spf, SpfError};
+
+pub(crate) fn explanation(
+    spf: &Spf,
+    resolver: &dyn SpfResolver,
+    context: &MacroContext,
+) -> Result<Option<String>, SpfError> {
+    let exp = match spf.exp() {
+        Some(exp) => exp,
+        None => return Ok(None),
+    };
+    let target = exp.expand_macros(context).map_err(SpfError::InvalidMacro)?;
+    // Any failure to find a usable explanation is treated as if there were no `exp=`.
+    let records = match resolver.lookup_txt(&target) {
+        Ok(records) => records,
+        Err(_) => return Ok(None),
+    };
+    let record = match records.as_slice() {
+        [record] => record,
+        _ => return Ok(None),
+    };
+    Ok(context
+        .expand_explanation(record)
+        .ok()
+        .filter(|explanation| explanation.is_ascii()))
+}
@@ -0,0 +1,81 @@
+//! Fetching the Spf record of a domain through an [`SpfResolver`](crate::evaluate::SpfResolver).
+use crate::evaluate::{self, LookupError, SpfResolver};
+use crate::spf::{ParseOptions, Spf, SpfError};
+
+/// The errors which can prevent the Spf record of a domain from being fetched. See
+/// [`Spf::from_resolver()`](crate::Spf::from_resolver)
+#[derive(Debug, PartialEq)]
+pub enum SpfFetchError {
+    /// The TXT lookup of the domain failed.
+    Lookup(LookupError),
+    /// The domain has no Spf record.
+    NoRecord(String),
+    /// The domain has more than one Spf record. RFC 7208 section 4.5
+    MultipleRecords(String),
+    /// The Spf record of the domain could not be parsed.
+    Invalid(SpfError),
+}
+
+impl std::fmt::Display for SpfFetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpfFetchError::Lookup(err) => write!(f, "{}", err),
+            SpfFetchError::NoRecord(domain) => write!(f, "{} has no Spf record.", domain),
+            SpfFetchError::MultipleRecords(domain) => {
+                write!(f, "{} has more than one Spf record.", domain)
+            }
+            SpfFetchError::Invalid(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SpfFetchError {}
+
+impl SpfFetchError {
+    /// Returns `true` if the TXT lookup failed.
+    pub fn is_lookup(&self) -> bool {
+        matches!(self, Self::Lookup(_))
+    }
+    /// Returns `true` if the domain has no Spf record.
+    pub fn is_no_record(&self) -> bool {
+        matches!(self, Self::NoRecord(_))
+    }
+    /// Returns `true` if the domain has more than one Spf record.
+    pub fn is_multiple_records(&self) -> bool {
+        matches!(self, Self::MultipleRecords(_))
+    }
+    /// Returns `true` if the Spf record could not be parsed.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Self::Invalid(_))
+    }
+}
+
+pub(crate) fn fetch(resolver: &dyn SpfResolver, domain: &str) -> Result<Spf, SpfFetchError> {
+    let records = resolver.lookup_txt(domain).map_err(SpfFetchError::Lookup)?;
+    let mut records = records.iter().filter(|record| evaluate::is_spf1(record));
+    let record = match (records.next(), records.next()) {
+        (None, _) => return Err(SpfFetchError::NoRecord(domain.to_string())),
+        (Some(record), None) => record,
+        (Some(_), Some(_)) => return Err(SpfFetchError::MultipleRecords(domain.to_string())),
+    };
+    parse_txt(record).map_err(SpfFetchError::Invalid)
+}
+
+pub(crate) fn find_in_txt_records(records: &[String]) -> Result<Spf, SpfError> {
+    let found: Vec<&String> = records
+        .iter()
+        .filter(|record| evaluate::is_spf1(record))
+        .collect();
+    match found[..] {
+        [] => Err(SpfError::NoSpfRecordFound),
+        [record] => parse_txt(record),
+        _ => Err(SpfError::MultipleSpfRecords(found.len())),
+    }
+}
+
+fn parse_txt(record: &str) -> Result<Spf, SpfError> {
+    // The record may have been published as several character-strings.
+    let mut spf = Spf::parse(record, &ParseOptions::default(), usize::MAX)?;
+    spf.max_length = Some(usize::MAX);
+    Ok(spf)
+}
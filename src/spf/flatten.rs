@@ -0,0 +1,323 @@
+//! Flattening an Spf record into `ip4:` and `ip6:` mechanisms using DNS data supplied by the
+//! caller.
+use crate::mechanism::{Kind, Mechanism, MechanismRef, Qualifier};
+use crate::spf::{Spf, Warning};
+use ipnetwork::IpNetwork;
+use std::collections::HashMap;
+
+/// The DNS data needed to flatten an Spf record. See [`Spf::flatten()`](crate::Spf::flatten)
+///
+/// Domains are matched case-insensitively.
+///
+/// # Example:
+///```rust
+/// use decon_spf::{ResolutionMap, Spf};
+/// let mut resolutions = ResolutionMap::new();
+/// resolutions.set_domain("example.com");
+/// let spf: Spf = "v=spf1 ip4:198.51.100.0/24 -all".parse().unwrap();
+/// resolutions.insert_record("_spf.example.net", spf);
+/// resolutions.insert_a("example.com", vec!["192.0.2.10".parse().unwrap()]);
+/// assert_eq!(resolutions.record("_SPF.Example.net").is_some(), true);
+/// assert_eq!(resolutions.a("example.com").unwrap().len(), 1);
+/// assert_eq!(resolutions.mx("example.com"), None);
+///```
+#[derive(Debug, Clone, Default)]
+pub struct ResolutionMap {
+    domain: Option<String>,
+    records: HashMap<String, Spf>,
+    a: HashMap<String, Vec<IpNetwork>>,
+    mx: HashMap<String, Vec<IpNetwork>>,
+}
+
+impl ResolutionMap {
+    /// Create a new, empty map.
+    pub fn new() -> Self {
+        ResolutionMap::default()
+    }
+    /// Set the domain of the record being flattened. This is used by `a`, `mx` and
+    /// `redirect=` terms of that record which have no domain-spec.
+    pub fn set_domain(&mut self, domain: &str) {
+        self.domain = Some(domain.to_ascii_lowercase());
+    }
+    /// Returns the domain of the record being flattened.
+    pub fn domain(&self) -> Option<&str> {
+        self.domain.as_deref()
+    }
+    /// Insert the Spf record published by `domain`, used for `include:` and `redirect=`.
+    pub fn insert_record(&mut self, domain: &str, spf: Spf) {
+        self.records.insert(domain.to_ascii_lowercase(), spf);
+    }
+    /// Insert the addresses of `domain`, used for `a`.
+    pub fn insert_a(&mut self, domain: &str, networks: Vec<IpNetwork>) {
+        self.a.insert(domain.to_ascii_lowercase(), networks);
+    }
+    /// Insert the addresses of the mail exchangers of `domain`, used for `mx`.
+    pub fn insert_mx(&mut self, domain: &str, networks: Vec<IpNetwork>) {
+        self.mx.insert(domain.to_ascii_lowercase(), networks);
+    }
+    /// Returns the Spf record of `domain`.
+    pub fn record(&self, domain: &str) -> Option<&Spf> {
+        self.records.get(&domain.to_ascii_lowercase())
+    }
+    /// Returns the addresses of `domain`.
+    pub fn a(&self, domain: &str) -> Option<&[IpNetwork]> {
+        self.a.get(&domain.to_ascii_lowercase()).map(Vec::as_slice)
+    }
+    /// Returns the addresses of the mail exchangers of `domain`.
+    pub fn mx(&self, domain: &str) -> Option<&[IpNetwork]> {
+        self.mx.get(&domain.to_ascii_lowercase()).map(Vec::as_slice)
+    }
+}
+
+/// The errors which can prevent an Spf record from being flattened. See
+/// [`Spf::flatten()`](crate::Spf::flatten)
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlattenError {
+    /// The Spf record of the domain of an `include:` or `redirect=` is not in the map.
+    MissingRecord(String),
+    /// The addresses of the domain of an `a` or `mx` are not in the map.
+    MissingAddresses {
+        /// `Kind::A` or `Kind::MX`
+        kind: Kind,
+        /// The domain which was looked for.
+        domain: String,
+    },
+    /// An `include:` or `redirect=` refers to a record which is already being flattened.
+    IncludeCycle(String),
+    /// A term without a domain-spec refers to the domain of the record, which is not known.
+    /// See [`ResolutionMap::set_domain()`](ResolutionMap::set_domain)
+    NoDomain(Kind),
+    /// The term depends on the message being checked and can not be written as networks.
+    /// e.g. `exists:`, `ptr` or a domain-spec with macros. Also given for a term of an included
+    /// record which does not pass but is followed by a term which does.
+    Unflattenable(String),
+}
+
+impl std::fmt::Display for FlattenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlattenError::MissingRecord(domain) => {
+                write!(f, "No Spf record for {} was supplied.", domain)
+            }
+            FlattenError::MissingAddresses { kind, domain } => write!(
+                f,
+                "No addresses for {} of {} were supplied.",
+                kind.as_str(),
+                domain
+            ),
+            FlattenError::IncludeCycle(domain) => {
+                write!(f, "The record of {} includes itself.", domain)
+            }
+            FlattenError::NoDomain(kind) => write!(
+                f,
+                "{} refers to the domain of the record, which was not supplied.",
+                kind.as_str()
+            ),
+            FlattenError::Unflattenable(term) => {
+                write!(f, "{} can not be flattened.", term)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FlattenError {}
+
+impl FlattenError {
+    /// Returns `true` if the Spf record of a domain was not supplied.
+    pub fn is_missing_record(&self) -> bool {
+        matches!(self, Self::MissingRecord(_))
+    }
+    /// Returns `true` if the addresses of a domain were not supplied.
+    pub fn is_missing_addresses(&self) -> bool {
+        matches!(self, Self::MissingAddresses { .. })
+    }
+    /// Returns `true` if a record includes itself.
+    pub fn is_include_cycle(&self) -> bool {
+        matches!(self, Self::IncludeCycle(_))
+    }
+    /// Returns `true` if the domain of the record was needed but not supplied.
+    pub fn is_no_domain(&self) -> bool {
+        matches!(self, Self::NoDomain(_))
+    }
+    /// Returns `true` if a term can not be written as networks.
+    pub fn is_unflattenable(&self) -> bool {
+        matches!(self, Self::Unflattenable(_))
+    }
+}
+
+pub(crate) fn flatten(spf: &Spf, resolutions: &ResolutionMap) -> Result<Spf, FlattenError> {
+    let mut flattener = Flattener {
+        resolutions,
+        domains: Vec::new(),
+        networks: Vec::new(),
+    };
+    let domain = resolutions.domain().map(str::to_string);
+    let all = flattener.record(spf, domain.as_deref(), None)?;
+    let (ip4, ip6): (Vec<_>, Vec<_>) = flattener
+        .networks
+        .into_iter()
+        .partition(|m| m.as_network().is_ipv4());
+    let mut flattened = Spf {
+        version: spf.version.clone(),
+        version_str: spf.version_str.clone(),
+        ip4: Some(ip4).filter(|networks| !networks.is_empty()),
+        ip6: Some(ip6).filter(|networks| !networks.is_empty()),
+        all,
+        exp: spf.exp.clone(),
+        unknown_modifiers: spf.unknown_modifiers.clone(),
+        ..Spf::default()
+    };
+    flattened.dedup();
+    if flattened.remaining_length() < 0 {
+        flattened.parse_warnings = Some(vec![Warning::RecordTooLong {
+            length: flattened.serialized_len(),
+        }]);
+    }
+    Ok(flattened)
+}
+
+struct Flattener<'a> {
+    resolutions: &'a ResolutionMap,
+    // The domains of the records being flattened, to find cycles.
+    domains: Vec<String>,
+    networks: Vec<Mechanism<IpNetwork>>,
+}
+
+impl Flattener<'_> {
+    // Flatten the terms of `spf`, which is published by `domain`, returning its `all`.
+    //
+    // `include` is the qualifier of the `include:` being flattened, or `None` for the record
+    // itself. A term of an included record only matters when it passes, as any other result
+    // only stops the `include:` from matching. A term which passes gives the result of the
+    // `include:`. A term which does not pass can only be dropped when no term which passes
+    // follows it, as otherwise it would stop that term from matching.
+    fn record(
+        &mut self,
+        spf: &Spf,
+        domain: Option<&str>,
+        include: Option<Qualifier>,
+    ) -> Result<Option<Mechanism<String>>, FlattenError> {
+        let mut all = None;
+        let last_pass = spf
+            .iter()
+            .enumerate()
+            .filter(|(_, term)| term.qualifier().is_pass() && !ignored(spf, term.kind()))
+            .map(|(index, _)| index)
+            .last();
+        for (index, term) in spf.iter().enumerate() {
+            let qualifier = match include {
+                None => *term.qualifier(),
+                Some(_) if !term.qualifier().is_pass() => {
+                    if last_pass.is_some_and(|last| index < last) {
+                        return Err(FlattenError::Unflattenable(term.to_string()));
+                    }
+                    continue;
+                }
+                Some(qualifier) => qualifier,
+            };
+            let mechanism = match term {
+                MechanismRef::IP(network) => {
+                    self.networks
+                        .push(network.clone().with_qualifier(qualifier));
+                    continue;
+                }
+                MechanismRef::TXT(mechanism) => mechanism,
+            };
+            if mechanism.has_macros() {
+                return Err(FlattenError::Unflattenable(mechanism.to_string()));
+            }
+            match mechanism.kind() {
+                Kind::A | Kind::MX => self.addresses(mechanism, domain, qualifier)?,
+                Kind::Include => {
+                    let target = target(mechanism, domain)?;
+                    self.nested(&target, Some(qualifier))?;
+                }
+                // `redirect=` is ignored when the record has `all`.
+                Kind::Redirect if spf.all().is_some() => {}
+                Kind::Redirect => {
+                    let target = target(mechanism, domain)?;
+                    all = self.nested(&target, include)?;
+                }
+                Kind::All if include.is_none() => all = Some(mechanism.clone()),
+                // An included record which passes every address.
+                Kind::All => {
+                    for network in ["0.0.0.0/0", "::/0"] {
+                        let network = network.parse().unwrap();
+                        self.networks.push(Mechanism::ip(qualifier, network));
+                    }
+                }
+                Kind::Exp => {}
+                Kind::Exists | Kind::Ptr | Kind::IpV4 | Kind::IpV6 => {
+                    return Err(FlattenError::Unflattenable(mechanism.to_string()))
+                }
+            }
+        }
+        Ok(all)
+    }
+    fn nested(
+        &mut self,
+        domain: &str,
+        include: Option<Qualifier>,
+    ) -> Result<Option<Mechanism<String>>, FlattenError> {
+        if self.domains.iter().any(|d| d.eq_ignore_ascii_case(domain)) {
+            return Err(FlattenError::IncludeCycle(domain.to_string()));
+        }
+        let spf = self
+            .resolutions
+            .record(domain)
+            .ok_or_else(|| FlattenError::MissingRecord(domain.to_string()))?;
+        self.domains.push(domain.to_string());
+        let all = self.record(spf, Some(domain), include)?;
+        self.domains.pop();
+        Ok(all)
+    }
+    // The networks of an `a` or `mx`, with its cidr suffix applied.
+    fn addresses(
+        &mut self,
+        mechanism: &Mechanism<String>,
+        domain: Option<&str>,
+        qualifier: Qualifier,
+    ) -> Result<(), FlattenError> {
+        let kind = mechanism.kind();
+        let domain = target(mechanism, domain)?;
+        let networks = match kind {
+            Kind::A => self.resolutions.a(&domain),
+            _ => self.resolutions.mx(&domain),
+        };
+        let networks = networks.ok_or(FlattenError::MissingAddresses {
+            kind: *kind,
+            domain,
+        })?;
+        let (ip4, ip6) = mechanism.cidr().unwrap_or_default();
+        for network in networks {
+            let prefix = match network {
+                IpNetwork::V4(_) => ip4,
+                IpNetwork::V6(_) => ip6,
+            };
+            let prefix = prefix.map_or(network.prefix(), |p| p.min(network.prefix()));
+            let network = IpNetwork::new(network.ip(), prefix).unwrap();
+            let network = IpNetwork::new(network.network(), prefix).unwrap();
+            self.networks.push(Mechanism::ip(qualifier, network));
+        }
+        Ok(())
+    }
+}
+
+// The domain a term refers to. A term without a domain-spec refers to `domain`.
+fn target(mechanism: &Mechanism<String>, domain: Option<&str>) -> Result<String, FlattenError> {
+    mechanism
+        .domain()
+        .or(domain)
+        .map(str::to_string)
+        .ok_or(FlattenError::NoDomain(*mechanism.kind()))
+}
+
+// Whether a term of `spf` is never evaluated against an address: `exp=`, and `redirect=` when
+// the record has `all`.
+fn ignored(spf: &Spf, kind: &Kind) -> bool {
+    match kind {
+        Kind::Exp => true,
+        Kind::Redirect => spf.all().is_some(),
+        _ => false,
+    }
+}
@@ -0,0 +1,131 @@
+//! The graph of the `include:` and `redirect=` terms of a set of Spf records.
+use crate::mechanism::MechanismRef;
+use crate::spf::Spf;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The records of a set of domains and the domains each refers to with `include:` and
+/// `redirect=`. No DNS lookups are made.
+///
+/// Domains are compared case-insensitively and are returned in lowercase. A domain-spec with
+/// macros is only known when a message is checked and is not part of the graph.
+///
+/// # Example:
+///```rust
+/// use decon_spf::{Spf, SpfGraph};
+/// use std::collections::HashMap;
+/// let mut records: HashMap<String, Spf> = HashMap::new();
+/// records.insert("a.example.com".into(), "v=spf1 include:b.example.com -all".parse().unwrap());
+/// records.insert("b.example.com".into(), "v=spf1 redirect=a.example.com".parse().unwrap());
+/// records.insert("c.example.com".into(), "v=spf1 include:a.example.com include:d.example.com -all".parse().unwrap());
+/// let graph = SpfGraph::new(records);
+/// assert_eq!(
+///     graph.find_cycles(),
+///     vec![vec!["a.example.com", "b.example.com", "a.example.com"]]
+/// );
+/// assert_eq!(graph.missing(), vec!["d.example.com"]);
+/// assert_eq!(graph.max_depth_from("c.example.com"), 2);
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpfGraph {
+    edges: BTreeMap<String, Vec<String>>,
+}
+
+impl SpfGraph {
+    /// Create the graph of `records`, each keyed by the domain which publishes it.
+    pub fn new(records: HashMap<String, Spf>) -> Self {
+        let edges = records
+            .iter()
+            .map(|(domain, spf)| (domain.to_ascii_lowercase(), targets(spf)))
+            .collect();
+        SpfGraph { edges }
+    }
+    /// Returns the domains referred to by the record of `domain`, in the order of
+    /// [`Spf::iter()`](crate::Spf::iter). Returns `None` if `domain` has no record in the graph.
+    pub fn targets(&self, domain: &str) -> Option<&[String]> {
+        self.edges
+            .get(&domain.to_ascii_lowercase())
+            .map(Vec::as_slice)
+    }
+    /// Returns every cycle of records which include or redirect to each other. Each cycle is
+    /// listed from its first domain, in alphabetical order, back to that domain. e.g.
+    /// `["a.example.com", "b.example.com", "a.example.com"]`
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles = Vec::new();
+        for start in self.edges.keys() {
+            let mut path = vec![start.as_str()];
+            self.cycles_from(start, &mut path, &mut cycles);
+        }
+        cycles
+    }
+    // Each cycle through `path` back to its first domain. Only domains after the first are
+    // visited, so a cycle is found once, from its first domain.
+    fn cycles_from<'a>(
+        &'a self,
+        domain: &str,
+        path: &mut Vec<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        let start = path[0];
+        for target in self.edges.get(domain).into_iter().flatten() {
+            if target == start {
+                let mut cycle: Vec<String> = path.iter().map(|d| d.to_string()).collect();
+                cycle.push(start.to_string());
+                cycles.push(cycle);
+            } else if target.as_str() > start && !path.contains(&target.as_str()) {
+                path.push(target);
+                self.cycles_from(target, path, cycles);
+                path.pop();
+            }
+        }
+    }
+    /// Returns the longest chain of `include:` and `redirect=` which starts at the record of
+    /// `domain`. A record which refers to no other domain has a depth of `0`.
+    ///
+    /// A domain which is referred to but has no record counts as a step of the chain. A chain
+    /// ends before it would return to a domain already in it.
+    pub fn max_depth_from(&self, domain: &str) -> usize {
+        let domain = domain.to_ascii_lowercase();
+        let mut path = vec![domain.as_str()];
+        self.depth(&domain, &mut path)
+    }
+    fn depth<'a>(&'a self, domain: &str, path: &mut Vec<&'a str>) -> usize {
+        let mut deepest = 0;
+        for target in self.edges.get(domain).into_iter().flatten() {
+            if path.contains(&target.as_str()) {
+                continue;
+            }
+            path.push(target);
+            deepest = deepest.max(1 + self.depth(target, path));
+            path.pop();
+        }
+        deepest
+    }
+    /// Returns the domains which are referred to but have no record in the graph, in
+    /// alphabetical order.
+    pub fn missing(&self) -> Vec<&str> {
+        let missing: BTreeSet<&str> = self
+            .edges
+            .values()
+            .flatten()
+            .filter(|target| !self.edges.contains_key(*target))
+            .map(String::as_str)
+            .collect();
+        missing.into_iter().collect()
+    }
+}
+
+// The domains `spf` refers to with `include:` and `redirect=`, without repeats.
+fn targets(spf: &Spf) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for m in spf.iter() {
+        if let MechanismRef::TXT(m) = m {
+            if (m.kind().is_include() || m.kind().is_redirect()) && !m.has_macros() {
+                let target = m.raw().to_ascii_lowercase();
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+    }
+    targets
+}
@@ -0,0 +1,180 @@
+//! Iterators over the mechanisms of an Spf record.
+use crate::mechanism::{MechanismRef, ParsedMechanism};
+use crate::spf::Spf;
+use std::iter::FromIterator;
+
+/// An iterator over the mechanisms and modifiers of an [`Spf`](crate::Spf), in the order they
+/// are written when building a string. See [`Spf::iter()`](crate::Spf::iter)
+pub struct SpfIter<'a> {
+    inner: Box<dyn Iterator<Item = MechanismRef<'a>> + 'a>,
+}
+
+impl<'a> SpfIter<'a> {
+    pub(crate) fn new(spf: &'a Spf) -> Self {
+        let networks = spf.ip4.iter().flatten().chain(spf.ip6.iter().flatten());
+        let inner = spf
+            .a
+            .iter()
+            .flatten()
+            .chain(spf.mx.iter().flatten())
+            .chain(spf.include.iter().flatten())
+            .map(MechanismRef::TXT)
+            .chain(networks.map(MechanismRef::IP))
+            .chain(
+                spf.exists
+                    .iter()
+                    .flatten()
+                    .chain(spf.ptr.iter())
+                    .chain(spf.all.iter())
+                    .chain(spf.redirect.iter())
+                    .chain(spf.exp.iter())
+                    .map(MechanismRef::TXT),
+            );
+        SpfIter {
+            inner: Box::new(inner),
+        }
+    }
+}
+
+impl<'a> Iterator for SpfIter<'a> {
+    type Item = MechanismRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl std::fmt::Debug for SpfIter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpfIter").finish()
+    }
+}
+
+/// An iterator which consumes an [`Spf`](crate::Spf), yielding each mechanism and modifier
+/// in the same order as [`Spf::iter()`](crate::Spf::iter)
+#[derive(Debug)]
+pub struct SpfIntoIter {
+    inner: std::vec::IntoIter<ParsedMechanism>,
+}
+
+impl SpfIntoIter {
+    pub(crate) fn new(spf: Spf) -> Self {
+        let networks = spf
+            .ip4
+            .into_iter()
+            .flatten()
+            .chain(spf.ip6.into_iter().flatten());
+        let mechanisms: Vec<ParsedMechanism> = spf
+            .a
+            .into_iter()
+            .flatten()
+            .chain(spf.mx.into_iter().flatten())
+            .chain(spf.include.into_iter().flatten())
+            .map(ParsedMechanism::TXT)
+            .chain(networks.map(ParsedMechanism::IP))
+            .chain(
+                spf.exists
+                    .into_iter()
+                    .flatten()
+                    .chain(spf.ptr)
+                    .chain(spf.all)
+                    .chain(spf.redirect)
+                    .chain(spf.exp)
+                    .map(ParsedMechanism::TXT),
+            )
+            .collect();
+        SpfIntoIter {
+            inner: mechanisms.into_iter(),
+        }
+    }
+}
+
+impl Iterator for SpfIntoIter {
+    type Item = ParsedMechanism;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterate over the mechanisms and modifiers of the Spf. See [`Spf::iter()`](Spf::iter)
+///
+/// # Example:
+///```rust
+/// use decon_spf::Spf;
+/// let spf: Spf = "v=spf1 mx include:_spf.example.com -all".parse().unwrap();
+/// let mut lookups = 0;
+/// for mechanism in &spf {
+///     if mechanism.kind().is_mx() || mechanism.kind().is_include() {
+///         lookups += 1;
+///     }
+/// }
+/// assert_eq!(lookups, 2);
+///```
+impl<'a> IntoIterator for &'a Spf {
+    type Item = MechanismRef<'a>;
+    type IntoIter = SpfIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SpfIter::new(self)
+    }
+}
+
+/// Consume the Spf, yielding each mechanism and modifier as a
+/// [`ParsedMechanism`](crate::mechanism::ParsedMechanism), in the same order as
+/// [`Spf::iter()`](Spf::iter). Unknown modifiers are not included.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::ParsedMechanism;
+/// use decon_spf::Spf;
+/// let spf: Spf = "v=spf1 ip4:203.0.113.0/24 a -all".parse().unwrap();
+/// let networks: Vec<_> = spf
+///     .into_iter()
+///     .filter_map(|m| match m {
+///         ParsedMechanism::IP(m) => Some(*m.as_network()),
+///         ParsedMechanism::TXT(_) => None,
+///     })
+///     .collect();
+/// assert_eq!(networks, ["203.0.113.0/24".parse().unwrap()]);
+///```
+impl IntoIterator for Spf {
+    type Item = ParsedMechanism;
+    type IntoIter = SpfIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SpfIntoIter::new(self)
+    }
+}
+
+/// Build a `v=spf1` Spf from a sequence of mechanisms. Each mechanism is appended as with
+/// [`append_mechanism`](Spf::append_mechanism) or
+/// [`append_ip_mechanism`](Spf::append_ip_mechanism), so a conflicting mechanism is ignored or
+/// replaces an earlier one. Use [`Spf::from_mechanisms()`](Spf::from_mechanisms) to be told of
+/// conflicts.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::{Mechanism, ParsedMechanism, Qualifier};
+/// use decon_spf::Spf;
+/// let spf: Spf = ["mx", "include:_spf.example.com", "ip4:203.0.113.0/24", "-all"]
+///     .iter()
+///     .map(|term| term.parse::<ParsedMechanism>().unwrap())
+///     .collect();
+/// assert_eq!(spf.to_string(), "v=spf1 mx include:_spf.example.com ip4:203.0.113.0/24 -all");
+///```
+impl FromIterator<ParsedMechanism> for Spf {
+    fn from_iter<I: IntoIterator<Item = ParsedMechanism>>(mechanisms: I) -> Self {
+        let mut spf = Spf::new_v1();
+        for mechanism in mechanisms {
+            match mechanism {
+                ParsedMechanism::TXT(m) => spf.append_mechanism(m),
+                ParsedMechanism::IP(m) => spf.append_ip_mechanism(m),
+            }
+        }
+        spf
+    }
+}
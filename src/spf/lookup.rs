@@ -0,0 +1,256 @@
+//! The DNS lookups needed by the terms of an Spf record.
+use crate::mechanism::{Kind, MechanismRef};
+use crate::spf::{Spf, SpfError, SpfFetchError, SpfSource};
+
+/// The most DNS lookups an evaluation may cause. RFC 7208 section 4.6.4
+const MAX_LOOKUPS: usize = 10;
+
+/// A term of an Spf and the DNS lookups it needs. See
+/// [`Spf::lookup_breakdown()`](crate::Spf::lookup_breakdown)
+///
+/// The lookups are found from the term alone. No DNS lookups are made.
+///
+/// # Example:
+///```rust
+/// use decon_spf::Spf;
+/// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 mx include:_spf.example.com -all".parse().unwrap();
+/// let breakdown = spf.lookup_breakdown();
+/// let mx = &breakdown[0];
+/// assert_eq!(mx.mechanism().to_string(), "mx");
+/// assert_eq!(mx.costs_lookup(), true);
+/// assert_eq!(mx.additional_lookups(), 10);
+/// assert_eq!(breakdown[1].may_be_void(), true);
+/// assert_eq!(breakdown[2].costs_lookup(), false);
+///```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LookupItem<'a> {
+    mechanism: MechanismRef<'a>,
+    index: usize,
+}
+
+impl<'a> LookupItem<'a> {
+    pub(crate) fn new(mechanism: MechanismRef<'a>, index: usize) -> Self {
+        LookupItem { mechanism, index }
+    }
+    /// Returns the mechanism or modifier.
+    pub fn mechanism(&self) -> MechanismRef<'a> {
+        self.mechanism
+    }
+    /// Returns the position of the term in [`Spf::iter()`](crate::Spf::iter)
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// Returns `true` if the term counts towards the limit of 10 DNS lookups. These are the
+    /// `a`, `mx`, `include:`, `exists:`, `ptr` and `redirect=` terms.
+    pub fn costs_lookup(&self) -> bool {
+        matches!(
+            self.mechanism.kind(),
+            Kind::A | Kind::MX | Kind::Include | Kind::Exists | Kind::Ptr | Kind::Redirect
+        )
+    }
+    /// Returns the most lookups the term may need beyond its own, which are not counted
+    /// towards the limit of 10. `mx` may look up the address of up to 10 mail exchangers and
+    /// `ptr` the address of up to 10 host names. See RFC 7208 section 4.6.4
+    ///
+    /// The lookups needed by the record of an `include:` or `redirect=` are not known
+    /// without resolving it, and are not counted.
+    pub fn additional_lookups(&self) -> usize {
+        match self.mechanism.kind() {
+            Kind::MX | Kind::Ptr => 10,
+            _ => 0,
+        }
+    }
+    /// Returns `true` if the lookup is for a name which may not exist, and so may be a void
+    /// lookup. These are the targets of `exists:`, `include:` and `redirect=`.
+    pub fn may_be_void(&self) -> bool {
+        matches!(
+            self.mechanism.kind(),
+            Kind::Exists | Kind::Include | Kind::Redirect
+        )
+    }
+}
+
+/// The DNS lookups of an Spf record and of the records it includes or redirects to. See
+/// [`Spf::total_lookup_count()`](crate::Spf::total_lookup_count)
+///
+/// # Example:
+///```rust
+/// use decon_spf::{ResolutionMap, Spf};
+/// let mut records = ResolutionMap::new();
+/// records.insert_record("_spf.example.net", "v=spf1 a mx include:_b.example.net -all".parse().unwrap());
+/// records.insert_record("_b.example.net", "v=spf1 a:x.example.net -all".parse().unwrap());
+/// records.insert_record("_spf.example.org", "v=spf1 ip4:192.0.2.0/24 -all".parse().unwrap());
+/// let spf: Spf = "v=spf1 mx include:_spf.example.net include:_spf.example.org -all"
+///     .parse()
+///     .unwrap();
+/// let report = spf.total_lookup_count(&records).unwrap();
+/// assert_eq!(report.total(), 7);
+/// assert_eq!(report.is_exceeded(), false);
+/// assert_eq!(report.heaviest().unwrap().domain(), Some("_spf.example.net"));
+/// assert_eq!(report.heaviest().unwrap().total(), 4);
+///```
+#[derive(Debug, PartialEq)]
+pub struct LookupReport {
+    domains: Vec<DomainLookups>,
+    total: usize,
+}
+
+impl LookupReport {
+    /// Returns the number of lookups of every record which was followed.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+    /// Returns `true` if the records need more than the 10 lookups allowed by RFC 7208. Once
+    /// the limit is passed no further records are followed, so [`total()`](LookupReport::total)
+    /// may be lower than the lookups the records need.
+    pub fn is_exceeded(&self) -> bool {
+        self.total > MAX_LOOKUPS
+    }
+    /// Returns the lookups of each record, with a record given before the records it
+    /// includes or redirects to.
+    pub fn domains(&self) -> &[DomainLookups] {
+        &self.domains
+    }
+    /// Returns the lookups of the record of `domain`. If the record was reached more than once,
+    /// the first is returned.
+    pub fn domain(&self, domain: &str) -> Option<&DomainLookups> {
+        self.domains
+            .iter()
+            .find(|d| d.domain().is_some_and(|d| d.eq_ignore_ascii_case(domain)))
+    }
+    /// Returns the domains whose record could not be found.
+    pub fn missing(&self) -> Vec<&str> {
+        self.domains
+            .iter()
+            .filter(|d| d.is_missing())
+            .filter_map(|d| d.domain())
+            .collect()
+    }
+    /// Returns the record included or redirected to by the checked record whose records
+    /// need the most lookups.
+    pub fn heaviest(&self) -> Option<&DomainLookups> {
+        self.domains.iter().filter(|d| d.depth() == 1).fold(
+            None,
+            |heaviest: Option<&DomainLookups>, d| match heaviest {
+                Some(h) if h.total() >= d.total() => Some(h),
+                _ => Some(d),
+            },
+        )
+    }
+}
+
+/// The DNS lookups of a single record within a [`LookupReport`](LookupReport)
+#[derive(Debug, PartialEq)]
+pub struct DomainLookups {
+    domain: Option<String>,
+    depth: usize,
+    lookups: usize,
+    total: usize,
+    error: Option<SpfFetchError>,
+}
+
+impl DomainLookups {
+    /// Returns the domain of the record. This is `None` for the record which was checked.
+    pub fn domain(&self) -> Option<&str> {
+        self.domain.as_deref()
+    }
+    /// Returns how many `include:` or `redirect=` separate the record from the record which
+    /// was checked.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+    /// Returns the lookups of the terms of the record. See
+    /// [`Spf::lookup_count()`](crate::Spf::lookup_count)
+    pub fn lookups(&self) -> usize {
+        self.lookups
+    }
+    /// Returns the lookups of the record and of every record below it.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+    /// Returns `true` if the record could not be found.
+    pub fn is_missing(&self) -> bool {
+        self.error.is_some()
+    }
+    /// Returns why the record could not be found.
+    pub fn error(&self) -> Option<&SpfFetchError> {
+        self.error.as_ref()
+    }
+}
+
+pub(crate) fn report<S: SpfSource + ?Sized>(
+    spf: &Spf,
+    source: &S,
+) -> Result<LookupReport, SpfError> {
+    let mut counter = Counter {
+        source,
+        domains: Vec::new(),
+        path: Vec::new(),
+        total: 0,
+    };
+    counter.record(spf, None)?;
+    Ok(LookupReport {
+        domains: counter.domains,
+        total: counter.total,
+    })
+}
+
+struct Counter<'a, S: ?Sized> {
+    source: &'a S,
+    domains: Vec<DomainLookups>,
+    // The domains of the records above the record being counted.
+    path: Vec<String>,
+    total: usize,
+}
+
+impl<S: SpfSource + ?Sized> Counter<'_, S> {
+    // Count the lookups of `spf` and the records below it, returning their sum.
+    fn record(&mut self, spf: &Spf, domain: Option<String>) -> Result<usize, SpfError> {
+        let index = self.domains.len();
+        let lookups = spf.lookup_count();
+        self.domains.push(DomainLookups {
+            domain,
+            depth: self.path.len(),
+            lookups,
+            total: lookups,
+            error: None,
+        });
+        self.total += lookups;
+        let mut total = lookups;
+        // A domain-spec with macros is only known when a message is checked.
+        let targets = spf.iter().filter_map(|m| match m {
+            MechanismRef::TXT(m)
+                if (m.kind().is_include() || m.kind().is_redirect()) && !m.has_macros() =>
+            {
+                Some(m.raw().to_ascii_lowercase())
+            }
+            _ => None,
+        });
+        for target in targets {
+            if self.total > MAX_LOOKUPS {
+                break;
+            }
+            if self.path.contains(&target) {
+                let mut path = self.path.clone();
+                path.push(target);
+                return Err(SpfError::IncludeCycle(path));
+            }
+            match self.source.spf(&target) {
+                Ok(record) => {
+                    self.path.push(target.clone());
+                    total += self.record(&record, Some(target))?;
+                    self.path.pop();
+                }
+                Err(error) => self.domains.push(DomainLookups {
+                    domain: Some(target),
+                    depth: self.path.len() + 1,
+                    lookups: 0,
+                    total: 0,
+                    error: Some(error),
+                }),
+            }
+        }
+        self.domains[index].total = total;
+        Ok(total)
+    }
+}
@@ -2,25 +2,74 @@
 //! constituent parts.  
 //! It is not intended to validate the spf record.
 
+mod aggregate;
+mod builder;
+mod diff;
+mod disposition;
 mod errors;
+mod expand;
+mod explain;
+mod explanation;
+mod fetch;
+mod flatten;
+mod graph;
+mod iter;
+mod lookup;
+mod options;
+mod overlap;
+mod report;
+mod scope;
+mod sort;
+mod space;
+mod subset;
+mod summary;
 mod tests;
 mod validate;
+mod version;
+mod warning;
 
+use crate::evaluate::SpfResolver;
 use crate::helpers;
-use crate::mechanism::Kind;
-pub use crate::mechanism::{Mechanism, ParsedMechanism, Qualifier};
-pub use crate::spf::errors::SpfError;
+use crate::mechanism::{Kind, MacroContext, MechanismError, MechanismRef, ParsedMechanism};
+pub use crate::mechanism::{Mechanism, Qualifier};
+pub use crate::spf::builder::SpfBuilder;
+pub use crate::spf::diff::SpfDiff;
+pub use crate::spf::disposition::Disposition;
+pub use crate::spf::errors::{ParseError, SpfError};
+pub use crate::spf::expand::{ExpandError, SpfSource, SpfTree, SpfTreeIter, SpfTreeNetworks};
+pub use crate::spf::explain::Explanation;
+pub use crate::spf::fetch::SpfFetchError;
+pub use crate::spf::flatten::{FlattenError, ResolutionMap};
+pub use crate::spf::graph::SpfGraph;
+pub use crate::spf::iter::{SpfIntoIter, SpfIter};
+pub use crate::spf::lookup::{DomainLookups, LookupItem, LookupReport};
+pub use crate::spf::options::ParseOptions;
+pub use crate::spf::overlap::{IpOverlap, IpRelation};
+pub use crate::spf::report::{Finding, FindingCode, Severity, ValidationOptions, ValidationReport};
+pub use crate::spf::scope::Scope;
+pub use crate::spf::sort::SortStrategy;
+pub use crate::spf::space::{IpSpace, IpSpaceStats};
+pub use crate::spf::subset::SubsetResult;
+pub use crate::spf::summary::SpfSummary;
+pub use crate::spf::validate::SpfRfcStandard;
+pub use crate::spf::version::Version;
+pub use crate::spf::warning::Warning;
 use ipnetwork::IpNetwork;
 // Make this public in the future
-use crate::spf::validate::{SpfRfcStandard, SpfValidationResult};
-use std::{convert::TryFrom, str::FromStr};
+use crate::spf::validate::SpfValidationResult;
+use std::{convert::TryFrom, net::IpAddr, str::FromStr};
 
 /// The definition of the Spf struct which contains all information related a single
 /// SPF record.
-#[derive(Debug, Default)]
+///
+/// `Spf::default()` and [`Spf::new()`](Spf::new) have no version set. Use
+/// [`Spf::new_v1()`](Spf::new_v1) to start a `v=spf1` record.
+#[derive(Debug, Clone, Default)]
 pub struct Spf {
     source: String,
-    version: String,
+    version: Option<Version>,
+    // The version as it is written. Its case is kept when parsed with `preserve_case`.
+    version_str: String,
     from_src: bool,
     redirect: Option<Mechanism<String>>,
     is_redirected: bool,
@@ -32,11 +81,17 @@ pub struct Spf {
     ptr: Option<Mechanism<String>>,
     exists: Option<Vec<Mechanism<String>>>,
     all: Option<Mechanism<String>>,
+    exp: Option<Mechanism<String>>,
+    unknown_modifiers: Option<Vec<(String, String)>>,
     was_parsed: bool,
     was_validated: bool,
     is_valid: bool,
+    is_modified: bool,
     #[allow(dead_code)]
     warnings: Option<Vec<String>>,
+    parse_warnings: Option<Vec<Warning>>,
+    // The longest the record may be written. `None` is `MAX_SPF_STRING_LENGTH`.
+    max_length: Option<usize>,
 }
 
 impl std::fmt::Display for Spf {
@@ -45,6 +100,50 @@ impl std::fmt::Display for Spf {
     }
 }
 
+/// Two Spf records are equal when they have the same version and hold the same mechanisms and
+/// modifiers. Mechanisms are compared as described for [`Mechanism`](Mechanism), so a
+/// mechanism written with an explicit `+` or an uppercase domain is equal to its plain form.
+///
+/// The order of the terms is not compared, but the number of times each term occurs is. The
+/// source string and whether the record was parsed, validated or produced warnings are not
+/// compared.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::{Mechanism, Qualifier};
+/// use decon_spf::Spf;
+/// let parsed: Spf = "v=spf1 +all MX include:_SPF.Example.com".parse().unwrap();
+/// let mut built = Spf::new_v1();
+/// built.append_mechanism(Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap());
+/// built.append_mechanism(Mechanism::mx(Qualifier::Pass));
+/// built.append_mechanism(Mechanism::all(Qualifier::Pass));
+/// assert_eq!(parsed, built);
+///```
+impl PartialEq for Spf {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version
+            && same_multiset(&self.a, &other.a, PartialEq::eq)
+            && same_multiset(&self.mx, &other.mx, PartialEq::eq)
+            && same_multiset(&self.include, &other.include, PartialEq::eq)
+            && same_multiset(&self.ip4, &other.ip4, PartialEq::eq)
+            && same_multiset(&self.ip6, &other.ip6, PartialEq::eq)
+            && same_multiset(&self.exists, &other.exists, PartialEq::eq)
+            && self.ptr == other.ptr
+            && self.all == other.all
+            && self.redirect == other.redirect
+            && self.exp == other.exp
+            && same_multiset(
+                &self.unknown_modifiers,
+                &other.unknown_modifiers,
+                |(name, value), (other_name, other_value)| {
+                    name.eq_ignore_ascii_case(other_name) && value == other_value
+                },
+            )
+    }
+}
+
+impl Eq for Spf {}
+
 /// Creates an `Spf Struct` by parsing a string representation of Spf.
 ///
 /// # Examples:
@@ -63,6 +162,16 @@ impl std::fmt::Display for Spf {
 /// assert_eq!(err.to_string(), SpfError::WhiteSpaceSyntaxError.to_string());
 /// //  err.to_string() -> "Spf contains two or more consecutive whitespace characters.");
 ///
+/// // The version is case-insensitive. A malformed version is reported as `InvalidVersion`.
+/// let spf: Spf = "V=SPF1 a -all".parse().unwrap();
+/// assert_eq!(spf.to_string(), "v=spf1 a -all");
+/// let err = "v=spf10 a -all".parse::<Spf>().unwrap_err();
+/// assert_eq!(err, SpfError::InvalidVersion("v=spf10".to_string()));
+///
+/// // An empty or blank record.
+/// let err = "  ".parse::<Spf>().unwrap_err();
+/// assert_eq!(err, SpfError::EmptyInput);
+///
 /// // Example with warn-dns feature enabled.
 /// // Spf contains an invalid DNS host entry
 /// let bad_spf2: Spf = "v=spf1 a mx:example.m/24 -all".parse().unwrap();
@@ -76,15 +185,554 @@ impl std::fmt::Display for Spf {
 impl FromStr for Spf {
     type Err = SpfError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Spf::from_str_with_options(s, &ParseOptions::default())
+    }
+}
+
+impl TryFrom<&str> for Spf {
+    type Error = SpfError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Spf::from_str(s)
+    }
+}
+// Record how `term` was written so that it can be reproduced by `to_string()`.
+fn preserve_presentation<T>(m: &mut Mechanism<T>, term: &str, options: &ParseOptions) {
+    if options.preserves_case() {
+        m.preserve_name_case(term);
+    }
+    if options.preserves_explicit_pass() {
+        m.preserve_explicit_pass(term);
+    }
+}
+// Remove the first mechanism in `list` equal to `mechanism`. An emptied list becomes `None`.
+fn remove_from<T>(list: &mut Option<Vec<Mechanism<T>>>, mechanism: &Mechanism<T>) -> bool
+where
+    Mechanism<T>: PartialEq,
+{
+    let mechanisms = match list {
+        Some(mechanisms) => mechanisms,
+        None => return false,
+    };
+    match mechanisms.iter().position(|m| m == mechanism) {
+        Some(index) => {
+            mechanisms.remove(index);
+            if mechanisms.is_empty() {
+                *list = None;
+            }
+            true
+        }
+        None => false,
+    }
+}
+// Parse the value of an `ip4:` or `ip6:` kind `Mechanism<String>` as a `Mechanism<IpNetwork>`.
+fn network_from_string(mechanism: &Mechanism<String>) -> Result<Mechanism<IpNetwork>, SpfError> {
+    mechanism
+        .to_string()
+        .parse::<Mechanism<IpNetwork>>()
+        .map_err(SpfError::InvalidMechanism)
+}
+// Check that `mechanism` is of the `expected` kind and is written as a valid term.
+fn check_single(mechanism: &Mechanism<String>, expected: Kind) -> Result<(), SpfError> {
+    if mechanism.kind() != &expected {
+        return Err(SpfError::KindMismatch {
+            expected,
+            found: *mechanism.kind(),
+        });
+    }
+    match mechanism.to_string().parse::<Mechanism<String>>() {
+        Ok(_) => Ok(()),
+        Err(error) => Err(SpfError::InvalidMechanism(error)),
+    }
+}
+// `true` if both mechanisms have the same kind and domain-spec, ignoring case.
+fn same_rrdata(m: &Mechanism<String>, other: &Mechanism<String>) -> bool {
+    m.kind() == other.kind()
+        && match (m.mechanism(), other.mechanism()) {
+            (Some(m), Some(other)) => {
+                helpers::lowercase_domain_spec(m) == helpers::lowercase_domain_spec(other)
+            }
+            (m, other) => m == other,
+        }
+}
+// `true` if both lists hold the same items the same number of times, in any order. A list
+// which is `None` is treated as empty.
+fn same_multiset<T>(
+    first: &Option<Vec<T>>,
+    second: &Option<Vec<T>>,
+    eq: impl Fn(&T, &T) -> bool,
+) -> bool {
+    let first = first.as_deref().unwrap_or_default();
+    let second = second.as_deref().unwrap_or_default();
+    let count = |list: &[T], item: &T| list.iter().filter(|other| eq(item, other)).count();
+    first.len() == second.len()
+        && first
+            .iter()
+            .all(|item| count(first, item) == count(second, item))
+}
+// The mechanisms of `first` followed by those of `second`.
+fn merge_lists<T: Clone>(
+    first: &Option<Vec<Mechanism<T>>>,
+    second: &Option<Vec<Mechanism<T>>>,
+) -> Option<Vec<Mechanism<T>>> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(first.iter().chain(second).cloned().collect()),
+        (first, second) => first.clone().or_else(|| second.clone()),
+    }
+}
+// Remove each mechanism in `list` which is a duplicate of an earlier one. An emptied list
+// becomes `None`.
+fn dedup_list<T>(
+    list: &mut Option<Vec<Mechanism<T>>>,
+    is_duplicate: impl Fn(&Mechanism<T>, &Mechanism<T>) -> bool,
+) -> usize {
+    let mechanisms = match list {
+        Some(mechanisms) => mechanisms,
+        None => return 0,
+    };
+    let before = mechanisms.len();
+    let mut kept: Vec<Mechanism<T>> = Vec::with_capacity(before);
+    for mechanism in mechanisms.drain(..) {
+        if !kept.iter().any(|m| is_duplicate(m, &mechanism)) {
+            kept.push(mechanism);
+        }
+    }
+    *mechanisms = kept;
+    before - mechanisms.len()
+}
+// Remove each network in `list` which is contained in another network with the same
+// qualifier, when removing it can not change the result of an evaluation.
+fn remove_subsumed(list: &mut Option<Vec<Mechanism<IpNetwork>>>) -> usize {
+    let networks = match list {
+        Some(networks) => networks,
+        None => return 0,
+    };
+    let contains = |outer: &IpNetwork, inner: &IpNetwork| {
+        outer.prefix() <= inner.prefix() && outer.contains(inner.network())
+    };
+    let subsumed: Vec<bool> = (0..networks.len())
+        .map(|i| {
+            let n = &networks[i];
+            (0..networks.len()).any(|j| {
+                let m = &networks[j];
+                j != i
+                    && m.qualifier() == n.qualifier()
+                    && contains(m.as_network(), n.as_network())
+                    // An equivalent network, such as one with host bits set, only removes
+                    // the later of the two.
+                    && (j < i
+                        || (!contains(n.as_network(), m.as_network())
+                            && networks[i + 1..j]
+                                .iter()
+                                .all(|k| k.qualifier() == n.qualifier())))
+            })
+        })
+        .collect();
+    let before = networks.len();
+    let mut index = 0;
+    networks.retain(|_| {
+        index += 1;
+        !subsumed[index - 1]
+    });
+    before - networks.len()
+}
+// Remove `single` if it is equal to `mechanism`.
+fn remove_single(single: &mut Option<Mechanism<String>>, mechanism: &Mechanism<String>) -> bool {
+    if single.as_ref() == Some(mechanism) {
+        *single = None;
+        true
+    } else {
+        false
+    }
+}
+// Replace the mechanism at `index` in `list`, returning the mechanism which was replaced.
+fn replace_in<T>(
+    list: &mut Option<Vec<Mechanism<T>>>,
+    index: usize,
+    mechanism: Mechanism<T>,
+) -> Result<Mechanism<T>, SpfError> {
+    let len = list.as_ref().map_or(0, Vec::len);
+    match list
+        .as_mut()
+        .and_then(|mechanisms| mechanisms.get_mut(index))
+    {
+        Some(current) => Ok(std::mem::replace(current, mechanism)),
+        None => Err(SpfError::IndexOutOfBounds {
+            kind: *mechanism.kind(),
+            index,
+            len,
+        }),
+    }
+}
+// Replace `single`, which only has index `0`, returning the mechanism which was replaced.
+fn replace_single(
+    single: &mut Option<Mechanism<String>>,
+    index: usize,
+    mechanism: Mechanism<String>,
+) -> Result<Mechanism<String>, SpfError> {
+    match single {
+        Some(current) if index == 0 => Ok(std::mem::replace(current, mechanism)),
+        _ => Err(SpfError::IndexOutOfBounds {
+            kind: *mechanism.kind(),
+            index,
+            len: usize::from(single.is_some()),
+        }),
+    }
+}
+// Record a network with host bits set, rewriting it when networks are being normalised.
+fn check_host_bits(
+    network: &mut Mechanism<IpNetwork>,
+    position: usize,
+    options: &ParseOptions,
+    warnings: &mut Vec<Warning>,
+) {
+    if network.is_normalized() {
+        return;
+    }
+    let normalized = network.normalized();
+    warnings.push(Warning::HostBitsSet {
+        network: *normalized.as_network(),
+        position,
+    });
+    if options.normalizes_networks() {
+        *network = normalized;
+    }
+}
+// The error for a prefix length which is out of range for the family of `kind`.
+fn cidr_length_error(kind: Kind, length: &str) -> SpfError {
+    if kind.is_ip_v4() {
+        SpfError::InvalidIPv4CidrLength(length.to_string())
+    } else {
+        SpfError::InvalidIPv6CidrLength(length.to_string())
+    }
+}
+
+impl Spf {
+    /// Create a new empty Spf struct. The version is not set, so the Spf is written without
+    /// one until a version is set. This is the same as `Spf::default()`.
+    ///
+    /// Use [`new_v1()`](Spf::new_v1) or one of the `new_v2_*()` constructors to start a
+    /// record with its version set.
+    pub fn new() -> Self {
+        Spf::default()
+    }
+    // An empty Spf with `version` set.
+    fn with_version(version: Version) -> Self {
+        Spf {
+            version_str: version.to_string(),
+            version: Some(version),
+            ..Spf::default()
+        }
+    }
+    // Store a version which is known to be valid.
+    fn store_version(&mut self, version: Version) {
+        self.modified();
+        self.version_str = version.to_string();
+        self.version = Some(version);
+    }
+    /// Create a new empty Spf struct with its version set to `v=spf1`
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf = Spf::new_v1();
+    /// spf.append_mechanism(Mechanism::all(Qualifier::Fail));
+    /// assert_eq!(spf.to_string(), "v=spf1 -all");
+    /// ```
+    pub fn new_v1() -> Self {
+        Spf::with_version(Version::V1)
+    }
+    /// Create a new empty Spf struct with its version set to `spf2.0/pra`
+    pub fn new_v2_pra() -> Self {
+        Spf::with_version(Version::V2(vec![Scope::Pra]))
+    }
+    /// Create a new empty Spf struct with its version set to `spf2.0/mfrom`
+    pub fn new_v2_mfrom() -> Self {
+        Spf::with_version(Version::V2(vec![Scope::MFrom]))
+    }
+    /// Create a new empty Spf struct with its version set to `spf2.0/pra,mfrom`
+    pub fn new_v2_pra_mfrom() -> Self {
+        Spf::with_version(Version::V2(vec![Scope::Pra, Scope::MFrom]))
+    }
+    /// Create a new empty Spf struct with its version set to `spf2.0/mfrom,pra`
+    pub fn new_v2_mfrom_pra() -> Self {
+        Spf::with_version(Version::V2(vec![Scope::MFrom, Scope::Pra]))
+    }
+    /// Creates an `Spf Struct` by parsing a string representation of Spf in *strict* mode.
+    ///
+    /// Any term which is not a recognised mechanism, modifier or the version string results in
+    /// [`UnknownTerm`](SpfError::UnknownTerm) rather than being ignored. A term shaped like a
+    /// mechanism with an unknown name results in
+    /// [`UnknownMechanismName`](crate::mechanism::MechanismError::UnknownMechanismName)
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Spf, SpfError};
+    /// use decon_spf::mechanism::MechanismError;
+    /// let err = Spf::from_str_strict("v=spf1 a bogusmech -all").unwrap_err();
+    /// assert_eq!(
+    ///     err.inner(),
+    ///     &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName("bogusmech".to_string()))
+    /// );
+    /// let err = Spf::from_str_strict("v=spf1 a bogus.example.com -all").unwrap_err();
+    /// assert_eq!(err.inner(), &SpfError::UnknownTerm("bogus.example.com".to_string()));
+    /// // The default parser ignores the unknown term.
+    /// let spf: Spf = "v=spf1 a bogusmech -all".parse().unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a -all");
+    ///```
+    pub fn from_str_strict(s: &str) -> Result<Spf, SpfError> {
+        Spf::from_str_with_options(s, &ParseOptions::new().strict(true))
+    }
+    /// Creates an `Spf Struct` by parsing a string representation of Spf using the
+    /// provided [`ParseOptions`](ParseOptions).
+    pub fn from_str_with_options(s: &str, options: &ParseOptions) -> Result<Spf, SpfError> {
+        Spf::parse(s, options, helpers::MAX_SPF_STRING_LENGTH)
+    }
+    /// Creates an `Spf Struct` by parsing a string representation of Spf, reporting every
+    /// term which fails rather than only the first.
+    ///
+    /// Each error is an [`SpfError::Parse`](SpfError::Parse) recording the failed term and
+    /// its position. The same error in an identical term is only reported once. Errors in the
+    /// record as a whole, such as a missing version, are returned alone.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let errors = Spf::parse_all_errors("v=spf1 ip4:10.0.0.0/33 ip6:2001:db8::/129 -all")
+    ///     .unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].parse_error().unwrap().term(), "ip4:10.0.0.0/33");
+    /// assert_eq!(errors[1].parse_error().unwrap().term(), "ip6:2001:db8::/129");
+    ///```
+    pub fn parse_all_errors(s: &str) -> Result<Spf, Vec<SpfError>> {
+        Spf::parse_all_errors_with_options(s, &ParseOptions::default())
+    }
+    /// As [`parse_all_errors()`](Spf::parse_all_errors) using the provided
+    /// [`ParseOptions`](ParseOptions).
+    pub fn parse_all_errors_with_options(
+        s: &str,
+        options: &ParseOptions,
+    ) -> Result<Spf, Vec<SpfError>> {
+        Spf::parse_terms(s, options, helpers::MAX_SPF_STRING_LENGTH, true)
+    }
+    /// Creates an `Spf Struct` from the character-strings of a single DNS TXT record.
+    ///
+    /// Records longer than 255 characters are published as several character-strings which
+    /// are concatenated, without adding whitespace, before being parsed. A term may be split
+    /// across two character-strings.
+    ///
+    /// Each character-string is limited to 255 characters. See
+    /// [`SourceLengthExceeded`](SpfError::SourceLengthExceeded). An empty character-string
+    /// results in [`EmptyTxtChunk`](SpfError::EmptyTxtChunk).
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf = Spf::from_txt_strings(&["v=spf1 ip4:192.0.2.0/24 incl", "ude:_spf.example.com -all"])
+    ///     .unwrap();
+    /// assert_eq!(spf.includes().unwrap()[0].raw(), "_spf.example.com");
+    /// assert_eq!(spf.to_string(), "v=spf1 include:_spf.example.com ip4:192.0.2.0/24 -all");
+    ///```
+    pub fn from_txt_strings(chunks: &[&str]) -> Result<Spf, SpfError> {
+        for (index, chunk) in chunks.iter().enumerate() {
+            if chunk.is_empty() {
+                return Err(SpfError::EmptyTxtChunk { index });
+            }
+            if chunk.len() > helpers::MAX_SPF_STRING_LENGTH {
+                return Err(SpfError::SourceLengthExceeded);
+            }
+        }
+        // Each chunk has been checked. RFC 7208 places no limit on the joined record.
+        let mut spf = Spf::parse(&chunks.concat(), &ParseOptions::default(), usize::MAX)?;
+        spf.max_length = Some(usize::MAX);
+        Ok(spf)
+    }
+    /// Creates an `Spf Struct` from the Spf record of `domain`, found with a TXT lookup made
+    /// through `resolver`.
+    ///
+    /// TXT records which do not start with `v=spf1` are ignored. The character-strings of the
+    /// record are joined before it is parsed, as with
+    /// [`from_txt_strings()`](Spf::from_txt_strings).
+    ///
+    /// # Errors:
+    /// - [`Lookup`](SpfFetchError::Lookup) if the lookup fails.
+    /// - [`NoRecord`](SpfFetchError::NoRecord) if `domain` has no Spf record.
+    /// - [`MultipleRecords`](SpfFetchError::MultipleRecords) if `domain` has more than one.
+    /// - [`Invalid`](SpfFetchError::Invalid) if the record can not be parsed.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::evaluate::{LookupError, SpfResolver};
+    /// use decon_spf::Spf;
+    /// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    ///
+    /// struct Resolver;
+    ///
+    /// impl SpfResolver for Resolver {
+    ///     fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+    ///         match name {
+    ///             "example.com" => Ok(vec![
+    ///                 "google-site-verification=abc".to_string(),
+    ///                 "v=spf1 mx -all".to_string(),
+    ///             ]),
+    ///             _ => Ok(vec![]),
+    ///         }
+    ///     }
+    ///     // The other lookups are not used.
+    /// #   fn lookup_a(&self, _: &str) -> Result<Vec<Ipv4Addr>, LookupError> { Ok(vec![]) }
+    /// #   fn lookup_aaaa(&self, _: &str) -> Result<Vec<Ipv6Addr>, LookupError> { Ok(vec![]) }
+    /// #   fn lookup_mx(&self, _: &str) -> Result<Vec<String>, LookupError> { Ok(vec![]) }
+    /// #   fn lookup_ptr(&self, _: IpAddr) -> Result<Vec<String>, LookupError> { Ok(vec![]) }
+    /// }
+    ///
+    /// let spf = Spf::from_resolver(&Resolver, "example.com").unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 mx -all");
+    /// assert_eq!(Spf::from_resolver(&Resolver, "example.org").unwrap_err().is_no_record(), true);
+    ///```
+    pub fn from_resolver(resolver: &dyn SpfResolver, domain: &str) -> Result<Spf, SpfFetchError> {
+        fetch::fetch(resolver, domain)
+    }
+    /// Creates an `Spf Struct` from the TXT records of a domain, which may hold other data
+    /// such as verification tokens. Only the records which start with `v=spf1`, in any case,
+    /// are parsed. As for [`from_resolver()`](Spf::from_resolver), the record is not limited
+    /// to 255 characters.
+    ///
+    /// # Errors:
+    /// - [`NoSpfRecordFound`](SpfError::NoSpfRecordFound) when no record starts with `v=spf1`.
+    /// - [`MultipleSpfRecords`](SpfError::MultipleSpfRecords) when more than one does. RFC 7208
+    ///   section 4.5 requires a receiver to treat this as a permanent error.
+    /// - Any error found while parsing the Spf record.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Spf, SpfError};
+    /// let records = vec![
+    ///     "google-site-verification=abc".to_string(),
+    ///     "V=SPF1 mx -all".to_string(),
+    /// ];
+    /// let spf = Spf::find_in_txt_records(&records).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 mx -all");
+    /// let records = vec!["v=spf1 mx -all".to_string(), "v=spf1 a -all".to_string()];
+    /// assert_eq!(
+    ///     Spf::find_in_txt_records(&records).unwrap_err(),
+    ///     SpfError::MultipleSpfRecords(2)
+    /// );
+    ///```
+    pub fn find_in_txt_records(records: &[String]) -> Result<Spf, SpfError> {
+        fetch::find_in_txt_records(records)
+    }
+    /// Creates an `Spf Struct` from the Spf record of `domain`, looked up with a
+    /// `hickory_resolver::Resolver`. See [`from_resolver()`](Spf::from_resolver)
+    ///
+    /// # Example:
+    ///```no_run
+    /// use decon_spf::Spf;
+    /// use hickory_resolver::Resolver;
+    /// let resolver = Resolver::from_system_conf().unwrap();
+    /// let spf = Spf::from_domain(&resolver, "gmail.com").unwrap();
+    /// println!("{}", spf);
+    ///```
+    #[cfg(feature = "hickory-resolver")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hickory-resolver")))]
+    pub fn from_domain(
+        resolver: &hickory_resolver::Resolver,
+        domain: &str,
+    ) -> Result<Spf, SpfFetchError> {
+        Spf::from_resolver(resolver, domain)
+    }
+    /// Build the record as the character-strings of a DNS TXT record, each no longer than
+    /// `max` characters. The character-strings may be parsed with
+    /// [`from_txt_strings()`](Spf::from_txt_strings)
+    ///
+    /// Use a `max` of 255, the most a character-string may hold. A larger `max` is treated
+    /// as 255. A term is never split across two character-strings. The space which separates
+    /// two terms starts the second character-string, so the character-strings are joined
+    /// without adding whitespace.
+    ///
+    /// # Errors:
+    /// - [`SourceLengthExceeded`](SpfError::SourceLengthExceeded) if a term and the space
+    ///   before it are longer than `max`.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a include:_spf.example.com -all".parse().unwrap();
+    /// let chunks = spf.to_txt_chunks(29).unwrap();
+    /// assert_eq!(chunks, vec!["v=spf1 a", " include:_spf.example.com", " -all"]);
+    /// assert_eq!(chunks.concat(), spf.to_string());
+    /// assert_eq!(spf.to_txt_chunks(255).unwrap().len(), 1);
+    ///```
+    pub fn to_txt_chunks(&self, max: usize) -> Result<Vec<String>, SpfError> {
+        let max = max.min(helpers::MAX_SPF_STRING_LENGTH);
+        let mut chunks = Vec::new();
+        let mut chunk = String::new();
+        for (index, term) in self.to_string().split(' ').enumerate() {
+            let separator = if index == 0 { "" } else { " " };
+            let len = separator.len() + term.len();
+            if len > max {
+                return Err(SpfError::SourceLengthExceeded);
+            }
+            if chunk.len() + len > max {
+                chunks.push(std::mem::take(&mut chunk));
+            }
+            chunk.push_str(separator);
+            chunk.push_str(term);
+        }
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+    pub(crate) fn parse(
+        s: &str,
+        options: &ParseOptions,
+        max_length: usize,
+    ) -> Result<Spf, SpfError> {
+        Spf::parse_terms(s, options, max_length, false).map_err(|mut errors| errors.remove(0))
+    }
+    /// Parses `s`. When `collect_errors` is `true` parsing continues past a term which fails
+    /// and every failure is returned. Otherwise the first failure is returned.
+    fn parse_terms(
+        s: &str,
+        options: &ParseOptions,
+        max_length: usize,
+        collect_errors: bool,
+    ) -> Result<Spf, Vec<SpfError>> {
         let source = String::from(s);
-        if !source.starts_with("v=spf1") && !source.starts_with("spf2.0") {
-            return Err(SpfError::InvalidSource);
+        if source.trim().is_empty() {
+            return Err(vec![SpfError::EmptyInput]);
+        }
+        let mut vec_of_parse_warnings: Vec<Warning> = Vec::new();
+        // Leading whitespace is only tolerated when whitespace is lenient.
+        let start = if options.is_lenient_whitespace() {
+            source.trim_start()
+        } else {
+            source.as_str()
         };
-        if source.len() > helpers::MAX_SPF_STRING_LENGTH {
-            return Err(SpfError::SourceLengthExceeded);
+        // The version must be the whole of the first term.
+        let first = start.split(char::is_whitespace).next().unwrap_or_default();
+        if let Some(scopes) = helpers::strip_prefix_ignore_case(first, "spf2.0/") {
+            scope::parse_scopes(scopes).map_err(|error| vec![error])?;
+        }
+        if helpers::is_malformed_version(first) {
+            return Err(vec![SpfError::InvalidVersion(first.to_string())]);
+        }
+        if !helpers::is_version_term(first) {
+            if let Some(position) = source.split_whitespace().position(helpers::is_version_term) {
+                return Err(vec![SpfError::VersionNotAtStart { position }]);
+            }
+            return Err(vec![SpfError::InvalidSource]);
         };
-        if helpers::spf_check_whitespace(source.as_str()) {
-            return Err(SpfError::WhiteSpaceSyntaxError);
+        if source.len() > max_length {
+            return Err(vec![SpfError::SourceLengthExceeded]);
+        };
+        if options.is_lenient_whitespace() {
+            for position in helpers::extra_whitespace_positions(source.as_str()) {
+                vec_of_parse_warnings.push(Warning::ExtraWhitespace { position });
+            }
+        } else if helpers::spf_check_whitespace(source.as_str()) {
+            return Err(vec![SpfError::WhiteSpaceSyntaxError]);
         };
         // Basic Checks are ok.
         let mut spf = Spf::new();
@@ -96,43 +744,162 @@ impl FromStr for Spf {
         let mut vec_of_a: Vec<Mechanism<String>> = Vec::new();
         let mut vec_of_mx: Vec<Mechanism<String>> = Vec::new();
         let mut vec_of_exists: Vec<Mechanism<String>> = Vec::new();
+        let mut vec_of_unknown_modifiers: Vec<(String, String)> = Vec::new();
         #[cfg(feature = "warn-dns")]
         let mut vec_of_warnings: Vec<String> = Vec::new();
-        for record in records {
-            // Consider ensuring we do this once at least and then skip
-            if record.contains("v=spf1") || record.starts_with("spf2.0") {
-                spf.version = record.to_string();
-            } else if record.contains("redirect=") {
-                let mut items = record.rsplit('=');
-                if let Some(rrdata) = items.next() {
-                    let m = Mechanism::generic_inclusive(
+        // Position of the first `all` term.
+        let mut all_position: Option<usize> = None;
+        // Positions of the first `redirect=` and `exp=` terms.
+        let mut redirect_position: Option<usize> = None;
+        let mut exp_position: Option<usize> = None;
+        let mut errors: Vec<SpfError> = Vec::new();
+        for (position, record) in records.enumerate() {
+            // Errors found in this term record the term and its position.
+            let offset = record.as_ptr() as usize - source.as_ptr() as usize;
+            // Record a failure in this term. Unless collecting errors, stop at the first.
+            macro_rules! term_error {
+                ($error:expr) => {{
+                    let error = ParseError::new(position, offset, record, $error);
+                    if !collect_errors {
+                        return Err(vec![SpfError::Parse(Box::new(error))]);
+                    }
+                    // The same failure in an identical term is only reported once.
+                    if !errors
+                        .iter()
+                        .filter_map(SpfError::parse_error)
+                        .any(|seen| seen.is_same_failure(&error))
+                    {
+                        errors.push(SpfError::Parse(Box::new(error)));
+                    }
+                    continue;
+                }};
+            }
+            // Mechanisms after `all` can never be evaluated. Modifiers may appear anywhere.
+            if let Some(all) = all_position {
+                if helpers::is_mechanism_term(record) {
+                    if options.is_strict() {
+                        term_error!(SpfError::MechanismAfterAll { all, position });
+                    }
+                    vec_of_parse_warnings.push(Warning::MechanismAfterAll { all, position });
+                }
+            }
+            if let Some(domain) = helpers::domain_with_empty_label(record) {
+                term_error!(SpfError::InvalidMechanism(
+                    MechanismError::InvalidDomainHost(domain.to_string())
+                ));
+            }
+            // Modifiers take no qualifier. Unless strict, the qualifier is removed.
+            let record = match helpers::qualified_modifier(record) {
+                Some(kind) => {
+                    if options.is_strict() {
+                        term_error!(SpfError::InvalidMechanism(
+                            MechanismError::QualifierNotAllowedForModifier(kind)
+                        ));
+                    }
+                    vec_of_parse_warnings.push(Warning::QualifierOnModifier { kind, position });
+                    helpers::strip_qualifier(record)
+                }
+                None => record,
+            };
+            // Mechanism and modifier names are case-insensitive. Each name is matched at the
+            // start of the term, after any qualifier, so that a domain-spec containing another
+            // name is never mistaken for it. Values are taken from `record` to preserve their case.
+            // The source is known to start with the version.
+            if position == 0 {
+                // The version is case-insensitive and is written in lowercase.
+                spf.version = record.parse().ok();
+                spf.version_str = if options.preserves_case() {
+                    record.to_string()
+                } else {
+                    record.to_ascii_lowercase()
+                };
+            } else if helpers::is_version_term(record) {
+                term_error!(SpfError::DuplicateVersion { position });
+            } else if let Some(rrdata) = helpers::strip_prefix_ignore_case(record, "redirect=") {
+                if let Some(first) = redirect_position {
+                    if !options.is_lenient_modifiers() {
+                        term_error!(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect,));
+                    }
+                    vec_of_parse_warnings.push(Warning::DuplicateModifier {
+                        kind: Kind::Redirect,
+                        first,
+                        second: position,
+                    });
+                } else if rrdata.is_empty() {
+                    term_error!(SpfError::InvalidMechanism(
+                        MechanismError::InvalidMechanismFormat(record.to_string()),
+                    ));
+                } else {
+                    let mut m = Mechanism::generic_inclusive(
                         Kind::Redirect,
                         Qualifier::Pass,
                         Some(rrdata.to_string()),
                     );
+                    preserve_presentation(&mut m, record, options);
                     #[cfg(feature = "warn-dns")]
                     {
                         helpers::check_for_dns_warning(&mut vec_of_warnings, &m.raw());
                     }
                     spf.redirect = Some(m);
                     spf.is_redirected = true;
+                    redirect_position = Some(position);
                 }
-            } else if record.contains("include:") {
-                let qualifier_and_modified_str = helpers::return_and_remove_qualifier(record, 'i');
-                if let Some(rrdata) = record.rsplit(':').next() {
-                    let m = Mechanism::generic_inclusive(
+            } else if let Some(rrdata) = helpers::strip_prefix_ignore_case(record, "exp=") {
+                if let Some(first) = exp_position {
+                    if !options.is_lenient_modifiers() {
+                        term_error!(SpfError::ModifierMayOccurOnlyOnce(Kind::Exp));
+                    }
+                    vec_of_parse_warnings.push(Warning::DuplicateModifier {
+                        kind: Kind::Exp,
+                        first,
+                        second: position,
+                    });
+                } else if rrdata.is_empty() {
+                    term_error!(SpfError::InvalidMechanism(
+                        MechanismError::InvalidMechanismFormat(record.to_string()),
+                    ));
+                } else {
+                    let mut m = Mechanism::generic_inclusive(
+                        Kind::Exp,
+                        Qualifier::Pass,
+                        Some(rrdata.to_string()),
+                    );
+                    preserve_presentation(&mut m, record, options);
+                    #[cfg(feature = "warn-dns")]
+                    {
+                        helpers::check_for_dns_warning(&mut vec_of_warnings, &m.raw());
+                    }
+                    spf.exp = Some(m);
+                    exp_position = Some(position);
+                }
+            } else if let Some((name, value)) = helpers::split_unknown_modifier(record) {
+                vec_of_unknown_modifiers.push((name.to_string(), value.to_string()));
+            } else if let Some(rrdata) =
+                helpers::strip_prefix_ignore_case(helpers::strip_qualifier(record), "include:")
+            {
+                // The domain-spec is everything after `include:` and may itself contain `:`.
+                if rrdata.is_empty() {
+                    term_error!(SpfError::InvalidMechanism(
+                        MechanismError::InvalidMechanismFormat(record.to_string()),
+                    ));
+                } else {
+                    let mut m = Mechanism::generic_inclusive(
                         Kind::Include,
-                        qualifier_and_modified_str.0,
+                        helpers::return_and_remove_qualifier(record, 'i').0,
                         Some(rrdata.to_string()),
                     );
+                    preserve_presentation(&mut m, record, options);
                     #[cfg(feature = "warn-dns")]
                     {
                         helpers::check_for_dns_warning(&mut vec_of_warnings, &m.raw());
                     }
                     vec_of_includes.push(m);
                 }
-            } else if let Some(exists_mechanism) = helpers::capture_matches(record, Kind::Exists) {
-                if !exists_mechanism.raw().contains('/') {
+            } else if let Some(mut exists_mechanism) =
+                helpers::capture_matches(record, Kind::Exists)
+            {
+                if !helpers::after_macros(&exists_mechanism.raw()).contains('/') {
+                    preserve_presentation(&mut exists_mechanism, record, options);
                     #[cfg(feature = "warn-dns")]
                     {
                         helpers::check_for_dns_warning(
@@ -141,39 +908,109 @@ impl FromStr for Spf {
                         );
                     }
                     vec_of_exists.push(exists_mechanism);
+                } else if options.is_strict() {
+                    term_error!(SpfError::UnknownTerm(record.to_string()));
                 }
-            } else if record.contains("ip4:") {
+            } else if let Some(raw_ip4) =
+                helpers::strip_prefix_ignore_case(helpers::strip_qualifier(record), "ip4:")
+            {
                 // Match an ip4
+                // As for `Mechanism<IpNetwork>`, a term without a network has its own error.
+                if raw_ip4.is_empty() {
+                    term_error!(SpfError::InvalidMechanism(MechanismError::EmptyIpValue(
+                        record.to_string()
+                    )));
+                }
                 let qualifier_and_modified_str = helpers::return_and_remove_qualifier(record, 'i');
-                if let Some(raw_ip4) = qualifier_and_modified_str.1.strip_prefix("ip4:") {
-                    let valid_ip4 = raw_ip4.parse();
-                    match valid_ip4 {
-                        Ok(ip4) => {
-                            let network = Mechanism::ip(qualifier_and_modified_str.0, ip4);
-                            vec_of_ip4.push(network);
+                let valid_ip4 = raw_ip4.parse();
+                match valid_ip4 {
+                    Ok(ip4) => {
+                        if let Err(error) = helpers::check_ip_family(&Kind::IpV4, &ip4) {
+                            term_error!(SpfError::InvalidMechanism(error));
+                        }
+                        let mut network = Mechanism::ip(qualifier_and_modified_str.0, ip4);
+                        network.preserve_implicit_prefix(raw_ip4);
+                        preserve_presentation(&mut network, record, options);
+                        check_host_bits(
+                            &mut network,
+                            position,
+                            options,
+                            &mut vec_of_parse_warnings,
+                        );
+                        vec_of_ip4.push(network);
+                    }
+                    Err(ip4) => {
+                        if let Some(length) = helpers::invalid_cidr_length(raw_ip4, &Kind::IpV4) {
+                            term_error!(cidr_length_error(Kind::IpV4, length));
                         }
-                        Err(ip4) => return Err(SpfError::InvalidIPAddr(ip4)),
+                        term_error!(SpfError::InvalidIPAddr(ip4));
                     }
                 }
-            } else if record.contains("ip6:") {
+            } else if let Some(raw_ip6) =
+                helpers::strip_prefix_ignore_case(helpers::strip_qualifier(record), "ip6:")
+            {
                 // Match an ip6
+                // As for `Mechanism<IpNetwork>`, a term without a network has its own error.
+                if raw_ip6.is_empty() {
+                    term_error!(SpfError::InvalidMechanism(MechanismError::EmptyIpValue(
+                        record.to_string()
+                    )));
+                }
                 let qualifier_and_modified_str = helpers::return_and_remove_qualifier(record, 'i');
-                if let Some(raw_ip6) = qualifier_and_modified_str.1.strip_prefix("ip6:") {
-                    let valid_ip6 = raw_ip6.parse();
-                    match valid_ip6 {
-                        Ok(ip6) => {
-                            let network = Mechanism::ip(qualifier_and_modified_str.0, ip6);
-                            vec_of_ip6.push(network);
+                let valid_ip6 = raw_ip6.parse();
+                match valid_ip6 {
+                    Ok(ip6) => {
+                        if let Err(error) = helpers::check_ip_family(&Kind::IpV6, &ip6) {
+                            term_error!(SpfError::InvalidMechanism(error));
+                        }
+                        let mut network = Mechanism::ip(qualifier_and_modified_str.0, ip6);
+                        network.preserve_implicit_prefix(raw_ip6);
+                        preserve_presentation(&mut network, record, options);
+                        check_host_bits(
+                            &mut network,
+                            position,
+                            options,
+                            &mut vec_of_parse_warnings,
+                        );
+                        vec_of_ip6.push(network);
+                    }
+                    Err(ip6) => {
+                        if let Some(length) = helpers::invalid_cidr_length(raw_ip6, &Kind::IpV6) {
+                            term_error!(cidr_length_error(Kind::IpV6, length));
                         }
-                        Err(ip6) => return Err(SpfError::InvalidIPAddr(ip6)),
+                        term_error!(SpfError::InvalidIPAddr(ip6));
                     }
                 }
-            } else if record.ends_with("all") && (record.len() == 3 || record.len() == 4) {
-                spf.all = Some(Mechanism::all(
-                    helpers::return_and_remove_qualifier(record, 'a').0,
-                ));
+            } else if helpers::is_all_term(record) {
+                if let Some(first) = all_position {
+                    // Only the first `all` can ever be evaluated.
+                    if options.is_strict() {
+                        term_error!(SpfError::DuplicateAll {
+                            first,
+                            second: position,
+                        });
+                    }
+                    vec_of_parse_warnings.push(Warning::DuplicateAll {
+                        first,
+                        second: position,
+                    });
+                } else {
+                    let mut m = Mechanism::all(helpers::return_and_remove_qualifier(record, 'a').0);
+                    preserve_presentation(&mut m, record, options);
+                    spf.all = Some(m);
+                    all_position = Some(position);
+                }
             // Handle A, MX and PTR types.
-            } else if let Some(a_mechanism) = helpers::capture_matches(record, Kind::A) {
+            } else if helpers::malformed_dual_cidr(record) {
+                term_error!(SpfError::InvalidMechanism(MechanismError::InvalidDualCidr(
+                    record.to_string()
+                ),));
+            } else if let Some(mut a_mechanism) = helpers::capture_matches(record, Kind::A) {
+                if let Some((kind, length)) = helpers::invalid_dual_cidr_length(&a_mechanism.raw())
+                {
+                    term_error!(cidr_length_error(kind, length));
+                }
+                preserve_presentation(&mut a_mechanism, record, options);
                 #[cfg(feature = "warn-dns")]
                 {
                     if !a_mechanism.raw().starts_with('/')
@@ -185,7 +1022,12 @@ impl FromStr for Spf {
                     }
                 }
                 vec_of_a.push(a_mechanism);
-            } else if let Some(mx_mechanism) = helpers::capture_matches(record, Kind::MX) {
+            } else if let Some(mut mx_mechanism) = helpers::capture_matches(record, Kind::MX) {
+                if let Some((kind, length)) = helpers::invalid_dual_cidr_length(&mx_mechanism.raw())
+                {
+                    term_error!(cidr_length_error(kind, length));
+                }
+                preserve_presentation(&mut mx_mechanism, record, options);
                 #[cfg(feature = "warn-dns")]
                 {
                     if !mx_mechanism.raw().starts_with('/')
@@ -197,14 +1039,25 @@ impl FromStr for Spf {
                     }
                 }
                 vec_of_mx.push(mx_mechanism);
-            } else if let Some(ptr_mechanism) = helpers::capture_matches(record, Kind::Ptr) {
+            } else if let Some(mut ptr_mechanism) = helpers::capture_matches(record, Kind::Ptr) {
+                preserve_presentation(&mut ptr_mechanism, record, options);
                 #[cfg(feature = "warn-dns")]
                 {
                     helpers::check_for_dns_warning(&mut vec_of_warnings, &ptr_mechanism.raw());
                 }
                 spf.ptr = Some(ptr_mechanism);
+            } else if options.is_strict() {
+                if let Some(name) = helpers::unknown_mechanism_name(record) {
+                    term_error!(SpfError::InvalidMechanism(
+                        MechanismError::UnknownMechanismName(name.to_string())
+                    ));
+                }
+                term_error!(SpfError::UnknownTerm(record.to_string()));
             }
         }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
         // Move vec_of_* int the SPF struct
         if !vec_of_includes.is_empty() {
             spf.include = Some(vec_of_includes);
@@ -224,6 +1077,12 @@ impl FromStr for Spf {
         if !vec_of_exists.is_empty() {
             spf.exists = Some(vec_of_exists);
         }
+        if !vec_of_unknown_modifiers.is_empty() {
+            spf.unknown_modifiers = Some(vec_of_unknown_modifiers);
+        }
+        if !vec_of_parse_warnings.is_empty() {
+            spf.parse_warnings = Some(vec_of_parse_warnings);
+        }
         #[cfg(feature = "warn-dns")]
         {
             if !vec_of_warnings.is_empty() {
@@ -231,32 +1090,39 @@ impl FromStr for Spf {
             }
         }
 
+        spf.from_src = true;
         spf.was_parsed = true;
-        spf.is_valid = true;
         spf.source = source;
         Ok(spf)
     }
-}
-
-impl TryFrom<&str> for Spf {
-    type Error = SpfError;
-
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Spf::from_str(s)
-    }
-}
-impl Spf {
-    /// Create a new empty Spf struct.
-    pub fn new() -> Self {
-        Spf::default()
-    }
     /// Check that data stored in the Spf Struct is considered a valid Spf Record.
+    /// This is only `true` once a validation pass has succeeded. Parsing alone does not
+    /// validate the record.
+    ///
+    /// Any change to the Spf, such as appending, removing or clearing a mechanism, forgets an
+    /// earlier validation. The Spf must be validated again before this returns `true`.
     pub fn is_valid(&self) -> bool {
-        if self.was_parsed || self.was_validated {
+        if self.was_validated {
             return self.is_valid;
         };
         false
     }
+    /// Returns `true` if the Spf has been changed since it was parsed or created. The
+    /// [`source()`](Spf::source) of a modified Spf no longer describes it.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+    /// assert_eq!(spf.is_modified(), false);
+    /// spf.append_mechanism(Mechanism::mx(Qualifier::Pass));
+    /// assert_eq!(spf.is_modified(), true);
+    /// assert_eq!(spf.source(), "v=spf1 a -all");
+    /// ```
+    pub fn is_modified(&self) -> bool {
+        self.is_modified
+    }
     /// Check if there were any warnings when parsing the Spf String.
     /// This can only be changed to `true` when `warn-dns` feature has been eabled. Other wise it
     /// will always be `false`
@@ -267,43 +1133,88 @@ impl Spf {
     }
     /// Set version to `v=spf1`
     pub fn set_v1(&mut self) {
-        self.version = String::from("v=spf1");
+        self.store_version(Version::V1);
     }
     /// Set version to `spf2.0/pra`
     pub fn set_v2_pra(&mut self) {
-        self.version = String::from("spf2.0/pra");
+        self.store_version(Version::V2(vec![Scope::Pra]));
     }
     /// Set version to `spf2.0/mfrom`
     pub fn set_v2_mfrom(&mut self) {
-        self.version = String::from("spf2.0/mfrom");
+        self.store_version(Version::V2(vec![Scope::MFrom]));
     }
     /// Set version to `spf2.0/pra,mfrom`
     pub fn set_v2_pra_mfrom(&mut self) {
-        self.version = String::from("spf2.0/pra,mfrom");
+        self.store_version(Version::V2(vec![Scope::Pra, Scope::MFrom]));
     }
     /// Set version to `spf2.0/mfrom,pra`
     pub fn set_v2_mfrom_pra(&mut self) {
-        self.version = String::from("spf2.0/mfrom,pra");
+        self.store_version(Version::V2(vec![Scope::MFrom, Scope::Pra]));
     }
     /// Check that version is v1
     pub fn is_v1(&self) -> bool {
-        self.version.contains("v=spf1")
+        matches!(self.version, Some(Version::V1))
     }
     /// Check that version is v2
     pub fn is_v2(&self) -> bool {
-        self.version.starts_with("spf2.0")
+        matches!(self.version, Some(Version::V2(_)))
+    }
+    /// Returns the scopes of an `spf2.0` record in the order they were written. Returns `None`
+    /// for `v=spf1`.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Scope, Spf};
+    /// let spf: Spf = "spf2.0/mfrom,pra a -all".parse().unwrap();
+    /// assert_eq!(spf.scopes().unwrap(), vec![Scope::MFrom, Scope::Pra]);
+    /// assert_eq!(spf.to_string(), "spf2.0/mfrom,pra a -all");
+    ///```
+    pub fn scopes(&self) -> Option<Vec<Scope>> {
+        Some(self.version.as_ref()?.scopes()?.to_vec())
     }
-    /// Return a reference to version
+    /// Return a reference to version as it is written. This is empty when no version is set.
     pub fn version(&self) -> &String {
-        &self.version
+        &self.version_str
+    }
+    /// Return the version as a [`Version`](Version). Returns `None` when no version is set.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Scope, Spf, Version};
+    /// let spf: Spf = "spf2.0/pra a -all".parse().unwrap();
+    /// assert_eq!(spf.version_kind(), Some(&Version::V2(vec![Scope::Pra])));
+    /// assert_eq!(Spf::new().version_kind(), None);
+    ///```
+    pub fn version_kind(&self) -> Option<&Version> {
+        self.version.as_ref()
+    }
+    /// Set the version. The `set_v1()` and `set_v2_*()` methods are shorthand for this.
+    ///
+    /// # Errors:
+    /// - [`InvalidScope`](SpfError::InvalidScope) if an `spf2.0` version has no scopes or
+    ///   repeats a scope.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Scope, Spf, SpfError, Version};
+    /// let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+    /// spf.set_version(Version::V2(vec![Scope::MFrom])).unwrap();
+    /// assert_eq!(spf.to_string(), "spf2.0/mfrom a -all");
+    /// assert_eq!(
+    ///     spf.set_version(Version::V2(vec![])).unwrap_err(),
+    ///     SpfError::InvalidScope("".to_string())
+    /// );
+    ///```
+    pub fn set_version(&mut self, version: Version) -> Result<(), SpfError> {
+        version.check()?;
+        self.store_version(version);
+        Ok(())
     }
-    /// Append a Redirect Mechanism to the Spf Struct.
+    /// Append a Redirect Mechanism to the Spf Struct. An existing `all` is kept.
     fn append_mechanism_of_redirect(&mut self, mechanism: Mechanism<String>) {
+        self.modified();
         self.redirect = Some(mechanism);
         self.is_redirected = true;
-        if self.all.is_some() {
-            self.all = None;
-        }
     }
     /// Clear the passed Kind which has been passed.
     /// Sets the passed mechanism to `None`
@@ -315,8 +1226,7 @@ impl Spf {
     /// ```
     /// use decon_spf::mechanism::{Qualifier, Kind, Mechanism};
     /// use decon_spf::Spf;
-    /// let mut new_spf_record = Spf::new();
-    /// new_spf_record.set_v1();
+    /// let mut new_spf_record = Spf::new_v1();
     /// new_spf_record.append_mechanism(Mechanism::all(Qualifier::Pass));
     /// new_spf_record.append_mechanism(Mechanism::a(Qualifier::Pass));
     /// new_spf_record.append_ip_mechanism(Mechanism::new_ip(Qualifier::Pass,
@@ -326,38 +1236,1172 @@ impl Spf {
     /// new_spf_record.clear_mechanism(Kind::IpV4);
     /// assert_eq!(new_spf_record.to_string(), "v=spf1 a all".to_string());
     ///```
+    ///
+    /// See [`take_mechanisms()`](Spf::take_mechanisms) to keep the mechanisms which are removed.
     pub fn clear_mechanism(&mut self, kind: Kind) {
-        match kind {
+        self.take_mechanisms(kind);
+    }
+    /// Remove and return every mechanism of the [`Kind`](Kind) provided, in the order they
+    /// are held. Returns an empty `Vec` if there are none.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Kind;
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a include:_spf.example.com include:_spf.example.net -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let removed = spf.take_mechanisms(Kind::Include);
+    /// assert_eq!(removed.len(), 2);
+    /// assert_eq!(removed[1].to_string(), "include:_spf.example.net");
+    /// assert_eq!(spf.to_string(), "v=spf1 a -all");
+    /// assert_eq!(spf.take_mechanisms(Kind::Include).is_empty(), true);
+    ///```
+    pub fn take_mechanisms(&mut self, kind: Kind) -> Vec<ParsedMechanism> {
+        let txt = |list: Option<Vec<Mechanism<String>>>| -> Vec<ParsedMechanism> {
+            list.into_iter()
+                .flatten()
+                .map(ParsedMechanism::TXT)
+                .collect()
+        };
+        let ip = |list: Option<Vec<Mechanism<IpNetwork>>>| -> Vec<ParsedMechanism> {
+            list.into_iter()
+                .flatten()
+                .map(ParsedMechanism::IP)
+                .collect()
+        };
+        let taken = match kind {
             Kind::Redirect => {
-                self.redirect = None;
                 self.is_redirected = false;
+                txt(self.redirect.take().map(|m| vec![m]))
             }
-            Kind::A => self.a = None,
-            Kind::MX => self.mx = None,
-            Kind::Include => self.include = None,
-            Kind::IpV4 => self.ip4 = None,
-            Kind::IpV6 => self.ip6 = None,
-            Kind::Exists => self.exists = None,
-            Kind::Ptr => self.ptr = None,
-            Kind::All => self.all = None,
+            Kind::A => txt(self.a.take()),
+            Kind::MX => txt(self.mx.take()),
+            Kind::Include => txt(self.include.take()),
+            Kind::IpV4 => ip(self.ip4.take()),
+            Kind::IpV6 => ip(self.ip6.take()),
+            Kind::Exists => txt(self.exists.take()),
+            Kind::Ptr => txt(self.ptr.take().map(|m| vec![m])),
+            Kind::All => txt(self.all.take().map(|m| vec![m])),
+            Kind::Exp => txt(self.exp.take().map(|m| vec![m])),
+        };
+        if !taken.is_empty() {
+            self.modified();
         }
+        taken
     }
 
-    fn append_mechanism_of_a(&mut self, mechanism: Mechanism<String>) {
-        if let Some(a) = &mut self.a {
-            a.push(mechanism);
-        } else {
-            self.a = Some(vec![mechanism]);
+    /// Remove every mechanism, modifier and warning, keeping only the version.
+    ///
+    /// The Spf is then as if it had been created with [`new()`](Spf::new) and had its version
+    /// set. The source it was parsed from is also removed, as it no longer describes the record.
+    /// Its [`max_length()`](Spf::max_length) is kept. A record without any mechanisms or
+    /// modifiers is not changed.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a mx redirect=_spf.example.com".parse().unwrap();
+    /// spf.clear();
+    /// assert_eq!(spf.to_string(), "v=spf1");
+    /// assert_eq!(spf.is_redirect(), false);
+    /// spf.append_str("-all").unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 -all");
+    /// ```
+    pub fn clear(&mut self) {
+        let cleared = Spf {
+            version: self.version.clone(),
+            version_str: self.version_str.clone(),
+            max_length: self.max_length,
+            ..Spf::default()
+        };
+        // A record without any terms is left as it is.
+        if *self != cleared {
+            *self = cleared;
+            self.modified();
         }
     }
-    fn append_mechanism_of_mx(&mut self, mechanism: Mechanism<String>) {
-        if let Some(mx) = &mut self.mx {
-            mx.push(mechanism);
-        } else {
-            self.mx = Some(vec![mechanism]);
+    /// Remove every mechanism, modifier and warning, and the version. The Spf is then the
+    /// same as one created with [`new()`](Spf::new).
+    pub fn clear_all(&mut self) {
+        *self = Spf::default();
+        self.modified();
+    }
+    /// Remove mechanisms which are duplicates of an earlier mechanism of the same kind,
+    /// returning the number removed. The first occurrence of each mechanism is kept.
+    ///
+    /// A duplicate has the same qualifier. Domain-specs are compared case-insensitively and
+    /// networks exactly.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 include:_spf.google.com ip4:192.0.2.0/24 \
+    ///                     include:_SPF.Google.com ip4:192.0.2.0/24 -all".parse().unwrap();
+    /// assert_eq!(spf.dedup(), 2);
+    /// assert_eq!(spf.to_string(), "v=spf1 include:_spf.google.com ip4:192.0.2.0/24 -all");
+    /// ```
+    pub fn dedup(&mut self) -> usize {
+        let mut removed = 0;
+        for list in [
+            &mut self.a,
+            &mut self.mx,
+            &mut self.include,
+            &mut self.exists,
+        ] {
+            removed += dedup_list(list, |m, other| {
+                m.qualifier() == other.qualifier() && same_rrdata(m, other)
+            });
+        }
+        for list in [&mut self.ip4, &mut self.ip6] {
+            removed += dedup_list(list, |m, other| m == other);
+        }
+        if removed > 0 {
+            self.modified();
+        }
+        removed
+    }
+    /// As [`dedup()`](Spf::dedup), and also remove `ip4:` and `ip6:` networks which are
+    /// contained in another network with the same qualifier. Returns the number removed.
+    ///
+    /// A network is only removed when doing so can not change the result of an evaluation.
+    /// That is, the containing network is earlier, or every network between the two has the
+    /// same qualifier.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.128/25 ip4:10.1.0.0/16 \
+    ///                     ip4:10.0.0.0/8 -all".parse().unwrap();
+    /// assert_eq!(spf.dedup_subsumed(), 2);
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.0/24 ip4:10.0.0.0/8 -all");
+    /// ```
+    pub fn dedup_subsumed(&mut self) -> usize {
+        let removed = self.dedup();
+        let mut subsumed = 0;
+        for list in [&mut self.ip4, &mut self.ip6] {
+            subsumed += remove_subsumed(list);
+        }
+        if subsumed > 0 {
+            self.modified();
+        }
+        removed + subsumed
+    }
+    /// Replace the `ip4:` and `ip6:` networks with the fewest networks which cover the same
+    /// addresses, returning the number of mechanisms removed.
+    ///
+    /// Networks which overlap, are contained in another or are adjacent are merged into a
+    /// single network where the addresses they cover allow it. Only consecutive networks with
+    /// the same qualifier are aggregated, so the result of an evaluation is not changed. A run
+    /// of networks which can not be made shorter is left as it is.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 ip4:192.0.2.0/25 ip4:192.0.2.128/25 ip4:192.0.2.16/28 \
+    ///                     -ip4:198.51.100.0/24 ip6:2001:db8::/33 ip6:2001:db8:8000::/33 -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(spf.aggregate_ips(), 3);
+    /// assert_eq!(
+    ///     spf.to_string(),
+    ///     "v=spf1 ip4:192.0.2.0/24 -ip4:198.51.100.0/24 ip6:2001:db8::/32 -all"
+    /// );
+    /// ```
+    pub fn aggregate_ips(&mut self) -> usize {
+        let mut removed = 0;
+        for networks in self.ip4.iter_mut().chain(self.ip6.iter_mut()) {
+            let aggregated = aggregate::aggregate_networks(networks);
+            removed += networks.len() - aggregated.len();
+            *networks = aggregated;
+        }
+        if removed > 0 {
+            self.modified();
+        }
+        removed
+    }
+    /// Returns the `ip4:` and `ip6:` networks as they would be after
+    /// [`aggregate_ips()`](Spf::aggregate_ips), without changing the Spf.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:10.0.0.0/9 ip4:10.128.0.0/9 ip6:2001:db8::1 -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let (ip4, ip6) = spf.aggregated_ips();
+    /// assert_eq!(ip4, vec!["10.0.0.0/8".parse().unwrap()]);
+    /// assert_eq!(ip6, vec!["2001:db8::1/128".parse().unwrap()]);
+    /// ```
+    pub fn aggregated_ips(&self) -> (Vec<IpNetwork>, Vec<IpNetwork>) {
+        let aggregated = |list: &Option<Vec<Mechanism<IpNetwork>>>| -> Vec<IpNetwork> {
+            let networks = list.as_deref().unwrap_or_default();
+            aggregate::aggregate_networks(networks)
+                .iter()
+                .map(|m| *m.as_network())
+                .collect()
+        };
+        (aggregated(&self.ip4), aggregated(&self.ip6))
+    }
+    /// Combine this Spf with `other`, returning a new Spf which holds the mechanisms of both.
+    ///
+    /// The `a`, `mx`, `include:`, `exists:`, `ip4:` and `ip6:` mechanisms of `other` follow
+    /// those of this Spf, and duplicates are removed as with [`dedup()`](Spf::dedup). The
+    /// version, `all`, `exp=` and unknown modifiers are taken from this Spf. `ptr` is taken
+    /// from `other` only when this Spf has none.
+    ///
+    /// # Errors:
+    /// - [`MergeConflict`](SpfError::MergeConflict) if either Spf has a `redirect=`.
+    /// - [`SourceLengthExceeded`](SpfError::SourceLengthExceeded) if the merged record is
+    ///   longer than 255 characters.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let first: Spf = "v=spf1 mx include:_spf.google.com -all".parse().unwrap();
+    /// let second: Spf = "v=spf1 include:_spf.google.com ip4:192.0.2.0/24 ~all".parse().unwrap();
+    /// let merged = first.merge(&second).unwrap();
+    /// assert_eq!(merged.to_string(), "v=spf1 mx include:_spf.google.com ip4:192.0.2.0/24 -all");
+    /// ```
+    pub fn merge(&self, other: &Spf) -> Result<Spf, SpfError> {
+        if self.redirect.is_some() || other.redirect.is_some() {
+            return Err(SpfError::MergeConflict(Kind::Redirect));
+        }
+        let mut merged = Spf {
+            version: self.version.clone(),
+            version_str: self.version_str.clone(),
+            a: merge_lists(&self.a, &other.a),
+            mx: merge_lists(&self.mx, &other.mx),
+            include: merge_lists(&self.include, &other.include),
+            ip4: merge_lists(&self.ip4, &other.ip4),
+            ip6: merge_lists(&self.ip6, &other.ip6),
+            ptr: self.ptr.clone().or_else(|| other.ptr.clone()),
+            exists: merge_lists(&self.exists, &other.exists),
+            all: self.all.clone(),
+            exp: self.exp.clone(),
+            unknown_modifiers: self.unknown_modifiers.clone(),
+            ..Spf::default()
+        };
+        merged.dedup();
+        if merged.to_string().len() > helpers::MAX_SPF_STRING_LENGTH {
+            return Err(SpfError::SourceLengthExceeded);
+        }
+        Ok(merged)
+    }
+    /// Rewrite the Spf into a canonical form, so that equivalent records written differently
+    /// produce the same string. See [`canonical_string()`](Spf::canonical_string)
+    ///
+    /// The following steps are taken:
+    /// 1. Mechanism and modifier names are written in lowercase, and an explicit `+`
+    ///    qualifier is removed.
+    /// 2. Domain-specs are written in lowercase. The contents of each macro, such as `%{IR}`,
+    ///    are unchanged.
+    /// 3. Duplicates are removed as with [`dedup()`](Spf::dedup)
+    /// 4. An `ip4:` host is written without `/32`, and an `ip6:` host without `/128`. Other
+    ///    networks always include their prefix length.
+    /// 5. The mechanisms of each kind are sorted, by their domain-spec or network and then by
+    ///    their qualifier. Unknown modifiers are sorted by name.
+    ///
+    /// As always, terms are grouped by kind in the order `a`, `mx`, `include:`, `ip4:`,
+    /// `ip6:`, `exists:`, `ptr`, `redirect=`, `all`, `exp=` and then unknown modifiers.
+    ///
+    /// Steps 1 to 4 do not change the result of an evaluation. Sorting, and the grouping by
+    /// kind, change the order in which mechanisms are evaluated. This only changes the result
+    /// when mechanisms with different qualifiers match the same sender.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{ParseOptions, Spf};
+    /// let options = ParseOptions::new().preserve_case(true).preserve_explicit_pass(true);
+    /// let mut spf =
+    ///     Spf::from_str_with_options("v=spf1 +MX Include:B.example.com include:a.example.com ~ALL", &options)
+    ///         .unwrap();
+    /// spf.normalize();
+    /// assert_eq!(spf.to_string(), "v=spf1 mx include:a.example.com include:b.example.com ~all");
+    /// ```
+    pub fn normalize(&mut self) {
+        let before = self.to_string();
+        for list in [
+            &mut self.a,
+            &mut self.mx,
+            &mut self.include,
+            &mut self.exists,
+        ] {
+            for mechanism in list.iter_mut().flatten() {
+                mechanism.clear_presentation();
+                mechanism.lowercase_rrdata();
+            }
+        }
+        for single in [
+            &mut self.ptr,
+            &mut self.redirect,
+            &mut self.all,
+            &mut self.exp,
+        ] {
+            for mechanism in single.iter_mut() {
+                mechanism.clear_presentation();
+                mechanism.lowercase_rrdata();
+            }
+        }
+        for list in [&mut self.ip4, &mut self.ip6] {
+            for mechanism in list.iter_mut().flatten() {
+                mechanism.clear_presentation();
+                mechanism.omit_host_prefix();
+            }
+        }
+        self.dedup();
+        for list in [
+            &mut self.a,
+            &mut self.mx,
+            &mut self.include,
+            &mut self.exists,
+        ] {
+            for mechanisms in list.iter_mut() {
+                mechanisms.sort_by_key(|m| (m.raw(), m.to_string()));
+            }
+        }
+        for list in [&mut self.ip4, &mut self.ip6] {
+            for networks in list.iter_mut() {
+                networks.sort_by_key(|m| {
+                    let network = m.as_network();
+                    (network.ip(), network.prefix(), m.to_string())
+                });
+            }
+        }
+        if let Some(modifiers) = &mut self.unknown_modifiers {
+            for (name, _) in modifiers.iter_mut() {
+                name.make_ascii_lowercase();
+            }
+            modifiers.sort();
+        }
+        // A record which was already in canonical form is left unmodified.
+        if self.to_string() != before {
+            self.modified();
+        }
+    }
+    /// Returns the canonical form of the Spf as a string, without changing the Spf. See
+    /// [`normalize()`](Spf::normalize)
+    ///
+    /// The canonical form of a record is stable across versions of this crate, so it may be
+    /// stored, hashed and compared with a string produced by a later version.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let first: Spf = "v=spf1 ip4:192.0.2.1/32 include:_SPF.example.com -all".parse().unwrap();
+    /// let second: Spf = "v=spf1 include:_spf.example.com ip4:192.0.2.1 ip4:192.0.2.1 -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(first.canonical_string(), second.canonical_string());
+    /// assert_eq!(first.canonical_string(), "v=spf1 include:_spf.example.com ip4:192.0.2.1 -all");
+    /// ```
+    pub fn canonical_string(&self) -> String {
+        let mut spf = self.clone();
+        spf.normalize();
+        spf.to_string()
+    }
+    /// Describe each term of the record in plain English, in the order of
+    /// [`iter()`](Spf::iter) followed by any unknown modifiers. See
+    /// [`Explanation`](Explanation)
+    ///
+    /// The descriptions only depend on the record, so the same record is always described in
+    /// the same words.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 -mx:example.com/24 -all".parse().unwrap();
+    /// let lines: Vec<String> = spf.explain().iter().map(|e| e.to_string()).collect();
+    /// assert_eq!(
+    ///     lines,
+    ///     [
+    ///         "-mx:example.com/24 \u{2014} reject mail from the mail servers (MX) of \
+    ///          example.com, widened to /24 for ip4 (fail; costs 1 DNS lookup)",
+    ///         "ip4:192.0.2.0/24 \u{2014} allow mail from any address in 192.0.2.0/24",
+    ///         "-all \u{2014} mail from any other source should be rejected (fail)",
+    ///     ]
+    /// );
+    /// ```
+    pub fn explain(&self) -> Vec<Explanation> {
+        explain::explain(self)
+    }
+    /// Sort the mechanisms of each kind, and the unknown modifiers, using `strategy`. The new
+    /// order is used when building a string. See [`SortStrategy`](SortStrategy)
+    ///
+    /// Mechanisms are evaluated in the order they are written, and the first match decides
+    /// the result. Sorting only changes the result of an evaluation when mechanisms with
+    /// different qualifiers match the same sender, such as `-ip4:203.0.113.1` and
+    /// `ip4:203.0.113.0/24`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{SortStrategy, Spf};
+    /// let mut spf: Spf = "v=spf1 include:b.example.com ip4:203.0.113.10 ip4:203.0.113.9 \
+    ///                     include:a.example.com -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// spf.sort_mechanisms(SortStrategy::ByKindThenValue);
+    /// assert_eq!(
+    ///     spf.to_string(),
+    ///     "v=spf1 include:a.example.com include:b.example.com ip4:203.0.113.9 \
+    ///      ip4:203.0.113.10 -all"
+    /// );
+    /// ```
+    pub fn sort_mechanisms(&mut self, strategy: SortStrategy) {
+        if strategy == SortStrategy::Preserve {
+            return;
+        }
+        let before = self.to_string();
+        for list in [
+            &mut self.a,
+            &mut self.mx,
+            &mut self.include,
+            &mut self.exists,
+        ] {
+            for mechanisms in list.iter_mut() {
+                sort::sort_terms(mechanisms, strategy);
+            }
+        }
+        for list in [&mut self.ip4, &mut self.ip6] {
+            for networks in list.iter_mut() {
+                sort::sort_networks(networks, strategy);
+            }
+        }
+        if let Some(modifiers) = &mut self.unknown_modifiers {
+            sort::sort_modifiers(modifiers, strategy);
+        }
+        // A record which was already in order is left unmodified.
+        if self.to_string() != before {
+            self.modified();
+        }
+    }
+    /// Compare this Spf with `other`, returning the terms which were added, removed or
+    /// modified. See [`SpfDiff`](SpfDiff)
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let before: Spf = "v=spf1 a ip4:192.0.2.0/24 ~all".parse().unwrap();
+    /// let after: Spf = "v=spf1 ip4:192.0.2.0/24 a -all".parse().unwrap();
+    /// let diff = before.diff(&after);
+    /// assert_eq!(diff.added().is_empty(), true);
+    /// assert_eq!(diff.to_string(), "* ~all -> -all");
+    /// assert_eq!(before.diff(&before).is_empty(), true);
+    /// ```
+    pub fn diff(&self, other: &Spf) -> SpfDiff {
+        SpfDiff::new(self, other)
+    }
+    /// Returns `true` if the Spf holds a mechanism or modifier equal to `mechanism`. The
+    /// qualifier must match and domain-specs are compared case-insensitively.
+    ///
+    /// Use [`contains_ip`](Spf::contains_ip) for `ip4:` and `ip6:`, and
+    /// [`contains_any_qualifier`](Spf::contains_any_qualifier) to ignore the qualifier.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 include:_spf.protection.outlook.com -all".parse().unwrap();
+    /// let outlook = Mechanism::include(Qualifier::Pass, "_SPF.Protection.Outlook.com").unwrap();
+    /// assert_eq!(spf.contains(&outlook), true);
+    /// assert_eq!(spf.contains(&Mechanism::all(Qualifier::SoftFail)), false);
+    /// assert_eq!(spf.contains_any_qualifier(&Mechanism::all(Qualifier::SoftFail)), true);
+    /// ```
+    pub fn contains(&self, mechanism: &Mechanism<String>) -> bool {
+        self.mechanisms_of(mechanism.kind())
+            .any(|m| m.qualifier() == mechanism.qualifier() && same_rrdata(m, mechanism))
+    }
+    /// As [`contains`](Spf::contains), ignoring the qualifier.
+    pub fn contains_any_qualifier(&self, mechanism: &Mechanism<String>) -> bool {
+        self.mechanisms_of(mechanism.kind())
+            .any(|m| same_rrdata(m, mechanism))
+    }
+    /// Returns `true` if the Spf holds an `ip4:` or `ip6:` mechanism equal to `mechanism`.
+    /// The qualifier must match.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Mechanism;
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:203.0.113.0/24 -all".parse().unwrap();
+    /// let network: Mechanism<_> = "ip4:203.0.113.0/24".parse().unwrap();
+    /// assert_eq!(spf.contains_ip(&network), true);
+    /// let network: Mechanism<_> = "-ip4:203.0.113.0/24".parse().unwrap();
+    /// assert_eq!(spf.contains_ip(&network), false);
+    /// assert_eq!(spf.contains_ip_any_qualifier(&network), true);
+    /// ```
+    pub fn contains_ip(&self, mechanism: &Mechanism<IpNetwork>) -> bool {
+        self.networks_of(mechanism.kind()).any(|m| m == mechanism)
+    }
+    /// As [`contains_ip`](Spf::contains_ip), ignoring the qualifier.
+    pub fn contains_ip_any_qualifier(&self, mechanism: &Mechanism<IpNetwork>) -> bool {
+        self.networks_of(mechanism.kind())
+            .any(|m| m.as_network() == mechanism.as_network())
+    }
+    /// Returns an iterator over the `ip4:` mechanisms followed by the `ip6:` mechanisms, in the
+    /// order they are written by [`Display`](std::fmt::Display).
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip6:2001:db8::/32 ip4:192.0.2.0/24 -ip4:192.0.2.1 -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let terms: Vec<String> = spf.iter_ip().map(|m| m.to_string()).collect();
+    /// assert_eq!(terms, ["ip4:192.0.2.0/24", "-ip4:192.0.2.1", "ip6:2001:db8::/32"]);
+    /// let spf: Spf = "v=spf1 mx -all".parse().unwrap();
+    /// assert_eq!(spf.iter_ip().next(), None);
+    /// ```
+    pub fn iter_ip(&self) -> impl Iterator<Item = &Mechanism<IpNetwork>> + '_ {
+        self.ip4.iter().flatten().chain(self.ip6.iter().flatten())
+    }
+    /// Returns the networks of the `ip4:` and `ip6:` mechanisms, whatever their qualifier, in
+    /// the order of [`iter_ip()`](Spf::iter_ip).
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all".parse().unwrap();
+    /// assert_eq!(
+    ///     spf.networks(),
+    ///     vec!["192.0.2.0/24".parse().unwrap(), "2001:db8::/32".parse().unwrap()]
+    /// );
+    /// ```
+    pub fn networks(&self) -> Vec<IpNetwork> {
+        self.iter_ip().map(|m| *m.as_network()).collect()
+    }
+    /// As [`networks()`](Spf::networks), keeping only the mechanisms with `qualifier`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Qualifier;
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 -ip4:192.0.2.1 ~all".parse().unwrap();
+    /// assert_eq!(
+    ///     spf.networks_with_qualifier(Qualifier::Fail),
+    ///     vec!["192.0.2.1/32".parse().unwrap()]
+    /// );
+    /// ```
+    pub fn networks_with_qualifier(&self, qualifier: Qualifier) -> Vec<IpNetwork> {
+        self.iter_ip()
+            .filter(|m| *m.qualifier() == qualifier)
+            .map(|m| *m.as_network())
+            .collect()
+    }
+    /// Returns the first `ip4:` or `ip6:` mechanism whose network contains `ip`, with its
+    /// qualifier. Returns `None` if no network contains `ip`.
+    ///
+    /// The mechanisms are checked in the order they are held. An ip4 address is only checked
+    /// against `ip4:` mechanisms and an ip6 address against `ip6:` mechanisms. An IPv4-mapped
+    /// ip6 address, such as `::ffff:203.0.113.25`, is checked as the ip4 address it holds.
+    ///
+    /// # Note:
+    /// No DNS lookups are made. `a`, `mx`, `include:`, `exists:`, `ptr` and `redirect=` are not
+    /// resolved, so `None` does not mean that the record does not authorise `ip`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Qualifier;
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 -ip4:203.0.113.128/25 ip4:203.0.113.0/24 ~all".parse().unwrap();
+    /// let (mechanism, qualifier) = spf.authorizes_ip("203.0.113.25".parse().unwrap()).unwrap();
+    /// assert_eq!(mechanism.to_string(), "ip4:203.0.113.0/24");
+    /// assert_eq!(qualifier, Qualifier::Pass);
+    /// let (_, qualifier) = spf.authorizes_ip("203.0.113.200".parse().unwrap()).unwrap();
+    /// assert_eq!(qualifier, Qualifier::Fail);
+    /// assert_eq!(spf.authorizes_ip("192.0.2.1".parse().unwrap()), None);
+    /// ```
+    pub fn authorizes_ip(&self, ip: IpAddr) -> Option<(&Mechanism<IpNetwork>, Qualifier)> {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            IpAddr::V4(_) => ip,
+        };
+        let networks = if ip.is_ipv4() { &self.ip4 } else { &self.ip6 };
+        networks
+            .iter()
+            .flatten()
+            .find(|m| m.as_network().contains(ip))
+            .map(|m| (m, *m.qualifier()))
+    }
+    /// Returns each pair of `ip4:` and `ip6:` mechanisms whose networks share addresses, such
+    /// as `ip4:10.0.0.0/8 -ip4:10.1.0.0/16`. See [`IpOverlap`](IpOverlap)
+    ///
+    /// The mechanisms are compared in the order they are evaluated, which is the order of
+    /// [`iter()`](Spf::iter). A pair is listed once, in the order of its later mechanism.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{IpRelation, Spf};
+    /// let spf: Spf = "v=spf1 ip6:2001:db8:1::/48 ip6:2001:db8::/32 ip4:192.0.2.0/24 -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let overlaps = spf.find_ip_overlaps();
+    /// assert_eq!(overlaps.len(), 1);
+    /// assert_eq!(overlaps[0].relation(), IpRelation::Covers);
+    /// assert_eq!(overlaps[0].is_unreachable(), false);
+    /// assert_eq!(overlaps[0].is_conflict(), false);
+    /// ```
+    pub fn find_ip_overlaps(&self) -> Vec<IpOverlap<'_>> {
+        IpOverlap::find(self)
+    }
+    /// Compare the addresses authorised by this record with those authorised by `other`,
+    /// finding the networks of this record which `other` does not authorise. See
+    /// [`SubsetResult`](SubsetResult)
+    ///
+    /// A network is authorised by an `ip4:` or `ip6:` with a pass or neutral qualifier, and
+    /// every address by such an `all`. The order of the terms is not considered, so a network
+    /// which an earlier failing term overrides is still authorised.
+    ///
+    /// Terms which need DNS lookups are not resolved. They are listed as unknown in the
+    /// result, and any addresses they authorise are not compared.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let old: Spf = "v=spf1 ip4:192.0.2.0/25 ip4:192.0.2.128/25 ~all".parse().unwrap();
+    /// let new: Spf = "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 -all".parse().unwrap();
+    /// assert_eq!(old.is_subset_of(&new).is_subset(), true);
+    /// assert_eq!(
+    ///     new.is_subset_of(&old).uncovered(),
+    ///     ["198.51.100.0/24".parse().unwrap()]
+    /// );
+    /// ```
+    pub fn is_subset_of(&self, other: &Spf) -> SubsetResult {
+        subset::compare(self, other)
+    }
+    /// Returns the number of addresses authorised, or otherwise given a result, by the
+    /// `ip4:` and `ip6:` networks of the record. See [`IpSpaceStats`](IpSpaceStats)
+    ///
+    /// Addresses held by more than one network are counted once. ip6 space is counted in
+    /// `/64` networks.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:0.0.0.0/0 ip4:192.0.2.0/24 ip6:2001:db8::1 -all".parse().unwrap();
+    /// let stats = spf.ip_space();
+    /// assert_eq!(stats.total().ipv4_addresses(), 4_294_967_296);
+    /// assert_eq!(stats.total().ipv6_64s(), 1);
+    /// assert_eq!(stats.is_overly_broad(8, 32), true);
+    /// ```
+    pub fn ip_space(&self) -> IpSpaceStats {
+        space::stats(self)
+    }
+    /// Returns the counts of mechanisms and qualifiers, the lookup count, length and number of
+    /// warnings of the record in one [`SpfSummary`](SpfSummary). No DNS lookups are made.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a mx -ip4:192.0.2.1 redirect=_spf.example.com".parse().unwrap();
+    /// let summary = spf.summary();
+    /// assert_eq!(summary.lookups(), spf.lookup_count());
+    /// assert_eq!(summary.length(), spf.serialized_len());
+    /// assert_eq!(summary.has_redirect(), true);
+    /// assert_eq!(summary.has_exp(), false);
+    /// ```
+    pub fn summary(&self) -> SpfSummary {
+        summary::summary(self)
+    }
+    /// Returns the number of DNS lookups the record needs. Each `a`, `mx`, `include:`,
+    /// `exists:`, `ptr` and `redirect=` term counts as one lookup. See RFC 7208 section 4.6.4
+    ///
+    /// A record may need at most 10 lookups.
+    ///
+    /// # Note:
+    /// No DNS lookups are made. The lookups needed by the record of an `include:` or
+    /// `redirect=` are not counted, so the number needed to evaluate the record may be higher.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a mx include:_spf.google.com ip4:192.0.2.0/24 \
+    ///                 exists:%{i}.example.com ptr -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(spf.lookup_count(), 5);
+    /// ```
+    pub fn lookup_count(&self) -> usize {
+        let terms = [&self.a, &self.mx, &self.include, &self.exists];
+        terms.iter().copied().flatten().map(Vec::len).sum::<usize>()
+            + usize::from(self.ptr.is_some())
+            + usize::from(self.redirect.is_some())
+    }
+    /// Returns each term of the record, in the order of [`iter()`](Spf::iter), with the DNS
+    /// lookups it needs. See [`LookupItem`](LookupItem)
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a ip4:192.0.2.0/24 exists:%{i}.example.com -all".parse().unwrap();
+    /// let costs: Vec<(String, bool)> = spf
+    ///     .lookup_breakdown()
+    ///     .iter()
+    ///     .map(|item| (item.mechanism().to_string(), item.costs_lookup()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     costs,
+    ///     vec![
+    ///         ("a".to_string(), true),
+    ///         ("ip4:192.0.2.0/24".to_string(), false),
+    ///         ("exists:%{i}.example.com".to_string(), true),
+    ///         ("-all".to_string(), false),
+    ///     ]
+    /// );
+    /// ```
+    pub fn lookup_breakdown(&self) -> Vec<LookupItem<'_>> {
+        self.iter()
+            .enumerate()
+            .map(|(index, m)| LookupItem::new(m, index))
+            .collect()
+    }
+    /// Returns the DNS lookups of the record together with those of every record it includes
+    /// or redirects to, found through `source`. RFC 7208 limits the lookups of the whole
+    /// evaluation, including nested records, to 10. See [`LookupReport`](LookupReport)
+    ///
+    /// The lookups of each record are counted as by [`lookup_count()`](Spf::lookup_count).
+    /// Records are followed until the limit is passed. A record which can not be found is
+    /// noted in the report, see [`LookupReport::missing()`](LookupReport::missing), and the
+    /// lookup of its `include:` or `redirect=` is still counted. A domain-spec with macros is
+    /// counted but not followed.
+    ///
+    /// # Errors:
+    /// - [`IncludeCycle`](SpfError::IncludeCycle) if a record includes or redirects to a record
+    ///   above it.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{ResolutionMap, Spf};
+    /// let mut records = ResolutionMap::new();
+    /// records.insert_record("_spf.example.net", "v=spf1 a mx -all".parse().unwrap());
+    /// let spf: Spf = "v=spf1 a include:_spf.example.net include:_spf.example.org -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(spf.lookup_count(), 3);
+    /// let report = spf.total_lookup_count(&records).unwrap();
+    /// assert_eq!(report.total(), 5);
+    /// assert_eq!(report.missing(), vec!["_spf.example.org"]);
+    ///```
+    pub fn total_lookup_count<S: SpfSource + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<LookupReport, SpfError> {
+        lookup::report(self, source)
+    }
+    /// Returns the number of `exists:`, `include:` and `redirect=` terms, whose lookups may be
+    /// for a name which does not exist. RFC 7208 section 4.6.4 allows at most 2 such void
+    /// lookups. See [`LookupItem::may_be_void()`](LookupItem::may_be_void)
+    pub fn potential_void_lookups(&self) -> usize {
+        self.lookup_breakdown()
+            .iter()
+            .filter(|item| item.may_be_void())
+            .count()
+    }
+    /// Returns a new Spf in which `a`, `mx`, `include:` and `redirect=` are replaced with the
+    /// `ip4:` and `ip6:` networks they refer to, so that no DNS lookups are needed. The DNS
+    /// data is taken from `resolutions`. No DNS lookups are made.
+    ///
+    /// The order of evaluation and the qualifiers are kept. The networks of an included
+    /// record take the qualifier of the `include:`, and only its terms which pass are kept, as
+    /// any other result only stops the `include:` from matching. A term which does not pass is
+    /// only dropped when no term which passes follows it. The `all` of the record, or of
+    /// the record it redirects to, is kept, as are `exp=` and unknown modifiers. Repeated
+    /// networks are removed as with [`dedup()`](Spf::dedup).
+    ///
+    /// A record longer than 255 characters is still returned, with a
+    /// [`RecordTooLong`](Warning::RecordTooLong) warning in
+    /// [`parse_warnings()`](Spf::parse_warnings).
+    ///
+    /// # Errors:
+    /// - [`MissingRecord`](FlattenError::MissingRecord) or
+    ///   [`MissingAddresses`](FlattenError::MissingAddresses) if the map lacks data needed.
+    /// - [`IncludeCycle`](FlattenError::IncludeCycle) if a record includes itself.
+    /// - [`NoDomain`](FlattenError::NoDomain) if a term without a domain-spec is found and no
+    ///   domain was set with [`ResolutionMap::set_domain()`](ResolutionMap::set_domain).
+    /// - [`Unflattenable`](FlattenError::Unflattenable) for `exists:`, `ptr` and domain-specs
+    ///   with macros, which depend on the message being checked, and for a term of an included
+    ///   record which does not pass but is followed by a term which does.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{ResolutionMap, Spf};
+    /// let spf: Spf = "v=spf1 a mx:example.net include:_spf.example.org ~all".parse().unwrap();
+    /// let mut resolutions = ResolutionMap::new();
+    /// resolutions.set_domain("example.com");
+    /// resolutions.insert_a("example.com", vec!["192.0.2.10".parse().unwrap()]);
+    /// resolutions.insert_mx("example.net", vec!["2001:db8::25".parse().unwrap()]);
+    /// resolutions.insert_record(
+    ///     "_spf.example.org",
+    ///     "v=spf1 ip4:198.51.100.0/24 -all".parse().unwrap(),
+    /// );
+    /// let flattened = spf.flatten(&resolutions).unwrap();
+    /// assert_eq!(
+    ///     flattened.to_string(),
+    ///     "v=spf1 ip4:192.0.2.10/32 ip4:198.51.100.0/24 ip6:2001:db8::25/128 ~all"
+    /// );
+    /// assert_eq!(flattened.lookup_count(), 0);
+    /// ```
+    pub fn flatten(&self, resolutions: &ResolutionMap) -> Result<Spf, FlattenError> {
+        flatten::flatten(self, resolutions)
+    }
+    /// Expand the `include:` and `redirect=` terms of the record into a tree of the records
+    /// they refer to, found through `source`. The tree lists every record the evaluation of
+    /// this record may depend on.
+    ///
+    /// A record is at depth one if it is referred to by this record, two if it is referred to
+    /// by a record at depth one, and so on.
+    ///
+    /// # Errors:
+    /// - [`Cycle`](ExpandError::Cycle) if a record refers to a record above it.
+    /// - [`DepthExceeded`](ExpandError::DepthExceeded) if a record is deeper than `max_depth`.
+    /// - [`HasMacros`](ExpandError::HasMacros) if the domain-spec of a term has macros.
+    /// - [`Fetch`](ExpandError::Fetch) if the record of a domain is not found.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{ResolutionMap, Spf};
+    /// let mut records = ResolutionMap::new();
+    /// records.insert_record("a.example.com", "v=spf1 include:b.example.com -all".parse().unwrap());
+    /// records.insert_record("b.example.com", "v=spf1 include:a.example.com -all".parse().unwrap());
+    /// let spf: Spf = "v=spf1 include:a.example.com -all".parse().unwrap();
+    /// let err = spf.expand(&records, 10).unwrap_err();
+    /// assert_eq!(err.path().unwrap(), ["a.example.com", "b.example.com", "a.example.com"]);
+    /// assert_eq!(spf.expand(&records, 1).unwrap_err().is_depth_exceeded(), true);
+    ///```
+    pub fn expand<S: SpfSource + ?Sized>(
+        &self,
+        source: &S,
+        max_depth: usize,
+    ) -> Result<SpfTree, ExpandError> {
+        expand::expand(self, source, max_depth)
+    }
+    /// Returns the explanation given by the `exp=` modifier of the record, which a receiver
+    /// may show to the sender of a message which fails. See RFC 7208 section 6.2
+    ///
+    /// The domain-spec of `exp=` is expanded with `context` and its TXT record looked up
+    /// through `resolver`. The macros of the explanation are then expanded, which may use
+    /// `%{c}`, `%{r}` and `%{t}`. See
+    /// [`MacroContext::expand_explanation()`](MacroContext::expand_explanation)
+    ///
+    /// Returns `None` when there is no `exp=`. As the RFC requires, `None` is also returned
+    /// when the lookup fails, when there is not exactly one TXT record, or when the
+    /// explanation is not valid or not 7-bit ASCII.
+    ///
+    /// # Errors:
+    /// [`InvalidMacro`](SpfError::InvalidMacro) when the domain-spec of `exp=` can not be
+    /// expanded.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::evaluate::{LookupError, SpfResolver};
+    /// use decon_spf::mechanism::MacroContext;
+    /// use decon_spf::Spf;
+    /// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    ///
+    /// struct Resolver;
+    ///
+    /// impl SpfResolver for Resolver {
+    ///     fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+    ///         match name {
+    ///             "explain.example.com" => Ok(vec!["%{i} may not send for %{d}".to_string()]),
+    ///             _ => Ok(vec![]),
+    ///         }
+    ///     }
+    ///     // The other lookups are not used.
+    /// #   fn lookup_a(&self, _: &str) -> Result<Vec<Ipv4Addr>, LookupError> { Ok(vec![]) }
+    /// #   fn lookup_aaaa(&self, _: &str) -> Result<Vec<Ipv6Addr>, LookupError> { Ok(vec![]) }
+    /// #   fn lookup_mx(&self, _: &str) -> Result<Vec<String>, LookupError> { Ok(vec![]) }
+    /// #   fn lookup_ptr(&self, _: IpAddr) -> Result<Vec<String>, LookupError> { Ok(vec![]) }
+    /// }
+    ///
+    /// let spf: Spf = "v=spf1 mx -all exp=explain.%{d}".parse().unwrap();
+    /// let context = MacroContext::new("user@example.com", "192.0.2.3".parse().unwrap(), "");
+    /// assert_eq!(
+    ///     spf.explanation(&Resolver, &context).unwrap(),
+    ///     Some("192.0.2.3 may not send for example.com".to_string())
+    /// );
+    ///```
+    pub fn explanation(
+        &self,
+        resolver: &dyn SpfResolver,
+        context: &MacroContext,
+    ) -> Result<Option<String>, SpfError> {
+        explanation::explanation(self, resolver, context)
+    }
+    /// Returns each domain the record refers to, once, in the order of [`iter()`](Spf::iter).
+    ///
+    /// These are the domain-specs of `a`, `mx`, `include:`, `exists:`, `ptr`, `redirect=` and
+    /// `exp=`, without any cidr suffix. `a`, `mx` and `ptr` without a domain-spec refer to the
+    /// domain being checked and are left out. Domains are compared case-insensitively and the
+    /// first is kept.
+    ///
+    /// A domain-spec containing macros is left out, as the domain it refers to is only known
+    /// when the record is evaluated. Use [`domains_with_macros()`](Spf::domains_with_macros)
+    /// to include them as written.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a a:example.com/28 mx:Example.com include:_spf.example.net \
+    ///                 exists:%{i}._spf.example.com -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(spf.domains(), vec!["example.com", "_spf.example.net"]);
+    /// assert_eq!(
+    ///     spf.domains_with_macros(),
+    ///     vec!["example.com", "_spf.example.net", "%{i}._spf.example.com"]
+    /// );
+    /// ```
+    pub fn domains(&self) -> Vec<&str> {
+        self.referenced_domains(false)
+    }
+    /// As [`domains()`](Spf::domains), including domain-specs which contain macros as they
+    /// are written.
+    pub fn domains_with_macros(&self) -> Vec<&str> {
+        self.referenced_domains(true)
+    }
+    /// Returns each mechanism and `redirect=` whose domain-spec is `domain`, in the order of
+    /// [`iter()`](Spf::iter). Domains are compared case-insensitively, ignoring a trailing
+    /// dot. `a`, `mx` and `ptr` without a domain-spec, and `exp=`, are not matched.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a:mail.example.com/28 include:Example.com \
+    ///                 exists:%{i}._spf.example.com -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let found = spf.mechanisms_referencing("example.com");
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].to_string(), "include:Example.com");
+    /// assert_eq!(spf.mechanisms_referencing_subdomains("example.com").len(), 3);
+    /// ```
+    pub fn mechanisms_referencing(&self, domain: &str) -> Vec<MechanismRef<'_>> {
+        self.referencing(domain, false)
+    }
+    /// As [`mechanisms_referencing()`](Spf::mechanisms_referencing), also matching the
+    /// subdomains of `domain`. `example.com` matches `mail.example.com`, but not
+    /// `mailexample.com`.
+    pub fn mechanisms_referencing_subdomains(&self, domain: &str) -> Vec<MechanismRef<'_>> {
+        self.referencing(domain, true)
+    }
+    fn referencing(&self, domain: &str, subdomains: bool) -> Vec<MechanismRef<'_>> {
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        let suffix = format!(".{}", domain);
+        self.find(|m| match m {
+            MechanismRef::TXT(mechanism) if *mechanism.kind() != Kind::Exp => {
+                mechanism.domain().is_some_and(|target| {
+                    let target = target.trim_end_matches('.').to_ascii_lowercase();
+                    target == domain || (subdomains && target.ends_with(&suffix))
+                })
+            }
+            _ => false,
+        })
+    }
+    fn referenced_domains(&self, with_macros: bool) -> Vec<&str> {
+        let mut domains: Vec<&str> = Vec::new();
+        let mechanisms = self.iter().filter_map(|m| m.txt());
+        for mechanism in mechanisms.filter(|m| with_macros || !m.has_macros()) {
+            if let Some(domain) = mechanism.domain() {
+                if !domains.iter().any(|d| d.eq_ignore_ascii_case(domain)) {
+                    domains.push(domain);
+                }
+            }
+        }
+        domains
+    }
+    // The `Mechanism<String>` of `kind` held by the Spf.
+    fn mechanisms_of(&self, kind: &Kind) -> Box<dyn Iterator<Item = &Mechanism<String>> + '_> {
+        match kind {
+            Kind::A => Box::new(self.a.iter().flatten()),
+            Kind::MX => Box::new(self.mx.iter().flatten()),
+            Kind::Include => Box::new(self.include.iter().flatten()),
+            Kind::Exists => Box::new(self.exists.iter().flatten()),
+            Kind::Ptr => Box::new(self.ptr.iter()),
+            Kind::Redirect => Box::new(self.redirect.iter()),
+            Kind::All => Box::new(self.all.iter()),
+            Kind::Exp => Box::new(self.exp.iter()),
+            Kind::IpV4 | Kind::IpV6 => Box::new(std::iter::empty()),
+        }
+    }
+    // The `Mechanism<IpNetwork>` of `kind` held by the Spf.
+    fn networks_of(&self, kind: &Kind) -> Box<dyn Iterator<Item = &Mechanism<IpNetwork>> + '_> {
+        match kind {
+            Kind::IpV4 => Box::new(self.ip4.iter().flatten()),
+            Kind::IpV6 => Box::new(self.ip6.iter().flatten()),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+    /// Remove a single mechanism or modifier which is equal to `mechanism`. Returns `true` if
+    /// a mechanism was removed. When the last mechanism of a [`Kind`](Kind) is removed its
+    /// accessor returns `None`, as after [`clear_mechanism`](Spf::clear_mechanism).
+    ///
+    /// Use [`remove_ip_mechanism`](Spf::remove_ip_mechanism) for `ip4:` and `ip6:`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 include:a.example.com include:old-provider.example.net -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let old = Mechanism::include(Qualifier::Pass, "old-provider.example.net").unwrap();
+    /// assert_eq!(spf.remove_mechanism(&old), true);
+    /// assert_eq!(spf.remove_mechanism(&old), false);
+    /// assert_eq!(spf.to_string(), "v=spf1 include:a.example.com -all");
+    /// ```
+    pub fn remove_mechanism(&mut self, mechanism: &Mechanism<String>) -> bool {
+        let removed = match mechanism.kind() {
+            Kind::A => remove_from(&mut self.a, mechanism),
+            Kind::MX => remove_from(&mut self.mx, mechanism),
+            Kind::Include => remove_from(&mut self.include, mechanism),
+            Kind::Exists => remove_from(&mut self.exists, mechanism),
+            Kind::Redirect => {
+                let removed = remove_single(&mut self.redirect, mechanism);
+                if removed {
+                    self.is_redirected = false;
+                }
+                removed
+            }
+            Kind::Ptr => remove_single(&mut self.ptr, mechanism),
+            Kind::All => remove_single(&mut self.all, mechanism),
+            Kind::Exp => remove_single(&mut self.exp, mechanism),
+            Kind::IpV4 | Kind::IpV6 => false,
+        };
+        if removed {
+            self.modified();
+        }
+        removed
+    }
+    /// Remove a single `ip4:` or `ip6:` mechanism which is equal to `mechanism`. Returns `true`
+    /// if a mechanism was removed.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Mechanism;
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 -all".parse().unwrap();
+    /// let network: Mechanism<_> = "ip4:192.0.2.0/24".parse().unwrap();
+    /// assert_eq!(spf.remove_ip_mechanism(&network), true);
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:198.51.100.0/24 -all");
+    /// ```
+    pub fn remove_ip_mechanism(&mut self, mechanism: &Mechanism<IpNetwork>) -> bool {
+        let removed = match mechanism.kind() {
+            Kind::IpV4 => remove_from(&mut self.ip4, mechanism),
+            Kind::IpV6 => remove_from(&mut self.ip6, mechanism),
+            _ => false,
+        };
+        if removed {
+            self.modified();
+        }
+        removed
+    }
+    /// Replace the mechanism at `index` within the mechanisms of `kind`, returning the
+    /// mechanism which was replaced. The order of the other mechanisms is unchanged.
+    ///
+    /// `ptr`, `all`, `redirect=` and `exp=` occur at most once and only have index `0`.
+    /// Use [`replace_ip_mechanism`](Spf::replace_ip_mechanism) for `ip4:` and `ip6:`.
+    ///
+    /// # Errors:
+    /// - [`KindMismatch`](SpfError::KindMismatch) if `mechanism` is not of `kind`.
+    /// - [`IndexOutOfBounds`](SpfError::IndexOutOfBounds) if there is no mechanism of `kind`
+    ///   at `index`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Kind, Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 include:a.example.com include:b.example.com -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let new = Mechanism::include(Qualifier::Pass, "c.example.com").unwrap();
+    /// let old = spf.replace_mechanism(Kind::Include, 0, new).unwrap();
+    /// assert_eq!(old.raw(), "a.example.com");
+    /// assert_eq!(spf.to_string(), "v=spf1 include:c.example.com include:b.example.com -all");
+    /// ```
+    pub fn replace_mechanism(
+        &mut self,
+        kind: Kind,
+        index: usize,
+        mechanism: Mechanism<String>,
+    ) -> Result<Mechanism<String>, SpfError> {
+        if *mechanism.kind() != kind {
+            return Err(SpfError::KindMismatch {
+                expected: kind,
+                found: *mechanism.kind(),
+            });
+        }
+        let replaced = match kind {
+            Kind::A => replace_in(&mut self.a, index, mechanism),
+            Kind::MX => replace_in(&mut self.mx, index, mechanism),
+            Kind::Include => replace_in(&mut self.include, index, mechanism),
+            Kind::Exists => replace_in(&mut self.exists, index, mechanism),
+            Kind::Redirect => replace_single(&mut self.redirect, index, mechanism),
+            Kind::Ptr => replace_single(&mut self.ptr, index, mechanism),
+            Kind::All => replace_single(&mut self.all, index, mechanism),
+            Kind::Exp => replace_single(&mut self.exp, index, mechanism),
+            // Networks are only held as `Mechanism<IpNetwork>`.
+            Kind::IpV4 | Kind::IpV6 => Err(SpfError::IndexOutOfBounds {
+                kind,
+                index,
+                len: 0,
+            }),
+        };
+        if replaced.is_ok() {
+            self.modified();
+        }
+        replaced
+    }
+    /// Replace the `ip4:` or `ip6:` mechanism at `index`, returning the mechanism which was
+    /// replaced. `index` is within the mechanisms of the same kind as `mechanism`.
+    ///
+    /// # Errors:
+    /// - [`IndexOutOfBounds`](SpfError::IndexOutOfBounds) if there is no mechanism of that
+    ///   kind at `index`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Mechanism;
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip4:203.0.113.0/24 -all".parse().unwrap();
+    /// let new: Mechanism<_> = "ip4:198.51.100.0/24".parse().unwrap();
+    /// let old = spf.replace_ip_mechanism(1, new).unwrap();
+    /// assert_eq!(old.raw(), "203.0.113.0/24");
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 -all");
+    /// ```
+    pub fn replace_ip_mechanism(
+        &mut self,
+        index: usize,
+        mechanism: Mechanism<IpNetwork>,
+    ) -> Result<Mechanism<IpNetwork>, SpfError> {
+        let replaced = match mechanism.kind() {
+            Kind::IpV4 => replace_in(&mut self.ip4, index, mechanism),
+            _ => replace_in(&mut self.ip6, index, mechanism),
+        };
+        if replaced.is_ok() {
+            self.modified();
+        }
+        replaced
+    }
+
+    fn append_mechanism_of_a(&mut self, mechanism: Mechanism<String>) {
+        self.modified();
+        if let Some(a) = &mut self.a {
+            a.push(mechanism);
+        } else {
+            self.a = Some(vec![mechanism]);
+        }
+    }
+    fn append_mechanism_of_mx(&mut self, mechanism: Mechanism<String>) {
+        self.modified();
+        if let Some(mx) = &mut self.mx {
+            mx.push(mechanism);
+        } else {
+            self.mx = Some(vec![mechanism]);
         }
     }
     fn append_mechanism_of_include(&mut self, mechanism: Mechanism<String>) {
+        self.modified();
         if let Some(include) = &mut self.include {
             include.push(mechanism);
         } else {
@@ -365,6 +2409,7 @@ impl Spf {
         }
     }
     fn append_mechanism_of_ip4(&mut self, mechanism: Mechanism<IpNetwork>) {
+        self.modified();
         if let Some(ip4) = &mut self.ip4 {
             ip4.push(mechanism);
         } else {
@@ -372,6 +2417,7 @@ impl Spf {
         }
     }
     fn append_mechanism_of_ip6(&mut self, mechanism: Mechanism<IpNetwork>) {
+        self.modified();
         if let Some(ip6) = &mut self.ip6 {
             ip6.push(mechanism);
         } else {
@@ -379,6 +2425,7 @@ impl Spf {
         }
     }
     fn append_mechanism_of_exists(&mut self, mechanism: Mechanism<String>) {
+        self.modified();
         if let Some(exists) = &mut self.exists {
             exists.push(mechanism);
         } else {
@@ -386,32 +2433,45 @@ impl Spf {
         }
     }
     fn append_mechanism_of_ptr(&mut self, mechanism: Mechanism<String>) {
+        self.modified();
         self.ptr = Some(mechanism);
     }
     fn append_mechanism_of_all(&mut self, mechanism: Mechanism<String>) {
-        if self.redirect.is_none() {
-            self.all = Some(mechanism);
-        }
+        self.modified();
+        self.all = Some(mechanism);
+    }
+    fn append_mechanism_of_exp(&mut self, mechanism: Mechanism<String>) {
+        self.modified();
+        self.exp = Some(mechanism);
     }
     /// Appends the passed `Mechanism<String>` to the SPF struct.
-    /// This only works for Mechanism which are *NOT* `ip4:` or `ip6:`
+    /// This is intended for Mechanism which are *NOT* `ip4:` or `ip6:`. See
+    /// [`append_ip_mechanism`](Spf::append_ip_mechanism)
     ///
     /// # Example:
     /// ```
     /// use decon_spf::mechanism::{Qualifier, Mechanism};
     /// use decon_spf::Spf;
-    /// let mut new_spf_record = Spf::new();
-    /// new_spf_record.set_v1();
+    /// let mut new_spf_record = Spf::new_v1();
     /// new_spf_record.append_mechanism(Mechanism::new_redirect(Qualifier::Pass,
     ///                                 "_spf.example.com".to_string()));
     /// new_spf_record.append_mechanism(Mechanism::all(Qualifier::Pass));
-    /// assert_eq!(new_spf_record.to_string(), "v=spf1 redirect=_spf.example.com".to_string());
+    /// assert_eq!(new_spf_record.to_string(), "v=spf1 all redirect=_spf.example.com".to_string());
     /// ```
     ///
     /// # Note:
-    /// If the Spf is already set as `Redirect` trying to append an `All`
-    /// Mechanism will have no affect.
-    // Consider make this a Result
+    /// `All` and `Redirect` may both be appended. Whether a record may hold both is decided
+    /// when it is validated. See [`validate()`](Spf::validate)
+    ///
+    /// `Redirect` and `Exp` modifiers may only occur once. Appending either of these replaces
+    /// any existing modifier of the same kind.
+    ///
+    /// A mechanism of kind `ip4:` or `ip6:` has its value parsed as an ip network and is then
+    /// appended as with [`append_ip_mechanism`](Spf::append_ip_mechanism). It is not appended
+    /// if the value is not a network of the same family.
+    ///
+    /// See [`try_append_mechanism`](Spf::try_append_mechanism) to be told when a mechanism is
+    /// not appended.
     pub fn append_mechanism(&mut self, mechanism: Mechanism<String>) {
         match mechanism.kind() {
             Kind::Redirect => self.append_mechanism_of_redirect(mechanism),
@@ -421,8 +2481,284 @@ impl Spf {
             Kind::Exists => self.append_mechanism_of_exists(mechanism),
             Kind::Ptr => self.append_mechanism_of_ptr(mechanism),
             Kind::All => self.append_mechanism_of_all(mechanism),
+            Kind::Exp => self.append_mechanism_of_exp(mechanism),
+            Kind::IpV4 | Kind::IpV6 => {
+                if let Ok(network) = network_from_string(&mechanism) {
+                    self.append_ip_mechanism(network);
+                }
+            }
+        }
+    }
+    /// Appends the passed `Mechanism<String>` to the SPF struct, returning an error instead of
+    /// ignoring or replacing a mechanism as [`append_mechanism`](Spf::append_mechanism) does.
+    /// Nothing is appended when an error is returned.
+    ///
+    /// A mechanism of kind `ip4:` or `ip6:` must hold a network of the same family. It is
+    /// appended as with [`try_append_ip_mechanism`](Spf::try_append_ip_mechanism), otherwise an
+    /// [`InvalidMechanism`](SpfError::InvalidMechanism) is returned.
+    ///
+    /// For other kinds the checks are made in the following order:
+    /// 1. The mechanism must be written as a valid term. A missing or unexpected value is an
+    ///    [`InvalidMechanism`](SpfError::InvalidMechanism).
+    /// 2. `redirect=` and `exp=` may only be appended once. See
+    ///    [`ModifierMayOccurOnlyOnce`](SpfError::ModifierMayOccurOnlyOnce)
+    /// 3. `all` and `ptr` may only be appended once. See
+    ///    [`MechanismAlreadyPresent`](SpfError::MechanismAlreadyPresent)
+    /// 4. The record may not grow past its [`max_length()`](Spf::max_length). See
+    ///    [`SourceLengthExceeded`](SpfError::SourceLengthExceeded)
+    ///
+    /// Both `all` and `redirect=` may be appended. Whether a record may hold both depends on
+    /// the standard it is validated against. See [`validate()`](Spf::validate)
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Kind, Mechanism, Qualifier};
+    /// use decon_spf::{Spf, SpfError};
+    /// let mut spf = Spf::new_v1();
+    /// spf.try_append_mechanism(Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap())
+    ///     .unwrap();
+    /// let redirect = Mechanism::redirect(Qualifier::Pass, "_spf.example.net").unwrap();
+    /// let err = spf.try_append_mechanism(redirect);
+    /// assert_eq!(err.unwrap_err(), SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect));
+    /// spf.try_append_mechanism(Mechanism::all(Qualifier::Fail)).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 -all redirect=_spf.example.com");
+    /// ```
+    pub fn try_append_mechanism(&mut self, mechanism: Mechanism<String>) -> Result<(), SpfError> {
+        if mechanism.kind().is_ip() {
+            return self.try_append_ip_mechanism(network_from_string(&mechanism)?);
+        }
+        if let Err(error) = mechanism.to_string().parse::<Mechanism<String>>() {
+            return Err(SpfError::InvalidMechanism(error));
+        }
+        match mechanism.kind() {
+            Kind::Redirect if self.redirect.is_some() => {
+                return Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect))
+            }
+            Kind::Exp if self.exp.is_some() => {
+                return Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Exp))
+            }
+            Kind::All if self.all.is_some() => {
+                return Err(SpfError::MechanismAlreadyPresent(Kind::All))
+            }
+            Kind::Ptr if self.ptr.is_some() => {
+                return Err(SpfError::MechanismAlreadyPresent(Kind::Ptr))
+            }
             _ => {}
         }
+        self.check_room(&mechanism.to_string())?;
+        self.append_mechanism(mechanism);
+        Ok(())
+    }
+    /// Set the `redirect=` modifier, replacing any existing `redirect=`.
+    ///
+    /// # Errors:
+    /// - [`KindMismatch`](SpfError::KindMismatch) if `mechanism` is not a `redirect=`.
+    /// - [`InvalidMechanism`](SpfError::InvalidMechanism) if `mechanism` is not written as a
+    ///   valid term.
+    ///
+    /// An existing `all` is kept. Use [`take_all()`](Spf::take_all) first to replace `all`
+    /// with a `redirect=`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a redirect=old.example.com".parse().unwrap();
+    /// spf.set_redirect(Mechanism::redirect(Qualifier::Pass, "new.example.com").unwrap())
+    ///     .unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a redirect=new.example.com");
+    /// let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+    /// let redirect = Mechanism::redirect(Qualifier::Pass, "new.example.com").unwrap();
+    /// spf.set_redirect(redirect).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a -all redirect=new.example.com");
+    /// ```
+    pub fn set_redirect(&mut self, mechanism: Mechanism<String>) -> Result<(), SpfError> {
+        check_single(&mechanism, Kind::Redirect)?;
+        self.modified();
+        self.redirect = Some(mechanism);
+        self.is_redirected = true;
+        Ok(())
+    }
+    /// Set the `all` mechanism, replacing any existing `all`.
+    ///
+    /// # Errors:
+    /// - [`KindMismatch`](SpfError::KindMismatch) if `mechanism` is not `all`.
+    ///
+    /// An existing `redirect=` is kept. Use [`take_redirect()`](Spf::take_redirect) first to
+    /// replace a `redirect=` with `all`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a ~all".parse().unwrap();
+    /// spf.set_all(Mechanism::all(Qualifier::Fail)).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a -all");
+    /// ```
+    pub fn set_all(&mut self, mechanism: Mechanism<String>) -> Result<(), SpfError> {
+        check_single(&mechanism, Kind::All)?;
+        self.modified();
+        self.all = Some(mechanism);
+        Ok(())
+    }
+    /// Remove and return the `redirect=` modifier.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+    /// let redirect = spf.take_redirect().unwrap();
+    /// assert_eq!(redirect.raw(), "_spf.example.com");
+    /// assert_eq!(spf.is_redirect(), false);
+    /// spf.set_all(Mechanism::all(Qualifier::Fail)).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a -all");
+    /// ```
+    pub fn take_redirect(&mut self) -> Option<Mechanism<String>> {
+        if self.redirect.is_some() {
+            self.modified();
+        }
+        self.is_redirected = false;
+        self.redirect.take()
+    }
+    /// Change the target of the existing `redirect=` modifier in place. The rest of the record,
+    /// including the case in which `redirect` is written, is unchanged.
+    ///
+    /// # Errors:
+    /// - [`InvalidMechanism`](SpfError::InvalidMechanism) if `redirect=domain` is not a valid
+    ///   term. e.g. `domain` is empty or contains whitespace.
+    /// - [`IndexOutOfBounds`](SpfError::IndexOutOfBounds) if there is no `redirect=`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a redirect=_spf.oldvendor.com".parse().unwrap();
+    /// spf.set_redirect_domain("_spf.newvendor.com").unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a redirect=_spf.newvendor.com");
+    /// assert_eq!(spf.is_redirect(), true);
+    /// ```
+    pub fn set_redirect_domain(&mut self, domain: &str) -> Result<(), SpfError> {
+        check_single(
+            &Mechanism::redirect(Qualifier::Pass, domain)?,
+            Kind::Redirect,
+        )?;
+        match self.redirect.as_mut() {
+            Some(redirect) => redirect.set_rrdata(Some(domain.to_string()))?,
+            None => {
+                return Err(SpfError::IndexOutOfBounds {
+                    kind: Kind::Redirect,
+                    index: 0,
+                    len: 0,
+                })
+            }
+        }
+        self.modified();
+        Ok(())
+    }
+    /// Remove and return the `all` mechanism.
+    pub fn take_all(&mut self) -> Option<Mechanism<String>> {
+        if self.all.is_some() {
+            self.modified();
+        }
+        self.all.take()
+    }
+    /// Build an Spf with `version` from a sequence of mechanisms. `ip4:` and `ip6:` mechanisms
+    /// are appended as with [`try_append_ip_mechanism`](Spf::try_append_ip_mechanism), and all
+    /// others as with [`try_append_mechanism`](Spf::try_append_mechanism).
+    ///
+    /// Use `collect::<Spf>()` to build a `v=spf1` record, ignoring conflicts as
+    /// [`append_mechanism`](Spf::append_mechanism) does.
+    ///
+    /// # Errors:
+    /// - [`InvalidVersion`](SpfError::InvalidVersion) if `version` is not `v=spf1` or
+    ///   `spf2.0/` followed by scopes. [`InvalidScope`](SpfError::InvalidScope) if a scope is
+    ///   not known.
+    /// - The first error returned by [`try_append_mechanism`](Spf::try_append_mechanism) or
+    ///   [`try_append_ip_mechanism`](Spf::try_append_ip_mechanism). e.g.
+    ///   [`MechanismAlreadyPresent`](SpfError::MechanismAlreadyPresent) for a second `all`, or
+    ///   [`ModifierMayOccurOnlyOnce`](SpfError::ModifierMayOccurOnlyOnce) for a second
+    ///   `redirect=`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, ParsedMechanism, Qualifier};
+    /// use decon_spf::{Spf, SpfError};
+    /// let networks = ["203.0.113.0/24", "198.51.100.0/24"];
+    /// let mechanisms = networks
+    ///     .iter()
+    ///     .map(|n| ParsedMechanism::IP(Mechanism::ip(Qualifier::Pass, n.parse().unwrap())))
+    ///     .chain(Some(ParsedMechanism::TXT(Mechanism::all(Qualifier::Fail))));
+    /// let spf = Spf::from_mechanisms("v=spf1", mechanisms).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:203.0.113.0/24 ip4:198.51.100.0/24 -all");
+    ///
+    /// let twice = vec![
+    ///     ParsedMechanism::TXT(Mechanism::all(Qualifier::Fail)),
+    ///     ParsedMechanism::TXT(Mechanism::all(Qualifier::SoftFail)),
+    /// ];
+    /// let err = Spf::from_mechanisms("v=spf1", twice).unwrap_err();
+    /// assert_eq!(err.is_mechanism_already_present(), true);
+    /// ```
+    pub fn from_mechanisms<I>(version: &str, mechanisms: I) -> Result<Spf, SpfError>
+    where
+        I: IntoIterator<Item = ParsedMechanism>,
+    {
+        let mut spf = Spf::with_version(version.parse()?);
+        for mechanism in mechanisms {
+            match mechanism {
+                ParsedMechanism::TXT(m) => spf.try_append_mechanism(m)?,
+                ParsedMechanism::IP(m) => spf.try_append_ip_mechanism(m)?,
+            }
+        }
+        Ok(spf)
+    }
+    /// Parse a single term and append it to the SPF struct. `ip4:` and `ip6:` terms are
+    /// appended as with [`try_append_ip_mechanism`](Spf::try_append_ip_mechanism), unknown modifiers
+    /// are added to [`unknown_modifiers`](Spf::unknown_modifiers), and all other terms are
+    /// appended as with [`try_append_mechanism`](Spf::try_append_mechanism).
+    ///
+    /// # Errors:
+    /// - [`EmptyInput`](SpfError::EmptyInput) if `term` is empty.
+    /// - [`WhiteSpaceSyntaxError`](SpfError::WhiteSpaceSyntaxError) if `term` contains
+    ///   whitespace. Only one term may be appended at a time.
+    /// - [`InvalidMechanism`](SpfError::InvalidMechanism) if `term` is the version or can not
+    ///   be parsed.
+    /// - Any error of [`try_append_mechanism`](Spf::try_append_mechanism) or
+    ///   [`try_append_ip_mechanism`](Spf::try_append_ip_mechanism).
+    /// - Any error of [`append_modifier`](Spf::append_modifier) for an unknown modifier.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Spf, SpfError};
+    /// let mut spf = Spf::new_v1();
+    /// spf.append_str("a").unwrap();
+    /// spf.append_str("include:_spf.example.com").unwrap();
+    /// spf.append_str("ip4:203.0.113.0/24").unwrap();
+    /// spf.append_str("-all").unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a include:_spf.example.com ip4:203.0.113.0/24 -all");
+    ///
+    /// assert_eq!(spf.append_str("mx -all").unwrap_err(), SpfError::WhiteSpaceSyntaxError);
+    /// assert_eq!(spf.append_str("v=spf1").unwrap_err().is_invalid_mechanism(), true);
+    /// ```
+    pub fn append_str(&mut self, term: &str) -> Result<(), SpfError> {
+        if term.is_empty() {
+            return Err(SpfError::EmptyInput);
+        }
+        if term.contains(char::is_whitespace) {
+            return Err(SpfError::WhiteSpaceSyntaxError);
+        }
+        if helpers::is_version_term(term) {
+            return Err(SpfError::InvalidMechanism(
+                MechanismError::InvalidMechanismFormat(term.to_string()),
+            ));
+        }
+        match term.parse::<ParsedMechanism>() {
+            Ok(ParsedMechanism::IP(mechanism)) => self.try_append_ip_mechanism(mechanism),
+            Ok(ParsedMechanism::TXT(mechanism)) => self.try_append_mechanism(mechanism),
+            Err(error) => match helpers::split_unknown_modifier(term) {
+                Some((name, value)) => self.append_modifier(name, value),
+                None => Err(SpfError::InvalidMechanism(error)),
+            },
+        }
     }
     /// Appends the passed `Mechanism<IpNetwork>` to the SPF struct.
     ///
@@ -430,30 +2766,182 @@ impl Spf {
     /// ```
     /// use decon_spf::mechanism::{Qualifier, Mechanism};
     /// use decon_spf::Spf;
-    /// let mut new_spf_record = Spf::new();
-    /// new_spf_record.set_v1();
+    /// let mut new_spf_record = Spf::new_v1();
     /// new_spf_record.append_ip_mechanism(Mechanism::new_ip(Qualifier::Pass,
     ///                                 "203.32.160.0/23".parse().unwrap()));
     /// new_spf_record.append_mechanism(Mechanism::all(Qualifier::Pass));
     /// assert_eq!(new_spf_record.to_string(), "v=spf1 ip4:203.32.160.0/23 all".to_string());
-    /// ```    
+    /// ```
+    ///
+    /// # Note:
+    /// A mechanism whose kind is not `ip4:` or `ip6:`, or does not match the family of its
+    /// network, is ignored. The record may grow past its [`max_length()`](Spf::max_length); use
+    /// [`check_length()`](Spf::check_length) to test it. See
+    /// [`try_append_ip_mechanism`](Spf::try_append_ip_mechanism)
     pub fn append_ip_mechanism(&mut self, mechanism: Mechanism<IpNetwork>) {
+        if let Ok(is_ipv4) = Self::ip_family(&mechanism) {
+            self.push_ip_mechanism(mechanism, is_ipv4);
+        }
+    }
+    /// Appends the passed `Mechanism<IpNetwork>` to the SPF struct, returning an error instead
+    /// of ignoring a mechanism as [`append_ip_mechanism`](Spf::append_ip_mechanism) does.
+    ///
+    /// # Errors:
+    /// - [`WrongMechanismKind`](SpfError::WrongMechanismKind) if the kind of `mechanism` is not
+    ///   `ip4:` for an ip4 network, or `ip6:` for an ip6 network.
+    /// - [`SourceLengthExceeded`](SpfError::SourceLengthExceeded) if the record would grow past
+    ///   its [`max_length()`](Spf::max_length).
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Kind, Mechanism, Qualifier};
+    /// use decon_spf::{Spf, SpfError};
+    /// let mut spf = Spf::new_v1();
+    /// spf.try_append_ip_mechanism("ip4:203.0.113.0/24".parse().unwrap()).unwrap();
+    /// let wrong = Mechanism::generic_inclusive(
+    ///     Kind::A,
+    ///     Qualifier::Pass,
+    ///     Some("198.51.100.0/24".parse().unwrap()),
+    /// );
+    /// assert_eq!(
+    ///     spf.try_append_ip_mechanism(wrong).unwrap_err(),
+    ///     SpfError::WrongMechanismKind(Kind::A)
+    /// );
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:203.0.113.0/24");
+    /// ```
+    pub fn try_append_ip_mechanism(
+        &mut self,
+        mechanism: Mechanism<IpNetwork>,
+    ) -> Result<(), SpfError> {
+        let is_ipv4 = Self::ip_family(&mechanism)?;
+        self.check_room(&mechanism.to_string())?;
+        self.push_ip_mechanism(mechanism, is_ipv4);
+        Ok(())
+    }
+    // Returns true for an ip4 network, checking that the kind matches its family.
+    fn ip_family(mechanism: &Mechanism<IpNetwork>) -> Result<bool, SpfError> {
+        let is_ipv4 = match mechanism.mechanism() {
+            Some(network) => network.is_ipv4(),
+            None => return Err(SpfError::WrongMechanismKind(*mechanism.kind())),
+        };
         match mechanism.kind() {
-            Kind::IpV4 => self.append_mechanism_of_ip4(mechanism),
-            Kind::IpV6 => self.append_mechanism_of_ip6(mechanism),
-            _ => {
-                unreachable!()
-            }
+            Kind::IpV4 if is_ipv4 => Ok(true),
+            Kind::IpV6 if !is_ipv4 => Ok(false),
+            kind => Err(SpfError::WrongMechanismKind(*kind)),
+        }
+    }
+    fn push_ip_mechanism(&mut self, mechanism: Mechanism<IpNetwork>, is_ipv4: bool) {
+        if is_ipv4 {
+            self.append_mechanism_of_ip4(mechanism);
+        } else {
+            self.append_mechanism_of_ip6(mechanism);
+        }
+    }
+    /// Returns the longest the record may be written, in characters. This is 255 unless it
+    /// has been changed with [`set_max_length()`](Spf::set_max_length).
+    ///
+    /// A record made by [`from_txt_strings()`](Spf::from_txt_strings) has no maximum length.
+    pub fn max_length(&self) -> usize {
+        self.max_length.unwrap_or(helpers::MAX_SPF_STRING_LENGTH)
+    }
+    /// Change the longest the record may be written. Use this when the record is to be
+    /// published as more than one character-string of a TXT record.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf = Spf::new_v1();
+    /// assert_eq!(spf.max_length(), 255);
+    /// spf.set_max_length(512);
+    /// assert_eq!(spf.remaining_length(), 506);
+    /// ```
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = Some(max_length);
+    }
+    /// Returns the number of characters which may still be added before the record is longer
+    /// than its [`max_length()`](Spf::max_length). This is negative when the record is already
+    /// too long.
+    ///
+    /// Each appended term also adds the space which separates it from the term before.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a -all".parse().unwrap();
+    /// assert_eq!(spf.remaining_length(), 242);
+    /// ```
+    pub fn remaining_length(&self) -> isize {
+        let max_length = isize::try_from(self.max_length()).unwrap_or(isize::MAX);
+        max_length - self.serialized_len() as isize
+    }
+    /// Returns the number of characters which may still be added before the record is longer
+    /// than 255 characters, the most a single character-string of a TXT record may hold. This
+    /// is negative when the record is already too long.
+    ///
+    /// Unlike [`remaining_length()`](Spf::remaining_length), this does not use the
+    /// [`max_length()`](Spf::max_length) of the record.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+    /// spf.set_max_length(512);
+    /// assert_eq!(spf.remaining_budget(), 242);
+    /// assert_eq!(spf.remaining_length(), 499);
+    /// ```
+    pub fn remaining_budget(&self) -> isize {
+        helpers::MAX_SPF_STRING_LENGTH as isize - self.serialized_len() as isize
+    }
+    /// Returns the length of the string [`to_string()`](Spf::to_string) would build, without
+    /// building it. See [`Mechanism::serialized_len()`](Mechanism::serialized_len)
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 mx ip4:192.0.2.0/24 include:_spf.example.com ~all".parse().unwrap();
+    /// assert_eq!(spf.serialized_len(), spf.to_string().len());
+    /// assert_eq!(spf.serialized_len(), 56);
+    /// ```
+    pub fn serialized_len(&self) -> usize {
+        let mechanisms: usize = self
+            .iter()
+            .map(|m| match m {
+                MechanismRef::TXT(m) => m.serialized_len(),
+                MechanismRef::IP(m) => m.serialized_len(),
+            })
+            .sum();
+        let modifiers: usize = self
+            .unknown_modifiers
+            .iter()
+            .flatten()
+            .map(|(name, value)| name.len() + value.len() + 2)
+            .sum();
+        self.version_str.len() + mechanisms + modifiers
+    }
+    /// Check that the record is no longer than its [`max_length()`](Spf::max_length).
+    ///
+    /// # Errors:
+    /// - [`SourceLengthExceeded`](SpfError::SourceLengthExceeded) if the record is too long.
+    pub fn check_length(&self) -> Result<(), SpfError> {
+        if self.remaining_length() < 0 {
+            return Err(SpfError::SourceLengthExceeded);
         }
+        Ok(())
+    }
+    // A term and the space before it must fit in the remaining length.
+    fn check_room(&self, term: &str) -> Result<(), SpfError> {
+        if self.remaining_length() < term.len() as isize + 1 {
+            return Err(SpfError::SourceLengthExceeded);
+        }
+        Ok(())
     }
     /// # Note: Experimential
     /// *Do not use.*
     /// Very rudementary validation check.
     /// - Will fail if the length of `source` is more than MAX_SPF_STRING_LENGTH characters See:
-    /// [`SourceLengthExceeded`](SpfError::SourceLengthExceeded)
-    /// - Will fail if there are more than 10 DNS lookups. Looks are required for each `A`, `MX`
-    /// , `Redirect`, and `Include` Mechanism. See: [`LookupLimitExceeded`](SpfError::LookupLimitExceeded)
-    /// (This will change given new information)
+    ///   [`SourceLengthExceeded`](SpfError::SourceLengthExceeded)
+    /// - Will fail if there are more than 10 DNS lookups. See [`lookup_count()`](Spf::lookup_count)
+    ///   and [`LookupLimitExceeded`](SpfError::LookupLimitExceeded)
     #[deprecated(note = "This is expected to be depreciated.")]
     pub fn try_validate(&mut self) -> Result<(), SpfError> {
         if self.from_src {
@@ -467,22 +2955,63 @@ impl Spf {
         if self.redirect().is_some() && self.all().is_some() {
             return Err(SpfError::RedirectWithAllMechanism);
         }
-        if validate::check_lookup_count(self) > 10 {
+        validate::check_duplicate_all(self)?;
+        validate::check_duplicate_modifier(self)?;
+        if self.lookup_count() > 10 {
             return Err(SpfError::LookupLimitExceeded);
         }
+        self.was_validated = true;
         self.is_valid = true;
         Ok(())
     }
-    #[allow(dead_code)]
-    fn validate(&mut self, rfc: SpfRfcStandard) -> Result<&Self, SpfError> {
-        return match rfc {
+    /// Validate the record against the rules of `rfc`. See [`SpfRfcStandard`](SpfRfcStandard)
+    ///
+    /// When the record is valid it is marked as validated, so [`is_valid()`](Spf::is_valid)
+    /// returns `true` until the Spf is changed. A record which is not valid is left as it was.
+    ///
+    /// # Errors:
+    /// The first rule which the record breaks. e.g.
+    /// [`LookupLimitExceeded`](SpfError::LookupLimitExceeded),
+    /// [`DuplicateAll`](SpfError::DuplicateAll) or, for RFC 4408,
+    /// [`RedirectWithAllMechanism`](SpfError::RedirectWithAllMechanism).
+    ///
+    /// A record using `ptr` is valid under either standard. RFC 7208 says it should not be
+    /// used, which [`validation_report()`](Spf::validation_report) notes as a warning.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Spf, SpfError, SpfRfcStandard};
+    /// let mut spf: Spf = "v=spf1 ptr:example.com -all".parse().unwrap();
+    /// assert_eq!(spf.validate(SpfRfcStandard::Rfc4408), Ok(()));
+    /// assert_eq!(spf.is_valid(), true);
+    /// assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+    ///
+    /// let mut spf: Spf = "v=spf1 -all redirect=_spf.example.com".parse().unwrap();
+    /// assert_eq!(
+    ///     spf.validate(SpfRfcStandard::Rfc4408),
+    ///     Err(SpfError::RedirectWithAllMechanism)
+    /// );
+    /// ```
+    pub fn validate(&mut self, rfc: SpfRfcStandard) -> Result<(), SpfError> {
+        match rfc {
             SpfRfcStandard::Rfc4408 => validate::validate_rfc4408(self),
-        };
+            SpfRfcStandard::Rfc7208 => validate::validate_rfc7208(self),
+        }
+        .map(|_| ())
+    }
+    /// Check the record against every rule of `options` and report each problem found,
+    /// rather than stopping at the first as [`validate()`](Spf::validate) does. The record
+    /// is not changed.
+    ///
+    /// See [`ValidationReport`](ValidationReport) for an example.
+    pub fn validation_report(&self, options: &ValidationOptions) -> ValidationReport {
+        report::report(self, options)
     }
     #[allow(dead_code)]
-    fn validate_to_string(&mut self, rfc: SpfRfcStandard) -> SpfValidationResult {
+    fn validate_to_string(&mut self, rfc: SpfRfcStandard) -> SpfValidationResult<'_> {
         let res = match rfc {
             SpfRfcStandard::Rfc4408 => validate::validate_rfc4408(self),
+            SpfRfcStandard::Rfc7208 => validate::validate_rfc7208(self),
         };
         match res {
             Ok(x) => SpfValidationResult::Valid(x),
@@ -515,14 +3044,26 @@ impl Spf {
             spf.push(' ');
             spf.push_str(ptr.to_string().as_str());
         }
-        if self.is_redirected {
+        // A record may hold both. `redirect=` is then ignored when the record is evaluated.
+        if let Some(all) = self.all() {
+            spf.push(' ');
+            spf.push_str(all.to_string().as_str());
+        }
+        if let Some(redirect) = self.redirect() {
             spf.push(' ');
-            spf.push_str(self.redirect().unwrap().to_string().as_str());
+            spf.push_str(redirect.to_string().as_str());
         }
-        // All can only be used if this is not a redirect.
-        if !self.is_redirected && self.all().is_some() {
+        if let Some(exp) = self.exp() {
             spf.push(' ');
-            spf.push_str(self.all().unwrap().to_string().as_str());
+            spf.push_str(exp.to_string().as_str());
+        }
+        if let Some(modifiers) = self.unknown_modifiers() {
+            for (name, value) in modifiers.iter() {
+                spf.push(' ');
+                spf.push_str(name);
+                spf.push('=');
+                spf.push_str(value);
+            }
         }
         spf
     }
@@ -539,38 +3080,348 @@ impl Spf {
     pub fn redirect(&self) -> Option<&Mechanism<String>> {
         self.redirect.as_ref()
     }
+    /// Returns a mutable reference to the `Redirect` Mechanism. See [`all_mut()`](Spf::all_mut)
+    pub fn redirect_mut(&mut self) -> Option<&mut Mechanism<String>> {
+        if self.redirect.is_some() {
+            self.modified();
+        }
+        self.redirect.as_mut()
+    }
     /// Returns a reference to the a `Vec` of `Mechanism<String>` for `Include`
     pub fn includes(&self) -> Option<&Vec<Mechanism<String>>> {
         self.include.as_ref()
     }
+    /// Returns a mutable reference to the `Vec` of `Mechanism<String>` for `Include`. See [`all_mut()`](Spf::all_mut)
+    pub fn includes_mut(&mut self) -> Option<&mut Vec<Mechanism<String>>> {
+        if self.include.is_some() {
+            self.modified();
+        }
+        self.include.as_mut()
+    }
     /// Returns a reference to the a `Vec` of `Mechanism<String>` for `A`
     pub fn a(&self) -> Option<&Vec<Mechanism<String>>> {
         self.a.as_ref()
     }
+    /// Returns a mutable reference to the `Vec` of `Mechanism<String>` for `A`. See [`all_mut()`](Spf::all_mut)
+    pub fn a_mut(&mut self) -> Option<&mut Vec<Mechanism<String>>> {
+        if self.a.is_some() {
+            self.modified();
+        }
+        self.a.as_mut()
+    }
     /// Returns a reference to the a `Vec` of `Mechanism<String>` for `MX`
     pub fn mx(&self) -> Option<&Vec<Mechanism<String>>> {
         self.mx.as_ref()
     }
+    /// Returns a mutable reference to the `Vec` of `Mechanism<String>` for `MX`. See [`all_mut()`](Spf::all_mut)
+    pub fn mx_mut(&mut self) -> Option<&mut Vec<Mechanism<String>>> {
+        if self.mx.is_some() {
+            self.modified();
+        }
+        self.mx.as_mut()
+    }
     /// Returns a reference to the a `Vec` of `Mechanism<IpNetwork>` for `IP4`
     pub fn ip4(&self) -> Option<&Vec<Mechanism<IpNetwork>>> {
         self.ip4.as_ref()
     }
+    /// Returns a mutable reference to the `Vec` of `Mechanism<IpNetwork>` for `IP4`. See [`all_mut()`](Spf::all_mut)
+    pub fn ip4_mut(&mut self) -> Option<&mut Vec<Mechanism<IpNetwork>>> {
+        if self.ip4.is_some() {
+            self.modified();
+        }
+        self.ip4.as_mut()
+    }
     /// Returns a reference to the a `Vec` of `Mechanism<IpNetwork>` for `IP6`
     pub fn ip6(&self) -> Option<&Vec<Mechanism<IpNetwork>>> {
         self.ip6.as_ref()
     }
+    /// Returns a mutable reference to the `Vec` of `Mechanism<IpNetwork>` for `IP6`. See [`all_mut()`](Spf::all_mut)
+    pub fn ip6_mut(&mut self) -> Option<&mut Vec<Mechanism<IpNetwork>>> {
+        if self.ip6.is_some() {
+            self.modified();
+        }
+        self.ip6.as_mut()
+    }
     /// Returns a reference to the a `Vec` of `Mechanism<String>` for `Exists`
     pub fn exists(&self) -> Option<&Vec<Mechanism<String>>> {
         self.exists.as_ref()
     }
+    /// Returns a mutable reference to the `Vec` of `Mechanism<String>` for `Exists`. See [`all_mut()`](Spf::all_mut)
+    pub fn exists_mut(&mut self) -> Option<&mut Vec<Mechanism<String>>> {
+        if self.exists.is_some() {
+            self.modified();
+        }
+        self.exists.as_mut()
+    }
     /// Returns a reference to the a `Vec` of `Mechanism<String>` for `Ptr`
     pub fn ptr(&self) -> Option<&Mechanism<String>> {
         self.ptr.as_ref()
     }
+    /// Returns a mutable reference to the `Mechanism<String>` for `Ptr`. See [`all_mut()`](Spf::all_mut)
+    pub fn ptr_mut(&mut self) -> Option<&mut Mechanism<String>> {
+        if self.ptr.is_some() {
+            self.modified();
+        }
+        self.ptr.as_mut()
+    }
     /// Returns a reference to `Mechanism<String>` for `All`
     pub fn all(&self) -> Option<&Mechanism<String>> {
         self.all.as_ref()
     }
+    /// Returns a mutable reference to the `Mechanism<String>` for `All`, so its qualifier may
+    /// be changed in place.
+    ///
+    /// Each of the mutable accessors which returns `Some` marks the Spf as modified, so
+    /// [`is_valid()`](Spf::is_valid) returns `false` until it is validated again. The
+    /// [`Kind`](Kind) of a mechanism must not be changed through a mutable accessor. Use
+    /// [`remove_mechanism()`](Spf::remove_mechanism) and
+    /// [`append_mechanism()`](Spf::append_mechanism) instead.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Qualifier;
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf = "v=spf1 include:_spf.example.com ~all".parse().unwrap();
+    /// if let Some(all) = spf.all_mut() {
+    ///     all.set_qualifier(Qualifier::Fail);
+    /// }
+    /// assert_eq!(spf.to_string(), "v=spf1 include:_spf.example.com -all");
+    /// ```
+    pub fn all_mut(&mut self) -> Option<&mut Mechanism<String>> {
+        if self.all.is_some() {
+            self.modified();
+        }
+        self.all.as_mut()
+    }
+    // Forget any earlier validation, as the Spf may be changed.
+    fn modified(&mut self) {
+        self.was_validated = false;
+        self.is_valid = false;
+        self.is_modified = true;
+        // The positions in any parse warning refer to the source, which no longer matches.
+        self.parse_warnings = None;
+    }
+    /// Returns a reference to the `Exp` Modifier
+    pub fn exp(&self) -> Option<&Mechanism<String>> {
+        self.exp.as_ref()
+    }
+    /// Returns a reference to a `Vec` of `(name, value)` pairs for any modifier which is not
+    /// `redirect=` or `exp=`. These are retained so the record can be rebuilt without loss.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a t=y -all".parse().unwrap();
+    /// let modifiers = spf.unknown_modifiers().unwrap();
+    /// assert_eq!(modifiers[0], ("t".to_string(), "y".to_string()));
+    /// assert_eq!(spf.to_string(), "v=spf1 a -all t=y");
+    ///```
+    pub fn unknown_modifiers(&self) -> Option<&Vec<(String, String)>> {
+        self.unknown_modifiers.as_ref()
+    }
+    /// Append a modifier which is not `redirect=` or `exp=`, such as a vendor specific
+    /// `verify=token123`. It is written as `name=value` after the standard modifiers and is
+    /// held in [`unknown_modifiers`](Spf::unknown_modifiers).
+    ///
+    /// # Errors:
+    /// - [`InvalidModifierName`](SpfError::InvalidModifierName) if `name` is not a letter
+    ///   followed by letters, digits, `-`, `_` or `.`, or is `redirect`, `exp` or `v` in any
+    ///   case.
+    /// - [`WhiteSpaceSyntaxError`](SpfError::WhiteSpaceSyntaxError) if `value` contains
+    ///   whitespace.
+    /// - [`SourceLengthExceeded`](SpfError::SourceLengthExceeded) if the record would grow past
+    ///   its [`max_length()`](Spf::max_length).
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Spf, SpfError};
+    /// let mut spf: Spf = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+    /// spf.append_modifier("verify", "token123").unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a redirect=_spf.example.com verify=token123");
+    /// assert_eq!(
+    ///     spf.append_modifier("exp", "explain.example.com").unwrap_err(),
+    ///     SpfError::InvalidModifierName("exp".to_string())
+    /// );
+    ///```
+    pub fn append_modifier(&mut self, name: &str, value: &str) -> Result<(), SpfError> {
+        let reserved = ["redirect", "exp", "v"];
+        if !helpers::is_modifier_name(name) || reserved.iter().any(|r| name.eq_ignore_ascii_case(r))
+        {
+            return Err(SpfError::InvalidModifierName(name.to_string()));
+        }
+        if value.contains(char::is_whitespace) {
+            return Err(SpfError::WhiteSpaceSyntaxError);
+        }
+        self.check_room(&format!("{}={}", name, value))?;
+        self.modified();
+        self.unknown_modifiers
+            .get_or_insert_with(Vec::new)
+            .push((name.to_string(), value.to_string()));
+        Ok(())
+    }
+    /// Returns an iterator over each mechanism and modifier, in the order they are written
+    /// when building a string. Unknown modifiers are not included.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:203.0.113.0/24 mx include:_spf.example.com -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let terms: Vec<String> = spf.iter().map(|m| m.to_string()).collect();
+    /// assert_eq!(terms, ["mx", "include:_spf.example.com", "ip4:203.0.113.0/24", "-all"]);
+    /// assert_eq!(spf.to_string(), format!("{} {}", spf.version(), terms.join(" ")));
+    ///```
+    pub fn iter(&self) -> SpfIter<'_> {
+        SpfIter::new(self)
+    }
+    /// Returns each mechanism and modifier for which `predicate` returns `true`, in the order
+    /// of [`iter()`](Spf::iter).
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:203.0.113.0/24 ~mx include:_spf.example.com ~all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let softfail = spf.find(|m| m.qualifier().is_softfail());
+    /// assert_eq!(softfail.len(), 2);
+    /// assert_eq!(softfail[0].to_string(), "~mx");
+    ///```
+    pub fn find(&self, predicate: impl Fn(&MechanismRef) -> bool) -> Vec<MechanismRef<'_>> {
+        self.iter().filter(|m| predicate(m)).collect()
+    }
+    /// Returns the number of mechanisms and modifiers, including unknown modifiers. The version
+    /// is not counted. This is the number of terms written after the version when building a
+    /// string.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 a mx include:_spf.example.com -all x-note=ok".parse().unwrap();
+    /// assert_eq!(spf.len(), 5);
+    /// assert_eq!(Spf::new().len(), 0);
+    ///```
+    pub fn len(&self) -> usize {
+        self.iter().count() + self.unknown_modifiers.as_ref().map_or(0, Vec::len)
+    }
+    /// Returns `true` if the Spf holds no mechanisms or modifiers. See [`len()`](Spf::len)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the number of mechanisms or modifiers of `kind`.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::Kind;
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 include:a.example.com include:b.example.com ip4:203.0.113.0/24 -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(spf.count_of(Kind::Include), 2);
+    /// assert_eq!(spf.count_of(Kind::IpV4), 1);
+    /// assert_eq!(spf.count_of(Kind::All), 1);
+    /// assert_eq!(spf.count_of(Kind::Redirect), 0);
+    ///```
+    pub fn count_of(&self, kind: Kind) -> usize {
+        self.mechanisms_of(&kind).count() + self.networks_of(&kind).count()
+    }
+    /// Returns `true` if the Spf holds at least one mechanism or modifier of `kind`.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::Kind;
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip6:2001:db8::/32 redirect=_spf.example.com".parse().unwrap();
+    /// assert_eq!(spf.has_mechanism(Kind::IpV6), true);
+    /// assert_eq!(spf.has_mechanism(Kind::Redirect), true);
+    /// assert_eq!(spf.has_mechanism(Kind::IpV4), false);
+    ///```
+    pub fn has_mechanism(&self, kind: Kind) -> bool {
+        self.mechanisms_of(&kind).next().is_some() || self.networks_of(&kind).next().is_some()
+    }
+    /// Returns `true` if evaluating the record may need a PTR lookup of the sender, either
+    /// through the `ptr` mechanism or a `%{p}` macro in a domain-spec.
+    ///
+    /// RFC 7208 section 5.5 discourages both.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 exists:%{p}.%{d}.example.com -all".parse().unwrap();
+    /// assert_eq!(spf.uses_ptr(), true);
+    /// let spf: Spf = "v=spf1 exists:%{i}.%{d}.example.com -all".parse().unwrap();
+    /// assert_eq!(spf.uses_ptr(), false);
+    ///```
+    pub fn uses_ptr(&self) -> bool {
+        !self.ptr_terms().is_empty()
+    }
+    // The `ptr` mechanism and each term whose domain-spec uses the `%{p}` macro, with its
+    // position in `iter()`.
+    fn ptr_terms(&self) -> Vec<(usize, &Mechanism<String>)> {
+        self.iter()
+            .enumerate()
+            .filter_map(|(position, term)| term.txt().map(|m| (position, m)))
+            .filter(|(_, mechanism)| {
+                mechanism.kind().is_ptr()
+                    || (mechanism.has_macros()
+                        && mechanism.raw().to_ascii_lowercase().contains("%{p"))
+            })
+            .collect()
+    }
+    /// Returns what the record does with a sender which matches none of its mechanisms.
+    ///
+    /// A record with `redirect=` and without `all` gives [`Disposition::Redirected`]. When a
+    /// record has both, `redirect=` is ignored as RFC 7208 section 6.1 requires. A record
+    /// without either gives [`Disposition::Neutral`].
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Disposition, Spf};
+    /// let spf: Spf = "v=spf1 mx -all".parse().unwrap();
+    /// assert_eq!(spf.default_disposition(), Disposition::Fail);
+    /// let spf: Spf = "v=spf1 mx".parse().unwrap();
+    /// assert_eq!(spf.default_disposition(), Disposition::Neutral);
+    /// let spf: Spf = "v=spf1 mx redirect=_spf.example.com".parse().unwrap();
+    /// assert_eq!(spf.default_disposition(), Disposition::Redirected);
+    /// let spf: Spf = "v=spf1 mx ~all redirect=_spf.example.com".parse().unwrap();
+    /// assert_eq!(spf.default_disposition(), Disposition::SoftFail);
+    ///```
+    pub fn default_disposition(&self) -> Disposition {
+        match &self.all {
+            Some(all) => Disposition::from(*all.qualifier()),
+            None if self.is_redirected => Disposition::Redirected,
+            None => Disposition::Neutral,
+        }
+    }
+    /// Returns `true` if the record authorises senders only through `ip4:` and `ip6:`, so it
+    /// can be evaluated without any DNS lookups. A record without any `ip4:` or `ip6:` gives
+    /// `false`.
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::Spf;
+    /// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all".parse().unwrap();
+    /// assert_eq!(spf.is_fully_ip_based(), true);
+    /// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 include:_spf.example.com -all".parse().unwrap();
+    /// assert_eq!(spf.is_fully_ip_based(), false);
+    ///```
+    pub fn is_fully_ip_based(&self) -> bool {
+        self.lookup_count() == 0 && (self.ip4.is_some() || self.ip6.is_some())
+    }
+    /// Return a reference to the list of [`Warning`](Warning) found while parsing. These are
+    /// forgotten once the record is changed. See [`is_modified()`](Spf::is_modified)
+    ///
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{Spf, Warning};
+    /// let spf: Spf = "v=spf1 a ~all -all".parse().unwrap();
+    /// assert_eq!(spf.all().unwrap().to_string(), "~all");
+    /// assert_eq!(spf.parse_warnings().unwrap()[0], Warning::DuplicateAll { first: 2, second: 3 });
+    ///```
+    pub fn parse_warnings(&self) -> Option<&Vec<Warning>> {
+        self.parse_warnings.as_ref()
+    }
     /// Return a reference to the list of domains that gave warnings.
     #[cfg_attr(docsrs, doc(cfg(feature = "warn-dns")))]
     #[cfg(feature = "warn-dns")]
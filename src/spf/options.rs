@@ -0,0 +1,163 @@
+//! Options which alter the behaviour of the Spf parser.
+
+/// Controls how [`Spf::from_str_with_options`](crate::Spf::from_str_with_options)
+/// handles a string representation of an Spf record.
+///
+/// The default options are *lenient* and match the behaviour of `FromStr` for `Spf`.
+///
+/// # Example:
+///```rust
+/// use decon_spf::{ParseOptions, Spf};
+/// let options = ParseOptions::new().strict(true);
+/// assert_eq!(options.is_strict(), true);
+/// let spf = Spf::from_str_with_options("v=spf1 a -all", &options).unwrap();
+/// assert_eq!(spf.to_string(), "v=spf1 a -all");
+///```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseOptions {
+    strict: bool,
+    preserve_case: bool,
+    preserve_explicit_pass: bool,
+    lenient_whitespace: bool,
+    lenient_modifiers: bool,
+    normalize_networks: bool,
+}
+
+impl ParseOptions {
+    /// Create a new set of default (lenient) parse options.
+    pub fn new() -> Self {
+        ParseOptions::default()
+    }
+    /// Enable or disable strict parsing.
+    /// When enabled any term which is not a recognised mechanism, modifier, or the version
+    /// string will cause an [`UnknownTerm`](crate::SpfError::UnknownTerm) error.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    /// Returns `true` if strict parsing is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+    /// Enable or disable preserving the case of mechanism and modifier names.
+    /// Names are always matched case-insensitively. By default they are written in
+    /// lowercase when the record is converted back to a string. When enabled, each name
+    /// is written as it appeared in the source.
+    ///
+    /// The case of domain-spec values is always preserved.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{ParseOptions, Spf};
+    /// let source = "v=spf1 Include:_spf.Example.com -ALL";
+    /// let spf = Spf::from_str_with_options(source, &ParseOptions::new()).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 include:_spf.Example.com -all");
+    ///
+    /// let options = ParseOptions::new().preserve_case(true);
+    /// let spf = Spf::from_str_with_options(source, &options).unwrap();
+    /// assert_eq!(spf.to_string(), source);
+    ///```
+    pub fn preserve_case(mut self, preserve_case: bool) -> Self {
+        self.preserve_case = preserve_case;
+        self
+    }
+    /// Returns `true` if the case of mechanism and modifier names is preserved.
+    pub fn preserves_case(&self) -> bool {
+        self.preserve_case
+    }
+    /// Enable or disable preserving explicit `+` qualifiers.
+    /// By default a [`Pass`](crate::mechanism::Qualifier::Pass) qualifier is not written when the
+    /// record is converted back to a string. When enabled, mechanisms written with a leading `+`
+    /// in the source keep it.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{ParseOptions, Spf};
+    /// let source = "v=spf1 +a mx -all";
+    /// let spf = Spf::from_str_with_options(source, &ParseOptions::new()).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a mx -all");
+    ///
+    /// let options = ParseOptions::new().preserve_explicit_pass(true);
+    /// let spf = Spf::from_str_with_options(source, &options).unwrap();
+    /// assert_eq!(spf.to_string(), source);
+    ///```
+    pub fn preserve_explicit_pass(mut self, preserve_explicit_pass: bool) -> Self {
+        self.preserve_explicit_pass = preserve_explicit_pass;
+        self
+    }
+    /// Returns `true` if explicit `+` qualifiers are preserved.
+    pub fn preserves_explicit_pass(&self) -> bool {
+        self.preserve_explicit_pass
+    }
+    /// Enable or disable lenient whitespace handling.
+    /// By default consecutive, leading or trailing whitespace results in
+    /// [`WhiteSpaceSyntaxError`](crate::SpfError::WhiteSpaceSyntaxError). When enabled the
+    /// record is parsed and a [`Warning::ExtraWhitespace`](crate::Warning::ExtraWhitespace)
+    /// is recorded for each occurrence.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{ParseOptions, Spf, Warning};
+    /// let options = ParseOptions::new().lenient_whitespace(true);
+    /// let spf = Spf::from_str_with_options("v=spf1  a -all", &options).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a -all");
+    /// assert_eq!(spf.parse_warnings().unwrap()[0], Warning::ExtraWhitespace { position: 6 });
+    ///```
+    pub fn lenient_whitespace(mut self, lenient_whitespace: bool) -> Self {
+        self.lenient_whitespace = lenient_whitespace;
+        self
+    }
+    /// Returns `true` if whitespace handling is lenient.
+    pub fn is_lenient_whitespace(&self) -> bool {
+        self.lenient_whitespace
+    }
+    /// Enable or disable lenient handling of repeated modifiers.
+    /// By default a second `redirect=` or `exp=` results in
+    /// [`ModifierMayOccurOnlyOnce`](crate::SpfError::ModifierMayOccurOnlyOnce). When enabled the
+    /// first is kept and a [`Warning::DuplicateModifier`](crate::Warning::DuplicateModifier) is
+    /// recorded for each repeat. The record then fails validation.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::mechanism::Kind;
+    /// use decon_spf::{ParseOptions, Spf, Warning};
+    /// let source = "v=spf1 redirect=a.example.com redirect=b.example.com";
+    /// assert_eq!(source.parse::<Spf>().is_err(), true);
+    ///
+    /// let options = ParseOptions::new().lenient_modifiers(true);
+    /// let spf = Spf::from_str_with_options(source, &options).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 redirect=a.example.com");
+    /// assert_eq!(
+    ///     spf.parse_warnings().unwrap()[0],
+    ///     Warning::DuplicateModifier { kind: Kind::Redirect, first: 1, second: 2 }
+    /// );
+    ///```
+    pub fn lenient_modifiers(mut self, lenient_modifiers: bool) -> Self {
+        self.lenient_modifiers = lenient_modifiers;
+        self
+    }
+    /// Returns `true` if repeated modifiers are recorded as warnings.
+    pub fn is_lenient_modifiers(&self) -> bool {
+        self.lenient_modifiers
+    }
+    /// Enable or disable normalising `ip4` and `ip6` networks.
+    /// A network with bits set below its prefix length, such as `192.168.1.17/24`, always
+    /// records a [`Warning::HostBitsSet`](crate::Warning::HostBitsSet). When enabled the
+    /// network is also rewritten to the network address which is actually matched.
+    /// # Example:
+    ///```rust
+    /// use decon_spf::{ParseOptions, Spf};
+    /// let source = "v=spf1 ip4:192.168.1.17/24 -all";
+    /// let spf = Spf::from_str_with_options(source, &ParseOptions::new()).unwrap();
+    /// assert_eq!(spf.to_string(), source);
+    /// assert_eq!(spf.parse_warnings().unwrap()[0].is_host_bits_set(), true);
+    ///
+    /// let options = ParseOptions::new().normalize_networks(true);
+    /// let spf = Spf::from_str_with_options(source, &options).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:192.168.1.0/24 -all");
+    ///```
+    pub fn normalize_networks(mut self, normalize_networks: bool) -> Self {
+        self.normalize_networks = normalize_networks;
+        self
+    }
+    /// Returns `true` if networks with host bits set are rewritten.
+    pub fn normalizes_networks(&self) -> bool {
+        self.normalize_networks
+    }
+}
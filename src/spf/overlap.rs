@@ -0,0 +1,121 @@
+//! Overlapping `ip4:` and `ip6:` networks of an Spf record.
+use crate::mechanism::{Mechanism, MechanismRef};
+use crate::spf::Spf;
+use ipnetwork::IpNetwork;
+
+/// How the network of a later `ip4:` or `ip6:` mechanism relates to the network of an
+/// earlier one. See [`IpOverlap`](IpOverlap)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpRelation {
+    /// The networks hold the same addresses. The later mechanism is never matched.
+    Same,
+    /// The later network is contained in the earlier network. The later mechanism is never
+    /// matched.
+    Shadowed,
+    /// The later network contains the earlier network. The later mechanism only matches the
+    /// addresses which are not in the earlier network.
+    Covers,
+}
+
+/// Two `ip4:` or `ip6:` mechanisms of an Spf whose networks hold some of the same addresses.
+/// See [`Spf::find_ip_overlaps()`](crate::Spf::find_ip_overlaps)
+///
+/// Mechanisms are evaluated in the order they are written, so the earlier mechanism decides
+/// the result for every address the two networks share.
+///
+/// # Example:
+///```rust
+/// use decon_spf::{IpRelation, Spf};
+/// let spf: Spf = "v=spf1 ip4:10.0.0.0/8 -ip4:10.1.0.0/16 -all".parse().unwrap();
+/// let overlaps = spf.find_ip_overlaps();
+/// assert_eq!(overlaps.len(), 1);
+/// assert_eq!(overlaps[0].earlier().to_string(), "ip4:10.0.0.0/8");
+/// assert_eq!(overlaps[0].later().to_string(), "-ip4:10.1.0.0/16");
+/// assert_eq!(overlaps[0].relation(), IpRelation::Shadowed);
+/// assert_eq!(overlaps[0].is_conflict(), true);
+///```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IpOverlap<'a> {
+    earlier: &'a Mechanism<IpNetwork>,
+    earlier_index: usize,
+    later: &'a Mechanism<IpNetwork>,
+    later_index: usize,
+    relation: IpRelation,
+}
+
+impl<'a> IpOverlap<'a> {
+    // Each pair of overlapping networks, in the order the later network is written.
+    pub(crate) fn find(spf: &'a Spf) -> Vec<IpOverlap<'a>> {
+        let networks: Vec<(usize, &Mechanism<IpNetwork>)> = spf
+            .iter()
+            .enumerate()
+            .filter_map(|(index, m)| match m {
+                MechanismRef::IP(network) => Some((index, network)),
+                MechanismRef::TXT(_) => None,
+            })
+            .collect();
+        let mut overlaps = Vec::new();
+        for (position, &(later_index, later)) in networks.iter().enumerate() {
+            for &(earlier_index, earlier) in &networks[..position] {
+                if let Some(relation) = relation(earlier.as_network(), later.as_network()) {
+                    overlaps.push(IpOverlap {
+                        earlier,
+                        earlier_index,
+                        later,
+                        later_index,
+                        relation,
+                    });
+                }
+            }
+        }
+        overlaps
+    }
+    /// Returns the mechanism which is evaluated first.
+    pub fn earlier(&self) -> &'a Mechanism<IpNetwork> {
+        self.earlier
+    }
+    /// Returns the position of the earlier mechanism in [`Spf::iter()`](crate::Spf::iter)
+    pub fn earlier_index(&self) -> usize {
+        self.earlier_index
+    }
+    /// Returns the mechanism which is evaluated second.
+    pub fn later(&self) -> &'a Mechanism<IpNetwork> {
+        self.later
+    }
+    /// Returns the position of the later mechanism in [`Spf::iter()`](crate::Spf::iter)
+    pub fn later_index(&self) -> usize {
+        self.later_index
+    }
+    /// Returns how the later network relates to the earlier network.
+    pub fn relation(&self) -> IpRelation {
+        self.relation
+    }
+    /// Returns `true` if the later mechanism is never matched.
+    pub fn is_unreachable(&self) -> bool {
+        self.relation != IpRelation::Covers
+    }
+    /// Returns `true` if the two mechanisms have different qualifiers, so the shared
+    /// addresses are given the result of the earlier mechanism rather than the later one.
+    pub fn is_conflict(&self) -> bool {
+        self.earlier.qualifier() != self.later.qualifier()
+    }
+}
+
+// Networks of different families never overlap. Two networks which overlap are either the
+// same or one contains the other.
+fn relation(earlier: &IpNetwork, later: &IpNetwork) -> Option<IpRelation> {
+    if earlier.is_ipv4() != later.is_ipv4() {
+        return None;
+    }
+    if earlier.prefix() <= later.prefix() && earlier.contains(later.network()) {
+        if earlier.prefix() == later.prefix() {
+            Some(IpRelation::Same)
+        } else {
+            Some(IpRelation::Shadowed)
+        }
+    } else if later.prefix() < earlier.prefix() && later.contains(earlier.network()) {
+        Some(IpRelation::Covers)
+    } else {
+        None
+    }
+}
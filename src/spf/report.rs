@@ -0,0 +1,545 @@
+//! A report of every problem found while validating an Spf record.
+use crate::helpers;
+use crate::mechanism::{Kind, MechanismRef};
+use crate::spf::validate::SpfRfcStandard;
+use crate::spf::{ParseOptions, Spf, SpfError, Warning};
+use ipnetwork::IpNetwork;
+
+/// Controls the rules used by [`Spf::validation_report()`](crate::Spf::validation_report)
+///
+/// By default the rules of RFC 7208 are used, with its limit of 10 DNS lookups, and no
+/// prefix length is too short.
+///
+/// # Example:
+///```rust
+/// use decon_spf::{SpfRfcStandard, ValidationOptions};
+/// let options = ValidationOptions::new().lookup_limit(8).ip4_prefix_limit(16);
+/// assert_eq!(options.rfc_standard(), SpfRfcStandard::Rfc7208);
+/// assert_eq!(options.max_lookups(), 8);
+/// assert_eq!(options.min_ip4_prefix(), Some(16));
+/// assert_eq!(options.min_ip6_prefix(), None);
+/// assert_eq!(options.is_strict(), false);
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationOptions {
+    standard: SpfRfcStandard,
+    max_lookups: usize,
+    strict: bool,
+    min_ip4_prefix: Option<u8>,
+    min_ip6_prefix: Option<u8>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            standard: SpfRfcStandard::Rfc7208,
+            max_lookups: 10,
+            strict: false,
+            min_ip4_prefix: None,
+            min_ip6_prefix: None,
+        }
+    }
+}
+
+impl ValidationOptions {
+    /// Create a new set of default options.
+    pub fn new() -> Self {
+        ValidationOptions::default()
+    }
+    /// Set the standard whose rules are used. RFC 7208 discourages `ptr`, which is reported
+    /// as a [`Severity::Warning`]. RFC 4408 permits it.
+    pub fn standard(mut self, standard: SpfRfcStandard) -> Self {
+        self.standard = standard;
+        self
+    }
+    /// Returns the standard whose rules are used.
+    pub fn rfc_standard(&self) -> SpfRfcStandard {
+        self.standard
+    }
+    /// Set the most DNS lookups the record may need. A lower limit leaves room for the
+    /// lookups of included records.
+    pub fn lookup_limit(mut self, max_lookups: usize) -> Self {
+        self.max_lookups = max_lookups;
+        self
+    }
+    /// Returns the most DNS lookups the record may need.
+    pub fn max_lookups(&self) -> usize {
+        self.max_lookups
+    }
+    /// When `true` an `ip4:` or `ip6:` network which matches every address, and the use of
+    /// `ptr` under RFC 7208, are reported as [`Severity::Error`] rather than
+    /// [`Severity::Warning`].
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    /// Returns `true` if strict rules are used.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+    /// Warn for an `ip4:` network whose prefix length is shorter than `min_prefix`.
+    /// e.g. `16` warns for `ip4:10.0.0.0/8`
+    pub fn ip4_prefix_limit(mut self, min_prefix: u8) -> Self {
+        self.min_ip4_prefix = Some(min_prefix);
+        self
+    }
+    /// Returns the shortest `ip4:` prefix length which does not give a warning.
+    pub fn min_ip4_prefix(&self) -> Option<u8> {
+        self.min_ip4_prefix
+    }
+    /// Warn for an `ip6:` network whose prefix length is shorter than `min_prefix`.
+    pub fn ip6_prefix_limit(mut self, min_prefix: u8) -> Self {
+        self.min_ip6_prefix = Some(min_prefix);
+        self
+    }
+    /// Returns the shortest `ip6:` prefix length which does not give a warning.
+    pub fn min_ip6_prefix(&self) -> Option<u8> {
+        self.min_ip6_prefix
+    }
+}
+
+/// How serious a [`Finding`](Finding) is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// Something worth knowing which does not affect the record.
+    Info,
+    /// The record works but is likely to behave other than intended.
+    Warning,
+    /// The record is not valid and evaluating it may give `permerror`.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The stable code of a [`Finding`](Finding). The code of a problem does not change between
+/// releases, so it may be matched on or stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum FindingCode {
+    /// The record has no version.
+    MissingVersion,
+    /// The record is longer than its [`max_length()`](crate::Spf::max_length).
+    RecordTooLong,
+    /// The record needs more DNS lookups than permitted.
+    LookupLimitExceeded,
+    /// The record has both `redirect=` and `all`. This is an error under RFC 4408, while
+    /// RFC 7208 ignores `redirect=`.
+    RedirectWithAll,
+    /// `redirect=` or `exp=` appears more than once. Only found for a record parsed with
+    /// [`ParseOptions::lenient_modifiers`](crate::ParseOptions::lenient_modifiers)
+    DuplicateModifier,
+    /// More than one `all` mechanism is present.
+    DuplicateAll,
+    /// A term of the source is not a known mechanism or modifier and was left out.
+    UnknownTerm,
+    /// A modifier which is not known. It is ignored when the record is evaluated.
+    UnknownModifier,
+    /// `ptr` or the `%{p}` macro is used.
+    PtrUsed,
+    /// A domain-spec is not a valid macro-string. e.g. `%{x}`, or a `%` which is not
+    /// followed by `{`, `%`, `_` or `-`.
+    InvalidMacro,
+    /// A term can never be reached when the record is evaluated.
+    UnreachableTerm,
+    /// An `ip4:` or `ip6:` network has bits set below its prefix length.
+    HostBitsSet,
+    /// The source has whitespace other than a single space between terms.
+    ExtraWhitespace,
+    /// An `ip4:` or `ip6:` network with a prefix length of `0`, which matches every address.
+    AllAddresses,
+    /// An `ip4:` or `ip6:` network with a prefix length shorter than permitted by
+    /// [`ValidationOptions`](ValidationOptions).
+    BroadPrefix,
+    /// A single address written with `/32` or `/128`, which is implied.
+    RedundantPrefix,
+    /// The cidr suffix of an `a` or `mx` is out of range for its address family.
+    InvalidDualCidr,
+    /// A domain-spec is not a valid domain name. e.g. a label is longer than 63 octets, or
+    /// `include:` names a single label or an IP address.
+    InvalidDomain,
+    /// A modifier was written with a qualifier.
+    QualifierOnModifier,
+}
+
+impl FindingCode {
+    /// Returns the code as a string. e.g. `lookup-limit-exceeded`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FindingCode::MissingVersion => "missing-version",
+            FindingCode::RecordTooLong => "record-too-long",
+            FindingCode::LookupLimitExceeded => "lookup-limit-exceeded",
+            FindingCode::RedirectWithAll => "redirect-with-all",
+            FindingCode::DuplicateModifier => "duplicate-modifier",
+            FindingCode::DuplicateAll => "duplicate-all",
+            FindingCode::UnknownTerm => "unknown-term",
+            FindingCode::UnknownModifier => "unknown-modifier",
+            FindingCode::PtrUsed => "ptr-used",
+            FindingCode::InvalidMacro => "invalid-macro",
+            FindingCode::UnreachableTerm => "unreachable-term",
+            FindingCode::HostBitsSet => "host-bits-set",
+            FindingCode::ExtraWhitespace => "extra-whitespace",
+            FindingCode::AllAddresses => "all-addresses",
+            FindingCode::BroadPrefix => "broad-prefix",
+            FindingCode::RedundantPrefix => "redundant-prefix",
+            FindingCode::InvalidDualCidr => "invalid-dual-cidr",
+            FindingCode::InvalidDomain => "invalid-domain",
+            FindingCode::QualifierOnModifier => "qualifier-on-modifier",
+        }
+    }
+}
+
+impl std::fmt::Display for FindingCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single problem found in an Spf record. See [`ValidationReport`](ValidationReport)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Finding {
+    severity: Severity,
+    code: FindingCode,
+    term: Option<String>,
+    message: String,
+}
+
+impl Finding {
+    fn new(severity: Severity, code: FindingCode, term: Option<String>, message: String) -> Self {
+        Finding {
+            severity,
+            code,
+            term,
+            message,
+        }
+    }
+    /// Returns how serious the finding is.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+    /// Returns the stable code of the finding.
+    pub fn code(&self) -> FindingCode {
+        self.code
+    }
+    /// Returns the term involved, if the finding concerns a single term.
+    pub fn term(&self) -> Option<&str> {
+        self.term.as_deref()
+    }
+    /// Returns a description of the finding.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Written as `severity[code] term: message`
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]", self.severity, self.code)?;
+        if let Some(term) = &self.term {
+            write!(f, " {}", term)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Every problem found in an Spf record, in the order they were checked. See
+/// [`Spf::validation_report()`](crate::Spf::validation_report)
+///
+/// # Example:
+///```rust
+/// use decon_spf::{FindingCode, Severity, Spf, ValidationOptions};
+/// let spf: Spf = "v=spf1 ptr ip4:192.0.2.0/24 ip4:192.0.2.1 -all".parse().unwrap();
+/// let report = spf.validation_report(&ValidationOptions::new());
+/// assert_eq!(report.is_valid(), true);
+/// assert_eq!(report.codes(), [FindingCode::PtrUsed, FindingCode::UnreachableTerm]);
+/// assert_eq!(report.findings()[1].severity(), Severity::Warning);
+/// assert_eq!(
+///     report.to_string().lines().nth(1),
+///     Some("warning[unreachable-term] ip4:192.0.2.1: ip4:192.0.2.0/24 already matches \
+///           every address of ip4:192.0.2.1.")
+/// );
+/// let report = spf.validation_report(&ValidationOptions::new().strict(true));
+/// assert_eq!(report.is_valid(), false);
+///```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValidationReport {
+    findings: Vec<Finding>,
+}
+
+impl ValidationReport {
+    /// Returns every finding.
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+    /// Returns the code of each finding.
+    pub fn codes(&self) -> Vec<FindingCode> {
+        self.findings.iter().map(Finding::code).collect()
+    }
+    /// Returns the findings of `severity`.
+    pub fn with_severity(&self, severity: Severity) -> Vec<&Finding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .collect()
+    }
+    /// Returns `true` if there is no finding of [`Severity::Error`].
+    pub fn is_valid(&self) -> bool {
+        self.findings
+            .iter()
+            .all(|finding| finding.severity != Severity::Error)
+    }
+    /// Returns `true` if nothing was found.
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Writes each finding on its own line.
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for finding in &self.findings {
+            writeln!(f, "{}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn report(spf: &Spf, options: &ValidationOptions) -> ValidationReport {
+    let mut findings = Vec::new();
+    let mut add = |severity, code, term: Option<String>, message: String| {
+        findings.push(Finding::new(severity, code, term, message))
+    };
+    if spf.version_kind().is_none() {
+        let message = SpfError::InvalidSource.to_string();
+        add(Severity::Error, FindingCode::MissingVersion, None, message);
+    }
+    if spf.remaining_length() < 0 {
+        let message = format!(
+            "Record is {} characters, longer than {}.",
+            spf.serialized_len(),
+            spf.max_length()
+        );
+        add(Severity::Error, FindingCode::RecordTooLong, None, message);
+    }
+    if spf.lookup_count() > options.max_lookups {
+        let message = format!(
+            "Record needs {} DNS lookups, more than {}.",
+            spf.lookup_count(),
+            options.max_lookups
+        );
+        add(
+            Severity::Error,
+            FindingCode::LookupLimitExceeded,
+            None,
+            message,
+        );
+    }
+    if let (Some(redirect), Some(_)) = (spf.redirect(), spf.all()) {
+        let (severity, message) = match options.standard {
+            SpfRfcStandard::Rfc4408 => (
+                Severity::Error,
+                SpfError::RedirectWithAllMechanism.to_string(),
+            ),
+            SpfRfcStandard::Rfc7208 => (
+                Severity::Warning,
+                "redirect= is ignored as the record has an all mechanism.".to_string(),
+            ),
+        };
+        let term = Some(redirect.to_string());
+        add(severity, FindingCode::RedirectWithAll, term, message);
+    }
+    for term in unknown_terms(spf) {
+        let message = "Not a known mechanism or modifier. It was left out.".to_string();
+        add(
+            Severity::Error,
+            FindingCode::UnknownTerm,
+            Some(term),
+            message,
+        );
+    }
+    for (name, value) in spf.unknown_modifiers().into_iter().flatten() {
+        let message = "Unknown modifier, which is ignored.".to_string();
+        let term = Some(format!("{}={}", name, value));
+        add(Severity::Info, FindingCode::UnknownModifier, term, message);
+    }
+    // RFC 7208 section 5.5
+    if options.standard == SpfRfcStandard::Rfc7208 {
+        let severity = if options.strict {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+        for (position, mechanism) in spf.ptr_terms() {
+            let message = format!(
+                "Term {} needs a PTR lookup, which should not be used. It is slow, unreliable \
+                 and loads the .arpa name servers. Use ip4:, ip6:, a or mx instead.",
+                position
+            );
+            let term = Some(mechanism.to_string());
+            add(severity, FindingCode::PtrUsed, term, message);
+        }
+    }
+    for mechanism in spf.iter().filter_map(|m| m.txt()) {
+        for (offset, error) in mechanism.macro_syntax_errors() {
+            let message = format!("Offset {} of '{}': {}.", offset, mechanism.raw(), error);
+            let term = Some(mechanism.to_string());
+            add(Severity::Error, FindingCode::InvalidMacro, term, message);
+        }
+    }
+    for overlap in spf.find_ip_overlaps() {
+        if overlap.is_unreachable() {
+            let message = format!(
+                "{} already matches every address of {}.",
+                overlap.earlier(),
+                overlap.later()
+            );
+            let term = Some(overlap.later().to_string());
+            add(
+                Severity::Warning,
+                FindingCode::UnreachableTerm,
+                term,
+                message,
+            );
+        }
+    }
+    for warning in spf.parse_warnings().into_iter().flatten() {
+        let term = |position: &usize| spf.source.split_whitespace().nth(*position);
+        let (severity, code, term) = match warning {
+            Warning::DuplicateAll { second, .. } => {
+                (Severity::Error, FindingCode::DuplicateAll, term(second))
+            }
+            Warning::DuplicateModifier { second, .. } => (
+                Severity::Error,
+                FindingCode::DuplicateModifier,
+                term(second),
+            ),
+            Warning::MechanismAfterAll { position, .. } => (
+                Severity::Warning,
+                FindingCode::UnreachableTerm,
+                term(position),
+            ),
+            Warning::HostBitsSet { position, .. } => {
+                (Severity::Warning, FindingCode::HostBitsSet, term(position))
+            }
+            Warning::ExtraWhitespace { .. } => (Severity::Info, FindingCode::ExtraWhitespace, None),
+            Warning::QualifierOnModifier { position, .. } => (
+                Severity::Info,
+                FindingCode::QualifierOnModifier,
+                term(position),
+            ),
+            // Found above from the length of the record.
+            Warning::RecordTooLong { .. } => continue,
+        };
+        add(
+            severity,
+            code,
+            term.map(str::to_string),
+            warning.to_string(),
+        );
+    }
+    findings.extend(prefix_findings(spf, options));
+    for term in spf.iter() {
+        let mechanism = match term {
+            MechanismRef::TXT(mechanism) => mechanism,
+            MechanismRef::IP(_) => continue,
+        };
+        let problem = mechanism
+            .domain()
+            .and_then(|domain| helpers::domain_spec_problem(domain, mechanism.kind()));
+        if let Some((label, problem)) = problem {
+            let message = format!("'{}' {}.", label, problem);
+            let term = Some(mechanism.to_string());
+            let code = FindingCode::InvalidDomain;
+            findings.push(Finding::new(Severity::Error, code, term, message));
+        }
+    }
+    ValidationReport { findings }
+}
+
+// Problems with the prefix lengths of `ip4:`, `ip6:` and the cidr suffix of `a` and `mx`.
+fn prefix_findings(spf: &Spf, options: &ValidationOptions) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for mechanism in spf.iter_ip() {
+        let network = mechanism.as_network();
+        let (host, limit) = match network {
+            IpNetwork::V4(_) => (32, options.min_ip4_prefix),
+            IpNetwork::V6(_) => (128, options.min_ip6_prefix),
+        };
+        let term = Some(mechanism.to_string());
+        if network.prefix() == 0 {
+            let severity = if options.strict {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+            let message = "Matches every address of the internet.".to_string();
+            findings.push(Finding::new(
+                severity,
+                FindingCode::AllAddresses,
+                term,
+                message,
+            ));
+        } else if limit.is_some_and(|limit| network.prefix() < limit) {
+            let message = format!(
+                "Prefix length /{} is shorter than /{}.",
+                network.prefix(),
+                limit.unwrap_or_default()
+            );
+            let code = FindingCode::BroadPrefix;
+            findings.push(Finding::new(Severity::Warning, code, term, message));
+        } else if network.prefix() == host
+            && mechanism.to_string() == mechanism.to_string_with_prefix()
+        {
+            let message = format!("/{} is implied for a single address.", host);
+            let code = FindingCode::RedundantPrefix;
+            findings.push(Finding::new(Severity::Info, code, term, message));
+        }
+    }
+    let hosts = spf.a().into_iter().chain(spf.mx()).flatten();
+    for mechanism in hosts {
+        if let Some((kind, length)) = helpers::invalid_dual_cidr_length(&mechanism.raw()) {
+            let family = if kind == Kind::IpV4 { "ip4" } else { "ip6" };
+            let message = format!("{} is not a valid {} cidr length.", length, family);
+            let term = Some(mechanism.to_string());
+            let code = FindingCode::InvalidDualCidr;
+            findings.push(Finding::new(Severity::Error, code, term, message));
+        }
+    }
+    findings
+}
+
+// The terms of the source which a strict parse does not recognise. A lenient parse leaves
+// them out of the Spf.
+fn unknown_terms(spf: &Spf) -> Vec<String> {
+    if spf.source.is_empty() {
+        return Vec::new();
+    }
+    let options = ParseOptions::new().strict(true).lenient_whitespace(true);
+    let errors = match Spf::parse_terms(&spf.source, &options, usize::MAX, true) {
+        Ok(_) => return Vec::new(),
+        Err(errors) => errors,
+    };
+    errors
+        .iter()
+        .filter_map(SpfError::parse_error)
+        .filter(|error| match error.error() {
+            SpfError::UnknownTerm(_) => true,
+            SpfError::InvalidMechanism(error) => error.is_unknown_mechanism_name(),
+            _ => false,
+        })
+        .map(|error| error.term().to_string())
+        .collect()
+}
@@ -0,0 +1,84 @@
+//! The scopes of an `spf2.0` record. See RFC 4406.
+use crate::spf::SpfError;
+use std::str::FromStr;
+
+/// The identity an `spf2.0` record applies to. Found after the version. e.g. `spf2.0/mfrom,pra`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// The Purported Responsible Address. `pra`
+    Pra,
+    /// The MAIL FROM identity. `mfrom`
+    MFrom,
+}
+
+impl Scope {
+    /// Returns the scope as it is written in the version. `pra` or `mfrom`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Scope::Pra => "pra",
+            Scope::MFrom => "mfrom",
+        }
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = SpfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("pra") {
+            Ok(Scope::Pra)
+        } else if s.eq_ignore_ascii_case("mfrom") {
+            Ok(Scope::MFrom)
+        } else {
+            Err(SpfError::InvalidScope(s.to_string()))
+        }
+    }
+}
+
+// Parse the comma separated scopes which follow `spf2.0/`. Each scope may appear only once.
+pub(crate) fn parse_scopes(list: &str) -> Result<Vec<Scope>, SpfError> {
+    let mut scopes = Vec::new();
+    for scope in list.split(',') {
+        let scope: Scope = scope.parse()?;
+        if scopes.contains(&scope) {
+            return Err(SpfError::InvalidScope(scope.to_string()));
+        }
+        scopes.push(scope);
+    }
+    Ok(scopes)
+}
+
+#[test]
+fn scope_from_str() {
+    assert_eq!("pra".parse::<Scope>().unwrap(), Scope::Pra);
+    assert_eq!("MFrom".parse::<Scope>().unwrap(), Scope::MFrom);
+    assert_eq!(
+        "helo".parse::<Scope>().unwrap_err(),
+        SpfError::InvalidScope("helo".to_string())
+    );
+}
+#[test]
+fn scope_list() {
+    assert_eq!(
+        parse_scopes("mfrom,pra").unwrap(),
+        vec![Scope::MFrom, Scope::Pra]
+    );
+    assert_eq!(
+        parse_scopes("pra,pra").unwrap_err(),
+        SpfError::InvalidScope("pra".to_string())
+    );
+    assert_eq!(
+        parse_scopes("").unwrap_err(),
+        SpfError::InvalidScope("".to_string())
+    );
+    assert_eq!(
+        parse_scopes("pra,").unwrap_err(),
+        SpfError::InvalidScope("".to_string())
+    );
+}
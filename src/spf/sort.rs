@@ -0,0 +1,55 @@
+//! The orders in which the mechanisms of an Spf record may be sorted.
+use crate::helpers;
+use crate::mechanism::Mechanism;
+use ipnetwork::IpNetwork;
+
+/// The order used by [`Spf::sort_mechanisms()`](crate::Spf::sort_mechanisms)
+///
+/// Terms are always grouped by kind when building a string, in the order `a`, `mx`,
+/// `include:`, `ip4:`, `ip6:`, `exists:`, `ptr`, `redirect=`, `all`, `exp=` and then unknown
+/// modifiers. A strategy sets the order of the terms within each kind.
+///
+/// `ip4:` and `ip6:` networks are sorted by address and then prefix length, rather than as
+/// strings, by both `Lexical` and `ByKindThenValue`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortStrategy {
+    /// Keep the current order.
+    Preserve,
+    /// Sort by the term as it is written, ignoring case. A qualifier is part of the term, so
+    /// `-include:b.example.com` is placed before `include:a.example.com`.
+    Lexical,
+    /// Sort by the domain-spec, ignoring case, and then by the qualifier. Unknown modifiers are
+    /// sorted by name and then value.
+    ByKindThenValue,
+}
+
+pub(crate) fn sort_terms(mechanisms: &mut [Mechanism<String>], strategy: SortStrategy) {
+    match strategy {
+        SortStrategy::Preserve => {}
+        SortStrategy::Lexical => {
+            mechanisms.sort_by_cached_key(|m| m.to_string().to_ascii_lowercase())
+        }
+        SortStrategy::ByKindThenValue => mechanisms
+            .sort_by_cached_key(|m| (helpers::lowercase_domain_spec(&m.raw()), m.to_string())),
+    }
+}
+
+pub(crate) fn sort_networks(networks: &mut [Mechanism<IpNetwork>], strategy: SortStrategy) {
+    if strategy != SortStrategy::Preserve {
+        networks.sort_by_cached_key(|m| {
+            let network = m.as_network();
+            (network.ip(), network.prefix(), m.to_string())
+        });
+    }
+}
+
+pub(crate) fn sort_modifiers(modifiers: &mut [(String, String)], strategy: SortStrategy) {
+    match strategy {
+        SortStrategy::Preserve => {}
+        SortStrategy::Lexical => modifiers
+            .sort_by_cached_key(|(name, value)| format!("{}={}", name, value).to_ascii_lowercase()),
+        SortStrategy::ByKindThenValue => {
+            modifiers.sort_by_cached_key(|(name, value)| (name.to_ascii_lowercase(), value.clone()))
+        }
+    }
+}
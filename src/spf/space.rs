@@ -0,0 +1,140 @@
+//! The number of addresses authorised by the `ip4:` and `ip6:` networks of an Spf record.
+use crate::mechanism::{Mechanism, Qualifier};
+use crate::spf::aggregate::{merge, range};
+use crate::spf::Spf;
+use ipnetwork::IpNetwork;
+
+/// The addresses covered by a set of networks. See [`IpSpaceStats`](IpSpaceStats)
+///
+/// Addresses covered by more than one network are counted once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IpSpace {
+    ipv4_addresses: u64,
+    ipv6_64s: u128,
+}
+
+impl IpSpace {
+    /// Returns the number of ip4 addresses.
+    pub fn ipv4_addresses(&self) -> u64 {
+        self.ipv4_addresses
+    }
+    /// Returns the number of ip6 `/64` networks which hold at least one of the addresses.
+    /// A `/64` is the usual size of a single ip6 subnet.
+    pub fn ipv6_64s(&self) -> u128 {
+        self.ipv6_64s
+    }
+    /// Returns `true` if no address is covered.
+    pub fn is_empty(&self) -> bool {
+        self.ipv4_addresses == 0 && self.ipv6_64s == 0
+    }
+}
+
+/// The addresses of the `ip4:` and `ip6:` networks of an Spf, as a whole and for each
+/// qualifier. See [`Spf::ip_space()`](crate::Spf::ip_space)
+///
+/// Only the networks of the record are counted. `a`, `mx`, `include:` and other terms which
+/// need DNS lookups are not.
+///
+/// # Example:
+///```rust
+/// use decon_spf::Spf;
+/// use decon_spf::mechanism::Qualifier;
+/// let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.128/25 ~ip4:10.0.0.0/8 ip6:2001:db8::/48 -all"
+///     .parse()
+///     .unwrap();
+/// let stats = spf.ip_space();
+/// assert_eq!(stats.total().ipv4_addresses(), 256 + 16_777_216);
+/// assert_eq!(stats.total().ipv6_64s(), 65_536);
+/// assert_eq!(stats.qualifier(Qualifier::Pass).ipv4_addresses(), 256);
+/// assert_eq!(stats.qualifier(Qualifier::SoftFail).ipv4_addresses(), 16_777_216);
+/// assert_eq!(stats.is_overly_broad(16, 32), true);
+/// assert_eq!(stats.broad_networks(16, 32), ["10.0.0.0/8".parse().unwrap()]);
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpSpaceStats {
+    total: IpSpace,
+    pass: IpSpace,
+    fail: IpSpace,
+    softfail: IpSpace,
+    neutral: IpSpace,
+    networks: Vec<IpNetwork>,
+}
+
+impl IpSpaceStats {
+    /// Returns the addresses of every network, whatever its qualifier.
+    pub fn total(&self) -> IpSpace {
+        self.total
+    }
+    /// Returns the addresses of the networks with `qualifier`.
+    pub fn qualifier(&self, qualifier: Qualifier) -> IpSpace {
+        match qualifier {
+            Qualifier::Pass => self.pass,
+            Qualifier::Fail => self.fail,
+            Qualifier::SoftFail => self.softfail,
+            Qualifier::Neutral => self.neutral,
+        }
+    }
+    /// Returns the networks, whatever their qualifier, which are larger than a network
+    /// with the given prefix length. e.g. `10.0.0.0/8` is broader than a minimum of `16`.
+    pub fn broad_networks(&self, ipv4_min_prefix: u8, ipv6_min_prefix: u8) -> Vec<IpNetwork> {
+        self.networks
+            .iter()
+            .filter(|network| match network {
+                IpNetwork::V4(n) => n.prefix() < ipv4_min_prefix,
+                IpNetwork::V6(n) => n.prefix() < ipv6_min_prefix,
+            })
+            .copied()
+            .collect()
+    }
+    /// Returns `true` if any network is larger than a network with the given prefix length.
+    /// See [`broad_networks()`](IpSpaceStats::broad_networks)
+    pub fn is_overly_broad(&self, ipv4_min_prefix: u8, ipv6_min_prefix: u8) -> bool {
+        !self
+            .broad_networks(ipv4_min_prefix, ipv6_min_prefix)
+            .is_empty()
+    }
+}
+
+pub(crate) fn stats(spf: &Spf) -> IpSpaceStats {
+    let networks: Vec<&Mechanism<IpNetwork>> =
+        spf.ip4.iter().chain(spf.ip6.iter()).flatten().collect();
+    let with = |qualifier: Qualifier| {
+        space(
+            networks
+                .iter()
+                .filter(|m| *m.qualifier() == qualifier)
+                .map(|m| m.as_network()),
+        )
+    };
+    IpSpaceStats {
+        total: space(networks.iter().map(|m| m.as_network())),
+        pass: with(Qualifier::Pass),
+        fail: with(Qualifier::Fail),
+        softfail: with(Qualifier::SoftFail),
+        neutral: with(Qualifier::Neutral),
+        networks: networks.iter().map(|m| *m.as_network()).collect(),
+    }
+}
+
+fn space<'a>(networks: impl Iterator<Item = &'a IpNetwork>) -> IpSpace {
+    let (mut ip4, mut ip6) = (Vec::new(), Vec::new());
+    for network in networks {
+        let (first, last) = range(network);
+        if network.is_ipv4() {
+            ip4.push((first, last));
+        } else {
+            // Each /64 is counted once, so ranges are compared as /64s.
+            ip6.push((first >> 64, last >> 64));
+        }
+    }
+    let count = |ranges: Vec<(u128, u128)>| -> u128 {
+        merge(ranges)
+            .iter()
+            .map(|(first, last)| last - first + 1)
+            .sum()
+    };
+    IpSpace {
+        ipv4_addresses: count(ip4) as u64,
+        ipv6_64s: count(ip6),
+    }
+}
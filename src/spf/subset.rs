@@ -0,0 +1,130 @@
+//! Comparing the addresses authorised by the `ip4:` and `ip6:` networks of two Spf records.
+use crate::mechanism::{Mechanism, MechanismRef, Qualifier};
+use crate::spf::aggregate::{cidrs, merge, range};
+use crate::spf::Spf;
+use ipnetwork::IpNetwork;
+
+/// Whether the addresses authorised by one Spf are also authorised by another. See
+/// [`Spf::is_subset_of()`](crate::Spf::is_subset_of)
+///
+/// Only `ip4:` and `ip6:` networks, and `all`, are compared. `a`, `mx`, `include:`, `ptr`,
+/// `exists:` and `redirect=` need DNS lookups, which are not made. They are listed as unknown
+/// instead, and may authorise addresses which are not compared.
+///
+/// # Example:
+///```rust
+/// use decon_spf::Spf;
+/// let old: Spf = "v=spf1 ip4:192.0.2.0/25 ip4:198.51.100.1 mx -all".parse().unwrap();
+/// let new: Spf = "v=spf1 ip4:192.0.2.0/24 include:_spf.example.com -all".parse().unwrap();
+/// let result = old.is_subset_of(&new);
+/// assert_eq!(result.is_subset(), false);
+/// assert_eq!(result.uncovered(), ["198.51.100.1/32".parse().unwrap()]);
+/// assert_eq!(result.unknown()[0].to_string(), "mx");
+/// assert_eq!(result.other_unknown()[0].to_string(), "include:_spf.example.com");
+/// assert_eq!(result.is_certain(), false);
+///```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubsetResult {
+    uncovered: Vec<IpNetwork>,
+    unknown: Vec<Mechanism<String>>,
+    other_unknown: Vec<Mechanism<String>>,
+}
+
+impl SubsetResult {
+    /// Returns `true` if every network authorised by the first record is authorised by the
+    /// second.
+    pub fn is_subset(&self) -> bool {
+        self.uncovered.is_empty()
+    }
+    /// Returns `true` if neither record has a term which needs a DNS lookup, so the result
+    /// covers every address the records authorise.
+    pub fn is_certain(&self) -> bool {
+        self.unknown.is_empty() && self.other_unknown.is_empty()
+    }
+    /// Returns the networks authorised by the first record which are not authorised by the
+    /// second, as the fewest networks which cover them.
+    pub fn uncovered(&self) -> &[IpNetwork] {
+        &self.uncovered
+    }
+    /// Returns the terms of the first record which need a DNS lookup and may authorise
+    /// addresses.
+    pub fn unknown(&self) -> &[Mechanism<String>] {
+        &self.unknown
+    }
+    /// Returns the terms of the second record which need a DNS lookup and may authorise
+    /// addresses.
+    pub fn other_unknown(&self) -> &[Mechanism<String>] {
+        &self.other_unknown
+    }
+}
+
+pub(crate) fn compare(spf: &Spf, other: &Spf) -> SubsetResult {
+    let (ip4, ip6, unknown) = authorised(spf);
+    let (other_ip4, other_ip6, other_unknown) = authorised(other);
+    let mut uncovered = difference(&ip4, &other_ip4, true);
+    uncovered.extend(difference(&ip6, &other_ip6, false));
+    SubsetResult {
+        uncovered,
+        unknown,
+        other_unknown,
+    }
+}
+
+// Whether a term with `qualifier` gives a result other than a failure.
+fn authorises(qualifier: &Qualifier) -> bool {
+    qualifier.is_pass() || qualifier.is_neutral()
+}
+
+type Ranges = Vec<(u128, u128)>;
+
+// The ip4 and ip6 ranges authorised by `spf`, and its terms which need a lookup.
+fn authorised(spf: &Spf) -> (Ranges, Ranges, Vec<Mechanism<String>>) {
+    let (mut ip4, mut ip6, mut unknown) = (Vec::new(), Vec::new(), Vec::new());
+    for term in spf.iter() {
+        match term {
+            MechanismRef::IP(m) if authorises(m.qualifier()) => {
+                let network = m.as_network();
+                if network.is_ipv4() {
+                    ip4.push(range(network));
+                } else {
+                    ip6.push(range(network));
+                }
+            }
+            MechanismRef::TXT(m) if m.kind().is_all() && authorises(m.qualifier()) => {
+                ip4.push((0, u128::from(u32::MAX)));
+                ip6.push((0, u128::MAX));
+            }
+            MechanismRef::TXT(m) if m.kind().is_redirect() => unknown.push(m.clone()),
+            MechanismRef::TXT(m) if !m.kind().is_modifier() && authorises(m.qualifier()) => {
+                unknown.push(m.clone())
+            }
+            _ => {}
+        }
+    }
+    (merge(ip4), merge(ip6), unknown)
+}
+
+// The networks of `ranges` which are not in `other`. Both are merged.
+fn difference(ranges: &[(u128, u128)], other: &[(u128, u128)], is_ipv4: bool) -> Vec<IpNetwork> {
+    let mut uncovered = Vec::new();
+    for &(first, last) in ranges {
+        let mut next = Some(first);
+        for &(other_first, other_last) in other {
+            let start = match next {
+                Some(start) if other_first <= last => start,
+                _ => break,
+            };
+            if other_last < start {
+                continue;
+            }
+            if other_first > start {
+                uncovered.extend(cidrs(start, other_first - 1, is_ipv4));
+            }
+            next = other_last.checked_add(1).filter(|n| *n <= last);
+        }
+        if let Some(start) = next {
+            uncovered.extend(cidrs(start, last, is_ipv4));
+        }
+    }
+    uncovered
+}
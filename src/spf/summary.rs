@@ -0,0 +1,154 @@
+//! A summary of the numbers which describe an Spf record.
+use crate::mechanism::{Kind, Qualifier};
+use crate::spf::Spf;
+
+/// The counts, lookups and length of an Spf record, found without any DNS lookups. See
+/// [`Spf::summary()`](crate::Spf::summary)
+///
+/// With the `serde` feature enabled the summary implements `serde::Serialize`.
+///
+/// # Example:
+///```rust
+/// use decon_spf::mechanism::{Kind, Qualifier};
+/// use decon_spf::Spf;
+/// let spf: Spf = "v=spf1 mx include:_spf.example.com ip4:192.0.2.0/24 ~all".parse().unwrap();
+/// let summary = spf.summary();
+/// assert_eq!(summary.count_of(Kind::IpV4), 1);
+/// assert_eq!(summary.lookups(), 2);
+/// assert_eq!(summary.qualifier_count(Qualifier::SoftFail), 1);
+/// assert_eq!(
+///     summary.to_string(),
+///     "a=0 mx=1 include=1 ip4=1 ip6=0 exists=0 ptr=0 all=1 redirect=0 exp=0 \
+///      pass=3 fail=0 softfail=1 neutral=0 lookups=2 length=56 warnings=0"
+/// );
+///```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SpfSummary {
+    a: usize,
+    mx: usize,
+    include: usize,
+    ip4: usize,
+    ip6: usize,
+    exists: usize,
+    ptr: usize,
+    all: usize,
+    redirect: usize,
+    exp: usize,
+    pass: usize,
+    fail: usize,
+    softfail: usize,
+    neutral: usize,
+    lookups: usize,
+    length: usize,
+    warnings: usize,
+}
+
+pub(crate) fn summary(spf: &Spf) -> SpfSummary {
+    let mut summary = SpfSummary {
+        a: spf.count_of(Kind::A),
+        mx: spf.count_of(Kind::MX),
+        include: spf.count_of(Kind::Include),
+        ip4: spf.count_of(Kind::IpV4),
+        ip6: spf.count_of(Kind::IpV6),
+        exists: spf.count_of(Kind::Exists),
+        ptr: spf.count_of(Kind::Ptr),
+        all: spf.count_of(Kind::All),
+        redirect: spf.count_of(Kind::Redirect),
+        exp: spf.count_of(Kind::Exp),
+        lookups: spf.lookup_count(),
+        length: spf.serialized_len(),
+        warnings: spf.parse_warnings().map_or(0, Vec::len),
+        ..SpfSummary::default()
+    };
+    // Only mechanisms have a qualifier which matters.
+    for mechanism in spf.iter().filter(|m| !m.kind().is_modifier()) {
+        match mechanism.qualifier() {
+            Qualifier::Pass => summary.pass += 1,
+            Qualifier::Fail => summary.fail += 1,
+            Qualifier::SoftFail => summary.softfail += 1,
+            Qualifier::Neutral => summary.neutral += 1,
+        }
+    }
+    summary
+}
+
+impl SpfSummary {
+    /// Returns the number of mechanisms or modifiers of `kind`. As
+    /// [`Spf::count_of()`](crate::Spf::count_of)
+    pub fn count_of(&self, kind: Kind) -> usize {
+        match kind {
+            Kind::A => self.a,
+            Kind::MX => self.mx,
+            Kind::Include => self.include,
+            Kind::IpV4 => self.ip4,
+            Kind::IpV6 => self.ip6,
+            Kind::Exists => self.exists,
+            Kind::Ptr => self.ptr,
+            Kind::All => self.all,
+            Kind::Redirect => self.redirect,
+            Kind::Exp => self.exp,
+        }
+    }
+    /// Returns the number of mechanisms with `qualifier`. Modifiers are not counted.
+    pub fn qualifier_count(&self, qualifier: Qualifier) -> usize {
+        match qualifier {
+            Qualifier::Pass => self.pass,
+            Qualifier::Fail => self.fail,
+            Qualifier::SoftFail => self.softfail,
+            Qualifier::Neutral => self.neutral,
+        }
+    }
+    /// Returns the number of DNS lookups. As [`Spf::lookup_count()`](crate::Spf::lookup_count)
+    pub fn lookups(&self) -> usize {
+        self.lookups
+    }
+    /// Returns the length of the record as a string. As
+    /// [`Spf::serialized_len()`](crate::Spf::serialized_len)
+    pub fn length(&self) -> usize {
+        self.length
+    }
+    /// Returns the number of warnings found while parsing. See
+    /// [`Spf::parse_warnings()`](crate::Spf::parse_warnings)
+    pub fn warnings(&self) -> usize {
+        self.warnings
+    }
+    /// Returns `true` if the record has a `redirect=` modifier.
+    pub fn has_redirect(&self) -> bool {
+        self.redirect > 0
+    }
+    /// Returns `true` if the record has an `exp=` modifier.
+    pub fn has_exp(&self) -> bool {
+        self.exp > 0
+    }
+}
+
+impl std::fmt::Display for SpfSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "a={} mx={} include={} ip4={} ip6={} exists={} ptr={} all={} redirect={} exp={} ",
+            self.a,
+            self.mx,
+            self.include,
+            self.ip4,
+            self.ip6,
+            self.exists,
+            self.ptr,
+            self.all,
+            self.redirect,
+            self.exp
+        )?;
+        write!(
+            f,
+            "pass={} fail={} softfail={} neutral={} lookups={} length={} warnings={}",
+            self.pass,
+            self.fail,
+            self.softfail,
+            self.neutral,
+            self.lookups,
+            self.length,
+            self.warnings
+        )
+    }
+}
@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 mod parse {
 
     use crate::spf::Spf;
@@ -9,7 +8,7 @@ mod parse {
         let input = "v=spf1 a ~all";
 
         let spf: Spf = input.parse().unwrap();
-        assert_eq!(spf.is_valid(), true);
+        assert_eq!(spf.is_valid(), false);
         assert_eq!(spf.version(), "v=spf1");
         assert!(spf.a().is_some());
         assert_eq!(spf.a().unwrap()[0].qualifier().is_pass(), true);
@@ -45,3 +44,53 @@ mod parse {
         assert_eq!(spf.a().unwrap()[0].to_string(), "~a:example.com/24");
     }
 }
+#[cfg(test)]
+mod dual_cidr {
+
+    use crate::mechanism::MechanismError;
+    use crate::spf::{Spf, SpfError};
+
+    #[test]
+    fn round_trip() {
+        let input = "v=spf1 a:example.com/24//64 a//64 mx/30//96 mx:example.com//64 -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.a().unwrap().len(), 2);
+        assert_eq!(spf.mx().unwrap().len(), 2);
+        assert_eq!(spf.a().unwrap()[0].raw(), "example.com/24//64");
+        assert_eq!(spf.a().unwrap()[1].raw(), "//64");
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn malformed() {
+        for term in ["a/24//", "mx//", "a:foo/24/", "mx:/24", "-a/24/64"] {
+            let input = format!("v=spf1 {} -all", term);
+            let expected =
+                SpfError::InvalidMechanism(MechanismError::InvalidDualCidr(term.to_string()));
+
+            let err = input.parse::<Spf>().unwrap_err();
+            assert_eq!(err.inner(), &expected);
+            assert_eq!(err.parse_error().unwrap().term_index(), 1);
+            let err = Spf::from_str_strict(&input).unwrap_err();
+            assert_eq!(err.inner(), &expected);
+        }
+    }
+    #[test]
+    fn out_of_range_ip4_cidr() {
+        let input = "v=spf1 a/33 -all";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidIPv4CidrLength("33".to_string())
+        );
+    }
+    #[test]
+    fn out_of_range_ip6_cidr() {
+        let input = "v=spf1 mx:example.com/24//129 -all";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(err.is_invalid_ipv6_cidr_length(), true);
+        assert_eq!(err.parse_error().unwrap().term_index(), 1);
+    }
+}
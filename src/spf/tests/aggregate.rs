@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod networks {
+
+    use crate::spf::Spf;
+    use ipnetwork::IpNetwork;
+
+    fn aggregate(record: &str) -> (usize, String) {
+        let mut spf: Spf = record.parse().unwrap();
+        let removed = spf.aggregate_ips();
+        (removed, spf.to_string())
+    }
+    fn networks(list: &[&str]) -> Vec<IpNetwork> {
+        list.iter().map(|n| n.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn bloated_record() {
+        let record = "v=spf1 ip4:198.51.100.0/26 ip4:198.51.100.64/26 ip4:198.51.100.128/26 \
+                      ip4:198.51.100.192/26 ip4:198.51.100.10 ip4:198.51.100.77 \
+                      ip4:203.0.113.0/25 ip4:203.0.113.128/25 ip4:203.0.113.64/27 \
+                      ip4:192.0.2.4/30 ip4:192.0.2.0/30 include:_spf.example.com -all";
+        let mut spf: Spf = record.parse().unwrap();
+        let before = spf.to_string().len();
+        assert_eq!(spf.aggregate_ips(), 8);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:_spf.example.com ip4:192.0.2.0/29 ip4:198.51.100.0/24 \
+             ip4:203.0.113.0/24 -all"
+        );
+        assert_eq!(spf.to_string().len() < before - 150, true);
+        assert_eq!(spf.is_modified(), true);
+    }
+    #[test]
+    fn contained_network() {
+        assert_eq!(
+            aggregate("v=spf1 ip4:192.0.2.16/28 ip4:192.0.2.0/24 -all"),
+            (1, "v=spf1 ip4:192.0.2.0/24 -all".to_string())
+        );
+    }
+    #[test]
+    fn adjacent_networks() {
+        assert_eq!(
+            aggregate("v=spf1 ip4:192.0.2.128/25 ip4:192.0.2.0/25 -all"),
+            (1, "v=spf1 ip4:192.0.2.0/24 -all".to_string())
+        );
+    }
+    #[test]
+    fn adjacent_but_not_aligned() {
+        let record = "v=spf1 ip4:192.0.2.128/25 ip4:192.0.3.0/25 -all";
+        assert_eq!(aggregate(record), (0, record.to_string()));
+    }
+    #[test]
+    fn partly_merged() {
+        assert_eq!(
+            aggregate("v=spf1 ip4:192.0.2.0/26 ip4:192.0.2.64/26 ip4:192.0.2.128/26 -all"),
+            (
+                1,
+                "v=spf1 ip4:192.0.2.0/25 ip4:192.0.2.128/26 -all".to_string()
+            )
+        );
+    }
+    #[test]
+    fn different_qualifiers_untouched() {
+        let record = "v=spf1 ip4:192.0.2.0/25 -ip4:192.0.2.128/25 ~ip4:192.0.2.16/28 -all";
+        assert_eq!(aggregate(record), (0, record.to_string()));
+    }
+    #[test]
+    fn not_moved_past_other_qualifier() {
+        assert_eq!(
+            aggregate(
+                "v=spf1 ip4:192.0.2.0/25 ip4:192.0.2.128/25 -ip4:198.51.100.0/24 \
+                 ip4:198.51.100.0/25 ip4:198.51.100.128/25 -all"
+            ),
+            (
+                2,
+                "v=spf1 ip4:192.0.2.0/24 -ip4:198.51.100.0/24 ip4:198.51.100.0/24 -all".to_string()
+            )
+        );
+    }
+    #[test]
+    fn host_without_prefix_kept() {
+        let record = "v=spf1 ip4:192.0.2.1 ip4:192.0.2.1/32 ip4:203.0.113.5 -all";
+        assert_eq!(
+            aggregate(record),
+            (1, "v=spf1 ip4:192.0.2.1 ip4:203.0.113.5 -all".to_string())
+        );
+    }
+    #[test]
+    fn ipv6() {
+        assert_eq!(
+            aggregate(
+                "v=spf1 ip6:2001:db8::/49 ip6:2001:db8:0:8000::/49 ip6:2001:db8::1 \
+                 ip6:2001:db8:1::/48 -all"
+            ),
+            (3, "v=spf1 ip6:2001:db8::/47 -all".to_string())
+        );
+    }
+    #[test]
+    fn whole_address_space() {
+        assert_eq!(
+            aggregate("v=spf1 ip4:0.0.0.0/1 ip4:128.0.0.0/1 ip6:::/1 ip6:8000::/1 -all"),
+            (2, "v=spf1 ip4:0.0.0.0/0 ip6:::/0 -all".to_string())
+        );
+    }
+    #[test]
+    fn no_networks() {
+        let mut spf: Spf = "v=spf1 mx -all".parse().unwrap();
+        assert_eq!(spf.aggregate_ips(), 0);
+        assert_eq!(spf.aggregated_ips(), (vec![], vec![]));
+        assert_eq!(spf.to_string(), "v=spf1 mx -all");
+    }
+    #[test]
+    fn preview() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/25 ip4:192.0.2.128/25 -ip4:198.51.100.0/24 \
+                        ip6:2001:db8::/33 ip6:2001:db8:8000::/33 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            spf.aggregated_ips(),
+            (
+                networks(&["192.0.2.0/24", "198.51.100.0/24"]),
+                networks(&["2001:db8::/32"])
+            )
+        );
+        assert_eq!(spf.is_modified(), false);
+        assert_eq!(spf.ip4().unwrap().len(), 3);
+    }
+}
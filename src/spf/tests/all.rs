@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::Qualifier;
+    use crate::spf::{Spf, SpfError, Warning};
+
+    #[test]
+    fn single_all() {
+        let spf: Spf = "v=spf1 a -all".parse().unwrap();
+        assert_eq!(spf.all().unwrap().is_fail(), true);
+        assert_eq!(spf.parse_warnings().is_none(), true);
+    }
+    #[test]
+    fn each_qualifier() {
+        for (input, qualifier) in [
+            ("v=spf1 a all", Qualifier::Pass),
+            ("v=spf1 a +all", Qualifier::Pass),
+            ("v=spf1 a -all", Qualifier::Fail),
+            ("v=spf1 a ~all", Qualifier::SoftFail),
+            ("v=spf1 a ?all", Qualifier::Neutral),
+        ] {
+            let spf: Spf = input.parse().unwrap();
+            assert_eq!(spf.all().unwrap().qualifier(), &qualifier);
+        }
+    }
+    #[test]
+    fn name_ending_in_all() {
+        let spf: Spf = "v=spf1 a ball".parse().unwrap();
+        assert_eq!(spf.all().is_none(), true);
+        assert_eq!(Spf::from_str_strict("v=spf1 a -xall").is_err(), true);
+    }
+    #[test]
+    fn duplicate_all_keeps_first() {
+        let input = "v=spf1 a ~all -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.all().unwrap().is_softfail(), true);
+        assert_eq!(
+            spf.parse_warnings().unwrap(),
+            &vec![Warning::DuplicateAll {
+                first: 2,
+                second: 3
+            }]
+        );
+        assert_eq!(spf.to_string(), "v=spf1 a ~all");
+    }
+    #[test]
+    fn three_all() {
+        let spf: Spf = "v=spf1 -all mx ?all all".parse().unwrap();
+        let warnings = spf.parse_warnings().unwrap();
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(
+            warnings[0],
+            Warning::MechanismAfterAll {
+                all: 1,
+                position: 2
+            }
+        );
+        assert_eq!(
+            warnings[1],
+            Warning::DuplicateAll {
+                first: 1,
+                second: 3
+            }
+        );
+        assert_eq!(
+            warnings[2],
+            Warning::DuplicateAll {
+                first: 1,
+                second: 4
+            }
+        );
+    }
+    #[test]
+    fn duplicate_all_strict() {
+        let err = Spf::from_str_strict("v=spf1 a ~all -all").unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::DuplicateAll {
+                first: 2,
+                second: 3
+            }
+        );
+    }
+    #[test]
+    fn mechanism_after_all() {
+        let input = "v=spf1 -all include:_spf.example.com";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(
+            spf.parse_warnings().unwrap(),
+            &vec![Warning::MechanismAfterAll {
+                all: 1,
+                position: 2
+            }]
+        );
+        // The include is still parsed.
+        assert_eq!(spf.includes().unwrap().len(), 1);
+    }
+    #[test]
+    fn all_in_the_middle() {
+        let input = "v=spf1 a ~all mx ip4:203.0.113.0/24";
+
+        let spf: Spf = input.parse().unwrap();
+        let warnings = spf.parse_warnings().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].is_mechanism_after_all(), true);
+        assert_eq!(
+            warnings[1],
+            Warning::MechanismAfterAll {
+                all: 2,
+                position: 4
+            }
+        );
+    }
+    #[test]
+    fn all_at_the_end() {
+        let spf: Spf = "v=spf1 a mx include:_spf.example.com -all".parse().unwrap();
+        assert_eq!(spf.parse_warnings().is_none(), true);
+    }
+    #[test]
+    fn only_all() {
+        let spf: Spf = "v=spf1 -all".parse().unwrap();
+        assert_eq!(spf.parse_warnings().is_none(), true);
+    }
+    #[test]
+    fn modifiers_after_all() {
+        let spf: Spf = "v=spf1 a -all exp=explain.example.com x-note=ok"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.parse_warnings().is_none(), true);
+    }
+    #[test]
+    fn mechanism_after_all_strict() {
+        let err = Spf::from_str_strict("v=spf1 -all include:_spf.example.com").unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::MechanismAfterAll {
+                all: 1,
+                position: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,357 @@
+#[cfg(test)]
+mod try_append {
+
+    use crate::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
+    use crate::spf::{Spf, SpfError};
+
+    fn spf(input: &str) -> Spf {
+        input.parse().unwrap()
+    }
+    fn redirect() -> Mechanism<String> {
+        Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap()
+    }
+
+    #[test]
+    fn appends() {
+        let mut spf = spf("v=spf1 a");
+        spf.try_append_mechanism(Mechanism::mx(Qualifier::Pass))
+            .unwrap();
+        spf.try_append_mechanism(Mechanism::all(Qualifier::Fail))
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a mx -all");
+    }
+    #[test]
+    fn all_with_redirect() {
+        let mut spf = spf("v=spf1 a redirect=_spf.example.com");
+        spf.try_append_mechanism(Mechanism::all(Qualifier::Fail))
+            .unwrap();
+        assert_eq!(spf.all().is_some(), true);
+        assert_eq!(spf.to_string(), "v=spf1 a -all redirect=_spf.example.com");
+    }
+    #[test]
+    fn redirect_with_all() {
+        let mut spf = spf("v=spf1 a -all");
+        spf.try_append_mechanism(redirect()).unwrap();
+        // The `all` is not removed.
+        assert_eq!(spf.to_string(), "v=spf1 a -all redirect=_spf.example.com");
+    }
+    #[test]
+    fn duplicate_redirect() {
+        let mut spf = spf("v=spf1 a redirect=_spf.example.net");
+        let err = spf.try_append_mechanism(redirect()).unwrap_err();
+        assert_eq!(err, SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect));
+        assert_eq!(spf.redirect().unwrap().raw(), "_spf.example.net");
+    }
+    #[test]
+    fn duplicate_exp() {
+        let mut spf = spf("v=spf1 a -all exp=explain.example.com");
+        let err = spf
+            .try_append_mechanism(Mechanism::exp("other.example.com").unwrap())
+            .unwrap_err();
+        assert_eq!(err, SpfError::ModifierMayOccurOnlyOnce(Kind::Exp));
+    }
+    #[test]
+    fn duplicate_all() {
+        let mut spf = spf("v=spf1 a ~all");
+        let err = spf
+            .try_append_mechanism(Mechanism::all(Qualifier::Fail))
+            .unwrap_err();
+        assert_eq!(err, SpfError::MechanismAlreadyPresent(Kind::All));
+        assert_eq!(spf.to_string(), "v=spf1 a ~all");
+    }
+    #[test]
+    fn duplicate_ptr() {
+        let mut spf = spf("v=spf1 ptr -all");
+        let err = spf
+            .try_append_mechanism(Mechanism::ptr(Qualifier::Fail))
+            .unwrap_err();
+        assert_eq!(err, SpfError::MechanismAlreadyPresent(Kind::Ptr));
+    }
+    #[test]
+    fn ip_kind() {
+        let mut spf = spf("v=spf1 a -all");
+        let ip4 = Mechanism::generic_inclusive(
+            Kind::IpV4,
+            Qualifier::Pass,
+            Some("192.0.2.0/24".to_string()),
+        );
+        spf.try_append_mechanism(ip4).unwrap();
+        assert_eq!(spf.ip4().unwrap().len(), 1);
+        assert_eq!(spf.to_string(), "v=spf1 a ip4:192.0.2.0/24 -all");
+    }
+    #[test]
+    fn ip_kind_invalid() {
+        let mut spf = spf("v=spf1 a -all");
+        for (kind, rrdata) in [
+            (Kind::IpV4, Some("example.com")),
+            (Kind::IpV4, Some("2001:db8::/32")),
+            (Kind::IpV6, Some("192.0.2.0/24")),
+            (Kind::IpV4, Some("192.0.2.0/33")),
+            (Kind::IpV6, None),
+        ] {
+            let ip =
+                Mechanism::generic_inclusive(kind, Qualifier::Fail, rrdata.map(|r| r.to_string()));
+            let err = spf.try_append_mechanism(ip).unwrap_err();
+            assert_eq!(err.is_invalid_mechanism(), true, "{:?}", rrdata);
+        }
+        let ip4 = Mechanism::generic_inclusive(
+            Kind::IpV4,
+            Qualifier::Fail,
+            Some("2001:db8::/32".to_string()),
+        );
+        assert_eq!(
+            spf.try_append_mechanism(ip4).unwrap_err(),
+            SpfError::InvalidMechanism(MechanismError::NotIP4Network("2001:db8::/32".to_string()))
+        );
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+    #[test]
+    fn missing_value() {
+        let mut spf = spf("v=spf1 a -all");
+        let include = Mechanism::<String>::new(Kind::Include, Qualifier::Pass);
+        let err = spf.try_append_mechanism(include).unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat(
+                "include:".to_string()
+            ))
+        );
+        assert_eq!(spf.includes().is_none(), true);
+    }
+    #[test]
+    fn unexpected_value() {
+        let mut spf = spf("v=spf1 a");
+        let all = Mechanism::generic_inclusive(
+            Kind::All,
+            Qualifier::Fail,
+            Some("example.com".to_string()),
+        );
+        let err = spf.try_append_mechanism(all).unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat(
+                "-allexample.com".to_string()
+            ))
+        );
+        assert_eq!(spf.all().is_none(), true);
+    }
+}
+#[cfg(test)]
+mod append_str {
+
+    use crate::mechanism::{Kind, MechanismError};
+    use crate::spf::{Spf, SpfError};
+
+    fn v1() -> Spf {
+        let mut spf = Spf::new();
+        spf.set_v1();
+        spf
+    }
+
+    #[test]
+    fn routes_each_kind() {
+        let mut spf = v1();
+        for term in [
+            "~all",
+            "ip6:2001:db8::/32",
+            "a/24",
+            "exp=explain.example.com",
+            "ip4:192.0.2.1",
+            "?mx:example.com",
+            "x-note=ok",
+            "include:_spf.example.com",
+            "exists:%{i}._spf.example.com",
+            "ptr",
+        ] {
+            spf.append_str(term).unwrap();
+        }
+        assert_eq!(spf.ip4().unwrap().len(), 1);
+        assert_eq!(spf.ip6().unwrap().len(), 1);
+        assert_eq!(spf.unknown_modifiers().unwrap()[0].0, "x-note");
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 a/24 ?mx:example.com include:_spf.example.com ip4:192.0.2.1 \
+             ip6:2001:db8::/32 exists:%{i}._spf.example.com ptr ~all \
+             exp=explain.example.com x-note=ok"
+        );
+    }
+    #[test]
+    fn matches_parsed_record() {
+        let input = "v=spf1 a include:_spf.example.com ip4:203.0.113.0/24 -all";
+        let mut spf = v1();
+        for term in input.split(' ').skip(1) {
+            spf.append_str(term).unwrap();
+        }
+        assert_eq!(spf.to_string(), input.parse::<Spf>().unwrap().to_string());
+    }
+    #[test]
+    fn rejects_whitespace() {
+        let mut spf = v1();
+        assert_eq!(
+            spf.append_str("a -all").unwrap_err(),
+            SpfError::WhiteSpaceSyntaxError
+        );
+        assert_eq!(
+            spf.append_str(" a").unwrap_err(),
+            SpfError::WhiteSpaceSyntaxError
+        );
+        assert_eq!(spf.append_str("").unwrap_err(), SpfError::EmptyInput);
+        assert_eq!(spf.to_string(), "v=spf1");
+    }
+    #[test]
+    fn rejects_version() {
+        let mut spf = v1();
+        for term in ["v=spf1", "spf2.0/pra"] {
+            assert_eq!(
+                spf.append_str(term).unwrap_err(),
+                SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat(
+                    term.to_string()
+                ))
+            );
+        }
+        assert_eq!(spf.unknown_modifiers().is_none(), true);
+    }
+    #[test]
+    fn propagates_parse_errors() {
+        let mut spf = v1();
+        assert_eq!(
+            spf.append_str("ip4:192.0.2.0/33")
+                .unwrap_err()
+                .is_invalid_mechanism(),
+            true
+        );
+        assert_eq!(
+            spf.append_str("bogus:example.com").unwrap_err(),
+            SpfError::InvalidMechanism(MechanismError::UnknownMechanismName("bogus".to_string()))
+        );
+        assert_eq!(
+            spf.append_str("include:").unwrap_err(),
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat(
+                "include:".to_string()
+            ))
+        );
+    }
+    #[test]
+    fn propagates_append_errors() {
+        let mut spf = v1();
+        spf.append_str("redirect=_spf.example.com").unwrap();
+        spf.append_str("-all").unwrap();
+        assert_eq!(
+            spf.append_str("~all").unwrap_err(),
+            SpfError::MechanismAlreadyPresent(Kind::All)
+        );
+        assert_eq!(
+            spf.append_str("redirect=_spf.example.net").unwrap_err(),
+            SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect)
+        );
+        assert_eq!(
+            spf.append_str("+redirect=_spf.example.net")
+                .unwrap_err()
+                .is_invalid_mechanism(),
+            true
+        );
+    }
+}
+#[cfg(test)]
+mod try_append_ip {
+
+    use crate::mechanism::{Kind, Mechanism, ParsedMechanism, Qualifier};
+    use crate::spf::{Spf, SpfError};
+    use ipnetwork::IpNetwork;
+
+    fn wrong(kind: Kind, network: &str) -> Mechanism<IpNetwork> {
+        Mechanism::generic_inclusive(kind, Qualifier::Pass, Some(network.parse().unwrap()))
+    }
+
+    #[test]
+    fn appends() {
+        let mut spf = Spf::new_v1();
+        spf.try_append_ip_mechanism("ip4:203.0.113.0/24".parse().unwrap())
+            .unwrap();
+        spf.try_append_ip_mechanism("-ip6:2001:db8::/32".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:203.0.113.0/24 -ip6:2001:db8::/32"
+        );
+    }
+    #[test]
+    fn not_an_ip_kind() {
+        let mut spf = Spf::new_v1();
+        assert_eq!(
+            spf.try_append_ip_mechanism(wrong(Kind::A, "203.0.113.0/24")),
+            Err(SpfError::WrongMechanismKind(Kind::A))
+        );
+        assert_eq!(spf.to_string(), "v=spf1");
+    }
+    #[test]
+    fn family_mismatch() {
+        let mut spf = Spf::new_v1();
+        assert_eq!(
+            spf.try_append_ip_mechanism(wrong(Kind::IpV4, "2001:db8::/32")),
+            Err(SpfError::WrongMechanismKind(Kind::IpV4))
+        );
+        assert_eq!(
+            spf.try_append_ip_mechanism(wrong(Kind::IpV6, "203.0.113.0/24")),
+            Err(SpfError::WrongMechanismKind(Kind::IpV6))
+        );
+        assert_eq!(spf.ip4().is_none(), true);
+        assert_eq!(spf.ip6().is_none(), true);
+    }
+    #[test]
+    fn append_does_not_panic() {
+        let mut spf = Spf::new_v1();
+        spf.append_ip_mechanism(wrong(Kind::A, "203.0.113.0/24"));
+        spf.append_ip_mechanism(wrong(Kind::Include, "2001:db8::/32"));
+        spf.append_ip_mechanism(wrong(Kind::IpV4, "2001:db8::/32"));
+        assert_eq!(spf.to_string(), "v=spf1");
+    }
+    #[test]
+    fn from_mechanisms() {
+        let mechanisms = vec![ParsedMechanism::IP(wrong(Kind::MX, "203.0.113.0/24"))];
+        assert_eq!(
+            Spf::from_mechanisms("v=spf1", mechanisms).unwrap_err(),
+            SpfError::WrongMechanismKind(Kind::MX)
+        );
+    }
+}
+#[cfg(test)]
+mod append_ip_kind {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::Spf;
+
+    fn ip(kind: Kind, rrdata: Option<&str>) -> Mechanism<String> {
+        Mechanism::generic_inclusive(kind, Qualifier::SoftFail, rrdata.map(|r| r.to_string()))
+    }
+
+    #[test]
+    fn valid() {
+        let mut spf = Spf::new_v1();
+        spf.append_mechanism(ip(Kind::IpV4, Some("192.0.2.1")));
+        spf.append_mechanism(ip(Kind::IpV6, Some("2001:db8::/32")));
+        assert_eq!(
+            spf.ip4().unwrap()[0].as_network(),
+            &"192.0.2.1/32".parse().unwrap()
+        );
+        assert_eq!(spf.to_string(), "v=spf1 ~ip4:192.0.2.1 ~ip6:2001:db8::/32");
+    }
+    #[test]
+    fn invalid() {
+        let mut spf = Spf::new_v1();
+        spf.append_mechanism(ip(Kind::IpV4, Some("example.com")));
+        spf.append_mechanism(ip(Kind::IpV6, Some("192.0.2.0/24")));
+        assert_eq!(spf.ip4().is_none(), true);
+        assert_eq!(spf.ip6().is_none(), true);
+    }
+    #[test]
+    fn no_rrdata() {
+        let mut spf = Spf::new_v1();
+        spf.append_mechanism(ip(Kind::IpV4, None));
+        assert_eq!(spf.ip4().is_none(), true);
+        assert_eq!(
+            spf.try_append_mechanism(ip(Kind::IpV4, None)).is_err(),
+            true
+        );
+    }
+}
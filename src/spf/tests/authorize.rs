@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod ip {
+
+    use crate::mechanism::Qualifier;
+    use crate::spf::Spf;
+    use std::net::IpAddr;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+    fn authorizes(spf: &Spf, address: &str) -> Option<(String, Qualifier)> {
+        spf.authorizes_ip(ip(address))
+            .map(|(m, q)| (m.to_string(), q))
+    }
+
+    #[test]
+    fn first_match_wins() {
+        let spf: Spf = "v=spf1 ?ip4:203.0.113.16/28 -ip4:203.0.113.0/25 ip4:203.0.113.0/24 ~all"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            authorizes(&spf, "203.0.113.20"),
+            Some(("?ip4:203.0.113.16/28".to_string(), Qualifier::Neutral))
+        );
+        assert_eq!(
+            authorizes(&spf, "203.0.113.100"),
+            Some(("-ip4:203.0.113.0/25".to_string(), Qualifier::Fail))
+        );
+        assert_eq!(
+            authorizes(&spf, "203.0.113.200"),
+            Some(("ip4:203.0.113.0/24".to_string(), Qualifier::Pass))
+        );
+    }
+    #[test]
+    fn order_reversed() {
+        let spf: Spf = "v=spf1 ip4:203.0.113.0/24 -ip4:203.0.113.0/25 ?ip4:203.0.113.16/28 ~all"
+            .parse()
+            .unwrap();
+        for address in ["203.0.113.20", "203.0.113.100", "203.0.113.200"] {
+            assert_eq!(
+                authorizes(&spf, address),
+                Some(("ip4:203.0.113.0/24".to_string(), Qualifier::Pass))
+            );
+        }
+    }
+    #[test]
+    fn no_match() {
+        let spf: Spf = "v=spf1 a mx include:_spf.example.com ip4:203.0.113.0/24 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(authorizes(&spf, "198.51.100.1"), None);
+        assert_eq!(authorizes(&spf, "2001:db8::1"), None);
+        assert_eq!(Spf::new_v1().authorizes_ip(ip("203.0.113.1")), None);
+    }
+    #[test]
+    fn single_address() {
+        let spf: Spf = "v=spf1 ~ip4:192.0.2.10 -all".parse().unwrap();
+        assert_eq!(
+            authorizes(&spf, "192.0.2.10"),
+            Some(("~ip4:192.0.2.10".to_string(), Qualifier::SoftFail))
+        );
+        assert_eq!(authorizes(&spf, "192.0.2.11"), None);
+    }
+    #[test]
+    fn ip6() {
+        let spf: Spf = "v=spf1 -ip6:2001:db8:1::/48 ip6:2001:db8::/32 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            authorizes(&spf, "2001:db8:1::25"),
+            Some(("-ip6:2001:db8:1::/48".to_string(), Qualifier::Fail))
+        );
+        assert_eq!(
+            authorizes(&spf, "2001:db8:2::25"),
+            Some(("ip6:2001:db8::/32".to_string(), Qualifier::Pass))
+        );
+    }
+    #[test]
+    fn ipv4_mapped() {
+        let spf: Spf = "v=spf1 ip4:203.0.113.0/24 ip6:::ffff:0:0/96 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            authorizes(&spf, "::ffff:203.0.113.25"),
+            Some(("ip4:203.0.113.0/24".to_string(), Qualifier::Pass))
+        );
+        assert_eq!(authorizes(&spf, "::ffff:198.51.100.1"), None);
+    }
+    #[test]
+    fn families_are_separate() {
+        let spf: Spf = "v=spf1 ip6:::/0 -all".parse().unwrap();
+        assert_eq!(authorizes(&spf, "203.0.113.25"), None);
+        let spf: Spf = "v=spf1 ip4:0.0.0.0/0 -all".parse().unwrap();
+        assert_eq!(authorizes(&spf, "2001:db8::1"), None);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod build {
+
+    use crate::mechanism::{Kind, Qualifier};
+    use crate::spf::{SpfBuilder, SpfError};
+
+    #[test]
+    fn redirect_only() {
+        let spf = SpfBuilder::new()
+            .v1()
+            .redirect("_spf.example.com")
+            .build()
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 redirect=_spf.example.com");
+        assert_eq!(spf.is_redirect(), true);
+    }
+    #[test]
+    fn spf2() {
+        let spf = SpfBuilder::new()
+            .v2_mfrom_pra()
+            .ip("192.0.2.1")
+            .all(Qualifier::Fail)
+            .build()
+            .unwrap();
+        assert_eq!(spf.to_string(), "spf2.0/mfrom,pra ip4:192.0.2.1 -all");
+    }
+    #[test]
+    fn term() {
+        let spf = SpfBuilder::new()
+            .v1()
+            .term("~a:example.com/24")
+            .term("x-note=ok")
+            .all(Qualifier::Neutral)
+            .build()
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 ~a:example.com/24 ?all x-note=ok");
+    }
+    #[test]
+    fn all_twice() {
+        let err = SpfBuilder::new()
+            .v1()
+            .all(Qualifier::Fail)
+            .all(Qualifier::SoftFail)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, SpfError::MechanismAlreadyPresent(Kind::All));
+    }
+    #[test]
+    fn redirect_twice() {
+        let err = SpfBuilder::new()
+            .v1()
+            .redirect("_spf.example.com")
+            .redirect("_spf.example.net")
+            .build()
+            .unwrap_err();
+        assert_eq!(err, SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect));
+    }
+    #[test]
+    fn redirect_and_all() {
+        for builder in [
+            SpfBuilder::new()
+                .v1()
+                .redirect("_spf.example.com")
+                .all(Qualifier::Fail),
+            SpfBuilder::new()
+                .v1()
+                .all(Qualifier::Fail)
+                .redirect("_spf.example.com"),
+        ] {
+            assert_eq!(
+                builder.build().unwrap().to_string(),
+                "v=spf1 -all redirect=_spf.example.com"
+            );
+        }
+    }
+    #[test]
+    fn first_error_is_kept() {
+        let err = SpfBuilder::new()
+            .v1()
+            .all(Qualifier::Fail)
+            .all(Qualifier::Fail)
+            .redirect("_spf.example.com")
+            .build()
+            .unwrap_err();
+        assert_eq!(err, SpfError::MechanismAlreadyPresent(Kind::All));
+    }
+    #[test]
+    fn invalid_ip() {
+        let err = SpfBuilder::new()
+            .v1()
+            .ip("203.0.113.0/33")
+            .build()
+            .unwrap_err();
+        assert_eq!(err.is_invalid_ip_addr(), true);
+    }
+    #[test]
+    fn missing_version() {
+        let err = SpfBuilder::new()
+            .a(Qualifier::Pass, None)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, SpfError::InvalidSource);
+    }
+    #[test]
+    fn length_exceeded() {
+        let mut builder = SpfBuilder::new().v1();
+        for i in 0..20 {
+            builder = builder.ip(&format!("192.0.2.{}", i * 10));
+        }
+        assert_eq!(builder.build().unwrap_err(), SpfError::SourceLengthExceeded);
+    }
+    #[test]
+    fn lookup_limit() {
+        let mut builder = SpfBuilder::new().v1();
+        for i in 0..10 {
+            builder = builder.include(Qualifier::Pass, &format!("i{}.example.com", i));
+        }
+        assert_eq!(builder.build().is_ok(), true);
+
+        let mut builder = SpfBuilder::new().v1().a(Qualifier::Pass, None);
+        for i in 0..10 {
+            builder = builder.include(Qualifier::Pass, &format!("i{}.example.com", i));
+        }
+        assert_eq!(builder.build().unwrap_err(), SpfError::LookupLimitExceeded);
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::spf::{ParseOptions, Spf};
+
+    #[test]
+    fn mixed_case_names() {
+        let input = "v=spf1 IP4:1.2.3.4 Include:_spf.example.com -ALL";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.ip4().unwrap()[0].to_string(), "ip4:1.2.3.4");
+        assert_eq!(spf.includes().unwrap()[0].raw(), "_spf.example.com");
+        assert_eq!(spf.all().unwrap().is_fail(), true);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:_spf.example.com ip4:1.2.3.4 -all"
+        );
+    }
+    #[test]
+    fn uppercase_mechanisms() {
+        let input =
+            "v=spf1 A MX:Example.com/24 ~PTR IP6:2001:4860:4000::/36 EXISTS:%{I}.Example.com ?ALL";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.a().unwrap()[0].raw(), "a");
+        assert_eq!(spf.mx().unwrap()[0].raw(), "Example.com/24");
+        assert_eq!(spf.ptr().unwrap().is_softfail(), true);
+        assert_eq!(spf.ip6().unwrap()[0].raw(), "2001:4860:4000::/36");
+        assert_eq!(spf.exists().unwrap()[0].raw(), "%{I}.Example.com");
+        assert_eq!(spf.all().unwrap().is_neutral(), true);
+    }
+    #[test]
+    fn uppercase_modifiers() {
+        let input = "v=spf1 Redirect=_SPF.example.com EXP=%{D}.explain.example.com";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.is_redirect(), true);
+        assert_eq!(spf.redirect().unwrap().raw(), "_SPF.example.com");
+        assert_eq!(spf.exp().unwrap().raw(), "%{D}.explain.example.com");
+        assert_eq!(spf.unknown_modifiers().is_none(), true);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 redirect=_SPF.example.com exp=%{D}.explain.example.com"
+        );
+    }
+    #[test]
+    fn preserve_case() {
+        let input = "v=spf1 A:Example.com MX Include:_spf.example.com IP4:1.2.3.4/32 -ALL";
+        let options = ParseOptions::new().preserve_case(true);
+
+        let spf = Spf::from_str_with_options(input, &options).unwrap();
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn preserve_case_modifiers() {
+        let input = "v=spf1 Redirect=_spf.example.com Exp=explain.example.com";
+        let options = ParseOptions::new().preserve_case(true);
+
+        let spf = Spf::from_str_with_options(input, &options).unwrap();
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn strict_uppercase() {
+        let input = "v=spf1 IP4:1.2.3.4 -ALL";
+
+        let spf = Spf::from_str_strict(input).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 ip4:1.2.3.4 -all");
+    }
+}
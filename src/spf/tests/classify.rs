@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::MechanismError;
+    use crate::spf::{Spf, SpfError};
+
+    #[test]
+    fn domains_named_after_mechanisms() {
+        let input = "v=spf1 a:include.example.com mx:ip6.example.com include:exists.example.com \
+                     ip4:203.0.113.0/24 exists:ip4.checker.example.org ptr:a.example.com -all";
+
+        let spf = Spf::from_str_strict(input).unwrap();
+        assert_eq!(spf.a().unwrap()[0].raw(), "include.example.com");
+        assert_eq!(spf.mx().unwrap()[0].raw(), "ip6.example.com");
+        assert_eq!(spf.includes().unwrap()[0].raw(), "exists.example.com");
+        assert_eq!(spf.ip4().unwrap().len(), 1);
+        assert_eq!(spf.ip6().is_none(), true);
+        assert_eq!(spf.exists().unwrap()[0].raw(), "ip4.checker.example.org");
+        assert_eq!(spf.ptr().unwrap().raw(), "a.example.com");
+    }
+    #[test]
+    fn qualified_domains_named_after_mechanisms() {
+        let input = "v=spf1 -a:ip4.example.com ~include:a.example.com ?exists:mx.example.com -all";
+
+        let spf = Spf::from_str_strict(input).unwrap();
+        assert_eq!(spf.a().unwrap()[0].is_fail(), true);
+        assert_eq!(spf.a().unwrap()[0].raw(), "ip4.example.com");
+        assert_eq!(spf.includes().unwrap()[0].is_softfail(), true);
+        assert_eq!(spf.exists().unwrap()[0].is_neutral(), true);
+        assert_eq!(spf.ip4().is_none(), true);
+    }
+    #[test]
+    fn ip_name_inside_unknown_term() {
+        let input = "v=spf1 x:ip4:203.0.113.1 -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.ip4().is_none(), true);
+        let err = Spf::from_str_strict(input).unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName("x".to_string()))
+        );
+    }
+    #[test]
+    fn macro_embedding_mechanism_names() {
+        let input = "v=spf1 exists:%{i}.ip6.include.example.com -all";
+
+        let spf = Spf::from_str_strict(input).unwrap();
+        assert_eq!(
+            spf.exists().unwrap()[0].raw(),
+            "%{i}.ip6.include.example.com"
+        );
+        assert_eq!(spf.includes().is_none(), true);
+        assert_eq!(spf.ip6().is_none(), true);
+    }
+    #[test]
+    fn ptr_without_separator() {
+        let input = "v=spf1 ptr-example.com -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.ptr().is_none(), true);
+        let err = Spf::from_str_strict(input).unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::UnknownTerm("ptr-example.com".to_string())
+        );
+    }
+}
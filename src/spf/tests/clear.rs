@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::Spf;
+
+    #[test]
+    fn keeps_version() {
+        let input = "v=spf1 a mx/24 include:_spf.example.com ip4:192.0.2.0/24 ip6:2001:db8::/32 \
+                     exists:%{i}.example.com ptr -all exp=explain.example.com x-note=ok";
+        let mut spf: Spf = input.parse().unwrap();
+        spf.clear();
+        assert_eq!(spf.to_string(), "v=spf1");
+        assert_eq!(spf.version(), "v=spf1");
+        assert_eq!(spf.a().is_none(), true);
+        assert_eq!(spf.ip4().is_none(), true);
+        assert_eq!(spf.all().is_none(), true);
+        assert_eq!(spf.exp().is_none(), true);
+        assert_eq!(spf.unknown_modifiers().is_none(), true);
+        assert_eq!(spf.source(), "");
+    }
+    #[test]
+    fn keeps_spf2_version() {
+        let mut spf: Spf = "spf2.0/mfrom,pra mx ?all".parse().unwrap();
+        spf.clear();
+        assert_eq!(spf.to_string(), "spf2.0/mfrom,pra");
+    }
+    #[test]
+    fn resets_redirect() {
+        let mut spf: Spf = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+        spf.clear();
+        assert_eq!(spf.to_string(), "v=spf1");
+        assert_eq!(spf.is_redirect(), false);
+        assert_eq!(spf.redirect().is_none(), true);
+        // An `all` may be appended once the redirect is gone.
+        spf.try_append_mechanism(Mechanism::all(Qualifier::Fail))
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 -all");
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn resets_warnings_and_state() {
+        let mut spf: Spf = "v=spf1 -all a".parse().unwrap();
+        assert_eq!(spf.parse_warnings().is_some(), true);
+        spf.try_validate().unwrap();
+        assert_eq!(spf.is_valid(), true);
+        spf.clear();
+        assert_eq!(spf.parse_warnings().is_none(), true);
+        assert_eq!(spf.is_valid(), false);
+    }
+    #[test]
+    fn keeps_max_length() {
+        let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+        spf.set_max_length(512);
+        spf.clear();
+        assert_eq!(spf.max_length(), 512);
+        let mut spf = Spf::from_txt_strings(&["v=spf1 a ", "-all"]).unwrap();
+        spf.clear();
+        assert_eq!(spf.max_length(), usize::MAX);
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn empty_record_is_not_modified() {
+        let mut spf: Spf = "v=spf1".parse().unwrap();
+        spf.try_validate().unwrap();
+        spf.clear();
+        assert_eq!(spf.is_valid(), true);
+        assert_eq!(spf.is_modified(), false);
+        assert_eq!(spf.source(), "v=spf1");
+    }
+    #[test]
+    fn clear_all() {
+        let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+        spf.clear_all();
+        assert_eq!(spf.to_string(), "");
+        assert_eq!(spf.version(), "");
+        assert_eq!(spf.is_v1(), false);
+    }
+}
+#[cfg(test)]
+mod take {
+
+    use crate::mechanism::{Kind, Mechanism, ParsedMechanism, Qualifier};
+    use crate::spf::Spf;
+
+    fn strings(mechanisms: &[ParsedMechanism]) -> Vec<String> {
+        mechanisms.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn includes() {
+        let mut spf: Spf = "v=spf1 a include:one.example.com ~include:two.example.com -all"
+            .parse()
+            .unwrap();
+        let removed = spf.take_mechanisms(Kind::Include);
+        assert_eq!(
+            strings(&removed),
+            ["include:one.example.com", "~include:two.example.com"]
+        );
+        assert_eq!(removed.iter().any(|m| m.is_network()), false);
+        assert_eq!(spf.includes().is_none(), true);
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+    #[test]
+    fn networks() {
+        let mut spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 ip4:198.51.100.1 -all"
+            .parse()
+            .unwrap();
+        let removed = spf.take_mechanisms(Kind::IpV4);
+        assert_eq!(strings(&removed), ["ip4:192.0.2.0/24", "ip4:198.51.100.1"]);
+        assert_eq!(removed.iter().all(|m| m.is_network()), true);
+        assert_eq!(spf.to_string(), "v=spf1 ip6:2001:db8::/32 -all");
+        let removed = spf.take_mechanisms(Kind::IpV6);
+        assert_eq!(strings(&removed), ["ip6:2001:db8::/32"]);
+        assert_eq!(spf.to_string(), "v=spf1 -all");
+    }
+    #[test]
+    fn redirect() {
+        let mut spf: Spf = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+        let removed = spf.take_mechanisms(Kind::Redirect);
+        assert_eq!(strings(&removed), ["redirect=_spf.example.com"]);
+        assert_eq!(spf.is_redirect(), false);
+        assert_eq!(spf.to_string(), "v=spf1 a");
+        spf.try_append_mechanism(Mechanism::all(Qualifier::Fail))
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+    #[test]
+    fn single_terms() {
+        let mut spf: Spf = "v=spf1 a ptr -all exp=explain.example.com".parse().unwrap();
+        assert_eq!(strings(&spf.take_mechanisms(Kind::Ptr)), ["ptr"]);
+        assert_eq!(strings(&spf.take_mechanisms(Kind::All)), ["-all"]);
+        assert_eq!(
+            strings(&spf.take_mechanisms(Kind::Exp)),
+            ["exp=explain.example.com"]
+        );
+        assert_eq!(spf.to_string(), "v=spf1 a");
+    }
+    #[test]
+    fn nothing_present() {
+        let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+        for kind in [Kind::MX, Kind::Exists, Kind::Redirect, Kind::IpV4] {
+            assert_eq!(spf.take_mechanisms(kind).is_empty(), true);
+        }
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+    #[test]
+    fn undo() {
+        let input = "v=spf1 mx:mail.example.com mx:backup.example.com -all";
+        let mut spf: Spf = input.parse().unwrap();
+        let removed = spf.take_mechanisms(Kind::MX);
+        assert_eq!(spf.to_string(), "v=spf1 -all");
+        for mechanism in removed {
+            spf.append_mechanism(mechanism.txt());
+        }
+        assert_eq!(spf.to_string(), input);
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::{Kind, MechanismError};
+    use crate::spf::{ParseOptions, Spf, SpfError};
+
+    #[test]
+    fn three_problems() {
+        let input = "v=spf1 ip4:203.32.10.0/33 bogusmech redirect=a.example.com redirect=b.example.com -all";
+        let options = ParseOptions::new().strict(true);
+
+        let errors = Spf::parse_all_errors_with_options(input, &options).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].is_invalid_ipv4_cidr_length(), true);
+        assert_eq!(errors[0].parse_error().unwrap().term_index(), 1);
+        assert_eq!(
+            errors[1].inner(),
+            &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName(
+                "bogusmech".to_string()
+            ))
+        );
+        assert_eq!(errors[1].parse_error().unwrap().term_index(), 2);
+        assert_eq!(
+            errors[2].inner(),
+            &SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect)
+        );
+        assert_eq!(errors[2].parse_error().unwrap().term_index(), 4);
+        assert_eq!(errors[2].parse_error().unwrap().byte_offset(), 59);
+    }
+    #[test]
+    fn identical_terms_reported_once() {
+        let input = "v=spf1 bogusmech a bogusmech -all";
+        let options = ParseOptions::new().strict(true);
+
+        let errors = Spf::parse_all_errors_with_options(input, &options).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].parse_error().unwrap().term_index(), 1);
+    }
+    #[test]
+    fn first_error_matches_from_str() {
+        let input = "v=spf1 ip4:203.32.10.0/33 ip6:2001:4860:4000::/129 -all";
+
+        let errors = Spf::parse_all_errors(input).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0], input.parse::<Spf>().unwrap_err());
+    }
+    #[test]
+    fn source_error_is_returned_alone() {
+        let errors = Spf::parse_all_errors("a ip4:203.32.10.0/33").unwrap_err();
+        assert_eq!(errors, vec![SpfError::InvalidSource]);
+    }
+    #[test]
+    fn no_errors() {
+        let input = "v=spf1 a mx -all";
+
+        let spf = Spf::parse_all_errors(input).unwrap();
+        assert_eq!(spf.to_string(), input);
+    }
+}
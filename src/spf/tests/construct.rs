@@ -7,10 +7,24 @@ mod spf1 {
     fn make_v1() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.version(), "v=spf1");
         assert_eq!(spf.is_v1(), true);
     }
+    #[test]
+    fn new_v1() {
+        let spf = Spf::new_v1();
+        assert_eq!(spf.version(), "v=spf1");
+        assert_eq!(spf.is_v1(), true);
+        assert_eq!(spf.is_empty(), true);
+        assert_eq!(spf.to_string(), "v=spf1");
+    }
+    #[test]
+    fn default_has_no_version() {
+        assert_eq!(Spf::default().version(), "");
+        assert_eq!(Spf::new().to_string(), "");
+        assert_eq!(Spf::new().is_v1(), false);
+    }
 }
 #[cfg(test)]
 mod spf2 {
@@ -21,7 +35,7 @@ mod spf2 {
     fn make_v2_pra() {
         let mut spf = Spf::new();
         spf.set_v2_pra();
-        assert_eq!(spf.version, "spf2.0/pra");
+        assert_eq!(spf.version_str, "spf2.0/pra");
         assert_eq!(spf.is_v2(), true);
         assert_eq!(spf.version(), "spf2.0/pra")
     }
@@ -29,23 +43,36 @@ mod spf2 {
     fn make_v2_mfrom() {
         let mut spf = Spf::new();
         spf.set_v2_mfrom();
-        assert_eq!(spf.version, "spf2.0/mfrom");
+        assert_eq!(spf.version_str, "spf2.0/mfrom");
         assert_eq!(spf.is_v2(), true);
     }
     #[test]
     fn make_v2_mfrom_pra() {
         let mut spf = Spf::new();
         spf.set_v2_mfrom_pra();
-        assert_eq!(spf.version, "spf2.0/mfrom,pra");
+        assert_eq!(spf.version_str, "spf2.0/mfrom,pra");
         assert_eq!(spf.is_v2(), true);
     }
     #[test]
     fn make_v2_pra_mfrom() {
         let mut spf = Spf::new();
         spf.set_v2_pra_mfrom();
-        assert_eq!(spf.version, "spf2.0/pra,mfrom");
+        assert_eq!(spf.version_str, "spf2.0/pra,mfrom");
         assert_eq!(spf.is_v2(), true);
     }
+    #[test]
+    fn new_v2() {
+        for (spf, version) in [
+            (Spf::new_v2_pra(), "spf2.0/pra"),
+            (Spf::new_v2_mfrom(), "spf2.0/mfrom"),
+            (Spf::new_v2_pra_mfrom(), "spf2.0/pra,mfrom"),
+            (Spf::new_v2_mfrom_pra(), "spf2.0/mfrom,pra"),
+        ] {
+            assert_eq!(spf.version(), version);
+            assert_eq!(spf.is_v2(), true);
+            assert_eq!(spf.to_string(), version);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -69,7 +96,8 @@ mod build {
         );
         assert_eq!(spf.is_redirect(), true);
         spf.append_mechanism(Mechanism::all(Qualifier::Pass));
-        assert_eq!(spf.all().is_none(), true);
+        assert_eq!(spf.all().is_some(), true);
+        assert_eq!(spf.to_string(), "v=spf1 all redirect=_spf.example.com");
         spf.clear_mechanism(Kind::Redirect);
         assert_eq!(spf.is_redirect(), false);
         assert_eq!(spf.redirect.is_none(), true);
@@ -79,7 +107,7 @@ mod build {
     fn make_a_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_a(Mechanism::a(Qualifier::Pass));
         spf.append_mechanism_of_all(Mechanism::all(Qualifier::Fail));
@@ -91,7 +119,7 @@ mod build {
     fn make_a_with_mx_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_a(Mechanism::new_a_without_mechanism(Qualifier::Pass));
         spf.append_mechanism_of_mx(Mechanism::new_mx_without_mechanism(Qualifier::Pass));
@@ -104,7 +132,7 @@ mod build {
     fn make_a_with_mx_with_value_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_a(Mechanism::a(Qualifier::Pass));
         spf.append_mechanism_of_mx(Mechanism::new_mx_with_mechanism(
@@ -120,7 +148,7 @@ mod build {
     fn make_a_with_mx_with_value_x2_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_a(Mechanism::a(Qualifier::Pass));
         spf.append_mechanism_of_mx(Mechanism::new_mx_with_mechanism(
@@ -141,7 +169,7 @@ mod build {
     fn make_include_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_mx(Mechanism::new_include(
             Qualifier::Pass,
@@ -154,7 +182,7 @@ mod build {
     fn make_include_x2_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_mx(Mechanism::new_include(
             Qualifier::Pass,
@@ -174,7 +202,7 @@ mod build {
     fn make_ip4_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_ip4(Mechanism::ip(
             Qualifier::Pass,
@@ -190,7 +218,7 @@ mod build {
     fn make_ip4_x2_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_ip4(Mechanism::ip(
             Qualifier::Pass,
@@ -210,7 +238,7 @@ mod build {
     fn make_ip6_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_ip6(Mechanism::ip(
             Qualifier::Pass,
@@ -226,7 +254,7 @@ mod build {
     fn make_ip6_x2_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_ip6(Mechanism::ip(
             Qualifier::Pass,
@@ -246,7 +274,7 @@ mod build {
     fn make_exists_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_exists(Mechanism::new_exists(
             Qualifier::Pass,
@@ -259,7 +287,7 @@ mod build {
     fn make_exists_x2_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_exists(Mechanism::new_exists(
             Qualifier::Pass,
@@ -279,7 +307,7 @@ mod build {
     fn make_ptr_without_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_ptr(Mechanism::new_ptr_without_mechanism(Qualifier::Pass));
         assert_eq!(spf.to_string(), "v=spf1 ptr".to_string());
@@ -288,7 +316,7 @@ mod build {
     fn make_ptr_with_all() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_ptr(Mechanism::new_ptr_with_mechanism(
             Qualifier::Pass,
@@ -300,7 +328,7 @@ mod build {
     fn make_ip4_by_append_ip_mechanism() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_ip_mechanism(Mechanism::ip(
             Qualifier::Pass,
@@ -312,7 +340,7 @@ mod build {
     fn make_ip4_x2_by_append_ip_mechanism() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_ip_mechanism(Mechanism::ip(
             Qualifier::Pass,
@@ -331,7 +359,7 @@ mod build {
     fn make_ip6_by_append_ip_mechanism() {
         let mut spf = Spf::new();
         spf.set_v1();
-        assert_eq!(spf.version, "v=spf1");
+        assert_eq!(spf.version_str, "v=spf1");
         assert_eq!(spf.is_v1(), true);
         spf.append_mechanism_of_ip6(Mechanism::ip(
             Qualifier::Pass,
@@ -369,3 +397,120 @@ mod build {
         );
     }
 }
+#[cfg(test)]
+mod from_mechanisms {
+
+    use crate::mechanism::{Kind, Mechanism, ParsedMechanism, Qualifier};
+    use crate::spf::{Spf, SpfError};
+
+    const TERMS: [&str; 10] = [
+        "a",
+        "mx:mail.example.com",
+        "include:_spf.example.com",
+        "include:_spf.example.net",
+        "ip4:203.0.113.0/24",
+        "ip4:198.51.100.1",
+        "ip6:2001:db8::/32",
+        "exists:%{i}.example.com",
+        "ptr",
+        "-all",
+    ];
+
+    fn mechanisms() -> impl Iterator<Item = ParsedMechanism> {
+        TERMS.iter().map(|term| term.parse().unwrap())
+    }
+    fn hand_built() -> Spf {
+        let mut spf = Spf::new();
+        spf.set_v1();
+        spf.append_mechanism(Mechanism::a(Qualifier::Pass));
+        spf.append_mechanism(
+            Mechanism::mx(Qualifier::Pass)
+                .with_rrdata("mail.example.com")
+                .unwrap(),
+        );
+        spf.append_mechanism(Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap());
+        spf.append_mechanism(Mechanism::include(Qualifier::Pass, "_spf.example.net").unwrap());
+        spf.append_ip_mechanism("ip4:203.0.113.0/24".parse().unwrap());
+        spf.append_ip_mechanism("ip4:198.51.100.1".parse().unwrap());
+        spf.append_ip_mechanism("ip6:2001:db8::/32".parse().unwrap());
+        spf.append_mechanism(Mechanism::exists(Qualifier::Pass, "%{i}.example.com").unwrap());
+        spf.append_mechanism(Mechanism::ptr(Qualifier::Pass));
+        spf.append_mechanism(Mechanism::all(Qualifier::Fail));
+        spf
+    }
+
+    #[test]
+    fn ten_mechanisms() {
+        let spf = Spf::from_mechanisms("v=spf1", mechanisms()).unwrap();
+        let expected = hand_built();
+        assert_eq!(spf, expected);
+        assert_eq!(spf.to_string(), expected.to_string());
+        assert_eq!(spf.len(), 10);
+    }
+    #[test]
+    fn collect() {
+        let spf: Spf = mechanisms().collect();
+        assert_eq!(spf.version(), "v=spf1");
+        assert_eq!(spf, hand_built());
+        assert_eq!(spf.to_string(), hand_built().to_string());
+    }
+    #[test]
+    fn collect_keeps_last_all() {
+        let spf: Spf = ["a", "-all", "redirect=_spf.example.com", "~all"]
+            .iter()
+            .map(|term| term.parse::<ParsedMechanism>().unwrap())
+            .collect();
+        assert_eq!(spf.to_string(), "v=spf1 a ~all redirect=_spf.example.com");
+    }
+    #[test]
+    fn v2() {
+        let spf = Spf::from_mechanisms("spf2.0/mfrom,pra", mechanisms()).unwrap();
+        assert_eq!(spf.is_v2(), true);
+        assert_eq!(spf.to_string().starts_with("spf2.0/mfrom,pra a "), true);
+    }
+    #[test]
+    fn invalid_version() {
+        for version in ["v=spf2", "", "spf1", "a"] {
+            assert_eq!(
+                Spf::from_mechanisms(version, mechanisms()).unwrap_err(),
+                SpfError::InvalidVersion(version.to_string())
+            );
+        }
+        assert_eq!(
+            Spf::from_mechanisms("spf2.0/pra,pra", mechanisms())
+                .unwrap_err()
+                .is_invalid_scope(),
+            true
+        );
+    }
+    #[test]
+    fn conflicts() {
+        let parse = |terms: &[&str]| -> Vec<ParsedMechanism> {
+            terms.iter().map(|term| term.parse().unwrap()).collect()
+        };
+        assert_eq!(
+            Spf::from_mechanisms("v=spf1", parse(&["a", "-all", "~all"])).unwrap_err(),
+            SpfError::MechanismAlreadyPresent(Kind::All)
+        );
+        assert_eq!(
+            Spf::from_mechanisms(
+                "v=spf1",
+                parse(&["redirect=a.example.com", "redirect=b.example.com"])
+            )
+            .unwrap_err(),
+            SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect)
+        );
+        // Both are kept. Whether that is permitted is decided by validation.
+        for terms in [
+            ["-all", "redirect=_spf.example.com"],
+            ["redirect=_spf.example.com", "-all"],
+        ] {
+            assert_eq!(
+                Spf::from_mechanisms("v=spf1", parse(&terms))
+                    .unwrap()
+                    .to_string(),
+                "v=spf1 -all redirect=_spf.example.com"
+            );
+        }
+    }
+}
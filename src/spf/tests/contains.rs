@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::Spf;
+    use ipnetwork::IpNetwork;
+
+    const RECORD: &str = "v=spf1 a:Mail.Example.com include:_spf.protection.outlook.com \
+                          ~include:_spf.example.net ip4:203.0.113.0/24 ip6:2001:db8::/32 \
+                          ptr -all exp=explain.example.com";
+
+    fn spf() -> Spf {
+        RECORD.parse().unwrap()
+    }
+    fn network(input: &str) -> Mechanism<IpNetwork> {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn present() {
+        let spf = spf();
+        let include = Mechanism::include(Qualifier::Pass, "_spf.protection.outlook.com").unwrap();
+        assert_eq!(spf.contains(&include), true);
+        assert_eq!(
+            spf.contains(&"~include:_spf.example.net".parse().unwrap()),
+            true
+        );
+        assert_eq!(spf.contains(&Mechanism::all(Qualifier::Fail)), true);
+        assert_eq!(spf.contains(&Mechanism::ptr(Qualifier::Pass)), true);
+        assert_eq!(
+            spf.contains(&Mechanism::exp("explain.example.com").unwrap()),
+            true
+        );
+        assert_eq!(spf.contains_ip(&network("ip4:203.0.113.0/24")), true);
+        assert_eq!(spf.contains_ip(&network("ip6:2001:db8::/32")), true);
+    }
+    #[test]
+    fn domain_case() {
+        let spf = spf();
+        let a = Mechanism::a(Qualifier::Pass)
+            .with_rrdata("MAIL.example.com")
+            .unwrap();
+        assert_eq!(spf.contains(&a), true);
+        let include = Mechanism::include(Qualifier::Pass, "_SPF.Protection.Outlook.com").unwrap();
+        assert_eq!(spf.contains(&include), true);
+    }
+    #[test]
+    fn absent() {
+        let spf = spf();
+        let include = Mechanism::include(Qualifier::Pass, "_spf.google.com").unwrap();
+        assert_eq!(spf.contains(&include), false);
+        assert_eq!(spf.contains_any_qualifier(&include), false);
+        assert_eq!(spf.contains(&Mechanism::mx(Qualifier::Pass)), false);
+        // `a` without a domain-spec is not `a:mail.example.com`.
+        assert_eq!(spf.contains(&Mechanism::a(Qualifier::Pass)), false);
+        assert_eq!(spf.contains_ip(&network("ip4:203.0.113.0/25")), false);
+        assert_eq!(spf.contains_ip(&network("ip4:198.51.100.0/24")), false);
+        assert_eq!(Spf::new().contains(&Mechanism::all(Qualifier::Fail)), false);
+    }
+    #[test]
+    fn qualifier_mismatch() {
+        let spf = spf();
+        let include = Mechanism::include(Qualifier::Fail, "_spf.protection.outlook.com").unwrap();
+        assert_eq!(spf.contains(&include), false);
+        assert_eq!(spf.contains_any_qualifier(&include), true);
+        assert_eq!(spf.contains(&Mechanism::all(Qualifier::SoftFail)), false);
+        assert_eq!(
+            spf.contains_any_qualifier(&Mechanism::all(Qualifier::SoftFail)),
+            true
+        );
+        let ip4 = network("?ip4:203.0.113.0/24");
+        assert_eq!(spf.contains_ip(&ip4), false);
+        assert_eq!(spf.contains_ip_any_qualifier(&ip4), true);
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::Spf;
+
+    const KINDS: [Kind; 10] = [
+        Kind::A,
+        Kind::MX,
+        Kind::Include,
+        Kind::IpV4,
+        Kind::IpV6,
+        Kind::Exists,
+        Kind::Ptr,
+        Kind::Redirect,
+        Kind::All,
+        Kind::Exp,
+    ];
+
+    #[test]
+    fn parsed() {
+        let spf: Spf = "v=spf1 a a:example.com mx include:a.example.com include:b.example.com \
+                        include:c.example.com ip4:203.0.113.0/24 ip4:198.51.100.1 \
+                        ip6:2001:db8::/32 exists:%{i}.example.com ptr -all \
+                        exp=explain.example.com x-note=ok"
+            .parse()
+            .unwrap();
+        let expected = [2, 1, 3, 2, 1, 1, 1, 0, 1, 1];
+        for (kind, expected) in KINDS.iter().zip(expected) {
+            assert_eq!(spf.count_of(*kind), expected, "{:?}", kind);
+        }
+        assert_eq!(spf.len(), 14);
+        assert_eq!(spf.len(), spf.to_string().split_whitespace().count() - 1);
+        assert_eq!(spf.is_empty(), false);
+    }
+    #[test]
+    fn redirect() {
+        let spf: Spf = "v=spf1 mx redirect=_spf.example.com".parse().unwrap();
+        assert_eq!(spf.count_of(Kind::Redirect), 1);
+        assert_eq!(spf.count_of(Kind::All), 0);
+        assert_eq!(spf.len(), 2);
+    }
+    #[test]
+    fn empty() {
+        let spf = Spf::new();
+        assert_eq!(spf.len(), 0);
+        assert_eq!(spf.is_empty(), true);
+        for kind in KINDS {
+            assert_eq!(spf.count_of(kind), 0);
+        }
+        let spf: Spf = "v=spf1".parse().unwrap();
+        assert_eq!(spf.is_empty(), true);
+    }
+    #[test]
+    fn built() {
+        let mut spf = Spf::new();
+        spf.set_v1();
+        spf.append_mechanism(Mechanism::include(Qualifier::Pass, "a.example.com").unwrap());
+        spf.append_mechanism(Mechanism::include(Qualifier::Pass, "b.example.com").unwrap());
+        spf.append_ip_mechanism(Mechanism::ip(
+            Qualifier::Pass,
+            "203.0.113.0/24".parse().unwrap(),
+        ));
+        spf.append_mechanism(Mechanism::all(Qualifier::Fail));
+        assert_eq!(spf.count_of(Kind::Include), 2);
+        assert_eq!(spf.count_of(Kind::IpV4), 1);
+        assert_eq!(spf.count_of(Kind::All), 1);
+        assert_eq!(spf.len(), 4);
+        // Appending a redirect keeps `all`.
+        spf.append_mechanism(Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap());
+        assert_eq!(spf.count_of(Kind::All), 1);
+        assert_eq!(spf.count_of(Kind::Redirect), 1);
+        assert_eq!(spf.len(), 5);
+    }
+    #[test]
+    fn after_clear() {
+        let mut spf: Spf = "v=spf1 a include:a.example.com include:b.example.com -all"
+            .parse()
+            .unwrap();
+        spf.clear_mechanism(Kind::Include);
+        assert_eq!(spf.count_of(Kind::Include), 0);
+        assert_eq!(spf.len(), 2);
+        spf.clear();
+        assert_eq!(spf.len(), 0);
+        assert_eq!(spf.is_empty(), true);
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod exact {
+
+    use crate::spf::Spf;
+
+    #[test]
+    fn mixed_case_domains() {
+        let mut spf: Spf = "v=spf1 include:_spf.google.com mx:Example.com include:_SPF.GOOGLE.COM \
+                            mx:example.COM include:_spf.Google.com -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.dedup(), 3);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 mx:Example.com include:_spf.google.com -all"
+        );
+    }
+    #[test]
+    fn first_occurrence_order() {
+        let mut spf: Spf =
+            "v=spf1 include:b.example.com include:a.example.com include:B.example.com -all"
+                .parse()
+                .unwrap();
+        assert_eq!(spf.dedup(), 1);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:b.example.com include:a.example.com -all"
+        );
+    }
+    #[test]
+    fn networks_exact() {
+        let mut spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 ip4:192.0.2.0/24 \
+                            ip4:192.0.2.0/25 ip6:2001:DB8::/32 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.dedup(), 2);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.0/25 ip6:2001:db8::/32 -all"
+        );
+    }
+    #[test]
+    fn qualifier_differs() {
+        let input = "v=spf1 a ~a -mx mx ip4:192.0.2.0/24 -ip4:192.0.2.0/24 -all";
+        let mut spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.dedup(), 0);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn a_without_domain() {
+        let mut spf: Spf = "v=spf1 a a/24 a a:example.com -all".parse().unwrap();
+        assert_eq!(spf.dedup(), 1);
+        assert_eq!(spf.to_string(), "v=spf1 a a/24 a:example.com -all");
+    }
+    #[test]
+    fn nothing_to_remove() {
+        let mut spf = Spf::new();
+        assert_eq!(spf.dedup(), 0);
+        assert_eq!(spf.dedup_subsumed(), 0);
+    }
+}
+#[cfg(test)]
+mod subsumed {
+
+    use crate::spf::Spf;
+
+    #[test]
+    fn contained_in_earlier_network() {
+        let mut spf: Spf = "v=spf1 ip4:10.0.0.0/8 ip4:10.1.2.0/24 ip4:10.1.2.3 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.dedup_subsumed(), 2);
+        assert_eq!(spf.to_string(), "v=spf1 ip4:10.0.0.0/8 -all");
+    }
+    #[test]
+    fn contained_in_later_network() {
+        let mut spf: Spf = "v=spf1 ip6:2001:db8:1::/48 ip6:2001:db8::/32 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.dedup_subsumed(), 1);
+        assert_eq!(spf.to_string(), "v=spf1 ip6:2001:db8::/32 -all");
+    }
+    #[test]
+    fn different_qualifier_is_kept() {
+        let input = "v=spf1 -ip4:10.1.0.0/16 ip4:10.0.0.0/8 -all";
+        let mut spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.dedup_subsumed(), 0);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn different_qualifier_between() {
+        // Removing the first network would let the `-ip4` match 10.1.2.0/24.
+        let input = "v=spf1 ip4:10.1.2.0/24 -ip4:10.1.0.0/16 ip4:10.0.0.0/8 -all";
+        let mut spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.dedup_subsumed(), 0);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn equivalent_networks_keep_first() {
+        let mut spf: Spf = "v=spf1 ip4:192.0.2.17/24 ip4:192.0.2.0/24 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.dedup_subsumed(), 1);
+        assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.17/24 -all");
+    }
+    #[test]
+    fn includes_exact_duplicates() {
+        let mut spf: Spf = "v=spf1 include:a.example.com include:A.example.com \
+                            ip4:192.0.2.0/24 ip4:192.0.2.0/25 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.dedup_subsumed(), 2);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:a.example.com ip4:192.0.2.0/24 -all"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::spf::Spf;
+
+    fn spf(input: &str) -> Spf {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn provider_change() {
+        let before = spf("v=spf1 include:_spf.google.com include:mailgun.org ip4:192.0.2.10 ~all");
+        let after = spf("v=spf1 include:_spf.google.com include:sendgrid.net \
+                         ip4:192.0.2.10 ip4:198.51.100.0/24 -all");
+        let diff = before.diff(&after);
+        assert_eq!(diff.removed(), &["include:mailgun.org"]);
+        assert_eq!(
+            diff.added(),
+            &["include:sendgrid.net", "ip4:198.51.100.0/24"]
+        );
+        assert_eq!(diff.modified(), &[("~all".to_string(), "-all".to_string())]);
+        assert_eq!(
+            diff.to_string(),
+            "- include:mailgun.org\n+ include:sendgrid.net\n+ ip4:198.51.100.0/24\n* ~all -> -all"
+        );
+    }
+    #[test]
+    fn order_insensitive() {
+        let before = spf("v=spf1 a mx include:_spf.example.com -all");
+        let after = spf("v=spf1 include:_spf.example.com mx a -all");
+        assert_eq!(before.diff(&after).is_empty(), true);
+        assert_eq!(before.diff(&after).to_string(), "");
+    }
+    #[test]
+    fn redirect_replaces_all() {
+        let before = spf("v=spf1 mx -all");
+        let after = spf("v=spf1 mx redirect=_spf.example.com");
+        let diff = before.diff(&after);
+        assert_eq!(diff.removed(), &["-all"]);
+        assert_eq!(diff.added(), &["redirect=_spf.example.com"]);
+        assert_eq!(diff.modified().is_empty(), true);
+    }
+    #[test]
+    fn version_change() {
+        let before = spf("v=spf1 a -all");
+        let after = spf("spf2.0/pra a -all");
+        let diff = before.diff(&after);
+        assert_eq!(diff.removed(), &["v=spf1"]);
+        assert_eq!(diff.added(), &["spf2.0/pra"]);
+    }
+    #[test]
+    fn qualifier_only() {
+        let before = spf("v=spf1 a -mx ip6:2001:db8::/32 -all");
+        let after = spf("v=spf1 ?a mx ~ip6:2001:db8::/32 -all");
+        let diff = before.diff(&after);
+        assert_eq!(diff.added().is_empty(), true);
+        assert_eq!(diff.removed().is_empty(), true);
+        assert_eq!(
+            diff.modified(),
+            &[
+                ("a".to_string(), "?a".to_string()),
+                ("-mx".to_string(), "mx".to_string()),
+                (
+                    "ip6:2001:db8::/32".to_string(),
+                    "~ip6:2001:db8::/32".to_string()
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn implied_prefix_and_case() {
+        let before = spf("v=spf1 ip4:192.0.2.1 include:_SPF.example.com -all");
+        let after = spf("v=spf1 ip4:192.0.2.1/32 include:_spf.example.com -all");
+        let diff = before.diff(&after);
+        assert_eq!(diff.added().is_empty(), true);
+        assert_eq!(diff.modified().len(), 2);
+    }
+    #[test]
+    fn duplicates_are_counted() {
+        let before = spf("v=spf1 a -all");
+        let after = spf("v=spf1 a a -all");
+        assert_eq!(before.diff(&after).added(), &["a"]);
+        assert_eq!(after.diff(&before).removed(), &["a"]);
+    }
+    #[test]
+    fn modifiers() {
+        let before = spf("v=spf1 a -all exp=explain.example.com x-note=old");
+        let after = spf("v=spf1 a -all x-note=new");
+        let diff = before.diff(&after);
+        assert_eq!(diff.removed(), &["exp=explain.example.com", "x-note=old"]);
+        assert_eq!(diff.added(), &["x-note=new"]);
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(test)]
-use crate::spf::Spf;
+use crate::spf::{ParseOptions, Spf};
 
 #[test]
 fn basic() {
@@ -24,3 +24,38 @@ fn ip4_x3() {
     let spf: Spf = input.parse().unwrap();
     assert_eq!(spf.to_string(), input);
 }
+#[test]
+fn explicit_pass_default() {
+    let input = "v=spf1 +a +mx -all";
+
+    let spf: Spf = input.parse().unwrap();
+    assert_eq!(spf.to_string(), "v=spf1 a mx -all");
+}
+#[test]
+fn explicit_pass_preserved() {
+    let input = "v=spf1 +a +mx -all";
+    let options = ParseOptions::new().preserve_explicit_pass(true);
+
+    let spf = Spf::from_str_with_options(input, &options).unwrap();
+    assert_eq!(spf.to_string(), input);
+}
+#[test]
+fn explicit_pass_mixed() {
+    let input = "v=spf1 +a mx:example.com +include:_spf.example.com include:example.org +ip4:10.0.0.0/8 ip6:2001:4860:4000::/36 ~all";
+    let options = ParseOptions::new().preserve_explicit_pass(true);
+
+    let spf = Spf::from_str_with_options(input, &options).unwrap();
+    assert_eq!(spf.a().unwrap()[0].to_string(), "+a");
+    assert_eq!(spf.mx().unwrap()[0].to_string(), "mx:example.com");
+    assert_eq!(spf.to_string(), input);
+}
+#[test]
+fn explicit_pass_with_preserve_case() {
+    let input = "v=spf1 +A +MX ?INCLUDE:Example.com +ALL";
+    let options = ParseOptions::new()
+        .preserve_case(true)
+        .preserve_explicit_pass(true);
+
+    let spf = Spf::from_str_with_options(input, &options).unwrap();
+    assert_eq!(spf.to_string(), input);
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod referenced {
+
+    use crate::spf::Spf;
+
+    #[test]
+    fn cidr_stripped() {
+        let spf: Spf = "v=spf1 a:example.com/28 mx:mail.example.com/24//64 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.domains(), vec!["example.com", "mail.example.com"]);
+    }
+    #[test]
+    fn duplicates_collapse() {
+        let spf: Spf = "v=spf1 a:example.com a:example.com/24 mx:EXAMPLE.com \
+                        include:example.com ~include:example.com -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.domains(), vec!["example.com"]);
+    }
+    #[test]
+    fn bare_terms_left_out() {
+        let spf: Spf = "v=spf1 a mx ptr a/24 mx//64 ip4:192.0.2.0/24 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.domains().is_empty(), true);
+    }
+    #[test]
+    fn every_kind() {
+        let spf: Spf = "v=spf1 a:a.example.com mx:mx.example.com include:include.example.com \
+                        exists:exists.example.com ptr:ptr.example.com \
+                        redirect=redirect.example.com exp=exp.example.com"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            spf.domains(),
+            vec![
+                "a.example.com",
+                "mx.example.com",
+                "include.example.com",
+                "exists.example.com",
+                "ptr.example.com",
+                "redirect.example.com",
+                "exp.example.com",
+            ]
+        );
+    }
+    #[test]
+    fn macros() {
+        let spf: Spf = "v=spf1 include:_spf.example.com exists:%{i}._spf.%{d} \
+                        a:%{d2}/24 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.domains(), vec!["_spf.example.com"]);
+        assert_eq!(
+            spf.domains_with_macros(),
+            vec!["%{d2}", "_spf.example.com", "%{i}._spf.%{d}"]
+        );
+    }
+}
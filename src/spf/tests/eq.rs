@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::{Spf, SpfBuilder};
+
+    #[test]
+    fn parsed_equals_built() {
+        let parsed: Spf = "v=spf1 +a MX:Mail.Example.com ip4:203.0.113.1 \
+                           include:_spf.example.com ip6:2001:db8::/32 ~all"
+            .parse()
+            .unwrap();
+        let mut built = Spf::new();
+        built.set_v1();
+        built.append_mechanism(Mechanism::all(Qualifier::SoftFail));
+        built.append_ip_mechanism(Mechanism::ip(
+            Qualifier::Pass,
+            "2001:db8::/32".parse().unwrap(),
+        ));
+        built.append_mechanism(Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap());
+        built.append_ip_mechanism(Mechanism::ip(
+            Qualifier::Pass,
+            "203.0.113.1/32".parse().unwrap(),
+        ));
+        built.append_mechanism(
+            Mechanism::mx(Qualifier::Pass)
+                .with_rrdata("mail.example.com")
+                .unwrap(),
+        );
+        built.append_mechanism(Mechanism::a(Qualifier::Pass));
+        assert_eq!(parsed, built);
+        assert_eq!(built, parsed);
+    }
+    #[test]
+    fn parsed_equals_builder() {
+        let parsed: Spf = "v=spf1 a mx include:_spf.example.com redirect=_spf.example.net"
+            .parse()
+            .unwrap();
+        let built = SpfBuilder::new()
+            .v1()
+            .redirect("_spf.example.net")
+            .include(Qualifier::Pass, "_spf.example.com")
+            .mx(Qualifier::Pass, None)
+            .a(Qualifier::Pass, None)
+            .build()
+            .unwrap();
+        assert_eq!(parsed, built);
+    }
+    #[test]
+    fn state_is_ignored() {
+        let parsed: Spf = "v=spf1 a -all".parse().unwrap();
+        let mut validated = parsed.clone();
+        #[allow(deprecated)]
+        let _ = validated.try_validate();
+        let with_warnings: Spf = "v=spf1 a -all -all".parse().unwrap();
+        assert_eq!(with_warnings.parse_warnings().is_some(), true);
+        assert_eq!(parsed, validated);
+        assert_eq!(parsed, with_warnings);
+    }
+    #[test]
+    fn occurrences_are_compared() {
+        let once: Spf = "v=spf1 a mx -all".parse().unwrap();
+        let twice: Spf = "v=spf1 a a mx -all".parse().unwrap();
+        let a_twice: Spf = "v=spf1 a mx mx -all".parse().unwrap();
+        assert_ne!(once, twice);
+        assert_ne!(twice, a_twice);
+    }
+    #[test]
+    fn differ() {
+        let spf: Spf = "v=spf1 a include:_spf.example.com -all".parse().unwrap();
+        for other in [
+            "spf2.0/pra a include:_spf.example.com -all",
+            "v=spf1 a include:_spf.example.com ~all",
+            "v=spf1 a include:_spf.example.com",
+            "v=spf1 a ~include:_spf.example.com -all",
+            "v=spf1 a include:_spf.example.net -all",
+            "v=spf1 a include:_spf.example.com ip4:203.0.113.0/24 -all",
+            "v=spf1 a include:_spf.example.com -all exp=explain.example.com",
+            "v=spf1 a include:_spf.example.com -all x-note=ok",
+        ] {
+            let other: Spf = other.parse().unwrap();
+            assert_ne!(spf, other);
+        }
+    }
+    #[test]
+    fn unknown_modifiers() {
+        let spf: Spf = "v=spf1 -all X-Note=ok x-owner=dns".parse().unwrap();
+        let other: Spf = "v=spf1 x-owner=dns x-note=ok -all".parse().unwrap();
+        assert_eq!(spf, other);
+        let other: Spf = "v=spf1 -all x-note=OK x-owner=dns".parse().unwrap();
+        assert_ne!(spf, other);
+    }
+    #[test]
+    fn empty() {
+        assert_eq!(Spf::new(), Spf::default());
+        let spf: Spf = "v=spf1".parse().unwrap();
+        assert_ne!(Spf::new(), spf);
+    }
+}
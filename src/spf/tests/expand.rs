@@ -0,0 +1,222 @@
+#[cfg(test)]
+mod tree {
+
+    use crate::evaluate::{LookupError, SpfResolver};
+    use crate::spf::{ExpandError, ResolutionMap, Spf, SpfFetchError};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    fn map(records: &[(&str, &str)]) -> ResolutionMap {
+        let mut map = ResolutionMap::new();
+        for (domain, record) in records {
+            map.insert_record(domain, record.parse().unwrap());
+        }
+        map
+    }
+    // example.com includes two providers. One includes a further record and redirects.
+    fn three_levels() -> ResolutionMap {
+        map(&[
+            (
+                "_spf.provider.net",
+                "v=spf1 include:_netblocks.provider.net ip4:203.0.113.0/24 ~all",
+            ),
+            (
+                "_netblocks.provider.net",
+                "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 redirect=_extra.provider.net",
+            ),
+            ("_extra.provider.net", "v=spf1 ip4:198.51.100.0/24 -all"),
+            ("mail.example.org", "v=spf1 a -all"),
+        ])
+    }
+    fn spf(record: &str) -> Spf {
+        record.parse().unwrap()
+    }
+
+    #[test]
+    fn legitimate_tree() {
+        let spf =
+            spf("v=spf1 include:_spf.provider.net include:mail.example.org ip4:10.0.0.1 -all");
+        let tree = spf.expand(&three_levels(), 10).unwrap();
+        assert_eq!(tree.domain(), None);
+        assert_eq!(tree.mechanism(), None);
+        assert_eq!(tree.spf(), &spf);
+        assert_eq!(tree.children().len(), 2);
+        let provider = &tree.children()[0];
+        assert_eq!(provider.domain(), Some("_spf.provider.net"));
+        assert_eq!(
+            provider.children()[0].domain(),
+            Some("_netblocks.provider.net")
+        );
+        let extra = &provider.children()[0].children()[0];
+        assert_eq!(extra.domain(), Some("_extra.provider.net"));
+        assert_eq!(
+            extra.mechanism().unwrap().to_string(),
+            "redirect=_extra.provider.net"
+        );
+        assert_eq!(extra.is_leaf(), true);
+        assert_eq!(tree.children()[1].is_leaf(), true);
+    }
+    #[test]
+    fn nodes_in_preorder() {
+        let spf = spf("v=spf1 include:_spf.provider.net include:mail.example.org -all");
+        let tree = spf.expand(&three_levels(), 10).unwrap();
+        let domains: Vec<Option<&str>> = tree.iter().map(|node| node.domain()).collect();
+        assert_eq!(
+            domains,
+            vec![
+                None,
+                Some("_spf.provider.net"),
+                Some("_netblocks.provider.net"),
+                Some("_extra.provider.net"),
+                Some("mail.example.org"),
+            ]
+        );
+    }
+    #[test]
+    fn ip_networks() {
+        let spf = spf("v=spf1 ip4:10.0.0.1 include:_spf.provider.net -all");
+        let tree = spf.expand(&three_levels(), 10).unwrap();
+        let networks: Vec<String> = tree.ip_networks().map(|n| n.to_string()).collect();
+        assert_eq!(
+            networks,
+            vec![
+                "ip4:10.0.0.1",
+                "ip4:203.0.113.0/24",
+                "ip4:192.0.2.0/24",
+                "ip6:2001:db8::/32",
+                "ip4:198.51.100.0/24",
+            ]
+        );
+    }
+    #[test]
+    fn cycle() {
+        let records = map(&[
+            (
+                "a.example.com",
+                "v=spf1 ip4:192.0.2.1 include:b.example.com -all",
+            ),
+            ("b.example.com", "v=spf1 redirect=c.example.com"),
+            ("c.example.com", "v=spf1 include:A.example.com -all"),
+        ]);
+        let err = spf("v=spf1 include:a.example.com -all")
+            .expand(&records, 10)
+            .unwrap_err();
+        assert_eq!(err.is_cycle(), true);
+        assert_eq!(
+            err,
+            ExpandError::Cycle(vec![
+                "a.example.com".to_string(),
+                "b.example.com".to_string(),
+                "c.example.com".to_string(),
+                "a.example.com".to_string(),
+            ])
+        );
+        assert_eq!(
+            err.to_string(),
+            "Records include each other: a.example.com -> b.example.com -> c.example.com -> \
+             a.example.com"
+        );
+    }
+    #[test]
+    fn self_include() {
+        let records = map(&[("a.example.com", "v=spf1 include:a.example.com -all")]);
+        let err = spf("v=spf1 include:a.example.com -all")
+            .expand(&records, 10)
+            .unwrap_err();
+        assert_eq!(err.path().unwrap(), ["a.example.com", "a.example.com"]);
+    }
+    #[test]
+    fn same_record_twice_is_not_a_cycle() {
+        let records = map(&[
+            ("a.example.com", "v=spf1 include:c.example.com -all"),
+            ("b.example.com", "v=spf1 include:c.example.com -all"),
+            ("c.example.com", "v=spf1 ip4:192.0.2.1 -all"),
+        ]);
+        let tree = spf("v=spf1 include:a.example.com include:b.example.com -all")
+            .expand(&records, 10)
+            .unwrap();
+        assert_eq!(tree.iter().count(), 5);
+        assert_eq!(tree.ip_networks().count(), 2);
+    }
+    #[test]
+    fn depth_limit() {
+        let spf = spf("v=spf1 include:_spf.provider.net -all");
+        assert_eq!(spf.expand(&three_levels(), 3).is_ok(), true);
+        let err = spf.expand(&three_levels(), 2).unwrap_err();
+        assert_eq!(err.is_depth_exceeded(), true);
+        assert_eq!(
+            err.path().unwrap(),
+            [
+                "_spf.provider.net",
+                "_netblocks.provider.net",
+                "_extra.provider.net"
+            ]
+        );
+        let err = spf.expand(&three_levels(), 0).unwrap_err();
+        assert_eq!(err.path().unwrap(), ["_spf.provider.net"]);
+    }
+    #[test]
+    fn no_includes() {
+        let tree = spf("v=spf1 a mx -all")
+            .expand(&ResolutionMap::new(), 0)
+            .unwrap();
+        assert_eq!(tree.is_leaf(), true);
+        assert_eq!(tree.iter().count(), 1);
+    }
+    #[test]
+    fn missing_record() {
+        let err = spf("v=spf1 include:_spf.provider.net include:missing.example.com -all")
+            .expand(&three_levels(), 10)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ExpandError::Fetch {
+                domain: "missing.example.com".to_string(),
+                error: SpfFetchError::NoRecord("missing.example.com".to_string()),
+            }
+        );
+    }
+    #[test]
+    fn macros() {
+        let err = spf("v=spf1 include:%{d}.spf.example.com -all")
+            .expand(&three_levels(), 10)
+            .unwrap_err();
+        assert_eq!(err.has_macros(), true);
+        assert_eq!(err.path(), None);
+    }
+
+    struct Txt;
+
+    impl SpfResolver for Txt {
+        fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+            match name {
+                "_spf.example.net" => Ok(vec!["v=spf1 ip4:192.0.2.0/24 -all".to_string()]),
+                _ => Err(LookupError::new("timed out")),
+            }
+        }
+        fn lookup_a(&self, _name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_aaaa(&self, _name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_mx(&self, _name: &str) -> Result<Vec<String>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_ptr(&self, _ip: IpAddr) -> Result<Vec<String>, LookupError> {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn resolver_source() {
+        let spf = spf("v=spf1 include:_spf.example.net -all");
+        let tree = spf.expand(&Txt, 10).unwrap();
+        assert_eq!(tree.ip_networks().count(), 1);
+        let resolver: &dyn SpfResolver = &Txt;
+        assert_eq!(spf.expand(resolver, 10).unwrap(), tree);
+        let err = self::spf("v=spf1 include:example.org -all")
+            .expand(resolver, 10)
+            .unwrap_err();
+        assert_eq!(err.is_fetch(), true);
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod english {
+
+    use crate::spf::Spf;
+
+    fn lines(record: &str) -> Vec<String> {
+        let spf: Spf = record.parse().unwrap();
+        spf.explain().iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn each_all() {
+        assert_eq!(
+            lines("v=spf1 -all"),
+            ["-all \u{2014} mail from any other source should be rejected (fail)"]
+        );
+        assert_eq!(
+            lines("v=spf1 ~all"),
+            [
+                "~all \u{2014} mail from any other source should be treated with suspicion \
+              (soft fail)"
+            ]
+        );
+        assert_eq!(
+            lines("v=spf1 ?all"),
+            ["?all \u{2014} no statement is made about mail from any other source (neutral)"]
+        );
+        assert_eq!(
+            lines("v=spf1 +all"),
+            [
+                "all \u{2014} mail from any other source is allowed (pass), so anyone may send \
+                 as the domain"
+            ]
+        );
+        assert_eq!(lines("v=spf1"), Vec::<String>::new());
+    }
+    #[test]
+    fn qualifiers() {
+        assert_eq!(
+            lines("v=spf1 ip4:192.0.2.1 -ip4:192.0.2.0/24 ~ip6:2001:db8::/32 ?ip6:2001:db8::1"),
+            [
+                "ip4:192.0.2.1 \u{2014} allow mail from the address 192.0.2.1",
+                "-ip4:192.0.2.0/24 \u{2014} reject mail from any address in 192.0.2.0/24 (fail)",
+                "~ip6:2001:db8::/32 \u{2014} treat with suspicion mail from any address in \
+                 2001:db8::/32 (soft fail)",
+                "?ip6:2001:db8::1 \u{2014} make no statement about mail from the address \
+                 2001:db8::1 (neutral)",
+            ]
+        );
+    }
+    #[test]
+    fn lookups_and_cidr() {
+        assert_eq!(
+            lines("v=spf1 a a:example.com/24//64 mx//48 include:_spf.example.com ptr"),
+            [
+                "a \u{2014} allow mail from the addresses of the domain itself \
+                 (costs 1 DNS lookup)",
+                "a:example.com/24//64 \u{2014} allow mail from the addresses of example.com, \
+                 widened to /24 for ip4 and /64 for ip6 (costs 1 DNS lookup)",
+                "mx//48 \u{2014} allow mail from the mail servers (MX) of the domain itself, \
+                 widened to /48 for ip6 (costs 1 DNS lookup)",
+                "include:_spf.example.com \u{2014} allow every server that the SPF record of \
+                 _spf.example.com allows (costs 1 DNS lookup)",
+                "ptr \u{2014} allow mail from servers whose verified host name ends in the \
+                 domain itself (discouraged by RFC 7208; costs 1 DNS lookup)",
+            ]
+        );
+    }
+    #[test]
+    fn macros_and_modifiers() {
+        assert_eq!(
+            lines(
+                "v=spf1 -exists:%{i}._spf.example.com redirect=_spf.example.com \
+                   exp=explain.example.com note=hello"
+            ),
+            [
+                "-exists:%{i}._spf.example.com \u{2014} reject any mail when \
+                 %{i}._spf.example.com has an address record (fail; the domain is built from \
+                 each message with macros; costs 1 DNS lookup)",
+                "redirect=_spf.example.com \u{2014} use the SPF record of _spf.example.com for \
+                 mail which no mechanism matches (costs 1 DNS lookup)",
+                "exp=explain.example.com \u{2014} explain a failure with the text published at \
+                 explain.example.com",
+                "note=hello \u{2014} an unknown modifier, which is ignored",
+            ]
+        );
+    }
+    #[test]
+    fn deterministic() {
+        let spf: Spf = "v=spf1 mx include:a.example.com ip4:192.0.2.0/24 ~all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.explain(), spf.explain());
+        assert_eq!(spf.explain()[0].term(), "mx");
+        assert_eq!(
+            spf.explain()[0].description(),
+            "allow mail from the mail servers (MX) of the domain itself (costs 1 DNS lookup)"
+        );
+    }
+}
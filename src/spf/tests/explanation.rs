@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod exp_text {
+
+    use crate::evaluate::{LookupError, SpfResolver};
+    use crate::mechanism::MacroContext;
+    use crate::spf::Spf;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    // Answers TXT lookups of explain.example.com with `records`, and fails any lookup of
+    // fail.example.com.
+    struct Txt {
+        records: Vec<&'static str>,
+    }
+
+    impl SpfResolver for Txt {
+        fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+            match name {
+                "explain.example.com" => Ok(self.records.iter().map(|r| r.to_string()).collect()),
+                "fail.example.com" => Err(LookupError::new("timed out")),
+                _ => Ok(vec![]),
+            }
+        }
+        fn lookup_a(&self, _name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_aaaa(&self, _name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_mx(&self, _name: &str) -> Result<Vec<String>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_ptr(&self, _ip: IpAddr) -> Result<Vec<String>, LookupError> {
+            unreachable!()
+        }
+    }
+
+    fn context() -> MacroContext {
+        MacroContext::new(
+            "user@example.com",
+            "192.0.2.3".parse().unwrap(),
+            "mx.example.org",
+        )
+        .receiver("mx.example.net")
+        .timestamp(1_000_000)
+    }
+    fn explain(record: &str, records: &[&'static str]) -> Option<String> {
+        let spf: Spf = record.parse().unwrap();
+        let resolver = Txt {
+            records: records.to_vec(),
+        };
+        spf.explanation(&resolver, &context()).unwrap()
+    }
+
+    #[test]
+    fn no_exp() {
+        assert_eq!(explain("v=spf1 -all", &["unused"]), None);
+    }
+    #[test]
+    fn expanded() {
+        assert_eq!(
+            explain(
+                "v=spf1 -all exp=explain.%{d}",
+                &["%{c} is not one of %{d}'s designated mail servers, says %{r} at %{t}."]
+            ),
+            Some(
+                "192.0.2.3 is not one of example.com's designated mail servers, says \
+                 mx.example.net at 1000000."
+                    .to_string()
+            )
+        );
+    }
+    #[test]
+    fn lookup_failure() {
+        assert_eq!(explain("v=spf1 -all exp=fail.example.com", &[]), None);
+    }
+    #[test]
+    fn record_count() {
+        assert_eq!(explain("v=spf1 -all exp=explain.example.com", &[]), None);
+        assert_eq!(
+            explain("v=spf1 -all exp=explain.example.com", &["one", "two"]),
+            None
+        );
+        assert_eq!(
+            explain("v=spf1 -all exp=explain.example.com", &["one"]),
+            Some("one".to_string())
+        );
+    }
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            explain("v=spf1 -all exp=explain.example.com", &["sent by %{x}"]),
+            None
+        );
+        assert_eq!(
+            explain("v=spf1 -all exp=explain.example.com", &["100%"]),
+            None
+        );
+    }
+    #[test]
+    fn not_ascii() {
+        assert_eq!(
+            explain("v=spf1 -all exp=explain.example.com", &["caf\u{e9}"]),
+            None
+        );
+    }
+    #[test]
+    fn invalid_domain_spec() {
+        let spf: Spf = "v=spf1 -all exp=explain.%{x}.example.com".parse().unwrap();
+        let resolver = Txt { records: vec![] };
+        let err = spf.explanation(&resolver, &context()).unwrap_err();
+        assert_eq!(err.is_invalid_macro(), true);
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod domain {
+
+    use crate::evaluate::{LookupError, SpfResolver};
+    use crate::spf::{Spf, SpfFetchError};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    // Answers TXT lookups of example.com with `records`, or fails when `records` is `None`.
+    struct Txt {
+        records: Option<Vec<&'static str>>,
+    }
+
+    impl SpfResolver for Txt {
+        fn lookup_txt(&self, name: &str) -> Result<Vec<String>, LookupError> {
+            match (&self.records, name) {
+                (None, _) => Err(LookupError::new("timed out")),
+                (Some(records), "example.com") => {
+                    Ok(records.iter().map(|r| r.to_string()).collect())
+                }
+                (Some(_), _) => Ok(vec![]),
+            }
+        }
+        fn lookup_a(&self, _name: &str) -> Result<Vec<Ipv4Addr>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_aaaa(&self, _name: &str) -> Result<Vec<Ipv6Addr>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_mx(&self, _name: &str) -> Result<Vec<String>, LookupError> {
+            unreachable!()
+        }
+        fn lookup_ptr(&self, _ip: IpAddr) -> Result<Vec<String>, LookupError> {
+            unreachable!()
+        }
+    }
+
+    fn fetch(records: &[&'static str]) -> Result<Spf, SpfFetchError> {
+        let resolver = Txt {
+            records: Some(records.to_vec()),
+        };
+        Spf::from_resolver(&resolver, "example.com")
+    }
+
+    #[test]
+    fn single_record() {
+        let spf = fetch(&["v=spf1 include:_spf.example.net -all"]).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 include:_spf.example.net -all");
+        assert_eq!(spf.includes().unwrap().len(), 1);
+    }
+    #[test]
+    fn other_txt_records_ignored() {
+        let spf = fetch(&[
+            "google-site-verification=abc",
+            "v=spf10 +all",
+            "spf2.0/pra -all",
+            "V=SPF1 ip4:192.0.2.0/24 -all",
+        ])
+        .unwrap();
+        assert_eq!(spf.ip4().unwrap().len(), 1);
+    }
+    #[test]
+    fn longer_than_a_character_string() {
+        let mut record = "v=spf1".to_string();
+        for n in 1..=20 {
+            record.push_str(&format!(" ip4:198.51.100.{}", n));
+        }
+        let record: &'static str = Box::leak(record.into_boxed_str());
+        assert_eq!(record.len() > 255, true);
+        let spf = fetch(&[record]).unwrap();
+        assert_eq!(spf.ip4().unwrap().len(), 20);
+        assert_eq!(spf.to_string(), record);
+    }
+    #[test]
+    fn no_record() {
+        assert_eq!(
+            fetch(&["google-site-verification=abc"]).unwrap_err(),
+            SpfFetchError::NoRecord("example.com".to_string())
+        );
+        let resolver = Txt {
+            records: Some(vec![]),
+        };
+        let err = Spf::from_resolver(&resolver, "example.org").unwrap_err();
+        assert_eq!(err.is_no_record(), true);
+        assert_eq!(err.to_string(), "example.org has no Spf record.");
+    }
+    #[test]
+    fn multiple_records() {
+        let err = fetch(&["v=spf1 -all", "v=spf1 mx -all"]).unwrap_err();
+        assert_eq!(err.is_multiple_records(), true);
+        assert_eq!(err.to_string(), "example.com has more than one Spf record.");
+    }
+    #[test]
+    fn lookup_failure() {
+        let resolver = Txt { records: None };
+        let err = Spf::from_resolver(&resolver, "example.com").unwrap_err();
+        assert_eq!(err, SpfFetchError::Lookup(LookupError::new("timed out")));
+        assert_eq!(err.is_lookup(), true);
+        assert_eq!(err.to_string(), "DNS lookup failed: timed out");
+    }
+    #[test]
+    fn invalid_record() {
+        let err = fetch(&["v=spf1 ip4:192.0.2.0/33 -all"]).unwrap_err();
+        assert_eq!(err.is_invalid(), true);
+        if let SpfFetchError::Invalid(err) = err {
+            assert_eq!(err.parse_error().unwrap().term(), "ip4:192.0.2.0/33");
+        }
+    }
+}
+
+#[cfg(test)]
+mod txt_records {
+
+    use crate::spf::{Spf, SpfError};
+
+    fn records(records: &[&str]) -> Vec<String> {
+        records.iter().map(|r| r.to_string()).collect()
+    }
+
+    #[test]
+    fn no_spf_record() {
+        let err = Spf::find_in_txt_records(&records(&[])).unwrap_err();
+        assert_eq!(err, SpfError::NoSpfRecordFound);
+        let err = Spf::find_in_txt_records(&records(&[
+            "google-site-verification=abc",
+            "v=DKIM1; k=rsa; p=MIGf",
+            "v=spf10 a -all",
+            "spf2.0/pra a -all",
+        ]))
+        .unwrap_err();
+        assert_eq!(err.is_no_spf_record_found(), true);
+    }
+    #[test]
+    fn single_spf_record() {
+        let spf = Spf::find_in_txt_records(&records(&[
+            "google-site-verification=abc",
+            "V=Spf1 include:_spf.example.com ~all",
+            "MS=ms12345678",
+        ]))
+        .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 include:_spf.example.com ~all");
+        let spf = Spf::find_in_txt_records(&records(&["v=spf1"])).unwrap();
+        assert_eq!(spf.is_empty(), true);
+    }
+    #[test]
+    fn two_spf_records() {
+        let err = Spf::find_in_txt_records(&records(&[
+            "v=spf1 mx -all",
+            "google-site-verification=abc",
+            "V=SPF1 a -all",
+        ]))
+        .unwrap_err();
+        assert_eq!(err, SpfError::MultipleSpfRecords(2));
+        assert_eq!(err.is_multiple_spf_records(), true);
+    }
+    #[test]
+    fn invalid_spf_record() {
+        let err = Spf::find_in_txt_records(&records(&["verify=abc", "v=spf1 ip4:192.0.2.0/33"]))
+            .unwrap_err();
+        assert_eq!(err.parse_error().unwrap().term(), "ip4:192.0.2.0/33");
+    }
+}
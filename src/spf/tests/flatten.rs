@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod resolve {
+
+    use crate::mechanism::Kind;
+    use crate::spf::{FlattenError, ResolutionMap, Spf, Warning};
+
+    fn spf(record: &str) -> Spf {
+        record.parse().unwrap()
+    }
+    // The include tree of example.com, two levels deep.
+    fn resolutions() -> ResolutionMap {
+        let mut resolutions = ResolutionMap::new();
+        resolutions.set_domain("example.com");
+        resolutions.insert_record(
+            "_spf.example.com",
+            spf("v=spf1 ip4:192.0.2.0/25 include:_spf.mail.example.net mx -all"),
+        );
+        resolutions.insert_record(
+            "_spf.mail.example.net",
+            spf("v=spf1 ip4:198.51.100.0/24 ip6:2001:db8::/32 ~all"),
+        );
+        resolutions.insert_mx("_spf.example.com", vec!["203.0.113.25".parse().unwrap()]);
+        resolutions.insert_a("example.com", vec!["192.0.2.200".parse().unwrap()]);
+        resolutions
+    }
+
+    #[test]
+    fn two_level_include_tree() {
+        let flattened = spf("v=spf1 a include:_spf.example.com -all")
+            .flatten(&resolutions())
+            .unwrap();
+        assert_eq!(
+            flattened.to_string(),
+            "v=spf1 ip4:192.0.2.200/32 ip4:203.0.113.25/32 ip4:198.51.100.0/24 \
+             ip4:192.0.2.0/25 ip6:2001:db8::/32 -all"
+        );
+        assert_eq!(flattened.lookup_count(), 0);
+        assert_eq!(flattened.parse_warnings(), None);
+    }
+    #[test]
+    fn qualifiers_kept() {
+        let flattened = spf("v=spf1 -ip4:192.0.2.128/25 ~include:_spf.mail.example.net ?a ~all")
+            .flatten(&resolutions())
+            .unwrap();
+        assert_eq!(
+            flattened.to_string(),
+            "v=spf1 ?ip4:192.0.2.200/32 ~ip4:198.51.100.0/24 -ip4:192.0.2.128/25 \
+             ~ip6:2001:db8::/32 ~all"
+        );
+    }
+    #[test]
+    fn included_terms_which_do_not_pass() {
+        let mut resolutions = ResolutionMap::new();
+        resolutions.insert_record(
+            "_spf.example.com",
+            spf("v=spf1 ip4:192.0.2.0/24 -ip4:198.51.100.1 -ip6:2001:db8::1 ?all"),
+        );
+        let flattened = spf("v=spf1 include:_spf.example.com -all")
+            .flatten(&resolutions)
+            .unwrap();
+        assert_eq!(flattened.to_string(), "v=spf1 ip4:192.0.2.0/24 -all");
+    }
+    #[test]
+    fn included_term_which_does_not_pass_before_one_which_does() {
+        let mut resolutions = ResolutionMap::new();
+        resolutions.insert_record(
+            "_spf.example.org",
+            spf("v=spf1 -ip4:192.0.2.1 ip4:192.0.2.0/24 -all"),
+        );
+        let err = spf("v=spf1 include:_spf.example.org ~all")
+            .flatten(&resolutions)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            FlattenError::Unflattenable("-ip4:192.0.2.1".to_string())
+        );
+    }
+    #[test]
+    fn included_record_passes_all() {
+        let mut resolutions = ResolutionMap::new();
+        resolutions.insert_record("_spf.example.com", spf("v=spf1 +all"));
+        let flattened = spf("v=spf1 ~include:_spf.example.com -all")
+            .flatten(&resolutions)
+            .unwrap();
+        assert_eq!(
+            flattened.to_string(),
+            "v=spf1 ~ip4:0.0.0.0/0 ~ip6:::/0 -all"
+        );
+    }
+    #[test]
+    fn cidr_suffix_applied() {
+        let mut resolutions = ResolutionMap::new();
+        resolutions.insert_a(
+            "example.com",
+            vec![
+                "192.0.2.10".parse().unwrap(),
+                "2001:db8::10".parse().unwrap(),
+            ],
+        );
+        let flattened = spf("v=spf1 a:Example.com/24//64 -all")
+            .flatten(&resolutions)
+            .unwrap();
+        assert_eq!(
+            flattened.to_string(),
+            "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/64 -all"
+        );
+    }
+    #[test]
+    fn redirect() {
+        let flattened = spf("v=spf1 ip4:203.0.113.0/24 redirect=_spf.mail.example.net")
+            .flatten(&resolutions())
+            .unwrap();
+        assert_eq!(
+            flattened.to_string(),
+            "v=spf1 ip4:203.0.113.0/24 ip4:198.51.100.0/24 ip6:2001:db8::/32 ~all"
+        );
+    }
+    #[test]
+    fn shared_include_is_not_a_cycle() {
+        let mut resolutions = resolutions();
+        resolutions.insert_record(
+            "_spf2.example.com",
+            spf("v=spf1 include:_spf.mail.example.net -all"),
+        );
+        let flattened = spf("v=spf1 include:_spf2.example.com include:_spf.example.com -all")
+            .flatten(&resolutions)
+            .unwrap();
+        assert_eq!(flattened.ip4().unwrap().len(), 3);
+    }
+    #[test]
+    fn include_cycle() {
+        let mut resolutions = ResolutionMap::new();
+        resolutions.insert_record("a.example.com", spf("v=spf1 include:b.example.com -all"));
+        resolutions.insert_record("b.example.com", spf("v=spf1 include:A.example.com -all"));
+        let err = spf("v=spf1 include:a.example.com -all")
+            .flatten(&resolutions)
+            .unwrap_err();
+        assert_eq!(err, FlattenError::IncludeCycle("A.example.com".to_string()));
+        assert_eq!(err.is_include_cycle(), true);
+    }
+    #[test]
+    fn missing_entries() {
+        let err = spf("v=spf1 include:_spf.example.org -all")
+            .flatten(&resolutions())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            FlattenError::MissingRecord("_spf.example.org".to_string())
+        );
+        let err = spf("v=spf1 mx -all").flatten(&resolutions()).unwrap_err();
+        assert_eq!(
+            err,
+            FlattenError::MissingAddresses {
+                kind: Kind::MX,
+                domain: "example.com".to_string()
+            }
+        );
+        assert_eq!(err.is_missing_addresses(), true);
+        let err = spf("v=spf1 a -all")
+            .flatten(&ResolutionMap::new())
+            .unwrap_err();
+        assert_eq!(err, FlattenError::NoDomain(Kind::A));
+    }
+    #[test]
+    fn unflattenable() {
+        for record in [
+            "v=spf1 exists:%{i}.example.com -all",
+            "v=spf1 ptr -all",
+            "v=spf1 include:%{d}.example.com -all",
+        ] {
+            let err = spf(record).flatten(&resolutions()).unwrap_err();
+            assert_eq!(err.is_unflattenable(), true, "{}", record);
+        }
+    }
+    #[test]
+    fn oversized_record() {
+        let mut resolutions = ResolutionMap::new();
+        let networks = (0..20)
+            .map(|i| format!("203.0.{}.0/24", i).parse().unwrap())
+            .collect();
+        resolutions.insert_mx("example.com", networks);
+        let flattened = spf("v=spf1 mx:example.com -all")
+            .flatten(&resolutions)
+            .unwrap();
+        let length = flattened.to_string().len();
+        assert_eq!(length > 255, true);
+        assert_eq!(
+            flattened.parse_warnings(),
+            Some(&vec![Warning::RecordTooLong { length }])
+        );
+        assert_eq!(flattened.to_txt_chunks(255).unwrap().len(), 2);
+    }
+}
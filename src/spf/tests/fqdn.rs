@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::MechanismError;
+    use crate::spf::{Spf, SpfError};
+
+    #[test]
+    fn trailing_dots_round_trip() {
+        let input = "v=spf1 a:mail.example.org. mx:example.com./24 include:_spf.example.com. exists:%{i}._spf.example.com. ptr:example.com. -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.a().unwrap()[0].raw(), "mail.example.org.");
+        assert_eq!(spf.mx().unwrap()[0].raw(), "example.com./24");
+        assert_eq!(spf.includes().unwrap()[0].raw(), "_spf.example.com.");
+        assert_eq!(spf.exists().unwrap()[0].raw(), "%{i}._spf.example.com.");
+        assert_eq!(spf.ptr().unwrap().raw(), "example.com.");
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn empty_label() {
+        let input = "v=spf1 a include:_spf..example.com -all";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::InvalidDomainHost(
+                "_spf..example.com".to_string()
+            ))
+        );
+        assert_eq!(err.parse_error().unwrap().term_index(), 2);
+    }
+    #[test]
+    fn empty_label_a() {
+        let err = "v=spf1 a:example..com -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err.is_invalid_mechanism(), true);
+    }
+}
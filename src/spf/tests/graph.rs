@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod cycles {
+
+    use crate::spf::{Spf, SpfGraph};
+    use std::collections::HashMap;
+
+    fn graph(records: &[(&str, &str)]) -> SpfGraph {
+        let records: HashMap<String, Spf> = records
+            .iter()
+            .map(|(domain, record)| (domain.to_string(), record.parse().unwrap()))
+            .collect();
+        SpfGraph::new(records)
+    }
+    fn path(domains: &[&str]) -> Vec<String> {
+        domains.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn two_node_cycle() {
+        let graph = graph(&[
+            ("b.example.com", "v=spf1 include:a.example.com -all"),
+            (
+                "a.example.com",
+                "v=spf1 ip4:192.0.2.1 include:B.example.com -all",
+            ),
+        ]);
+        assert_eq!(
+            graph.find_cycles(),
+            vec![path(&["a.example.com", "b.example.com", "a.example.com"])]
+        );
+        assert_eq!(graph.missing(), Vec::<&str>::new());
+        // The chain stops before returning to a.example.com
+        assert_eq!(graph.max_depth_from("a.example.com"), 1);
+    }
+    #[test]
+    fn self_include() {
+        let graph = graph(&[
+            ("a.example.com", "v=spf1 include:a.example.com -all"),
+            ("b.example.com", "v=spf1 redirect=b.example.com"),
+        ]);
+        assert_eq!(
+            graph.find_cycles(),
+            vec![
+                path(&["a.example.com", "a.example.com"]),
+                path(&["b.example.com", "b.example.com"]),
+            ]
+        );
+        assert_eq!(graph.max_depth_from("a.example.com"), 0);
+    }
+    #[test]
+    fn acyclic_tree() {
+        let graph = graph(&[
+            (
+                "example.com",
+                "v=spf1 include:_spf.example.com include:_spf.provider.net -all",
+            ),
+            ("_spf.example.com", "v=spf1 ip4:192.0.2.0/24 -all"),
+            (
+                "_spf.provider.net",
+                "v=spf1 include:_a.provider.net redirect=_b.provider.net",
+            ),
+            ("_a.provider.net", "v=spf1 include:_c.provider.net -all"),
+            ("_b.provider.net", "v=spf1 ip4:198.51.100.0/24 -all"),
+        ]);
+        assert_eq!(graph.find_cycles(), Vec::<Vec<String>>::new());
+        assert_eq!(graph.missing(), vec!["_c.provider.net"]);
+        assert_eq!(graph.max_depth_from("example.com"), 3);
+        assert_eq!(graph.max_depth_from("Example.COM"), 3);
+        assert_eq!(graph.max_depth_from("_spf.provider.net"), 2);
+        assert_eq!(graph.max_depth_from("_spf.example.com"), 0);
+        assert_eq!(graph.max_depth_from("unknown.example.com"), 0);
+        assert_eq!(
+            graph.targets("_spf.provider.net").unwrap(),
+            ["_a.provider.net", "_b.provider.net"]
+        );
+        assert_eq!(graph.targets("_c.provider.net"), None);
+    }
+    #[test]
+    fn every_cycle() {
+        // a -> b -> a, a -> b -> c -> a and c -> c
+        let graph = graph(&[
+            ("a.example.com", "v=spf1 include:b.example.com -all"),
+            (
+                "b.example.com",
+                "v=spf1 include:a.example.com include:c.example.com -all",
+            ),
+            (
+                "c.example.com",
+                "v=spf1 include:c.example.com redirect=a.example.com",
+            ),
+        ]);
+        assert_eq!(
+            graph.find_cycles(),
+            vec![
+                path(&["a.example.com", "b.example.com", "a.example.com"]),
+                path(&[
+                    "a.example.com",
+                    "b.example.com",
+                    "c.example.com",
+                    "a.example.com"
+                ]),
+                path(&["c.example.com", "c.example.com"]),
+            ]
+        );
+        assert_eq!(graph.max_depth_from("a.example.com"), 2);
+    }
+    #[test]
+    fn macros_ignored() {
+        let graph = graph(&[("a.example.com", "v=spf1 include:%{d}.example.net -all")]);
+        assert_eq!(graph.targets("a.example.com").unwrap().len(), 0);
+        assert_eq!(graph.missing(), Vec::<&str>::new());
+    }
+}
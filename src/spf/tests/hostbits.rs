@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::spf::{ParseOptions, Spf, Warning};
+
+    #[test]
+    fn warning_for_each_network() {
+        let input = "v=spf1 ip4:192.168.1.17/24 ip4:192.0.2.0/24 ip6:2001:db8::1/127 -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(
+            spf.parse_warnings().unwrap(),
+            &vec![
+                Warning::HostBitsSet {
+                    network: "192.168.1.0/24".parse().unwrap(),
+                    position: 1
+                },
+                Warning::HostBitsSet {
+                    network: "2001:db8::/127".parse().unwrap(),
+                    position: 3
+                }
+            ]
+        );
+        // Without normalisation the record is unchanged.
+        assert_eq!(spf.to_string(), input);
+        assert_eq!(spf.ip4().unwrap()[0].is_normalized(), false);
+    }
+    #[test]
+    fn full_prefix_has_no_warning() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.1/32 ip4:192.0.2.1 ip6:2001:db8::1/128 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.parse_warnings().is_none(), true);
+    }
+    #[test]
+    fn normalize_networks() {
+        let options = ParseOptions::new().normalize_networks(true);
+        let input = "v=spf1 -ip4:192.0.2.1/0 ip4:192.0.2.1/31 ip6:2001:db8::1/0 -all";
+
+        let spf = Spf::from_str_with_options(input, &options).unwrap();
+        assert_eq!(spf.parse_warnings().unwrap().len(), 3);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 -ip4:0.0.0.0/0 ip4:192.0.2.0/31 ip6:::/0 -all"
+        );
+        assert_eq!(spf.ip4().unwrap()[0].qualifier().is_fail(), true);
+    }
+    #[test]
+    fn strict_is_not_an_error() {
+        let spf = Spf::from_str_strict("v=spf1 ip4:192.168.1.17/24 -all").unwrap();
+        assert_eq!(spf.parse_warnings().unwrap()[0].is_host_bits_set(), true);
+    }
+}
@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 mod parse {
 
     use crate::spf::Spf;
@@ -17,4 +16,41 @@ mod parse {
             "include:_spf.example.com"
         );
     }
+    #[test]
+    fn macro_targets_round_trip() {
+        // Include targets using macros as seen in published records.
+        let input =
+            "v=spf1 include:%{ir}.%{v}._spf.%{d2} include:%{l1r-}.user._spf.example.com ~all";
+
+        let spf: Spf = input.parse().unwrap();
+        let includes = spf.includes().unwrap();
+        assert_eq!(includes[0].raw(), "%{ir}.%{v}._spf.%{d2}");
+        assert_eq!(includes[1].raw(), "%{l1r-}.user._spf.example.com");
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn target_containing_colon() {
+        let input = "v=spf1 -include:ip:%{i}.include.example.com ~all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(
+            spf.includes().unwrap()[0].raw(),
+            "ip:%{i}.include.example.com"
+        );
+        assert_eq!(spf.includes().unwrap()[0].is_fail(), true);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn empty_target() {
+        let err = "v=spf1 include: ~all".parse::<Spf>().unwrap_err();
+        assert_eq!(err.is_invalid_mechanism(), true);
+    }
+    #[test]
+    fn exists_macro_with_colon() {
+        let input = "v=spf1 exists:%{l}:%{i}._spf.%{d} -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.exists().unwrap()[0].raw(), "%{l}:%{i}._spf.%{d}");
+        assert_eq!(spf.to_string(), input);
+    }
 }
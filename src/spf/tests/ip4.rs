@@ -1,8 +1,9 @@
 #[cfg(test)]
-
 mod parse {
 
-    use crate::spf::Spf;
+    use crate::mechanism::{Mechanism, MechanismError};
+    use crate::spf::{Spf, SpfError};
+    use ipnetwork::IpNetwork;
 
     #[test]
     fn test_ip4_valid() {
@@ -16,4 +17,40 @@ mod parse {
         assert_eq!(spf.ip4().unwrap()[0].as_network().prefix(), 23);
         assert_eq!(spf.to_string(), "v=spf1 ip4:10.0.0.0/23 ~all");
     }
+    #[test]
+    fn test_ip4_without_prefix() {
+        let input = "v=spf1 ip4:203.0.113.4 -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.ip4().unwrap()[0].as_network().prefix(), 32);
+        assert_eq!(spf.ip4().unwrap()[0].to_string(), "ip4:203.0.113.4");
+        assert_eq!(
+            spf.ip4().unwrap()[0].to_string_with_prefix(),
+            "ip4:203.0.113.4/32"
+        );
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn test_ip4_holding_ip6() {
+        let input = "v=spf1 ip4:2001:db8::/32 -all";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::NotIP4Network("2001:db8::/32".to_string()))
+        );
+        assert_eq!(err.parse_error().unwrap().term(), "ip4:2001:db8::/32");
+    }
+    #[test]
+    fn empty_ip4() {
+        for term in ["ip4:", "-ip4:"] {
+            let input = format!("v=spf1 {} -all", term);
+            let expected = term.parse::<Mechanism<IpNetwork>>().unwrap_err();
+            assert_eq!(expected, MechanismError::EmptyIpValue(term.to_string()));
+
+            let err = input.parse::<Spf>().unwrap_err();
+            assert_eq!(err.inner(), &SpfError::InvalidMechanism(expected));
+            assert_eq!(err.parse_error().unwrap().term(), term);
+        }
+    }
 }
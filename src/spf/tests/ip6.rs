@@ -1,8 +1,9 @@
 #[cfg(test)]
-
 mod parse {
 
-    use crate::spf::Spf;
+    use crate::mechanism::{Mechanism, MechanismError};
+    use crate::spf::{Spf, SpfError};
+    use ipnetwork::IpNetwork;
 
     #[test]
     fn test_ip6_valid() {
@@ -14,4 +15,62 @@ mod parse {
         assert_eq!(spf.ip6().unwrap()[0].raw(), "2001:4860:4000::/36");
         assert_eq!(spf.ip6().unwrap()[0].to_string(), "ip6:2001:4860:4000::/36");
     }
+    #[test]
+    fn test_ip6_without_prefix() {
+        let input = "v=spf1 ip6:2001:db8::1 -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.ip6().unwrap()[0].as_network().prefix(), 128);
+        assert_eq!(spf.ip6().unwrap()[0].to_string(), "ip6:2001:db8::1");
+        assert_eq!(
+            spf.ip6().unwrap()[0].to_string_with_prefix(),
+            "ip6:2001:db8::1/128"
+        );
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn mixed_prefixes() {
+        let input =
+            "v=spf1 ip4:203.0.113.4 ip4:198.51.100.0/24 ip4:192.0.2.1/32 ip6:2001:db8::1 ip6:2001:db8:1::/48 -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.to_string(), input);
+        assert_eq!(
+            spf.ip4().unwrap()[0].to_string_with_prefix(),
+            "ip4:203.0.113.4/32"
+        );
+        assert_eq!(
+            spf.ip4().unwrap()[2].to_string_with_prefix(),
+            "ip4:192.0.2.1/32"
+        );
+        assert_eq!(
+            spf.ip6().unwrap()[0].to_string_with_prefix(),
+            "ip6:2001:db8::1/128"
+        );
+    }
+    #[test]
+    fn test_ip6_holding_ip4() {
+        let input = "v=spf1 ip6:203.0.113.0/24 -all";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::NotIP6Network(
+                "203.0.113.0/24".to_string()
+            ))
+        );
+        assert_eq!(err.parse_error().unwrap().term(), "ip6:203.0.113.0/24");
+    }
+    #[test]
+    fn empty_ip6() {
+        for term in ["ip6:", "-ip6:"] {
+            let input = format!("v=spf1 {} -all", term);
+            let expected = term.parse::<Mechanism<IpNetwork>>().unwrap_err();
+            assert_eq!(expected, MechanismError::EmptyIpValue(term.to_string()));
+
+            let err = input.parse::<Spf>().unwrap_err();
+            assert_eq!(err.inner(), &SpfError::InvalidMechanism(expected));
+            assert_eq!(err.parse_error().unwrap().term(), term);
+        }
+    }
 }
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::{Kind, MechanismRef, Qualifier};
+    use crate::spf::Spf;
+
+    #[test]
+    fn every_kind() {
+        // Written out of order, so the iterator follows the output order.
+        let input = "v=spf1 -all ptr exists:%{i}.example.com ip6:2001:db8::/32 \
+                     ip4:203.0.113.0/24 ~include:_spf.example.com mx:mail.example.com a \
+                     exp=explain.example.com";
+        let spf: Spf = input.parse().unwrap();
+        let kinds: Vec<Kind> = spf.iter().map(|m| *m.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::A,
+                Kind::MX,
+                Kind::Include,
+                Kind::IpV4,
+                Kind::IpV6,
+                Kind::Exists,
+                Kind::Ptr,
+                Kind::All,
+                Kind::Exp,
+            ]
+        );
+        let terms: Vec<String> = spf.iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            spf.to_string(),
+            format!("{} {}", spf.version(), terms.join(" "))
+        );
+    }
+    #[test]
+    fn redirect() {
+        let spf: Spf = "v=spf1 a a:example.com redirect=_spf.example.com"
+            .parse()
+            .unwrap();
+        let terms: Vec<String> = spf.iter().map(|m| m.to_string()).collect();
+        assert_eq!(terms, ["a", "a:example.com", "redirect=_spf.example.com"]);
+    }
+    #[test]
+    fn count() {
+        let spf: Spf = "v=spf1 a mx include:a.example.com include:b.example.com \
+                        ip4:203.0.113.1 ip4:203.0.113.2 ip6:2001:db8::1 ~all x-note=ok"
+            .parse()
+            .unwrap();
+        // Unknown modifiers are not included.
+        assert_eq!(spf.iter().count(), 8);
+        assert_eq!(spf.iter().filter(|m| m.is_network()).count(), 3);
+        assert_eq!(Spf::new().iter().count(), 0);
+    }
+    #[test]
+    fn mechanism_ref() {
+        let spf: Spf = "v=spf1 -ip4:203.0.113.1 ?mx".parse().unwrap();
+        let mechanisms: Vec<MechanismRef> = spf.iter().collect();
+        assert_eq!(mechanisms[0].kind(), &Kind::MX);
+        assert_eq!(mechanisms[0].qualifier(), &Qualifier::Neutral);
+        assert_eq!(mechanisms[0].txt(), Some(&spf.mx().unwrap()[0]));
+        assert_eq!(mechanisms[0].network(), None);
+        assert_eq!(mechanisms[1].kind(), &Kind::IpV4);
+        assert_eq!(mechanisms[1].qualifier(), &Qualifier::Fail);
+        assert_eq!(mechanisms[1].network(), Some(&spf.ip4().unwrap()[0]));
+        assert_eq!(mechanisms[1].txt(), None);
+        assert_eq!(mechanisms[1].to_string(), "-ip4:203.0.113.1");
+    }
+}
+#[cfg(test)]
+mod into_iter {
+
+    use crate::mechanism::{MechanismRef, ParsedMechanism};
+    use crate::spf::Spf;
+
+    const RECORD: &str = "v=spf1 -all ptr exists:%{i}.example.com ip6:2001:db8::/32 \
+                          ip4:203.0.113.0/24 ~include:_spf.example.com mx:mail.example.com a \
+                          exp=explain.example.com x-note=ok";
+
+    #[test]
+    fn borrowed_agrees_with_iter() {
+        let spf: Spf = RECORD.parse().unwrap();
+        let mut looped: Vec<MechanismRef> = Vec::new();
+        for mechanism in &spf {
+            looped.push(mechanism);
+        }
+        assert_eq!(looped, spf.iter().collect::<Vec<MechanismRef>>());
+        assert_eq!((&spf).into_iter().count(), 9);
+    }
+    #[test]
+    fn owned_agrees_with_iter() {
+        let spf: Spf = RECORD.parse().unwrap();
+        let expected: Vec<String> = spf.iter().map(|m| m.to_string()).collect();
+        let owned: Vec<ParsedMechanism> = spf.into_iter().collect();
+        assert_eq!(owned.len(), expected.len());
+        for (owned, expected) in owned.iter().zip(&expected) {
+            assert_eq!(&owned.to_string(), expected);
+        }
+        assert_eq!(owned[3].is_network(), true);
+        assert_eq!(owned[4].is_network(), true);
+        assert_eq!(owned[5].is_network(), false);
+    }
+    #[test]
+    fn owned_redirect() {
+        let spf: Spf = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+        let terms: Vec<String> = spf.into_iter().map(|m| m.to_string()).collect();
+        assert_eq!(terms, ["a", "redirect=_spf.example.com"]);
+    }
+    #[test]
+    fn empty() {
+        assert_eq!(Spf::new().into_iter().count(), 0);
+        assert_eq!((&Spf::new()).into_iter().count(), 0);
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(test)]
+mod append {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::{Spf, SpfBuilder, SpfError};
+
+    // A domain of exactly `length` characters, made of labels no longer than 50 characters.
+    fn domain(length: usize) -> String {
+        let mut domain = String::from("example.com");
+        while domain.len() < length {
+            let mut label = (length - domain.len()).min(51);
+            if length - domain.len() - label == 1 {
+                label -= 1;
+            }
+            domain.insert_str(0, &format!("{}.", "a".repeat(label - 1)));
+        }
+        domain
+    }
+    // `v=spf1 include:<domain>` written with exactly `length` characters.
+    fn include_for(length: usize) -> Mechanism<String> {
+        Mechanism::include(Qualifier::Pass, &domain(length - 15)).unwrap()
+    }
+
+    #[test]
+    fn domain_length() {
+        for length in [200, 239, 240, 241] {
+            assert_eq!(domain(length).len(), length);
+        }
+    }
+    #[test]
+    fn under_limit() {
+        let mut spf = Spf::new_v1();
+        spf.try_append_mechanism(include_for(254)).unwrap();
+        assert_eq!(spf.to_string().len(), 254);
+        assert_eq!(spf.remaining_length(), 1);
+        assert_eq!(spf.check_length(), Ok(()));
+    }
+    #[test]
+    fn at_limit() {
+        let mut spf = Spf::new_v1();
+        spf.try_append_mechanism(include_for(255)).unwrap();
+        assert_eq!(spf.to_string().len(), 255);
+        assert_eq!(spf.remaining_length(), 0);
+        assert_eq!(spf.check_length(), Ok(()));
+    }
+    #[test]
+    fn over_limit() {
+        let mut spf = Spf::new_v1();
+        assert_eq!(
+            spf.try_append_mechanism(include_for(256)).unwrap_err(),
+            SpfError::SourceLengthExceeded
+        );
+        assert_eq!(spf.to_string(), "v=spf1");
+    }
+    #[test]
+    fn lenient_append_is_not_limited() {
+        let mut spf = Spf::new_v1();
+        spf.append_mechanism(include_for(256));
+        assert_eq!(spf.remaining_length(), -1);
+        assert_eq!(spf.check_length(), Err(SpfError::SourceLengthExceeded));
+    }
+    #[test]
+    fn lenient_ip_append_is_not_limited() {
+        let mut spf = Spf::new_v1();
+        for octet in 0..30 {
+            let network = format!("203.0.{}.0/24", octet).parse().unwrap();
+            spf.append_ip_mechanism(Mechanism::ip(Qualifier::Pass, network));
+        }
+        assert_eq!(spf.ip4().unwrap().len(), 30);
+        assert_eq!(spf.remaining_length() < 0, true);
+        assert_eq!(spf.check_length(), Err(SpfError::SourceLengthExceeded));
+    }
+    #[test]
+    fn ip_over_limit() {
+        let mut spf = Spf::new_v1();
+        // `v=spf1 include:<domain> ip4:203.0.113.0/24` is 256 characters.
+        spf.try_append_mechanism(include_for(237)).unwrap();
+        let ip = Mechanism::ip(Qualifier::Pass, "203.0.113.0/24".parse().unwrap());
+        assert_eq!(
+            spf.try_append_ip_mechanism(ip.clone()).unwrap_err(),
+            SpfError::SourceLengthExceeded
+        );
+        assert_eq!(spf.ip4().is_none(), true);
+        assert_eq!(
+            spf.append_str("ip4:203.0.113.0/24").unwrap_err(),
+            SpfError::SourceLengthExceeded
+        );
+        spf.set_max_length(256);
+        spf.try_append_ip_mechanism(ip).unwrap();
+        assert_eq!(spf.remaining_length(), 0);
+    }
+    #[test]
+    fn unknown_modifier_over_limit() {
+        let mut spf = Spf::new_v1();
+        spf.try_append_mechanism(include_for(249)).unwrap();
+        assert_eq!(
+            spf.append_str("x-a=bc").unwrap_err(),
+            SpfError::SourceLengthExceeded
+        );
+        spf.append_str("x-a=b").unwrap();
+        assert_eq!(spf.to_string().len(), 255);
+    }
+    #[test]
+    fn raised_limit() {
+        let mut spf = Spf::new_v1();
+        spf.set_max_length(300);
+        spf.try_append_mechanism(include_for(256)).unwrap();
+        assert_eq!(spf.max_length(), 300);
+        assert_eq!(spf.remaining_length(), 44);
+        assert_eq!(spf.check_length(), Ok(()));
+    }
+    #[test]
+    fn txt_strings_have_no_limit() {
+        let include = include_for(250).to_string();
+        let spf = Spf::from_txt_strings(&["v=spf1 ", &include, " -all"]).unwrap();
+        assert_eq!(spf.max_length(), usize::MAX);
+        assert_eq!(spf.check_length(), Ok(()));
+    }
+    #[test]
+    fn builder() {
+        let include = domain(240);
+        let err = SpfBuilder::new()
+            .v1()
+            .include(Qualifier::Pass, &include)
+            .all(Qualifier::Fail)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, SpfError::SourceLengthExceeded);
+        let spf = SpfBuilder::new()
+            .max_length(260)
+            .v1()
+            .include(Qualifier::Pass, &include)
+            .all(Qualifier::Fail)
+            .build()
+            .unwrap();
+        assert_eq!(spf.to_string().len(), 260);
+    }
+}
+#[cfg(test)]
+mod serialized {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::{ParseOptions, Spf};
+    use ipnetwork::IpNetwork;
+
+    const RECORDS: [&str; 8] = [
+        "v=spf1 -all",
+        "v=spf1 a mx ~all",
+        "v=spf1 a:example.com/28 mx:mail.example.com/24//64 ptr:example.com ?all",
+        "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.1 -ip6:2001:db8::/32 include:_spf.example.com -all",
+        "v=spf1 exists:%{i}._spf.%{d} redirect=_spf.example.com",
+        "v=spf1 include:a.example.com -all exp=explain.example.com",
+        "spf2.0/mfrom,pra mx ip4:203.0.113.0/24 ~all",
+        "v=spf1 a -all moo=cow foo.bar=%{d}",
+    ];
+
+    #[test]
+    fn matches_to_string() {
+        for record in RECORDS {
+            let spf: Spf = record.parse().unwrap();
+            assert_eq!(spf.serialized_len(), spf.to_string().len(), "{}", record);
+        }
+    }
+    #[test]
+    fn matches_preserved_presentation() {
+        let options = ParseOptions::new()
+            .preserve_case(true)
+            .preserve_explicit_pass(true);
+        for record in ["V=SPF1 +A Include:_spf.Example.com +IP4:192.0.2.1 -ALL"]
+            .iter()
+            .chain(RECORDS.iter())
+        {
+            let spf = Spf::from_str_with_options(record, &options).unwrap();
+            assert_eq!(spf.serialized_len(), spf.to_string().len(), "{}", record);
+        }
+    }
+    #[test]
+    fn built_records() {
+        assert_eq!(Spf::new().serialized_len(), 0);
+        let mut spf = Spf::new_v1();
+        assert_eq!(spf.serialized_len(), 6);
+        spf.append_mechanism(Mechanism::mx(Qualifier::Pass));
+        spf.append_ip_mechanism(Mechanism::ip(
+            Qualifier::Fail,
+            "2001:db8::/32".parse().unwrap(),
+        ));
+        spf.append_mechanism(Mechanism::all(Qualifier::SoftFail));
+        assert_eq!(spf.serialized_len(), spf.to_string().len());
+        assert_eq!(spf.remaining_budget(), 255 - spf.to_string().len() as isize);
+    }
+    #[test]
+    fn sum_of_terms() {
+        for record in RECORDS {
+            let spf: Spf = record.parse().unwrap();
+            let terms: usize = spf
+                .iter()
+                .map(|m| match m.network() {
+                    Some(network) => network.serialized_len(),
+                    None => m.txt().unwrap().serialized_len(),
+                })
+                .sum();
+            let modifiers: usize = spf
+                .unknown_modifiers()
+                .map_or(0, |m| m.iter().map(|(n, v)| n.len() + v.len() + 2).sum());
+            assert_eq!(
+                spf.version().len() + terms + modifiers,
+                spf.serialized_len()
+            );
+        }
+    }
+    #[test]
+    fn mechanism_includes_qualifier_and_space() {
+        let pass = Mechanism::include(Qualifier::Pass, "example.com").unwrap();
+        assert_eq!(pass.serialized_len(), " include:example.com".len());
+        let neutral = pass.with_qualifier(Qualifier::Neutral);
+        assert_eq!(neutral.serialized_len(), " ?include:example.com".len());
+        let ip: Mechanism<IpNetwork> = "-ip6:2001:db8::1".parse().unwrap();
+        assert_eq!(ip.serialized_len(), ip.to_string().len() + 1);
+    }
+    #[test]
+    fn over_budget() {
+        let mut spf = Spf::new_v1();
+        spf.set_max_length(1024);
+        for i in 0..20 {
+            spf.append_str(&format!("include:_spf{}.example.com", i))
+                .unwrap();
+        }
+        assert_eq!(spf.remaining_budget() < 0, true);
+        assert_eq!(spf.remaining_budget(), 255 - spf.to_string().len() as isize);
+        assert_eq!(spf.remaining_length() > 0, true);
+    }
+}
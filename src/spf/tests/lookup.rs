@@ -0,0 +1,256 @@
+#[cfg(test)]
+mod count {
+
+    use crate::spf::{Spf, SpfError};
+
+    #[test]
+    fn no_lookups() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.lookup_count(), 0);
+        assert_eq!(Spf::new_v1().lookup_count(), 0);
+    }
+    #[test]
+    fn each_kind() {
+        let spf: Spf = "v=spf1 a mx include:_spf.example.com exists:%{i}.example.com ptr \
+                        redirect=_spf.example.net"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.lookup_count(), 6);
+    }
+    #[test]
+    fn ten_lookups() {
+        let mut spf: Spf = "v=spf1 a mx:mail.example.com include:a.example.com \
+                            include:b.example.com include:c.example.com exists:%{i}.example.com \
+                            ptr a:web.example.com mx include:d.example.com -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.lookup_count(), 10);
+        #[allow(deprecated)]
+        let result = spf.try_validate();
+        assert_eq!(result, Ok(()));
+    }
+    #[test]
+    fn eleven_lookups() {
+        let mut spf: Spf = "v=spf1 a mx:mail.example.com include:a.example.com \
+                            include:b.example.com include:c.example.com exists:%{i}.example.com \
+                            ptr a:web.example.com mx include:d.example.com \
+                            exists:%{l}.example.com -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.lookup_count(), 11);
+        #[allow(deprecated)]
+        let result = spf.try_validate();
+        assert_eq!(result, Err(SpfError::LookupLimitExceeded));
+    }
+}
+#[cfg(test)]
+mod breakdown {
+
+    use crate::spf::Spf;
+
+    // Each term with whether it costs a lookup, its additional lookups and if it may be void.
+    fn breakdown(record: &str) -> Vec<(String, bool, usize, bool)> {
+        let spf: Spf = record.parse().unwrap();
+        spf.lookup_breakdown()
+            .iter()
+            .map(|item| {
+                (
+                    item.mechanism().to_string(),
+                    item.costs_lookup(),
+                    item.additional_lookups(),
+                    item.may_be_void(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ip_only() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all"
+            .parse()
+            .unwrap();
+        let breakdown = spf.lookup_breakdown();
+        assert_eq!(breakdown.len(), 3);
+        assert_eq!(breakdown.iter().any(|item| item.costs_lookup()), false);
+        assert_eq!(spf.potential_void_lookups(), 0);
+    }
+    #[test]
+    fn mixed_terms() {
+        assert_eq!(
+            breakdown(
+                "v=spf1 ip4:192.0.2.0/24 mx include:_spf.example.com ip6:2001:db8::/32 \
+                 exists:%{i}.example.com ptr a ~all"
+            ),
+            vec![
+                ("a".to_string(), true, 0, false),
+                ("mx".to_string(), true, 10, false),
+                ("include:_spf.example.com".to_string(), true, 0, true),
+                ("ip4:192.0.2.0/24".to_string(), false, 0, false),
+                ("ip6:2001:db8::/32".to_string(), false, 0, false),
+                ("exists:%{i}.example.com".to_string(), true, 0, true),
+                ("ptr".to_string(), true, 10, false),
+                ("~all".to_string(), false, 0, false),
+            ]
+        );
+    }
+    #[test]
+    fn redirect_and_exp() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 include:a.example.com \
+                        redirect=_spf.example.com exp=explain.example.com"
+            .parse()
+            .unwrap();
+        let breakdown = spf.lookup_breakdown();
+        let redirect = breakdown
+            .iter()
+            .find(|item| item.mechanism().kind().is_redirect())
+            .unwrap();
+        assert_eq!(redirect.costs_lookup(), true);
+        assert_eq!(redirect.may_be_void(), true);
+        let exp = breakdown.last().unwrap();
+        assert_eq!(exp.mechanism().kind().is_exp(), true);
+        assert_eq!(exp.costs_lookup(), false);
+        assert_eq!(spf.potential_void_lookups(), 2);
+    }
+    #[test]
+    fn agrees_with_lookup_count() {
+        let spf: Spf = "v=spf1 a mx:mail.example.com ip4:192.0.2.0/24 include:a.example.com \
+                        include:b.example.com ip6:2001:db8::/32 exists:%{i}.example.com -all"
+            .parse()
+            .unwrap();
+        let breakdown = spf.lookup_breakdown();
+        let costs = breakdown.iter().filter(|item| item.costs_lookup()).count();
+        assert_eq!(costs, spf.lookup_count());
+        assert_eq!(spf.potential_void_lookups(), 3);
+        for (index, item) in breakdown.iter().enumerate() {
+            assert_eq!(item.index(), index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod total {
+
+    use crate::spf::{ResolutionMap, Spf, SpfError};
+
+    fn map(records: &[(&str, &str)]) -> ResolutionMap {
+        let mut map = ResolutionMap::new();
+        for (domain, record) in records {
+            map.insert_record(domain, record.parse().unwrap());
+        }
+        map
+    }
+    fn spf(record: &str) -> Spf {
+        record.parse().unwrap()
+    }
+
+    #[test]
+    fn nested_includes() {
+        let records = map(&[
+            (
+                "_spf.provider.net",
+                "v=spf1 include:_a.provider.net include:_b.provider.net ~all",
+            ),
+            ("_a.provider.net", "v=spf1 a mx ptr -all"),
+            ("_b.provider.net", "v=spf1 redirect=_c.provider.net"),
+            ("_c.provider.net", "v=spf1 exists:%{i}.provider.net -all"),
+            ("_spf.example.org", "v=spf1 ip4:192.0.2.0/24 -all"),
+        ]);
+        let spf = spf("v=spf1 a include:_spf.provider.net include:_spf.example.org -all");
+        let report = spf.total_lookup_count(&records).unwrap();
+        assert_eq!(report.total(), 10);
+        assert_eq!(report.is_exceeded(), false);
+        let domains: Vec<(Option<&str>, usize, usize, usize)> = report
+            .domains()
+            .iter()
+            .map(|d| (d.domain(), d.depth(), d.lookups(), d.total()))
+            .collect();
+        assert_eq!(
+            domains,
+            vec![
+                (None, 0, 3, 10),
+                (Some("_spf.provider.net"), 1, 2, 7),
+                (Some("_a.provider.net"), 2, 3, 3),
+                (Some("_b.provider.net"), 2, 1, 2),
+                (Some("_c.provider.net"), 3, 1, 1),
+                (Some("_spf.example.org"), 1, 0, 0),
+            ]
+        );
+        assert_eq!(
+            report.heaviest().unwrap().domain(),
+            Some("_spf.provider.net")
+        );
+        assert_eq!(report.domain("_B.provider.net").unwrap().total(), 2);
+        assert_eq!(report.missing(), Vec::<&str>::new());
+    }
+    #[test]
+    fn exceeded() {
+        let records = map(&[
+            ("_a.example.net", "v=spf1 a a a a a a -all"),
+            ("_b.example.net", "v=spf1 mx mx mx mx mx -all"),
+            ("_c.example.net", "v=spf1 a -all"),
+        ]);
+        let spf =
+            spf("v=spf1 include:_a.example.net include:_b.example.net include:_c.example.net -all");
+        assert_eq!(spf.lookup_count(), 3);
+        let report = spf.total_lookup_count(&records).unwrap();
+        assert_eq!(report.is_exceeded(), true);
+        assert_eq!(report.total(), 14);
+        // Counting stops once the limit is passed.
+        assert_eq!(report.domain("_c.example.net"), None);
+        assert_eq!(report.heaviest().unwrap().domain(), Some("_a.example.net"));
+    }
+    #[test]
+    fn missing_record() {
+        let records = map(&[("_spf.example.net", "v=spf1 include:_gone.example.net -all")]);
+        let spf = spf("v=spf1 include:_spf.example.net include:_none.example.org -all");
+        let report = spf.total_lookup_count(&records).unwrap();
+        assert_eq!(report.total(), 3);
+        assert_eq!(
+            report.missing(),
+            vec!["_gone.example.net", "_none.example.org"]
+        );
+        let gone = report.domain("_gone.example.net").unwrap();
+        assert_eq!(gone.is_missing(), true);
+        assert_eq!(gone.depth(), 2);
+        assert_eq!(gone.error().unwrap().is_no_record(), true);
+        assert_eq!(
+            report.domain("_spf.example.net").unwrap().is_missing(),
+            false
+        );
+    }
+    #[test]
+    fn cycle() {
+        let records = map(&[
+            ("a.example.com", "v=spf1 mx include:b.example.com -all"),
+            ("b.example.com", "v=spf1 redirect=A.example.com"),
+        ]);
+        let err = spf("v=spf1 include:a.example.com -all")
+            .total_lookup_count(&records)
+            .unwrap_err();
+        assert_eq!(err.is_include_cycle(), true);
+        assert_eq!(
+            err,
+            SpfError::IncludeCycle(vec![
+                "a.example.com".to_string(),
+                "b.example.com".to_string(),
+                "a.example.com".to_string(),
+            ])
+        );
+    }
+    #[test]
+    fn macros_not_followed() {
+        let spf = spf("v=spf1 include:%{d}._spf.example.com -all");
+        let report = spf.total_lookup_count(&ResolutionMap::new()).unwrap();
+        assert_eq!(report.total(), 1);
+        assert_eq!(report.domains().len(), 1);
+    }
+    #[test]
+    fn no_lookups() {
+        let spf = spf("v=spf1 ip4:192.0.2.0/24 -all");
+        let report = spf.total_lookup_count(&ResolutionMap::new()).unwrap();
+        assert_eq!(report.total(), 0);
+        assert_eq!(report.heaviest(), None);
+    }
+}
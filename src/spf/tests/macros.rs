@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::MechanismError;
+    use crate::spf::{Spf, SpfError};
+
+    // Examples from RFC 7208 section 7.4
+    #[test]
+    fn rfc_examples_round_trip() {
+        for input in [
+            "v=spf1 include:%{ir}.%{v}._spf.%{d2} -all",
+            "v=spf1 exists:%{lr-}.lp._spf.%{d2} -all",
+            "v=spf1 exists:%{lr-}.lp.%{ir}.%{v}._spf.%{d2} -all",
+            "v=spf1 exists:%{ir}.%{v}.%{l1r-}.lp._spf.%{d2} -all",
+            "v=spf1 exists:%{d2}.trusted-domains.example.net -all",
+            "v=spf1 exists:%{ir}.%{l1r+-}._spf.%{d} -all",
+            "v=spf1 include:%{d2}.spf.example.net -all",
+            "v=spf1 redirect=%{ir}.%{v}._spf.%{d2}",
+        ] {
+            let spf: Spf = input.parse().unwrap();
+            assert_eq!(spf.to_string(), input);
+        }
+    }
+    #[test]
+    fn each_mechanism_kind() {
+        let input = "v=spf1 a:%{d} mx:%{d2}.example.com/24 include:%{d}.example.net exists:%{i}.%{d} ptr:%{d} -all exp=explain._spf.%{d}";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.a().unwrap()[0].has_macros(), true);
+        assert_eq!(spf.mx().unwrap()[0].raw(), "%{d2}.example.com/24");
+        assert_eq!(spf.includes().unwrap()[0].has_macros(), true);
+        assert_eq!(spf.exists().unwrap()[0].raw(), "%{i}.%{d}");
+        assert_eq!(spf.ptr().unwrap().raw(), "%{d}");
+        assert_eq!(spf.exp().unwrap().has_macros(), true);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn macro_with_slash_delimiter() {
+        let input = "v=spf1 a:%{l/}.example.com/24 exists:%{l/}._spf.%{d} -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.a().unwrap()[0].raw(), "%{l/}.example.com/24");
+        assert_eq!(spf.exists().unwrap()[0].raw(), "%{l/}._spf.%{d}");
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn domain_spec_starting_with_slash() {
+        for term in ["A:/ALL%{p}", "a:/x", "mx:/%{d}"] {
+            let input = format!("v=spf1 {} -all", term);
+            let expected =
+                SpfError::InvalidMechanism(MechanismError::InvalidDualCidr(term.to_string()));
+            assert_eq!(input.parse::<Spf>().unwrap_err().inner(), &expected);
+            assert_eq!(Spf::from_str_strict(&input).unwrap_err().inner(), &expected);
+        }
+        // Each macro domain-spec which is kept is written back as it was parsed.
+        let input = "v=spf1 a:%{d}/24 a:%{p}.x/24//64 mx:%{l/}.example.com -all";
+        let spf: Spf = input.parse().unwrap();
+        let reparsed: Spf = spf.to_string().parse().unwrap();
+        assert_eq!(reparsed.to_string(), input);
+        assert_eq!(reparsed, spf);
+    }
+    #[test]
+    fn escapes() {
+        let input = "v=spf1 exists:%{l}%%%_%-.example.com -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.exists().unwrap()[0].has_macros(), true);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn no_macros() {
+        let spf: Spf = "v=spf1 a:example.com -all".parse().unwrap();
+        assert_eq!(spf.a().unwrap()[0].has_macros(), false);
+        assert_eq!(spf.all().unwrap().has_macros(), false);
+    }
+}
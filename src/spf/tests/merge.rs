@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::Kind;
+    use crate::spf::{Spf, SpfError};
+
+    fn spf(input: &str) -> Spf {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn disjoint() {
+        let first = spf("v=spf1 a include:_spf.example.com ip4:192.0.2.0/24 -all");
+        let second = spf(
+            "v=spf1 mx:mail.example.net include:_spf.example.net ip6:2001:db8::/32 \
+                          exists:%{i}._spf.example.net ~all",
+        );
+        let merged = first.merge(&second).unwrap();
+        assert_eq!(
+            merged.to_string(),
+            "v=spf1 a mx:mail.example.net include:_spf.example.com include:_spf.example.net \
+             ip4:192.0.2.0/24 ip6:2001:db8::/32 exists:%{i}._spf.example.net -all"
+        );
+        // Neither record is changed.
+        assert_eq!(
+            first.to_string(),
+            "v=spf1 a include:_spf.example.com ip4:192.0.2.0/24 -all"
+        );
+    }
+    #[test]
+    fn overlapping() {
+        let first = spf("v=spf1 include:_spf.google.com ip4:192.0.2.0/24 mx ~all");
+        let second =
+            spf("v=spf1 mx include:_SPF.google.com ip4:192.0.2.0/24 ip4:198.51.100.0/24 -all");
+        let merged = first.merge(&second).unwrap();
+        assert_eq!(
+            merged.to_string(),
+            "v=spf1 mx include:_spf.google.com ip4:192.0.2.0/24 ip4:198.51.100.0/24 ~all"
+        );
+        assert_eq!(merged.includes().unwrap().len(), 1);
+    }
+    #[test]
+    fn keeps_first_version_and_modifiers() {
+        let first = spf("spf2.0/pra a exp=explain.example.com");
+        let second = spf("v=spf1 ptr -all exp=other.example.com x-note=ok");
+        let merged = first.merge(&second).unwrap();
+        assert_eq!(
+            merged.to_string(),
+            "spf2.0/pra a ptr exp=explain.example.com"
+        );
+    }
+    #[test]
+    fn redirect_conflict() {
+        let plain = spf("v=spf1 a -all");
+        let redirected = spf("v=spf1 redirect=_spf.example.com");
+        for err in [
+            plain.merge(&redirected).unwrap_err(),
+            redirected.merge(&plain).unwrap_err(),
+        ] {
+            assert_eq!(err, SpfError::MergeConflict(Kind::Redirect));
+            assert_eq!(err.to_string(), "Records with redirect= can not be merged.");
+        }
+    }
+    #[test]
+    fn length_exceeded() {
+        let networks = |third: u8| {
+            (0..8)
+                .map(|i| format!("ip4:10.{}.{}.0/24", third, i))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        let first = spf(&format!("v=spf1 {} -all", networks(1)));
+        let second = spf(&format!("v=spf1 {} -all", networks(2)));
+        assert_eq!(
+            first.merge(&second).unwrap_err(),
+            SpfError::SourceLengthExceeded
+        );
+        // Merging a record with itself only produces duplicates.
+        assert_eq!(first.merge(&first).unwrap().to_string(), first.to_string());
+    }
+}
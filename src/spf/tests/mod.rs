@@ -1,11 +1,61 @@
 mod a;
+mod aggregate;
+mod all;
+mod append;
+mod authorize;
+mod builder;
+mod case;
+mod classify;
+mod clear;
+mod collect;
 mod construct;
+mod contains;
+mod count;
+mod dedup;
+mod diff;
 mod display;
+mod domains;
+mod eq;
 mod exists;
+mod expand;
+mod explain;
+mod explanation;
+mod fetch;
+mod flatten;
+mod fqdn;
+mod graph;
+mod hostbits;
 mod include;
 mod ip4;
 mod ip6;
+mod iter;
+mod length;
+mod lookup;
+mod macros;
+mod merge;
+mod modifiers;
+mod mutable;
 mod mx;
+mod networks;
+mod normalize;
+mod overlap;
 mod parse;
+mod position;
+mod posture;
 mod ptr;
+mod redirect;
+mod reference;
+mod remove;
+mod replace;
+mod report;
+mod scope;
+mod sort;
+mod space;
+mod state;
+mod strict;
+mod subset;
+mod summary;
+mod txt;
+mod version;
 mod warndns;
+mod whitespace;
@@ -0,0 +1,258 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::{Kind, MechanismError};
+    use crate::spf::{Spf, SpfError, Warning};
+
+    #[test]
+    fn exp() {
+        let input = "v=spf1 a -all exp=explain._spf.example.com";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.exp().unwrap().kind().is_exp(), true);
+        assert_eq!(spf.exp().unwrap().raw(), "explain._spf.example.com");
+        assert_eq!(spf.unknown_modifiers().is_none(), true);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn exp_strict() {
+        let input = "v=spf1 a -all exp=explain._spf.example.com";
+
+        let spf = Spf::from_str_strict(input).unwrap();
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn empty_exp() {
+        let input = "v=spf1 a -all exp=";
+        let expected =
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat("exp=".to_string()));
+
+        assert_eq!(input.parse::<Spf>().unwrap_err().inner(), &expected);
+        assert_eq!(Spf::from_str_strict(input).unwrap_err().inner(), &expected);
+    }
+    #[test]
+    fn single_unknown_modifier() {
+        let input = "v=spf1 a -all t=y";
+
+        let spf: Spf = input.parse().unwrap();
+        let modifiers = spf.unknown_modifiers().unwrap();
+        assert_eq!(modifiers.len(), 1);
+        assert_eq!(modifiers[0], ("t".to_string(), "y".to_string()));
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn interleaved_unknown_modifiers() {
+        let input = "v=spf1 a t=y mx x-vendor.id=abc123 include:_spf.example.com empty= -all";
+
+        let spf: Spf = input.parse().unwrap();
+        let modifiers = spf.unknown_modifiers().unwrap();
+        assert_eq!(modifiers.len(), 3);
+        assert_eq!(modifiers[0], ("t".to_string(), "y".to_string()));
+        assert_eq!(
+            modifiers[1],
+            ("x-vendor.id".to_string(), "abc123".to_string())
+        );
+        assert_eq!(modifiers[2], ("empty".to_string(), "".to_string()));
+        assert_eq!(spf.a().unwrap().len(), 1);
+        assert_eq!(spf.mx().unwrap().len(), 1);
+        assert_eq!(spf.includes().unwrap().len(), 1);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 a mx include:_spf.example.com -all t=y x-vendor.id=abc123 empty="
+        );
+    }
+    #[test]
+    fn unknown_modifier_round_trip() {
+        let input = "v=spf1 a mx -all t=y x-vendor.id=abc123";
+
+        let spf: Spf = input.parse().unwrap();
+        let spf2: Spf = spf.to_string().parse().unwrap();
+        assert_eq!(spf2.to_string(), input);
+        assert_eq!(spf2.unknown_modifiers(), spf.unknown_modifiers());
+    }
+    #[test]
+    fn value_containing_equals() {
+        let input = "v=spf1 -all t=a=b";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(
+            spf.unknown_modifiers().unwrap()[0],
+            ("t".to_string(), "a=b".to_string())
+        );
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn invalid_modifier_name() {
+        let input = "v=spf1 a 1t=y -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.unknown_modifiers().is_none(), true);
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+        let err = Spf::from_str_strict(input).unwrap_err();
+        assert_eq!(err.inner(), &SpfError::UnknownTerm("1t=y".to_string()));
+    }
+    #[test]
+    fn unknown_modifier_strict() {
+        let input = "v=spf1 a -all t=y";
+
+        let spf = Spf::from_str_strict(input).unwrap();
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn qualified_modifiers_lenient() {
+        for qualifier in ["+", "-", "~", "?"] {
+            let input = format!(
+                "v=spf1 a {}redirect=_spf.example.com {}exp=explain.example.com",
+                qualifier, qualifier
+            );
+
+            let spf: Spf = input.parse().unwrap();
+            assert_eq!(spf.redirect().unwrap().raw(), "_spf.example.com");
+            assert_eq!(spf.exp().unwrap().raw(), "explain.example.com");
+            assert_eq!(
+                spf.parse_warnings().unwrap(),
+                &vec![
+                    Warning::QualifierOnModifier {
+                        kind: Kind::Redirect,
+                        position: 2
+                    },
+                    Warning::QualifierOnModifier {
+                        kind: Kind::Exp,
+                        position: 3
+                    },
+                ]
+            );
+            assert_eq!(
+                spf.to_string(),
+                "v=spf1 a redirect=_spf.example.com exp=explain.example.com"
+            );
+        }
+    }
+    #[test]
+    fn qualified_modifiers_strict() {
+        for qualifier in ["+", "-", "~", "?"] {
+            let input = format!("v=spf1 a {}redirect=_spf.example.com", qualifier);
+            let err = Spf::from_str_strict(&input).unwrap_err();
+            assert_eq!(
+                err.inner(),
+                &SpfError::InvalidMechanism(MechanismError::QualifierNotAllowedForModifier(
+                    Kind::Redirect
+                ))
+            );
+            assert_eq!(err.parse_error().unwrap().term_index(), 2);
+
+            let input = format!("v=spf1 a -all {}exp=explain.example.com", qualifier);
+            let err = Spf::from_str_strict(&input).unwrap_err();
+            assert_eq!(
+                err.inner(),
+                &SpfError::InvalidMechanism(MechanismError::QualifierNotAllowedForModifier(
+                    Kind::Exp
+                ))
+            );
+        }
+    }
+}
+#[cfg(test)]
+mod custom {
+
+    use crate::mechanism::Qualifier;
+    use crate::spf::{Spf, SpfBuilder, SpfError};
+
+    #[test]
+    fn append() {
+        let mut spf = Spf::new_v1();
+        spf.append_str("a").unwrap();
+        spf.append_modifier("verify", "token123").unwrap();
+        spf.append_str("-all").unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a -all verify=token123");
+        assert_eq!(
+            spf.unknown_modifiers().unwrap(),
+            &vec![("verify".to_string(), "token123".to_string())]
+        );
+    }
+    #[test]
+    fn round_trip_with_redirect() {
+        let mut spf = Spf::new_v1();
+        spf.append_str("mx").unwrap();
+        spf.append_str("redirect=_spf.example.com").unwrap();
+        spf.append_modifier("verify", "token123").unwrap();
+        let text = spf.to_string();
+        assert_eq!(text, "v=spf1 mx redirect=_spf.example.com verify=token123");
+
+        let parsed: Spf = text.parse().unwrap();
+        assert_eq!(parsed.is_redirect(), true);
+        assert_eq!(parsed.redirect().unwrap().raw(), "_spf.example.com");
+        assert_eq!(
+            parsed.unknown_modifiers().unwrap(),
+            &vec![("verify".to_string(), "token123".to_string())]
+        );
+        assert_eq!(parsed.to_string(), text);
+        assert_eq!(parsed, spf);
+    }
+    #[test]
+    fn after_standard_modifiers() {
+        let mut spf: Spf = "v=spf1 a -all exp=explain.example.com".parse().unwrap();
+        spf.append_modifier("x-vendor_tag.v2", "%{d}").unwrap();
+        spf.append_modifier("empty", "").unwrap();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 a -all exp=explain.example.com x-vendor_tag.v2=%{d} empty="
+        );
+    }
+    #[test]
+    fn reserved_names() {
+        let mut spf = Spf::new_v1();
+        for name in ["redirect", "exp", "v", "Redirect", "EXP", "V"] {
+            assert_eq!(
+                spf.append_modifier(name, "example.com").unwrap_err(),
+                SpfError::InvalidModifierName(name.to_string())
+            );
+        }
+        assert_eq!(
+            spf.append_str("v=spf2")
+                .unwrap_err()
+                .is_invalid_modifier_name(),
+            true
+        );
+        assert_eq!(spf.unknown_modifiers().is_none(), true);
+    }
+    #[test]
+    fn invalid_names() {
+        let mut spf = Spf::new_v1();
+        for name in ["", "1t", "-t", "include:t", "a b", "t=u"] {
+            assert_eq!(
+                spf.append_modifier(name, "y").unwrap_err(),
+                SpfError::InvalidModifierName(name.to_string())
+            );
+        }
+        assert_eq!(spf.to_string(), "v=spf1");
+    }
+    #[test]
+    fn value_with_whitespace() {
+        let mut spf = Spf::new_v1();
+        assert_eq!(
+            spf.append_modifier("verify", "token 123").unwrap_err(),
+            SpfError::WhiteSpaceSyntaxError
+        );
+    }
+    #[test]
+    fn builder() {
+        let spf = SpfBuilder::new()
+            .v1()
+            .include(Qualifier::Pass, "_spf.example.com")
+            .modifier("verify", "token123")
+            .all(Qualifier::SoftFail)
+            .build()
+            .unwrap();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:_spf.example.com ~all verify=token123"
+        );
+        let err = SpfBuilder::new()
+            .v1()
+            .modifier("redirect", "_spf.example.com")
+            .build()
+            .unwrap_err();
+        assert_eq!(err, SpfError::InvalidModifierName("redirect".to_string()));
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::Qualifier;
+    use crate::spf::{Spf, SpfBuilder};
+
+    const RECORD: &str = "v=spf1 a mx:mail.example.com include:_spf.example.com \
+                          ip4:203.0.113.0/24 ip6:2001:db8::/32 exists:%{i}.example.com ptr ~all";
+
+    #[test]
+    fn flip_all() {
+        let mut spf: Spf = "v=spf1 a ~all".parse().unwrap();
+        spf.all_mut().unwrap().set_qualifier(Qualifier::Fail);
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+        assert_eq!(spf.all().unwrap().is_fail(), true);
+    }
+    #[test]
+    fn each_accessor() {
+        let mut spf: Spf = RECORD.parse().unwrap();
+        spf.a_mut().unwrap()[0].set_qualifier(Qualifier::Neutral);
+        spf.mx_mut().unwrap()[0]
+            .set_rrdata(Some("mx.example.com".to_string()))
+            .unwrap();
+        spf.includes_mut().unwrap()[0]
+            .set_rrdata(Some("_spf.example.net".to_string()))
+            .unwrap();
+        spf.ip4_mut().unwrap()[0].set_rrdata("198.51.100.0/24".parse().unwrap());
+        spf.ip6_mut().unwrap()[0].set_qualifier(Qualifier::SoftFail);
+        spf.exists_mut().unwrap()[0].set_qualifier(Qualifier::Fail);
+        spf.ptr_mut().unwrap().set_qualifier(Qualifier::Fail);
+        spf.all_mut().unwrap().set_qualifier(Qualifier::Fail);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ?a mx:mx.example.com include:_spf.example.net ip4:198.51.100.0/24 \
+             ~ip6:2001:db8::/32 -exists:%{i}.example.com -ptr -all"
+        );
+    }
+    #[test]
+    fn redirect() {
+        let mut spf: Spf = "v=spf1 a redirect=old.example.com".parse().unwrap();
+        spf.redirect_mut()
+            .unwrap()
+            .set_rrdata(Some("new.example.com".to_string()))
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a redirect=new.example.com");
+    }
+    #[test]
+    fn absent() {
+        let mut spf: Spf = "v=spf1 a".parse().unwrap();
+        assert_eq!(spf.mx_mut().is_none(), true);
+        assert_eq!(spf.includes_mut().is_none(), true);
+        assert_eq!(spf.ip4_mut().is_none(), true);
+        assert_eq!(spf.ip6_mut().is_none(), true);
+        assert_eq!(spf.exists_mut().is_none(), true);
+        assert_eq!(spf.ptr_mut().is_none(), true);
+        assert_eq!(spf.redirect_mut().is_none(), true);
+        assert_eq!(spf.all_mut().is_none(), true);
+    }
+    #[test]
+    fn clears_validation() {
+        let mut spf = SpfBuilder::new()
+            .v1()
+            .a(Qualifier::Pass, None)
+            .all(Qualifier::SoftFail)
+            .build()
+            .unwrap();
+        assert_eq!(spf.is_valid(), true);
+        spf.all_mut().unwrap().set_qualifier(Qualifier::Fail);
+        assert_eq!(spf.is_valid(), false);
+        #[allow(deprecated)]
+        spf.try_validate().unwrap();
+        assert_eq!(spf.is_valid(), true);
+        let _ = spf.a_mut();
+        assert_eq!(spf.is_valid(), false);
+    }
+}
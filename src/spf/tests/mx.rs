@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 mod parse {
 
     use crate::spf::Spf;
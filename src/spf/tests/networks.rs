@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod combined {
+
+    use crate::mechanism::Qualifier;
+    use crate::spf::Spf;
+    use ipnetwork::IpNetwork;
+
+    fn network(s: &str) -> IpNetwork {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn no_ip_mechanisms() {
+        let spf: Spf = "v=spf1 a mx include:_spf.example.com -all".parse().unwrap();
+        assert_eq!(spf.iter_ip().count(), 0);
+        assert_eq!(spf.networks(), vec![]);
+        assert_eq!(spf.networks_with_qualifier(Qualifier::Pass), vec![]);
+        assert_eq!(Spf::default().iter_ip().count(), 0);
+    }
+    #[test]
+    fn order_matches_display() {
+        let spf: Spf = "v=spf1 ip6:2001:db8::/32 a ip4:192.0.2.0/24 ~ip6:2001:db8:1::/48 \
+                        -ip4:198.51.100.0/24 -all"
+            .parse()
+            .unwrap();
+        let from_iter: Vec<String> = spf.iter_ip().map(|m| m.to_string()).collect();
+        let from_display: Vec<String> = spf
+            .to_string()
+            .split(' ')
+            .filter(|term| term.contains("ip4:") || term.contains("ip6:"))
+            .map(str::to_string)
+            .collect();
+        assert_eq!(from_iter, from_display);
+        assert_eq!(
+            spf.networks(),
+            vec![
+                network("192.0.2.0/24"),
+                network("198.51.100.0/24"),
+                network("2001:db8::/32"),
+                network("2001:db8:1::/48"),
+            ]
+        );
+    }
+    #[test]
+    fn by_qualifier() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ~ip4:192.0.2.128/25 +ip6:2001:db8::/32 \
+                        ?ip6:2001:db8:1::/48 -all"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            spf.networks_with_qualifier(Qualifier::Pass),
+            vec![network("192.0.2.0/24"), network("2001:db8::/32")]
+        );
+        assert_eq!(
+            spf.networks_with_qualifier(Qualifier::SoftFail),
+            vec![network("192.0.2.128/25")]
+        );
+        assert_eq!(
+            spf.networks_with_qualifier(Qualifier::Neutral),
+            vec![network("2001:db8:1::/48")]
+        );
+        assert_eq!(spf.networks_with_qualifier(Qualifier::Fail), vec![]);
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::spf::{ParseOptions, Spf};
+
+    fn presented(input: &str) -> Spf {
+        let options = ParseOptions::new()
+            .preserve_case(true)
+            .preserve_explicit_pass(true);
+        Spf::from_str_with_options(input, &options).unwrap()
+    }
+
+    #[test]
+    fn equivalent_records() {
+        let first = presented(
+            "v=spf1 +A:Mail.Example.com IP4:203.0.113.5/32 include:_SPF.Google.com \
+             ip4:198.51.100.0/24 INCLUDE:_spf.google.com -ALL",
+        );
+        let second: Spf = "v=spf1 ip4:198.51.100.0/24 ip4:203.0.113.5 a:mail.example.com \
+                           include:_spf.google.com -all"
+            .parse()
+            .unwrap();
+        assert_ne!(first.to_string(), second.to_string());
+        assert_eq!(first.canonical_string(), second.canonical_string());
+        assert_eq!(
+            first.canonical_string(),
+            "v=spf1 a:mail.example.com include:_spf.google.com ip4:198.51.100.0/24 \
+             ip4:203.0.113.5 -all"
+        );
+    }
+    #[test]
+    fn macros_are_unchanged() {
+        let mut spf: Spf = "v=spf1 exists:%{IR}.%{l1r-}._SPF.Example.com -all exp=%{D}.Explain.com"
+            .parse()
+            .unwrap();
+        spf.normalize();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 exists:%{IR}.%{l1r-}._spf.example.com -all exp=%{D}.explain.com"
+        );
+    }
+    #[test]
+    fn hosts_without_prefix() {
+        let mut spf: Spf = "v=spf1 ip4:192.0.2.1/32 ip6:2001:db8::1/128 ip6:2001:db8::/32 -all"
+            .parse()
+            .unwrap();
+        spf.normalize();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:192.0.2.1 ip6:2001:db8::/32 ip6:2001:db8::1 -all"
+        );
+    }
+    #[test]
+    fn sorted_within_kind() {
+        let mut spf: Spf = "v=spf1 mx:b.example.com -mx:a.example.com mx:a.example.com \
+                            ip4:10.0.0.0/8 ip4:10.0.0.0/16 ip4:9.0.0.0/8 -all x-b=2 X-A=1"
+            .parse()
+            .unwrap();
+        spf.normalize();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 -mx:a.example.com mx:a.example.com mx:b.example.com ip4:9.0.0.0/8 \
+             ip4:10.0.0.0/8 ip4:10.0.0.0/16 -all x-a=1 x-b=2"
+        );
+    }
+    #[test]
+    fn idempotent() {
+        let mut spf = presented("v=spf1 +MX ~a Include:Z.example.com include:y.example.com ?ALL");
+        spf.normalize();
+        let once = spf.to_string();
+        spf.normalize();
+        assert_eq!(spf.to_string(), once);
+        assert_eq!(spf.canonical_string(), once);
+    }
+    #[test]
+    fn canonical_string_does_not_change_spf() {
+        let spf = presented("v=spf1 +MX -all");
+        assert_eq!(spf.canonical_string(), "v=spf1 mx -all");
+        assert_eq!(spf.to_string(), "v=spf1 +MX -all");
+    }
+}
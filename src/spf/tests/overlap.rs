@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod find {
+
+    use crate::spf::{IpRelation, Spf};
+
+    // Each overlap as its earlier term, later term, relation and whether it conflicts.
+    fn overlaps(record: &str) -> Vec<(String, String, IpRelation, bool)> {
+        let spf: Spf = record.parse().unwrap();
+        spf.find_ip_overlaps()
+            .iter()
+            .map(|o| {
+                (
+                    o.earlier().to_string(),
+                    o.later().to_string(),
+                    o.relation(),
+                    o.is_conflict(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_networks() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.1/24 -all"
+            .parse()
+            .unwrap();
+        let overlaps = spf.find_ip_overlaps();
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].relation(), IpRelation::Same);
+        assert_eq!(overlaps[0].is_unreachable(), true);
+        assert_eq!(overlaps[0].is_conflict(), false);
+        assert_eq!(overlaps[0].earlier_index(), 0);
+        assert_eq!(overlaps[0].later_index(), 1);
+    }
+    #[test]
+    fn nested_same_qualifier() {
+        assert_eq!(
+            overlaps("v=spf1 ip4:10.0.0.0/8 ip4:10.1.0.0/16 -all"),
+            vec![(
+                "ip4:10.0.0.0/8".to_string(),
+                "ip4:10.1.0.0/16".to_string(),
+                IpRelation::Shadowed,
+                false
+            )]
+        );
+    }
+    #[test]
+    fn nested_different_qualifier() {
+        assert_eq!(
+            overlaps("v=spf1 ip4:10.0.0.0/8 -ip4:10.1.0.0/16 -all"),
+            vec![(
+                "ip4:10.0.0.0/8".to_string(),
+                "-ip4:10.1.0.0/16".to_string(),
+                IpRelation::Shadowed,
+                true
+            )]
+        );
+    }
+    #[test]
+    fn narrower_first() {
+        let spf: Spf = "v=spf1 -ip4:10.1.0.0/16 ip4:10.0.0.0/8 -all"
+            .parse()
+            .unwrap();
+        let overlaps = spf.find_ip_overlaps();
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].earlier().to_string(), "-ip4:10.1.0.0/16");
+        assert_eq!(overlaps[0].relation(), IpRelation::Covers);
+        assert_eq!(overlaps[0].is_unreachable(), false);
+        assert_eq!(overlaps[0].is_conflict(), true);
+    }
+    #[test]
+    fn ipv6_prefixes() {
+        assert_eq!(
+            overlaps(
+                "v=spf1 ip6:2001:db8:8000::/33 ~ip6:2001:db8::/32 ip6:2001:db8:abcd::/48 \
+                 ip6:2001:db9::/32 -all"
+            ),
+            vec![
+                (
+                    "ip6:2001:db8:8000::/33".to_string(),
+                    "~ip6:2001:db8::/32".to_string(),
+                    IpRelation::Covers,
+                    true
+                ),
+                (
+                    "ip6:2001:db8:8000::/33".to_string(),
+                    "ip6:2001:db8:abcd::/48".to_string(),
+                    IpRelation::Shadowed,
+                    false
+                ),
+                (
+                    "~ip6:2001:db8::/32".to_string(),
+                    "ip6:2001:db8:abcd::/48".to_string(),
+                    IpRelation::Shadowed,
+                    true
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn families_do_not_overlap() {
+        assert_eq!(
+            overlaps("v=spf1 ip4:0.0.0.0/0 ip6:::/0 ip4:192.0.2.0/24 -all").len(),
+            1
+        );
+        assert_eq!(
+            overlaps("v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 ip6:2001:db8::/32 -all"),
+            vec![]
+        );
+    }
+    #[test]
+    fn indices_follow_iter() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 a mx -ip4:192.0.2.128/25 ip6:::/0 \
+                        ip6:2001:db8::1 -all"
+            .parse()
+            .unwrap();
+        let terms: Vec<String> = spf.iter().map(|m| m.to_string()).collect();
+        let overlaps = spf.find_ip_overlaps();
+        assert_eq!(overlaps.len(), 2);
+        for overlap in overlaps {
+            assert_eq!(
+                terms[overlap.earlier_index()],
+                overlap.earlier().to_string()
+            );
+            assert_eq!(terms[overlap.later_index()], overlap.later().to_string());
+        }
+    }
+}
@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 mod valid_spf_from_str {
 
     use crate::spf::Spf;
@@ -60,7 +59,7 @@ mod valid_spf_from_str {
         let spf: Spf = input.parse().unwrap();
         assert_eq!(spf.includes().is_none(), true);
         assert_eq!(spf.ip4().is_none(), true);
-        assert_eq!(!spf.ip6().is_none(), true);
+        assert_eq!(spf.ip6().is_some(), true);
         assert_eq!(spf.ip6().unwrap().len(), 6);
         assert_eq!(spf.ip6().unwrap()[0].to_string(), "ip6:2001:4860:4000::/36");
         assert_eq!(
@@ -77,10 +76,10 @@ mod valid_spf_from_str {
         assert_eq!(spf.unwrap().source(), input);
     }
     #[test]
-    fn valid_spf2() {
+    fn spf2_without_scope() {
         let input = "spf2.0 a";
-        let spf: Spf = input.parse().unwrap();
-        assert_eq!(spf.source(), input);
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::InvalidVersion("spf2.0".to_string()));
     }
 }
 
@@ -97,15 +96,33 @@ mod invalid_spf_from_str {
         assert_eq!(spf.is_err(), true);
         let err = spf.unwrap_err();
         assert_eq!(err.is_spf_error(), true);
-        assert_eq!(err.is_invalid_source(), true);
-        assert_eq!(err.to_string(), "Source string not valid.");
-        assert_eq!(err, SpfError::InvalidSource);
+        assert_eq!(err.is_invalid_version(), true);
+        assert_eq!(err.to_string(), "Invalid version: v=sf");
+        assert_eq!(err.inner(), &SpfError::InvalidVersion("v=sf".to_string()));
+    }
+    #[test]
+    fn empty_input() {
+        for input in ["", "   ", "\n", "\t\r\n"] {
+            let err = input.parse::<Spf>().unwrap_err();
+            assert_eq!(err, SpfError::EmptyInput);
+            assert_eq!(err.is_empty_input(), true);
+        }
+        let err = Spf::from_str_strict("").unwrap_err();
+        assert_eq!(err, SpfError::EmptyInput);
+    }
+    #[test]
+    fn verification_token_is_not_empty() {
+        let err = "google-site-verification=abc123"
+            .parse::<Spf>()
+            .unwrap_err();
+        assert_eq!(err.is_empty_input(), false);
     }
     #[test]
     fn invalid_spf2() {
         let input = "spf2 a";
         let spf: Result<Spf, SpfError> = input.parse();
         assert_eq!(spf.is_err(), true);
+        assert_eq!(spf.unwrap_err().is_invalid_version(), true);
     }
     #[test]
     fn valid_spf2_pra() {
@@ -145,8 +162,33 @@ mod invalid_ip {
         let spf: Result<Spf, SpfError> = input.parse();
         assert_eq!(spf.is_err(), true);
         let error = spf.unwrap_err();
+        assert_eq!(error.is_invalid_ipv4_cidr_length(), true);
+        assert_eq!(
+            error.inner().to_string(),
+            "33 is not a valid ip4 cidr length."
+        );
+        assert_eq!(
+            error.to_string(),
+            "33 is not a valid ip4 cidr length. (term 1 'ip4:203.32.10.0/33' at offset 7)"
+        );
+    }
+    #[test]
+    fn invalid_ip4_negative_cidr() {
+        let input = "v=spf1 ip4:203.32.10.0/-1";
+
+        let error = input.parse::<Spf>().unwrap_err();
+        assert_eq!(
+            error.inner(),
+            &SpfError::InvalidIPv4CidrLength("-1".to_string())
+        );
+    }
+    #[test]
+    fn invalid_ip4_address() {
+        let input = "v=spf1 ip4:203.32.10.256/24";
+
+        let error = input.parse::<Spf>().unwrap_err();
         assert_eq!(error.is_invalid_ip_addr(), true);
-        assert_eq!(error.to_string(), "invalid address: 203.32.10.0/33");
+        assert_eq!(error.is_invalid_ipv4_cidr_length(), false);
     }
     #[test]
     fn invalid_ip6() {
@@ -155,6 +197,20 @@ mod invalid_ip {
 
         assert_eq!(spf.is_err(), true);
         let error = spf.unwrap_err();
-        assert_eq!(error.to_string(), "invalid address: 2001:4860:4000::/129");
+        assert_eq!(
+            error.inner(),
+            &SpfError::InvalidIPv6CidrLength("129".to_string())
+        );
+        assert_eq!(
+            error.inner().to_string(),
+            "129 is not a valid ip6 cidr length."
+        );
+    }
+    #[test]
+    fn invalid_ip6_negative_cidr() {
+        let input = "v=spf1 ip6:2001:4860:4000::/-1";
+
+        let error = input.parse::<Spf>().unwrap_err();
+        assert_eq!(error.is_invalid_ipv6_cidr_length(), true);
     }
 }
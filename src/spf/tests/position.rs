@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::MechanismError;
+    use crate::spf::{Spf, SpfError};
+
+    #[test]
+    fn first_term() {
+        let input = "v=spf1 ip4:203.32.10.0/33 a mx -all";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(err.is_parse_error(), true);
+        assert_eq!(err.is_invalid_ipv4_cidr_length(), true);
+        let parse_error = err.parse_error().unwrap();
+        assert_eq!(parse_error.term_index(), 1);
+        assert_eq!(parse_error.byte_offset(), 7);
+        assert_eq!(parse_error.term(), "ip4:203.32.10.0/33");
+    }
+    #[test]
+    fn middle_term() {
+        let input = "v=spf1 a mx ip6:2001:4860:4000::/129 include:_spf.example.com -all";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        let parse_error = err.parse_error().unwrap();
+        assert_eq!(parse_error.term_index(), 3);
+        assert_eq!(parse_error.byte_offset(), 12);
+        assert_eq!(parse_error.term(), "ip6:2001:4860:4000::/129");
+        assert_eq!(parse_error.error().is_invalid_ipv6_cidr_length(), true);
+    }
+    #[test]
+    fn last_term() {
+        let input = "v=spf1 a mx -all bogusmech";
+
+        let err = Spf::from_str_strict(input).unwrap_err();
+        let parse_error = err.parse_error().unwrap();
+        assert_eq!(parse_error.term_index(), 4);
+        assert_eq!(parse_error.byte_offset(), 17);
+        assert_eq!(parse_error.term(), "bogusmech");
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName(
+                "bogusmech".to_string()
+            ))
+        );
+        assert_eq!(
+            err.to_string(),
+            "bogusmech is not a known mechanism (term 4 'bogusmech' at offset 17)"
+        );
+    }
+    #[test]
+    fn source_errors_are_not_wrapped() {
+        let err = "a mx -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err.is_parse_error(), false);
+        assert_eq!(err.parse_error(), None);
+    }
+}
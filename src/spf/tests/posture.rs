@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod helpers {
+
+    use crate::mechanism::Kind;
+    use crate::spf::{Disposition, Spf};
+
+    fn disposition(record: &str) -> Disposition {
+        record.parse::<Spf>().unwrap().default_disposition()
+    }
+
+    #[test]
+    fn each_terminal() {
+        assert_eq!(disposition("v=spf1 mx -all"), Disposition::Fail);
+        assert_eq!(disposition("v=spf1 mx ~all"), Disposition::SoftFail);
+        assert_eq!(disposition("v=spf1 mx ?all"), Disposition::Neutral);
+        assert_eq!(disposition("v=spf1 mx +all"), Disposition::Pass);
+        assert_eq!(disposition("v=spf1 mx all"), Disposition::Pass);
+        assert_eq!(disposition("v=spf1 mx"), Disposition::Neutral);
+        assert_eq!(disposition("v=spf1"), Disposition::Neutral);
+        assert_eq!(
+            disposition("v=spf1 mx redirect=_spf.example.com"),
+            Disposition::Redirected
+        );
+    }
+    #[test]
+    fn disposition_display() {
+        assert_eq!(Disposition::Fail.to_string(), "fail");
+        assert_eq!(Disposition::SoftFail.to_string(), "softfail");
+        assert_eq!(Disposition::Neutral.to_string(), "neutral");
+        assert_eq!(Disposition::Pass.to_string(), "pass");
+        assert_eq!(Disposition::Redirected.to_string(), "redirected");
+        assert_eq!(Disposition::Fail.is_fail(), true);
+        assert_eq!(Disposition::Redirected.is_redirected(), true);
+    }
+    #[test]
+    fn has_every_kind() {
+        let spf: Spf = "v=spf1 a mx include:_spf.example.com ip4:192.0.2.0/24 \
+                        ip6:2001:db8::/32 exists:%{i}.example.com ptr -all \
+                        exp=explain.example.com"
+            .parse()
+            .unwrap();
+        for kind in [
+            Kind::A,
+            Kind::MX,
+            Kind::Include,
+            Kind::IpV4,
+            Kind::IpV6,
+            Kind::Exists,
+            Kind::Ptr,
+            Kind::All,
+            Kind::Exp,
+        ] {
+            assert_eq!(spf.has_mechanism(kind), true);
+        }
+        assert_eq!(spf.has_mechanism(Kind::Redirect), false);
+        let spf: Spf = "v=spf1".parse().unwrap();
+        assert_eq!(spf.has_mechanism(Kind::All), false);
+        assert_eq!(spf.has_mechanism(Kind::IpV4), false);
+    }
+    #[test]
+    fn ptr_mechanism_and_macro() {
+        let spf: Spf = "v=spf1 ptr -all".parse().unwrap();
+        assert_eq!(spf.uses_ptr(), true);
+        let spf: Spf = "v=spf1 ?ptr:example.com -all".parse().unwrap();
+        assert_eq!(spf.uses_ptr(), true);
+        let spf: Spf = "v=spf1 include:%{P}.example.com -all".parse().unwrap();
+        assert_eq!(spf.uses_ptr(), true);
+        let spf: Spf = "v=spf1 a mx exists:%{ir}.example.com -all".parse().unwrap();
+        assert_eq!(spf.uses_ptr(), false);
+    }
+    #[test]
+    fn fully_ip_based() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 -all".parse().unwrap();
+        assert_eq!(spf.is_fully_ip_based(), true);
+        let spf: Spf = "v=spf1 ip6:2001:db8::/32 exp=explain.example.com"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.is_fully_ip_based(), true);
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 a -all".parse().unwrap();
+        assert_eq!(spf.is_fully_ip_based(), false);
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 redirect=_spf.example.com"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.is_fully_ip_based(), false);
+        let spf: Spf = "v=spf1 -all".parse().unwrap();
+        assert_eq!(spf.is_fully_ip_based(), false);
+    }
+}
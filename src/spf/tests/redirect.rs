@@ -0,0 +1,336 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::Kind;
+    use crate::spf::{Mechanism, ParseOptions, Qualifier, Spf, SpfError, SpfRfcStandard, Warning};
+
+    #[test]
+    fn target_contains_equals() {
+        let input = "v=spf1 redirect=%{d}=something";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.is_redirect(), true);
+        assert_eq!(spf.redirect().unwrap().raw(), "%{d}=something");
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn macro_target() {
+        let input = "v=spf1 redirect=%{ir}.%{v}._spf.example.com";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.redirect().unwrap().raw(), "%{ir}.%{v}._spf.example.com");
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn empty_target() {
+        let input = "v=spf1 redirect=";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(err.is_invalid_mechanism(), true);
+        assert_eq!(
+            err.inner().to_string(),
+            "redirect= does not conform to any Mechanism format"
+        );
+    }
+    #[test]
+    fn embedded_redirect_is_not_redirect() {
+        let input = "v=spf1 a x.redirect=_spf.example.com";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.is_redirect(), false);
+        assert_eq!(spf.unknown_modifiers().unwrap().len(), 1);
+    }
+    #[test]
+    fn empty_target_strict() {
+        let err = Spf::from_str_strict("v=spf1 a redirect=").unwrap_err();
+        assert_eq!(err.is_invalid_mechanism(), true);
+        assert_ne!(err, SpfError::UnknownTerm("redirect=".to_string()));
+    }
+    #[test]
+    fn two_redirects() {
+        let input = "v=spf1 redirect=a.example.com redirect=b.example.com";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect)
+        );
+        assert_eq!(err.is_modifier_may_occur_only_once(), true);
+    }
+    #[test]
+    fn two_redirects_different_case() {
+        let input = "v=spf1 redirect=a.example.com REDIRECT=b.example.com";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect)
+        );
+    }
+    #[test]
+    fn two_exp() {
+        let input = "v=spf1 -all exp=a.example.com Exp=b.example.com";
+
+        let err = input.parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::ModifierMayOccurOnlyOnce(Kind::Exp));
+    }
+    #[test]
+    fn two_modifiers_lenient() {
+        let input = "v=spf1 a redirect=a.example.com exp=c.example.com \
+                     Redirect=b.example.com exp=d.example.com";
+        let options = ParseOptions::new().lenient_modifiers(true);
+
+        let mut spf = Spf::from_str_with_options(input, &options).unwrap();
+        assert_eq!(spf.redirect().unwrap().raw(), "a.example.com");
+        assert_eq!(spf.exp().unwrap().raw(), "c.example.com");
+        assert_eq!(
+            spf.parse_warnings().unwrap(),
+            &vec![
+                Warning::DuplicateModifier {
+                    kind: Kind::Redirect,
+                    first: 2,
+                    second: 4
+                },
+                Warning::DuplicateModifier {
+                    kind: Kind::Exp,
+                    first: 3,
+                    second: 5
+                },
+            ]
+        );
+        assert_eq!(
+            spf.validate(SpfRfcStandard::Rfc7208),
+            Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect))
+        );
+        #[allow(deprecated)]
+        let err = spf.try_validate().unwrap_err();
+        assert_eq!(err.is_modifier_may_occur_only_once(), true);
+        spf.take_redirect();
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+    }
+    #[test]
+    fn append_replaces_redirect() {
+        let mut spf = Spf::new();
+        spf.set_v1();
+        spf.append_mechanism(Mechanism::redirect(Qualifier::Pass, "a.example.com").unwrap());
+        spf.append_mechanism(Mechanism::redirect(Qualifier::Pass, "b.example.com").unwrap());
+        assert_eq!(spf.to_string(), "v=spf1 redirect=b.example.com");
+    }
+}
+#[cfg(test)]
+mod set {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::{Spf, SpfError};
+
+    fn redirect(domain: &str) -> Mechanism<String> {
+        Mechanism::redirect(Qualifier::Pass, domain).unwrap()
+    }
+
+    #[test]
+    fn all_then_redirect() {
+        let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+        spf.set_redirect(redirect("_spf.example.com")).unwrap();
+        assert_eq!(spf.is_redirect(), true);
+        assert_eq!(spf.to_string(), "v=spf1 a -all redirect=_spf.example.com");
+        assert_eq!(spf.take_all().unwrap().to_string(), "-all");
+        assert_eq!(spf.to_string(), "v=spf1 a redirect=_spf.example.com");
+    }
+    #[test]
+    fn redirect_then_all() {
+        let mut spf: Spf = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+        spf.set_all(Mechanism::all(Qualifier::Fail)).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a -all redirect=_spf.example.com");
+        assert_eq!(spf.take_redirect().unwrap().raw(), "_spf.example.com");
+        assert_eq!(spf.is_redirect(), false);
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+    #[test]
+    fn replaces() {
+        let mut spf: Spf = "v=spf1 a redirect=old.example.com".parse().unwrap();
+        spf.set_redirect(redirect("new.example.com")).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a redirect=new.example.com");
+        let mut spf: Spf = "v=spf1 a ~all".parse().unwrap();
+        spf.set_all(Mechanism::all(Qualifier::Fail)).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+    #[test]
+    fn wrong_kind() {
+        let mut spf: Spf = "v=spf1 a".parse().unwrap();
+        let include = Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap();
+        assert_eq!(
+            spf.set_redirect(include.clone()),
+            Err(SpfError::KindMismatch {
+                expected: Kind::Redirect,
+                found: Kind::Include
+            })
+        );
+        assert_eq!(
+            spf.set_all(include),
+            Err(SpfError::KindMismatch {
+                expected: Kind::All,
+                found: Kind::Include
+            })
+        );
+        assert_eq!(
+            spf.set_all(redirect("_spf.example.com"))
+                .unwrap_err()
+                .is_kind_mismatch(),
+            true
+        );
+        assert_eq!(spf.to_string(), "v=spf1 a");
+    }
+    #[test]
+    fn invalid_redirect() {
+        let mut spf: Spf = "v=spf1 a".parse().unwrap();
+        let err = spf
+            .set_redirect(Mechanism::generic_inclusive(
+                Kind::Redirect,
+                Qualifier::Pass,
+                None,
+            ))
+            .unwrap_err();
+        assert_eq!(err.is_invalid_mechanism(), true);
+        assert_eq!(spf.is_redirect(), false);
+    }
+    #[test]
+    fn take_empty() {
+        let mut spf = Spf::new();
+        assert_eq!(spf.take_redirect(), None);
+        assert_eq!(spf.take_all(), None);
+    }
+}
+#[cfg(test)]
+mod domain {
+
+    use crate::mechanism::{Kind, MechanismError};
+    use crate::spf::{ParseOptions, Spf, SpfError};
+
+    #[test]
+    fn rename_target() {
+        let input =
+            "v=spf1 mx ip4:192.0.2.0/24 redirect=_spf.oldvendor.com exp=explain.example.com";
+        let mut spf: Spf = input.parse().unwrap();
+        spf.set_redirect_domain("_spf.newvendor.com").unwrap();
+        assert_eq!(
+            spf.to_string(),
+            input.replace("_spf.oldvendor.com", "_spf.newvendor.com")
+        );
+        assert_eq!(spf.is_redirect(), true);
+        assert_eq!(spf.redirect().unwrap().raw(), "_spf.newvendor.com");
+        assert_eq!(spf.all().is_none(), true);
+        assert_eq!(spf.is_modified(), true);
+    }
+    #[test]
+    fn keeps_written_case() {
+        let options = ParseOptions::new().preserve_case(true);
+        let mut spf =
+            Spf::from_str_with_options("v=spf1 a Redirect=old.example.com", &options).unwrap();
+        spf.set_redirect_domain("new.example.com").unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a Redirect=new.example.com");
+    }
+    #[test]
+    fn through_redirect_mut() {
+        let mut spf: Spf = "v=spf1 redirect=_spf.oldvendor.com".parse().unwrap();
+        spf.redirect_mut()
+            .unwrap()
+            .set_rrdata(Some("_spf.newvendor.com".to_string()))
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 redirect=_spf.newvendor.com");
+        assert_eq!(spf.is_redirect(), true);
+    }
+    #[test]
+    fn invalid_domain() {
+        let input = "v=spf1 a redirect=_spf.example.com";
+        let mut spf: Spf = input.parse().unwrap();
+        for domain in ["", "new .example.com"] {
+            assert_eq!(
+                spf.set_redirect_domain(domain)
+                    .unwrap_err()
+                    .is_invalid_mechanism(),
+                true
+            );
+        }
+        assert_eq!(
+            spf.set_redirect_domain("").unwrap_err(),
+            SpfError::InvalidMechanism(MechanismError::InvalidDomainHost(String::new()))
+        );
+        assert_eq!(spf.to_string(), input);
+        assert_eq!(spf.is_modified(), false);
+    }
+    #[test]
+    fn no_redirect() {
+        let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+        assert_eq!(
+            spf.set_redirect_domain("_spf.example.com").unwrap_err(),
+            SpfError::IndexOutOfBounds {
+                kind: Kind::Redirect,
+                index: 0,
+                len: 0
+            }
+        );
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+        spf.take_all();
+        assert_eq!(spf.set_redirect_domain("_spf.example.com").is_err(), true);
+    }
+}
+
+#[cfg(test)]
+mod with_all {
+
+    use crate::mechanism::Kind;
+    use crate::spf::{
+        Disposition, FindingCode, ResolutionMap, Severity, Spf, SpfRfcStandard, ValidationOptions,
+    };
+
+    #[test]
+    fn round_trip() {
+        let spf: Spf = "v=spf1 mx -all redirect=_spf.example.com".parse().unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 mx -all redirect=_spf.example.com");
+        assert_eq!(spf.is_redirect(), true);
+        assert_eq!(spf.all().unwrap().to_string(), "-all");
+        let reparsed: Spf = spf.to_string().parse().unwrap();
+        assert_eq!(reparsed, spf);
+        let terms: Vec<String> = spf.iter().map(|m| m.to_string()).collect();
+        assert_eq!(terms, ["mx", "-all", "redirect=_spf.example.com"]);
+        assert_eq!(spf.into_iter().count(), 3);
+    }
+    #[test]
+    fn redirect_written_first() {
+        let spf: Spf = "v=spf1 redirect=_spf.example.com ~all".parse().unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 ~all redirect=_spf.example.com");
+        assert_eq!(spf.count_of(Kind::Redirect), 1);
+        assert_eq!(spf.count_of(Kind::All), 1);
+    }
+    #[test]
+    fn all_decides() {
+        let spf: Spf = "v=spf1 mx ~all redirect=_spf.example.com".parse().unwrap();
+        assert_eq!(spf.default_disposition(), Disposition::SoftFail);
+    }
+    #[test]
+    fn report_under_each_standard() {
+        let spf: Spf = "v=spf1 mx -all redirect=_spf.example.com".parse().unwrap();
+        let report = spf.validation_report(&ValidationOptions::new());
+        assert_eq!(report.codes(), vec![FindingCode::RedirectWithAll]);
+        assert_eq!(report.findings()[0].severity(), Severity::Warning);
+        assert_eq!(
+            report.findings()[0].message(),
+            "redirect= is ignored as the record has an all mechanism."
+        );
+        assert_eq!(report.is_valid(), true);
+        let options = ValidationOptions::new().standard(SpfRfcStandard::Rfc4408);
+        let report = spf.validation_report(&options);
+        assert_eq!(report.codes(), vec![FindingCode::RedirectWithAll]);
+        assert_eq!(report.findings()[0].severity(), Severity::Error);
+        assert_eq!(report.is_valid(), false);
+    }
+    #[test]
+    fn flatten_ignores_redirect() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 -all redirect=_spf.example.com"
+            .parse()
+            .unwrap();
+        // The target of `redirect=` is not needed.
+        let flattened = spf.flatten(&ResolutionMap::new()).unwrap();
+        assert_eq!(flattened.to_string(), "v=spf1 ip4:192.0.2.0/24 -all");
+    }
+}
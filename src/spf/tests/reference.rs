@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod find {
+
+    use crate::mechanism::Kind;
+    use crate::spf::Spf;
+
+    fn spf() -> Spf {
+        "v=spf1 a a:Mail.Example.com/24 mx:example.com include:_spf.example.com \
+         ip4:192.0.2.0/24 exists:%{i}.example.com ptr:example.com. \
+         include:example.com.evil.net redirect=example.com exp=example.com"
+            .parse()
+            .unwrap()
+    }
+    fn strings(spf: &Spf, subdomains: bool, domain: &str) -> Vec<String> {
+        let found = match subdomains {
+            false => spf.mechanisms_referencing(domain),
+            true => spf.mechanisms_referencing_subdomains(domain),
+        };
+        found.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn by_predicate() {
+        let spf = spf();
+        let includes = spf.find(|m| *m.kind() == Kind::Include);
+        assert_eq!(includes.len(), 2);
+        assert_eq!(includes[0].to_string(), "include:_spf.example.com");
+        let networks = spf.find(|m| m.kind().is_ip());
+        assert_eq!(networks.len(), 1);
+        assert_eq!(spf.find(|m| m.qualifier().is_fail()).len(), 0);
+    }
+    #[test]
+    fn exact_domain() {
+        let spf = spf();
+        assert_eq!(
+            strings(&spf, false, "EXAMPLE.com"),
+            vec!["mx:example.com", "ptr:example.com.", "redirect=example.com"]
+        );
+        assert_eq!(
+            strings(&spf, false, "mail.example.com."),
+            vec!["a:Mail.Example.com/24"]
+        );
+    }
+    #[test]
+    fn subdomains() {
+        let spf = spf();
+        assert_eq!(
+            strings(&spf, true, "example.com"),
+            vec![
+                "a:Mail.Example.com/24",
+                "mx:example.com",
+                "include:_spf.example.com",
+                "exists:%{i}.example.com",
+                "ptr:example.com.",
+                "redirect=example.com"
+            ]
+        );
+    }
+    #[test]
+    fn no_match() {
+        let spf = spf();
+        assert_eq!(strings(&spf, false, "example.org"), Vec::<String>::new());
+        assert_eq!(strings(&spf, true, "ample.com"), Vec::<String>::new());
+        assert_eq!(strings(&spf, true, "evil.net").len(), 1);
+        let spf: Spf = "v=spf1 a mx ptr -all".parse().unwrap();
+        assert_eq!(spf.mechanisms_referencing("example.com").len(), 0);
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::Spf;
+    use ipnetwork::IpNetwork;
+
+    const INCLUDES: &str =
+        "v=spf1 include:a.example.com include:b.example.com include:c.example.com -all";
+
+    fn include(domain: &str) -> Mechanism<String> {
+        Mechanism::include(Qualifier::Pass, domain).unwrap()
+    }
+
+    #[test]
+    fn first() {
+        let mut spf: Spf = INCLUDES.parse().unwrap();
+        assert_eq!(spf.remove_mechanism(&include("a.example.com")), true);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:b.example.com include:c.example.com -all"
+        );
+    }
+    #[test]
+    fn middle() {
+        let mut spf: Spf = INCLUDES.parse().unwrap();
+        assert_eq!(spf.remove_mechanism(&include("b.example.com")), true);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:a.example.com include:c.example.com -all"
+        );
+    }
+    #[test]
+    fn last() {
+        let mut spf: Spf = INCLUDES.parse().unwrap();
+        assert_eq!(spf.remove_mechanism(&include("c.example.com")), true);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:a.example.com include:b.example.com -all"
+        );
+    }
+    #[test]
+    fn not_present() {
+        let mut spf: Spf = INCLUDES.parse().unwrap();
+        assert_eq!(spf.remove_mechanism(&include("d.example.com")), false);
+        // The qualifier must also match.
+        let fail = Mechanism::include(Qualifier::Fail, "a.example.com").unwrap();
+        assert_eq!(spf.remove_mechanism(&fail), false);
+        assert_eq!(spf.remove_mechanism(&Mechanism::mx(Qualifier::Pass)), false);
+        assert_eq!(spf.to_string(), INCLUDES);
+    }
+    #[test]
+    fn only_one_of_duplicates() {
+        let mut spf: Spf = "v=spf1 a a -all".parse().unwrap();
+        assert_eq!(spf.remove_mechanism(&Mechanism::a(Qualifier::Pass)), true);
+        assert_eq!(spf.a().unwrap().len(), 1);
+    }
+    #[test]
+    fn emptied_kind_is_none() {
+        let mut spf: Spf = "v=spf1 include:a.example.com mx -all".parse().unwrap();
+        assert_eq!(spf.remove_mechanism(&include("a.example.com")), true);
+        assert_eq!(spf.includes().is_none(), true);
+        assert_eq!(spf.to_string(), "v=spf1 mx -all");
+    }
+    #[test]
+    fn presentation_is_ignored() {
+        let mut spf: Spf = "v=spf1 +Include:a.example.com -all".parse().unwrap();
+        assert_eq!(spf.remove_mechanism(&include("a.example.com")), true);
+        assert_eq!(spf.includes().is_none(), true);
+    }
+    #[test]
+    fn all_and_redirect() {
+        let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+        assert_eq!(
+            spf.remove_mechanism(&Mechanism::all(Qualifier::Pass)),
+            false
+        );
+        assert_eq!(spf.remove_mechanism(&Mechanism::all(Qualifier::Fail)), true);
+        assert_eq!(spf.all().is_none(), true);
+
+        let mut spf: Spf = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+        let redirect = Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap();
+        assert_eq!(spf.remove_mechanism(&redirect), true);
+        assert_eq!(spf.is_redirect(), false);
+        assert_eq!(spf.to_string(), "v=spf1 a");
+    }
+    #[test]
+    fn ip_mechanism() {
+        let mut spf: Spf = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all"
+            .parse()
+            .unwrap();
+        let ip6: Mechanism<IpNetwork> = "ip6:2001:db8::/32".parse().unwrap();
+        let other: Mechanism<IpNetwork> = "ip4:198.51.100.0/24".parse().unwrap();
+        assert_eq!(spf.remove_ip_mechanism(&other), false);
+        assert_eq!(spf.remove_ip_mechanism(&ip6), true);
+        assert_eq!(spf.ip6().is_none(), true);
+        assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.0/24 -all");
+    }
+    #[test]
+    fn implicit_prefix_is_equal() {
+        let mut spf: Spf = "v=spf1 ip4:192.0.2.1 -all".parse().unwrap();
+        let network: Mechanism<IpNetwork> = "ip4:192.0.2.1/32".parse().unwrap();
+        assert_eq!(spf.remove_ip_mechanism(&network), true);
+        assert_eq!(spf.ip4().is_none(), true);
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::{Spf, SpfError};
+    use ipnetwork::IpNetwork;
+
+    const NETWORKS: &str =
+        "v=spf1 ip4:192.0.2.0/24 ip4:203.0.113.0/24 ip4:10.0.0.0/8 ip6:2001:db8::/32 -all";
+
+    fn network(input: &str) -> Mechanism<IpNetwork> {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn ip4_in_place() {
+        let mut spf: Spf = NETWORKS.parse().unwrap();
+        assert_eq!(spf.to_string(), NETWORKS);
+        let old = spf
+            .replace_ip_mechanism(1, network("ip4:198.51.100.0/24"))
+            .unwrap();
+        assert_eq!(old.to_string(), "ip4:203.0.113.0/24");
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 ip4:10.0.0.0/8 ip6:2001:db8::/32 -all"
+        );
+    }
+    #[test]
+    fn ip6_in_place() {
+        let mut spf: Spf = NETWORKS.parse().unwrap();
+        let old = spf
+            .replace_ip_mechanism(0, network("-ip6:2001:db8:1::/48"))
+            .unwrap();
+        assert_eq!(old.to_string(), "ip6:2001:db8::/32");
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:192.0.2.0/24 ip4:203.0.113.0/24 ip4:10.0.0.0/8 -ip6:2001:db8:1::/48 -all"
+        );
+    }
+    #[test]
+    fn ip_out_of_bounds() {
+        let mut spf: Spf = NETWORKS.parse().unwrap();
+        let err = spf
+            .replace_ip_mechanism(1, network("ip6:2001:db8:1::/48"))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::IndexOutOfBounds {
+                kind: Kind::IpV6,
+                index: 1,
+                len: 1
+            }
+        );
+        assert_eq!(spf.to_string(), NETWORKS);
+    }
+    #[test]
+    fn include_in_place() {
+        let input = "v=spf1 include:a.example.com include:b.example.com include:c.example.com -all";
+        let mut spf: Spf = input.parse().unwrap();
+        let new = Mechanism::include(Qualifier::SoftFail, "d.example.com").unwrap();
+        let old = spf.replace_mechanism(Kind::Include, 2, new).unwrap();
+        assert_eq!(old.to_string(), "include:c.example.com");
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:a.example.com include:b.example.com ~include:d.example.com -all"
+        );
+    }
+    #[test]
+    fn kind_mismatch() {
+        let mut spf: Spf = "v=spf1 include:a.example.com -all".parse().unwrap();
+        let err = spf
+            .replace_mechanism(Kind::Include, 0, Mechanism::a(Qualifier::Pass))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::KindMismatch {
+                expected: Kind::Include,
+                found: Kind::A
+            }
+        );
+        assert_eq!(spf.to_string(), "v=spf1 include:a.example.com -all");
+    }
+    #[test]
+    fn no_mechanisms_of_kind() {
+        let mut spf: Spf = "v=spf1 a -all".parse().unwrap();
+        let err = spf
+            .replace_mechanism(Kind::MX, 0, Mechanism::mx(Qualifier::Pass))
+            .unwrap_err();
+        assert_eq!(err.is_index_out_of_bounds(), true);
+    }
+    #[test]
+    fn single_mechanism() {
+        let mut spf: Spf = "v=spf1 a ~all".parse().unwrap();
+        let err = spf
+            .replace_mechanism(Kind::All, 1, Mechanism::all(Qualifier::Fail))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::IndexOutOfBounds {
+                kind: Kind::All,
+                index: 1,
+                len: 1
+            }
+        );
+        let old = spf
+            .replace_mechanism(Kind::All, 0, Mechanism::all(Qualifier::Fail))
+            .unwrap();
+        assert_eq!(old.is_softfail(), true);
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+}
@@ -0,0 +1,533 @@
+#[cfg(test)]
+mod findings {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::{
+        FindingCode, ParseOptions, Severity, Spf, SpfRfcStandard, ValidationOptions,
+        ValidationReport,
+    };
+
+    const MESSY: &str = "v=spf1 ptr a foo:bar ip4:192.0.2.1/24 +all mx -all \
+                         redirect=example.com custom=1";
+
+    fn report(record: &str) -> ValidationReport {
+        let spf: Spf = record.parse().unwrap();
+        spf.validation_report(&ValidationOptions::new())
+    }
+
+    #[test]
+    fn clean_record() {
+        let report = report("v=spf1 mx include:_spf.example.com -all");
+        assert_eq!(report.is_empty(), true);
+        assert_eq!(report.is_valid(), true);
+        assert_eq!(report.to_string(), "");
+    }
+    #[test]
+    fn messy_record() {
+        let report = report(MESSY);
+        assert_eq!(
+            report.codes(),
+            vec![
+                FindingCode::RedirectWithAll,
+                FindingCode::UnknownTerm,
+                FindingCode::UnknownModifier,
+                FindingCode::PtrUsed,
+                FindingCode::HostBitsSet,
+                FindingCode::UnreachableTerm,
+                FindingCode::DuplicateAll,
+            ]
+        );
+        assert_eq!(report.is_valid(), false);
+        let terms: Vec<Option<&str>> = report.findings().iter().map(|f| f.term()).collect();
+        assert_eq!(
+            terms,
+            vec![
+                Some("redirect=example.com"),
+                Some("foo:bar"),
+                Some("custom=1"),
+                Some("ptr"),
+                Some("ip4:192.0.2.1/24"),
+                Some("mx"),
+                Some("-all"),
+            ]
+        );
+        assert_eq!(report.with_severity(Severity::Error).len(), 2);
+        assert_eq!(report.with_severity(Severity::Warning).len(), 4);
+        assert_eq!(report.with_severity(Severity::Info).len(), 1);
+        assert_eq!(report.to_string().lines().count(), 7);
+        assert_eq!(
+            report.to_string().lines().nth(1),
+            Some(
+                "error[unknown-term] foo:bar: Not a known mechanism or modifier. It was left out."
+            )
+        );
+    }
+    #[test]
+    fn ptr_warning() {
+        let report = report("v=spf1 mx ptr:example.com -all");
+        assert_eq!(report.codes(), vec![FindingCode::PtrUsed]);
+        let finding = &report.findings()[0];
+        assert_eq!(finding.severity(), Severity::Warning);
+        assert_eq!(finding.term(), Some("ptr:example.com"));
+        assert_eq!(
+            finding.message(),
+            "Term 1 needs a PTR lookup, which should not be used. It is slow, unreliable and \
+             loads the .arpa name servers. Use ip4:, ip6:, a or mx instead."
+        );
+        assert_eq!(report.is_valid(), true);
+    }
+    #[test]
+    fn ptr_strict() {
+        let spf: Spf = "v=spf1 ptr -all".parse().unwrap();
+        let report = spf.validation_report(&ValidationOptions::new().strict(true));
+        assert_eq!(report.codes(), vec![FindingCode::PtrUsed]);
+        assert_eq!(report.findings()[0].severity(), Severity::Error);
+        assert_eq!(report.is_valid(), false);
+    }
+    #[test]
+    fn ptr_under_rfc4408() {
+        let spf: Spf = "v=spf1 ptr -all".parse().unwrap();
+        assert_eq!(spf.uses_ptr(), true);
+        let options = ValidationOptions::new().standard(SpfRfcStandard::Rfc4408);
+        assert_eq!(spf.validation_report(&options).is_empty(), true);
+        let options = options.strict(true);
+        assert_eq!(spf.validation_report(&options).is_empty(), true);
+    }
+    #[test]
+    fn without_ptr() {
+        let spf: Spf = "v=spf1 mx a:example.com exists:%{i}.example.com -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.uses_ptr(), false);
+        let report = spf.validation_report(&ValidationOptions::new().strict(true));
+        assert_eq!(report.is_empty(), true);
+    }
+    #[test]
+    fn ptr_macro() {
+        let report = report("v=spf1 exists:%{p}.example.com -all");
+        assert_eq!(report.codes(), vec![FindingCode::PtrUsed]);
+        assert_eq!(report.findings()[0].term(), Some("exists:%{p}.example.com"));
+    }
+    #[test]
+    fn lookup_limit() {
+        let record = "v=spf1 a mx include:a.example.com include:b.example.com -all";
+        assert_eq!(report(record).is_empty(), true);
+        let spf: Spf = record.parse().unwrap();
+        let report = spf.validation_report(&ValidationOptions::new().lookup_limit(3));
+        assert_eq!(report.codes(), vec![FindingCode::LookupLimitExceeded]);
+        assert_eq!(
+            report.findings()[0].message(),
+            "Record needs 4 DNS lookups, more than 3."
+        );
+        assert_eq!(report.findings()[0].term(), None);
+    }
+    #[test]
+    fn record_too_long() {
+        let mut spf = Spf::new_v1();
+        for n in 0..9 {
+            let domain = format!("_spf{}.mail.example.com", n);
+            spf.append_mechanism(Mechanism::include(Qualifier::Pass, &domain).unwrap());
+        }
+        assert_eq!(spf.remaining_length() < 0, true);
+        let report = spf.validation_report(&ValidationOptions::new());
+        assert_eq!(report.codes(), vec![FindingCode::RecordTooLong]);
+        assert_eq!(report.is_valid(), false);
+    }
+    #[test]
+    fn shadowed_network() {
+        let report = report("v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.1 -all");
+        assert_eq!(report.codes(), vec![FindingCode::UnreachableTerm]);
+        assert_eq!(report.findings()[0].term(), Some("ip4:192.0.2.1"));
+        assert_eq!(report.is_valid(), true);
+    }
+    #[test]
+    fn duplicate_modifier() {
+        let options = ParseOptions::new().lenient_modifiers(true);
+        let source = "v=spf1 redirect=a.example.com Redirect=b.example.com";
+        let spf = Spf::from_str_with_options(source, &options).unwrap();
+        let report = spf.validation_report(&ValidationOptions::new());
+        assert_eq!(report.codes(), vec![FindingCode::DuplicateModifier]);
+        assert_eq!(report.findings()[0].term(), Some("Redirect=b.example.com"));
+        assert_eq!(report.is_valid(), false);
+    }
+    #[test]
+    fn invalid_macro() {
+        let report = report("v=spf1 exists:%{z}.example.com -all");
+        assert_eq!(report.codes(), vec![FindingCode::InvalidMacro]);
+    }
+    #[test]
+    fn codes_are_stable() {
+        assert_eq!(FindingCode::UnknownTerm.as_str(), "unknown-term");
+        assert_eq!(
+            FindingCode::LookupLimitExceeded.to_string(),
+            "lookup-limit-exceeded"
+        );
+        assert_eq!(Severity::Warning.to_string(), "warning");
+        assert_eq!(Severity::Error > Severity::Warning, true);
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let report = report("v=spf1 ip4:0.0.0.0/0 -all");
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"findings":[{"severity":"warning","code":"all-addresses","term":"ip4:0.0.0.0/0","message":"Matches every address of the internet."}]}"#
+        );
+    }
+}
+
+#[cfg(test)]
+mod prefixes {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::{FindingCode, Severity, Spf, ValidationOptions, ValidationReport};
+
+    fn report(record: &str, options: &ValidationOptions) -> ValidationReport {
+        let spf: Spf = record.parse().unwrap();
+        spf.validation_report(options)
+    }
+
+    #[test]
+    fn every_address() {
+        let record = "v=spf1 ip4:0.0.0.0/0 ip6:::/0 -all";
+        let report = report(record, &ValidationOptions::new());
+        assert_eq!(
+            report.codes(),
+            vec![FindingCode::AllAddresses, FindingCode::AllAddresses]
+        );
+        assert_eq!(report.findings()[0].term(), Some("ip4:0.0.0.0/0"));
+        assert_eq!(report.findings()[1].term(), Some("ip6:::/0"));
+        assert_eq!(report.findings()[0].severity(), Severity::Warning);
+        assert_eq!(report.is_valid(), true);
+    }
+    #[test]
+    fn every_address_strict() {
+        let options = ValidationOptions::new().strict(true);
+        let report = report("v=spf1 ip4:0.0.0.0/0 -all", &options);
+        assert_eq!(report.codes(), vec![FindingCode::AllAddresses]);
+        assert_eq!(report.findings()[0].severity(), Severity::Error);
+        assert_eq!(report.is_valid(), false);
+    }
+    #[test]
+    fn redundant_host_prefix() {
+        let record = "v=spf1 ip4:192.0.2.1/32 ip4:192.0.2.2 ip6:2001:db8::1/128 -all";
+        let report = report(record, &ValidationOptions::new());
+        assert_eq!(
+            report.codes(),
+            vec![FindingCode::RedundantPrefix, FindingCode::RedundantPrefix]
+        );
+        assert_eq!(report.findings()[0].term(), Some("ip4:192.0.2.1/32"));
+        assert_eq!(report.findings()[1].term(), Some("ip6:2001:db8::1/128"));
+        assert_eq!(report.findings()[0].severity(), Severity::Info);
+    }
+    #[test]
+    fn broad_prefix_is_configurable() {
+        let record = "v=spf1 ip4:10.0.0.0/8 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all";
+        assert_eq!(report(record, &ValidationOptions::new()).is_empty(), true);
+        let options = ValidationOptions::new().ip4_prefix_limit(16);
+        let report_ip4 = report(record, &options);
+        assert_eq!(report_ip4.codes(), vec![FindingCode::BroadPrefix]);
+        assert_eq!(report_ip4.findings()[0].term(), Some("ip4:10.0.0.0/8"));
+        assert_eq!(
+            report_ip4.findings()[0].message(),
+            "Prefix length /8 is shorter than /16."
+        );
+        assert_eq!(report_ip4.findings()[0].severity(), Severity::Warning);
+        let options = options.ip6_prefix_limit(48);
+        assert_eq!(
+            report(record, &options).codes(),
+            vec![FindingCode::BroadPrefix, FindingCode::BroadPrefix]
+        );
+    }
+    #[test]
+    fn limit_does_not_hide_every_address() {
+        let options = ValidationOptions::new().ip4_prefix_limit(16);
+        let report = report("v=spf1 ip4:0.0.0.0/0 -all", &options);
+        assert_eq!(report.codes(), vec![FindingCode::AllAddresses]);
+    }
+    #[test]
+    fn dual_cidr_out_of_range() {
+        let mut spf: Spf = "v=spf1 a/24 -all".parse().unwrap();
+        let a = Mechanism::generic_inclusive(
+            Kind::A,
+            Qualifier::Pass,
+            Some("example.com/33//64".to_string()),
+        );
+        let mx = Mechanism::generic_inclusive(
+            Kind::MX,
+            Qualifier::Pass,
+            Some("example.com/24//129".to_string()),
+        );
+        spf.append_mechanism(a);
+        spf.append_mechanism(mx);
+        let report = spf.validation_report(&ValidationOptions::new());
+        assert_eq!(
+            report.codes(),
+            vec![FindingCode::InvalidDualCidr, FindingCode::InvalidDualCidr]
+        );
+        assert_eq!(report.findings()[0].term(), Some("a:example.com/33//64"));
+        assert_eq!(
+            report.findings()[0].message(),
+            "33 is not a valid ip4 cidr length."
+        );
+        assert_eq!(
+            report.findings()[1].message(),
+            "129 is not a valid ip6 cidr length."
+        );
+        assert_eq!(report.is_valid(), false);
+    }
+}
+
+#[cfg(test)]
+mod domains {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::{FindingCode, Spf, ValidationOptions, ValidationReport};
+
+    fn report(record: &str) -> ValidationReport {
+        let spf: Spf = record.parse().unwrap();
+        spf.validation_report(&ValidationOptions::new())
+    }
+    // The term and message of each finding.
+    fn invalid(spf: &Spf) -> Vec<(String, String)> {
+        let report = spf.validation_report(&ValidationOptions::new());
+        assert_eq!(
+            report
+                .codes()
+                .iter()
+                .all(|code| *code == FindingCode::InvalidDomain),
+            true
+        );
+        report
+            .findings()
+            .iter()
+            .map(|f| (f.term().unwrap().to_string(), f.message().to_string()))
+            .collect()
+    }
+    fn with_term(kind: Kind, rrdata: &str) -> Spf {
+        let mut spf = Spf::new_v1();
+        let mechanism = Mechanism::generic_inclusive(kind, Qualifier::Pass, Some(rrdata.into()));
+        spf.append_mechanism(mechanism);
+        spf
+    }
+
+    #[test]
+    fn valid_domains() {
+        let record = "v=spf1 a:mail mx:mail.example.com/24 include:_spf.example.com \
+                      exists:%{i}._spf.%{d} redirect=_spf.example.net.";
+        assert_eq!(report(record).is_empty(), true);
+    }
+    #[test]
+    fn invalid_characters() {
+        let spf = with_term(Kind::Include, "not a domain!!");
+        assert_eq!(
+            invalid(&spf),
+            vec![(
+                "include:not a domain!!".to_string(),
+                "'not a domain!!' contains a character other than a letter, digit or hyphen."
+                    .to_string()
+            )]
+        );
+        let spf = with_term(Kind::A, "mail_server.example.com");
+        assert_eq!(
+            invalid(&spf)[0].1,
+            "'mail_server' contains a character other than a letter, digit or hyphen."
+        );
+    }
+    #[test]
+    fn hyphens() {
+        let spf = with_term(Kind::MX, "-mail.example.com/24");
+        assert_eq!(
+            invalid(&spf),
+            vec![(
+                "mx:-mail.example.com/24".to_string(),
+                "'-mail' begins or ends with a hyphen.".to_string()
+            )]
+        );
+        let spf = with_term(Kind::A, "mail-.example.com");
+        assert_eq!(invalid(&spf)[0].1, "'mail-' begins or ends with a hyphen.");
+    }
+    #[test]
+    fn label_length() {
+        let label = "a".repeat(64);
+        let spf = with_term(Kind::Include, &format!("{}.example.com", label));
+        assert_eq!(
+            invalid(&spf)[0].1,
+            format!("'{}' is longer than 63 octets.", label)
+        );
+        assert_eq!(
+            invalid(&with_term(
+                Kind::Include,
+                &format!("{}.com", "a".repeat(63))
+            )),
+            vec![]
+        );
+    }
+    #[test]
+    fn domain_length() {
+        let domain = vec!["a".repeat(50); 6].join(".");
+        let spf = with_term(Kind::Exists, &domain);
+        let report = spf.validation_report(&ValidationOptions::new());
+        // The record itself is also too long.
+        assert_eq!(
+            report.codes(),
+            vec![FindingCode::RecordTooLong, FindingCode::InvalidDomain]
+        );
+        assert_eq!(
+            report.findings()[1].message(),
+            format!("'{}' is longer than 253 octets.", domain)
+        );
+    }
+    #[test]
+    fn empty_label() {
+        let spf = with_term(Kind::Include, "example..com");
+        assert_eq!(invalid(&spf)[0].1, "'example..com' has an empty label.");
+    }
+    #[test]
+    fn single_label() {
+        let spf = with_term(Kind::Include, "localhost");
+        assert_eq!(
+            invalid(&spf)[0].1,
+            "'localhost' must have at least two labels."
+        );
+        let spf = with_term(Kind::Redirect, "example");
+        assert_eq!(invalid(&spf).len(), 1);
+        let spf = with_term(Kind::Exists, "example");
+        assert_eq!(invalid(&spf).len(), 1);
+        let spf = with_term(Kind::A, "example");
+        assert_eq!(invalid(&spf), vec![]);
+    }
+    #[test]
+    fn ip_literal() {
+        let spf = with_term(Kind::A, "192.0.2.1");
+        assert_eq!(
+            invalid(&spf),
+            vec![(
+                "a:192.0.2.1".to_string(),
+                "'192.0.2.1' is an IP address, not a domain.".to_string()
+            )]
+        );
+        let spf = with_term(Kind::Include, "2001:db8::1");
+        assert_eq!(
+            invalid(&spf)[0].1,
+            "'2001:db8::1' is an IP address, not a domain."
+        );
+    }
+    #[test]
+    fn macros_are_not_checked() {
+        let spf = with_term(Kind::Exists, "%{i}.-not_valid!");
+        assert_eq!(invalid(&spf), vec![]);
+    }
+}
+
+#[cfg(test)]
+mod macros {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::{FindingCode, Spf, ValidationOptions};
+
+    // The term and message of each macro finding for a term of `kind` with the domain-spec
+    // `rrdata`.
+    fn invalid(kind: Kind, rrdata: &str) -> Vec<(String, String)> {
+        let mut spf = Spf::new_v1();
+        let mechanism = Mechanism::generic_inclusive(kind, Qualifier::Pass, Some(rrdata.into()));
+        spf.append_mechanism(mechanism);
+        let report = spf.validation_report(&ValidationOptions::new());
+        report
+            .findings()
+            .iter()
+            .filter(|f| f.code() == FindingCode::InvalidMacro)
+            .map(|f| (f.term().unwrap().to_string(), f.message().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn every_macro_letter() {
+        for letter in ['s', 'l', 'o', 'd', 'i', 'p', 'v', 'h'] {
+            let rrdata = format!(
+                "%{{{}}}.%{{{}2r}}.%{{{}r-+,/_=}}.example.com",
+                letter, letter, letter
+            );
+            assert_eq!(invalid(Kind::Exists, &rrdata), vec![]);
+            let upper = format!("%{{{}}}.example.com", letter.to_ascii_uppercase());
+            assert_eq!(invalid(Kind::Exists, &upper), vec![]);
+        }
+        assert_eq!(invalid(Kind::Include, "%%.%_.%-.example.com"), vec![]);
+    }
+    #[test]
+    fn unknown_letter() {
+        assert_eq!(
+            invalid(Kind::Exists, "%{i}.%{x}.example.com"),
+            vec![(
+                "exists:%{i}.%{x}.example.com".to_string(),
+                "Offset 5 of '%{i}.%{x}.example.com': 'x' is not a macro letter.".to_string()
+            )]
+        );
+    }
+    #[test]
+    fn explanation_only_letter() {
+        let findings = invalid(Kind::Exists, "%{c}.%{r}.%{t}.example.com");
+        assert_eq!(findings.len(), 3);
+        assert_eq!(
+            findings[2].1,
+            "Offset 10 of '%{c}.%{r}.%{t}.example.com': Macro t may only be used in an explanation."
+        );
+    }
+    #[test]
+    fn unclosed() {
+        assert_eq!(
+            invalid(Kind::Include, "_spf.%{d"),
+            vec![(
+                "include:_spf.%{d".to_string(),
+                "Offset 5 of '_spf.%{d': '%{' is not closed by '}'.".to_string()
+            )]
+        );
+    }
+    #[test]
+    fn parentheses() {
+        assert_eq!(
+            invalid(Kind::A, "%(d)/24")[0].1,
+            "Offset 0 of '%(d)/24': '%' is followed by '(' rather than '{', '%', '_' or '-'."
+        );
+    }
+    #[test]
+    fn stray_percent() {
+        let findings = invalid(Kind::Redirect, "%d.example.com%");
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "redirect=%d.example.com%".to_string(),
+                    "Offset 0 of '%d.example.com%': '%' is followed by 'd' rather than \
+                     '{', '%', '_' or '-'."
+                        .to_string()
+                ),
+                (
+                    "redirect=%d.example.com%".to_string(),
+                    "Offset 14 of '%d.example.com%': '%' ends the macro-string.".to_string()
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn literal() {
+        assert_eq!(
+            invalid(Kind::Exists, "%{i}.bad label")[0].1,
+            "Offset 8 of '%{i}.bad label': ' ' is not permitted."
+        );
+    }
+    #[test]
+    fn transformers() {
+        let findings = invalid(Kind::Exp, "%{d0}.%{l1x}.%{}.example.com");
+        let messages: Vec<&str> = findings.iter().map(|(_, m)| m.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Offset 0 of '%{d0}.%{l1x}.%{}.example.com': A transformer must keep at least \
+                 one part.",
+                "Offset 6 of '%{d0}.%{l1x}.%{}.example.com': 'x' is not a delimiter.",
+                "Offset 13 of '%{d0}.%{l1x}.%{}.example.com': '%{}' has no macro letter.",
+            ]
+        );
+    }
+}
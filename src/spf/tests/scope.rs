@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::spf::{Scope, Spf, SpfError};
+
+    #[test]
+    fn single_scope() {
+        let spf: Spf = "spf2.0/pra a -all".parse().unwrap();
+        assert_eq!(spf.is_v2(), true);
+        assert_eq!(spf.scopes().unwrap(), vec![Scope::Pra]);
+    }
+    #[test]
+    fn scope_order_is_kept() {
+        for (input, scopes) in [
+            ("spf2.0/mfrom,pra a -all", vec![Scope::MFrom, Scope::Pra]),
+            ("spf2.0/pra,mfrom a -all", vec![Scope::Pra, Scope::MFrom]),
+        ] {
+            let spf: Spf = input.parse().unwrap();
+            assert_eq!(spf.scopes().unwrap(), scopes);
+            assert_eq!(spf.to_string(), input);
+        }
+    }
+    #[test]
+    fn v1_has_no_scopes() {
+        let spf: Spf = "v=spf1 a -all".parse().unwrap();
+        assert_eq!(spf.scopes().is_none(), true);
+    }
+    #[test]
+    fn unknown_scope() {
+        let err = "spf2.0/pra,helo a -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err, SpfError::InvalidScope("helo".to_string()));
+        assert_eq!(err.is_invalid_scope(), true);
+    }
+    #[test]
+    fn repeated_scope() {
+        let err = "spf2.0/mfrom,mfrom a -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err, SpfError::InvalidScope("mfrom".to_string()));
+    }
+    #[test]
+    fn built_scopes() {
+        let mut spf = Spf::new();
+        spf.set_v2_mfrom_pra();
+        assert_eq!(spf.scopes().unwrap(), vec![Scope::MFrom, Scope::Pra]);
+        spf.set_v1();
+        assert_eq!(spf.scopes().is_none(), true);
+    }
+}
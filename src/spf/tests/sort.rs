@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod spf {
+
+    use crate::spf::{SortStrategy, Spf};
+
+    const RECORD: &str = "v=spf1 mx:mx2.example.com -MX:Mx1.example.com \
+                          include:_spf.example.net ~include:_spf.example.com \
+                          ip4:203.0.113.10 ip4:203.0.113.0/24 ip4:198.51.100.9 \
+                          ip6:2001:db8::10 ip6:2001:db8::2 exists:%{i}.example.com \
+                          -all x-z=1 X-a=2";
+
+    fn sorted(strategy: SortStrategy) -> String {
+        let mut spf: Spf = RECORD.parse().unwrap();
+        spf.sort_mechanisms(strategy);
+        spf.to_string()
+    }
+
+    #[test]
+    fn preserve() {
+        let spf: Spf = RECORD.parse().unwrap();
+        assert_eq!(sorted(SortStrategy::Preserve), spf.to_string());
+    }
+    #[test]
+    fn lexical() {
+        assert_eq!(
+            sorted(SortStrategy::Lexical),
+            "v=spf1 -mx:Mx1.example.com mx:mx2.example.com include:_spf.example.net \
+             ~include:_spf.example.com ip4:198.51.100.9 ip4:203.0.113.0/24 ip4:203.0.113.10 \
+             ip6:2001:db8::2 ip6:2001:db8::10 exists:%{i}.example.com -all X-a=2 x-z=1"
+        );
+    }
+    #[test]
+    fn lexical_qualifier_first() {
+        let mut spf: Spf = "v=spf1 include:a.example.com -include:b.example.com -all"
+            .parse()
+            .unwrap();
+        spf.sort_mechanisms(SortStrategy::Lexical);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 -include:b.example.com include:a.example.com -all"
+        );
+    }
+    #[test]
+    fn by_kind_then_value() {
+        assert_eq!(
+            sorted(SortStrategy::ByKindThenValue),
+            "v=spf1 -mx:Mx1.example.com mx:mx2.example.com ~include:_spf.example.com \
+             include:_spf.example.net ip4:198.51.100.9 ip4:203.0.113.0/24 ip4:203.0.113.10 \
+             ip6:2001:db8::2 ip6:2001:db8::10 exists:%{i}.example.com -all X-a=2 x-z=1"
+        );
+        let mut spf: Spf = "v=spf1 include:a.example.com -include:b.example.com -all"
+            .parse()
+            .unwrap();
+        spf.sort_mechanisms(SortStrategy::ByKindThenValue);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 include:a.example.com -include:b.example.com -all"
+        );
+    }
+    #[test]
+    fn same_value_by_qualifier() {
+        let mut spf: Spf = "v=spf1 ~a:example.com -a:example.com a:example.com"
+            .parse()
+            .unwrap();
+        spf.sort_mechanisms(SortStrategy::ByKindThenValue);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 -a:example.com a:example.com ~a:example.com"
+        );
+    }
+    #[test]
+    fn networks_by_address_then_prefix() {
+        let mut spf: Spf = "v=spf1 ip4:10.0.0.0/16 ip4:9.0.0.0/8 ip4:10.0.0.0/8 ip4:10.0.0.100"
+            .parse()
+            .unwrap();
+        spf.sort_mechanisms(SortStrategy::Lexical);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:9.0.0.0/8 ip4:10.0.0.0/8 ip4:10.0.0.0/16 ip4:10.0.0.100"
+        );
+    }
+    #[test]
+    fn stable_across_runs() {
+        for strategy in [SortStrategy::Lexical, SortStrategy::ByKindThenValue] {
+            let first = sorted(strategy);
+            for _ in 0..10 {
+                assert_eq!(sorted(strategy), first);
+            }
+            // Sorting a sorted record changes nothing.
+            let mut spf: Spf = first.parse().unwrap();
+            spf.sort_mechanisms(strategy);
+            assert_eq!(spf.to_string(), first);
+        }
+    }
+    #[test]
+    fn input_order_does_not_matter() {
+        let mut first: Spf =
+            "v=spf1 include:b.example.com include:a.example.com ip4:203.0.113.2 ip4:203.0.113.1"
+                .parse()
+                .unwrap();
+        let mut second: Spf =
+            "v=spf1 ip4:203.0.113.1 include:a.example.com ip4:203.0.113.2 include:b.example.com"
+                .parse()
+                .unwrap();
+        first.sort_mechanisms(SortStrategy::ByKindThenValue);
+        second.sort_mechanisms(SortStrategy::ByKindThenValue);
+        assert_eq!(first.to_string(), second.to_string());
+    }
+}
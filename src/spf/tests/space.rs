@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod stats {
+
+    use crate::mechanism::Qualifier;
+    use crate::spf::{IpSpace, Spf};
+
+    fn spf(record: &str) -> Spf {
+        record.parse().unwrap()
+    }
+
+    #[test]
+    fn overlapping_networks() {
+        let stats = spf(
+            "v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.0/25 ip4:192.0.2.200 ip4:192.0.2.255/32 \
+             ip4:198.51.100.0/31 -all",
+        )
+        .ip_space();
+        assert_eq!(stats.total().ipv4_addresses(), 256 + 2);
+        assert_eq!(stats.total().ipv6_64s(), 0);
+    }
+    #[test]
+    fn adjacent_networks() {
+        let stats =
+            spf("v=spf1 ip4:192.0.2.0/25 ip4:192.0.2.128/25 ip4:192.0.3.0/24 -all").ip_space();
+        assert_eq!(stats.total().ipv4_addresses(), 512);
+    }
+    #[test]
+    fn whole_ipv4_space() {
+        let stats = spf("v=spf1 ip4:0.0.0.0/0 ip4:10.0.0.0/8 ip4:255.255.255.255 -all").ip_space();
+        assert_eq!(stats.total().ipv4_addresses(), 1 << 32);
+        assert_eq!(stats.broad_networks(8, 0), ["0.0.0.0/0".parse().unwrap()]);
+    }
+    #[test]
+    fn whole_ipv6_space() {
+        let stats = spf("v=spf1 ip6:::/0 ip6:2001:db8::/32 -all").ip_space();
+        assert_eq!(stats.total().ipv6_64s(), 1 << 64);
+        assert_eq!(stats.total().ipv4_addresses(), 0);
+    }
+    #[test]
+    fn ipv6_counted_in_64s() {
+        let stats = spf(
+            "v=spf1 ip6:2001:db8::1 ip6:2001:db8::2 ip6:2001:db8:0:1::/64 ip6:2001:db8:1::/56 \
+             ip6:2001:db8:0:1:8000::/65 -all",
+        )
+        .ip_space();
+        assert_eq!(stats.total().ipv6_64s(), 1 + 1 + 256);
+    }
+    #[test]
+    fn per_qualifier() {
+        let stats = spf(
+            "v=spf1 ip4:192.0.2.0/24 -ip4:192.0.2.0/28 ~ip4:198.51.100.0/24 \
+             ?ip6:2001:db8::/63 -all",
+        )
+        .ip_space();
+        assert_eq!(stats.qualifier(Qualifier::Pass).ipv4_addresses(), 256);
+        assert_eq!(stats.qualifier(Qualifier::Fail).ipv4_addresses(), 16);
+        assert_eq!(stats.qualifier(Qualifier::SoftFail).ipv4_addresses(), 256);
+        assert_eq!(stats.qualifier(Qualifier::Neutral).ipv6_64s(), 2);
+        assert_eq!(stats.qualifier(Qualifier::Neutral).ipv4_addresses(), 0);
+        // The failing network is within the passing network.
+        assert_eq!(stats.total().ipv4_addresses(), 512);
+    }
+    #[test]
+    fn overly_broad() {
+        let stats = spf("v=spf1 ip4:10.0.0.0/8 ip4:192.0.2.0/24 ip6:2001:db8::/28 -all").ip_space();
+        assert_eq!(stats.is_overly_broad(8, 28), false);
+        assert_eq!(stats.is_overly_broad(9, 28), true);
+        assert_eq!(stats.broad_networks(16, 32).len(), 2);
+        assert_eq!(
+            stats.broad_networks(8, 32),
+            ["2001:db8::/28".parse().unwrap()]
+        );
+    }
+    #[test]
+    fn no_networks() {
+        let stats = spf("v=spf1 a mx include:_spf.example.com -all").ip_space();
+        assert_eq!(stats.total(), IpSpace::default());
+        assert_eq!(stats.total().is_empty(), true);
+        assert_eq!(stats.is_overly_broad(32, 128), false);
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::{Mechanism, Qualifier};
+    use crate::spf::Spf;
+
+    #[test]
+    fn parse_only() {
+        let spf: Spf = "v=spf1 a mx -all".parse().unwrap();
+        assert_eq!(spf.from_src, true);
+        assert_eq!(spf.was_parsed, true);
+        assert_eq!(spf.was_validated, false);
+        assert_eq!(spf.is_valid(), false);
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn parse_and_validate() {
+        let mut spf: Spf = "v=spf1 a mx -all".parse().unwrap();
+        assert_eq!(spf.try_validate().is_ok(), true);
+        assert_eq!(spf.from_src, true);
+        assert_eq!(spf.was_parsed, true);
+        assert_eq!(spf.was_validated, true);
+        assert_eq!(spf.is_valid(), true);
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn parse_and_fail_validation() {
+        let mut spf: Spf = "v=spf1 a ~all -all".parse().unwrap();
+        assert_eq!(spf.try_validate().is_err(), true);
+        assert_eq!(spf.is_valid(), false);
+    }
+    #[test]
+    fn parse_strict() {
+        let spf = Spf::from_str_strict("v=spf1 a mx -all").unwrap();
+        assert_eq!(spf.from_src, true);
+        assert_eq!(spf.was_parsed, true);
+        assert_eq!(spf.is_valid(), false);
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn built() {
+        let mut spf = Spf::new();
+        spf.set_v1();
+        spf.append_mechanism(Mechanism::a(Qualifier::Pass));
+        spf.append_mechanism(Mechanism::all(Qualifier::Fail));
+        assert_eq!(spf.from_src, false);
+        assert_eq!(spf.was_parsed, false);
+        assert_eq!(spf.is_valid(), false);
+        assert_eq!(spf.try_validate().is_ok(), true);
+        assert_eq!(spf.from_src, false);
+        assert_eq!(spf.was_parsed, false);
+        assert_eq!(spf.is_valid(), true);
+    }
+}
+#[cfg(test)]
+mod mutate {
+
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::spf::{Spf, SpfError};
+
+    fn validated(input: &str) -> Spf {
+        let mut spf: Spf = input.parse().unwrap();
+        #[allow(deprecated)]
+        spf.try_validate().unwrap();
+        assert_eq!(spf.is_valid(), true);
+        spf
+    }
+
+    #[test]
+    fn parsed_is_not_modified() {
+        let spf: Spf = "v=spf1 a mx -all".parse().unwrap();
+        assert_eq!(spf.is_modified(), false);
+        assert_eq!(Spf::new_v1().is_modified(), false);
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn append_past_lookup_limit() {
+        let mut spf = validated(
+            "v=spf1 a mx include:a.example.com include:b.example.com include:c.example.com \
+             include:d.example.com -all",
+        );
+        for domain in ["e", "f", "g", "h", "i"] {
+            let include = format!("{}.example.com", domain);
+            spf.append_mechanism(Mechanism::include(Qualifier::Pass, &include).unwrap());
+            assert_eq!(spf.is_valid(), false);
+        }
+        assert_eq!(spf.is_modified(), true);
+        assert_eq!(spf.try_validate(), Err(SpfError::LookupLimitExceeded));
+        assert_eq!(spf.is_valid(), false);
+
+        assert_eq!(spf.remove_mechanism(&Mechanism::mx(Qualifier::Pass)), true);
+        assert_eq!(spf.try_validate(), Ok(()));
+        assert_eq!(spf.is_valid(), true);
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn remove_and_revalidate() {
+        let mut spf = validated("v=spf1 a mx -all");
+        assert_eq!(spf.remove_mechanism(&Mechanism::a(Qualifier::Pass)), true);
+        assert_eq!(spf.is_valid(), false);
+        assert_eq!(spf.is_modified(), true);
+        assert_eq!(spf.try_validate(), Ok(()));
+        assert_eq!(spf.is_valid(), true);
+        assert_eq!(spf.is_modified(), true);
+    }
+    #[test]
+    fn unchanged_by_failed_calls() {
+        let mut spf = validated("v=spf1 a -all");
+        assert_eq!(spf.remove_mechanism(&Mechanism::mx(Qualifier::Pass)), false);
+        let include = Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap();
+        assert_eq!(spf.set_redirect(include).is_err(), true);
+        assert_eq!(
+            spf.try_append_mechanism(Mechanism::all(Qualifier::Pass))
+                .is_err(),
+            true
+        );
+        assert_eq!(spf.take_redirect(), None);
+        assert_eq!(spf.is_valid(), true);
+        assert_eq!(spf.is_modified(), false);
+    }
+    #[test]
+    fn unchanged_by_no_op_calls() {
+        let mut spf = validated("v=spf1 a mx include:a.example.com ip4:203.0.113.0/24 -all");
+        assert_eq!(spf.dedup(), 0);
+        assert_eq!(spf.dedup_subsumed(), 0);
+        assert_eq!(spf.aggregate_ips(), 0);
+        spf.sort_mechanisms(crate::spf::SortStrategy::Preserve);
+        spf.sort_mechanisms(crate::spf::SortStrategy::Lexical);
+        spf.normalize();
+        assert_eq!(spf.take_mechanisms(Kind::Exists).is_empty(), true);
+        assert_eq!(spf.exists_mut(), None);
+        assert_eq!(spf.ptr_mut(), None);
+        assert_eq!(spf.redirect_mut(), None);
+        assert_eq!(spf.is_valid(), true);
+        assert_eq!(spf.is_modified(), false);
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 a mx include:a.example.com ip4:203.0.113.0/24 -all"
+        );
+        assert_eq!(spf.a_mut().is_some(), true);
+        assert_eq!(spf.is_modified(), true);
+    }
+    #[test]
+    fn each_mutation() {
+        let input = "v=spf1 a a mx include:b.example.com include:a.example.com \
+                     ip4:203.0.113.0/24 x-note=ok -all";
+        let mutations: Vec<fn(&mut Spf)> = vec![
+            |spf| spf.set_v1(),
+            |spf| spf.append_mechanism(Mechanism::ptr(Qualifier::Pass)),
+            |spf| {
+                spf.try_append_mechanism(Mechanism::ptr(Qualifier::Pass))
+                    .unwrap()
+            },
+            |spf| spf.append_ip_mechanism("ip6:2001:db8::/32".parse().unwrap()),
+            |spf| spf.append_str("x-more=yes").unwrap(),
+            |spf| spf.clear_mechanism(Kind::MX),
+            |spf| spf.clear(),
+            |spf| spf.clear_all(),
+            |spf| {
+                spf.dedup();
+            },
+            |spf| spf.normalize(),
+            |spf| spf.sort_mechanisms(crate::spf::SortStrategy::Lexical),
+            |spf| {
+                spf.replace_mechanism(Kind::A, 0, Mechanism::a(Qualifier::Fail))
+                    .unwrap();
+            },
+            |spf| {
+                spf.remove_ip_mechanism(&"ip4:203.0.113.0/24".parse().unwrap());
+            },
+            |spf| {
+                spf.take_all();
+            },
+            |spf| spf.set_all(Mechanism::all(Qualifier::SoftFail)).unwrap(),
+        ];
+        for mutate in mutations {
+            let mut spf = validated(input);
+            mutate(&mut spf);
+            assert_eq!(spf.is_valid(), false);
+            assert_eq!(spf.is_modified(), true);
+        }
+    }
+    #[test]
+    fn builder_is_valid() {
+        let spf = crate::spf::SpfBuilder::new()
+            .v1()
+            .a(Qualifier::Pass, None)
+            .all(Qualifier::Fail)
+            .build()
+            .unwrap();
+        assert_eq!(spf.is_valid(), true);
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::mechanism::MechanismError;
+    use crate::spf::{ParseOptions, Spf, SpfError};
+
+    #[test]
+    fn valid_record() {
+        let input = "v=spf1 a mx include:_spf.example.com ip4:203.32.160.0/24 -all";
+
+        let spf = Spf::from_str_strict(input).unwrap();
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn misspelled_include() {
+        let input = "v=spf1 a inclde:_spf.example.com -all";
+
+        let err = Spf::from_str_strict(input).unwrap_err();
+        assert_eq!(err.is_unknown_term(), false);
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName("inclde".to_string()))
+        );
+    }
+    #[test]
+    fn misspelled_mx() {
+        let input = "v=spf1 a mxx -all";
+
+        let err = Spf::from_str_strict(input).unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName("mxx".to_string()))
+        );
+    }
+    #[test]
+    fn unknown_mechanism() {
+        let input = "v=spf1 a bogusmech -all";
+
+        let err = Spf::from_str_strict(input).unwrap_err();
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName(
+                "bogusmech".to_string()
+            ))
+        );
+        assert_eq!(
+            err.to_string(),
+            "bogusmech is not a known mechanism (term 2 'bogusmech' at offset 9)"
+        );
+    }
+    #[test]
+    fn stray_punctuation() {
+        let input = "v=spf1 a , -all";
+
+        let err = Spf::from_str_strict(input).unwrap_err();
+        assert_eq!(err.inner(), &SpfError::UnknownTerm(",".to_string()));
+    }
+    #[test]
+    fn qualifier_with_unknown_name() {
+        let input = "v=spf1 a -foo:bar -all";
+
+        let err = Spf::from_str_strict(input).unwrap_err();
+        // The qualifier is kept with the name.
+        assert_eq!(
+            err.inner(),
+            &SpfError::InvalidMechanism(MechanismError::UnknownMechanismName("-foo".to_string()))
+        );
+    }
+    #[test]
+    fn lenient_is_default() {
+        let input = "v=spf1 a -foo:bar bogusmech -all";
+
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+        let spf = Spf::from_str_with_options(input, &ParseOptions::default()).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+    }
+}
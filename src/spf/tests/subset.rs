@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod authorised {
+
+    use crate::spf::{Spf, SubsetResult};
+    use ipnetwork::IpNetwork;
+
+    fn compare(spf: &str, other: &str) -> SubsetResult {
+        let spf: Spf = spf.parse().unwrap();
+        spf.is_subset_of(&other.parse().unwrap())
+    }
+    fn networks(list: &[&str]) -> Vec<IpNetwork> {
+        list.iter().map(|n| n.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn identical() {
+        let record = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all";
+        let result = compare(record, record);
+        assert_eq!(result.is_subset(), true);
+        assert_eq!(result.is_certain(), true);
+        assert_eq!(result.uncovered(), []);
+    }
+    #[test]
+    fn same_addresses_written_differently() {
+        let result = compare(
+            "v=spf1 ip4:192.0.2.0/24 -all",
+            "v=spf1 ip4:192.0.2.128/25 ip4:192.0.2.0/25 -all",
+        );
+        assert_eq!(result.is_subset(), true);
+    }
+    #[test]
+    fn superset() {
+        let old = "v=spf1 ip4:192.0.2.10 ip4:198.51.100.0/25 ip6:2001:db8:1::/48 -all";
+        let new = "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 ip6:2001:db8::/32 -all";
+        assert_eq!(compare(old, new).is_subset(), true);
+        let result = compare(new, old);
+        assert_eq!(result.is_subset(), false);
+        assert_eq!(result.uncovered().len(), 8 + 1 + 16);
+        assert_eq!(result.uncovered()[0], "192.0.2.0/29".parse().unwrap());
+        assert_eq!(result.uncovered()[8], "198.51.100.128/25".parse().unwrap());
+    }
+    #[test]
+    fn partial_overlap() {
+        let result = compare(
+            "v=spf1 ip4:192.0.2.0/24 ip4:203.0.113.0/24 -all",
+            "v=spf1 ip4:192.0.2.0/25 ip4:192.0.2.192/26 ip4:203.0.113.0/24 -all",
+        );
+        assert_eq!(result.is_subset(), false);
+        assert_eq!(result.uncovered(), networks(&["192.0.2.128/26"]).as_slice());
+    }
+    #[test]
+    fn disjoint_families() {
+        let result = compare(
+            "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/64 -all",
+            "v=spf1 ip6:2001:db8::/32 -all",
+        );
+        assert_eq!(result.uncovered(), networks(&["192.0.2.0/24"]).as_slice());
+    }
+    #[test]
+    fn qualifiers() {
+        // Failing networks authorise nothing. Neutral networks are authorised.
+        let result = compare(
+            "v=spf1 -ip4:10.0.0.0/8 ~ip4:172.16.0.0/12 ?ip4:192.0.2.0/24 -all",
+            "v=spf1 ip4:192.0.2.0/24 -all",
+        );
+        assert_eq!(result.is_subset(), true);
+        let result = compare(
+            "v=spf1 ip4:192.0.2.0/24 -all",
+            "v=spf1 -ip4:192.0.2.0/24 -all",
+        );
+        assert_eq!(result.uncovered(), networks(&["192.0.2.0/24"]).as_slice());
+    }
+    #[test]
+    fn all() {
+        let result = compare("v=spf1 ip4:192.0.2.0/24 ip6:::/0 -all", "v=spf1 ?all");
+        assert_eq!(result.is_subset(), true);
+        let result = compare("v=spf1 +all", "v=spf1 ip4:0.0.0.0/1 -all");
+        assert_eq!(
+            result.uncovered(),
+            networks(&["128.0.0.0/1", "::/0"]).as_slice()
+        );
+    }
+    #[test]
+    fn lookups_unknown() {
+        let result = compare(
+            "v=spf1 a -mx include:_spf.example.com ptr exists:%{i}.example.com ip4:192.0.2.1 -all",
+            "v=spf1 ip4:192.0.2.0/24 redirect=_spf.example.net",
+        );
+        assert_eq!(result.is_subset(), true);
+        assert_eq!(result.is_certain(), false);
+        let unknown: Vec<String> = result.unknown().iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            unknown,
+            vec![
+                "a",
+                "include:_spf.example.com",
+                "exists:%{i}.example.com",
+                "ptr"
+            ]
+        );
+        assert_eq!(
+            result.other_unknown()[0].to_string(),
+            "redirect=_spf.example.net"
+        );
+    }
+    #[test]
+    fn empty_record() {
+        let result = compare("v=spf1 -all", "v=spf1 -all");
+        assert_eq!(result.is_subset(), true);
+        assert_eq!(result.is_certain(), true);
+    }
+}
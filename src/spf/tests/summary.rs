@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod report {
+
+    use crate::mechanism::{Kind, Qualifier};
+    use crate::spf::Spf;
+
+    const RECORD: &str = "v=spf1 a mx:example.com ~include:_spf.example.com \
+                          include:_spf.example.net ip4:192.0.2.0/24 -ip4:192.0.2.1 \
+                          ip6:2001:db8::/32 ?exists:%{i}.example.com ~all \
+                          exp=explain.example.com";
+
+    #[test]
+    fn known_record() {
+        let spf: Spf = RECORD.parse().unwrap();
+        let summary = spf.summary();
+        let counts = [
+            (Kind::A, 1),
+            (Kind::MX, 1),
+            (Kind::Include, 2),
+            (Kind::IpV4, 2),
+            (Kind::IpV6, 1),
+            (Kind::Exists, 1),
+            (Kind::Ptr, 0),
+            (Kind::All, 1),
+            (Kind::Redirect, 0),
+            (Kind::Exp, 1),
+        ];
+        for (kind, count) in counts {
+            assert_eq!(summary.count_of(kind), count);
+        }
+        assert_eq!(summary.qualifier_count(Qualifier::Pass), 5);
+        assert_eq!(summary.qualifier_count(Qualifier::Fail), 1);
+        assert_eq!(summary.qualifier_count(Qualifier::SoftFail), 2);
+        assert_eq!(summary.qualifier_count(Qualifier::Neutral), 1);
+        assert_eq!(summary.lookups(), 5);
+        assert_eq!(summary.length(), RECORD.len());
+        assert_eq!(summary.warnings(), 0);
+        assert_eq!(summary.has_redirect(), false);
+        assert_eq!(summary.has_exp(), true);
+    }
+    #[test]
+    fn follows_the_record() {
+        let spf: Spf = "v=spf1 a a ~all -all redirect=_spf.example.com"
+            .parse()
+            .unwrap();
+        let summary = spf.summary();
+        assert_eq!(summary.lookups(), spf.lookup_count());
+        assert_eq!(summary.length(), spf.serialized_len());
+        assert_eq!(summary.warnings(), spf.parse_warnings().unwrap().len());
+        assert_eq!(summary.has_redirect(), true);
+    }
+    #[test]
+    fn one_line() {
+        let spf: Spf = "v=spf1 ip4:192.0.2.0/24 -all".parse().unwrap();
+        assert_eq!(
+            spf.summary().to_string(),
+            "a=0 mx=0 include=0 ip4=1 ip6=0 exists=0 ptr=0 all=1 redirect=0 exp=0 pass=1 \
+             fail=1 softfail=0 neutral=0 lookups=0 length=28 warnings=0"
+        );
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let spf: Spf = "v=spf1 mx -all".parse().unwrap();
+        let json = serde_json::to_value(spf.summary()).unwrap();
+        assert_eq!(json["mx"], 1);
+        assert_eq!(json["fail"], 1);
+        assert_eq!(json["lookups"], 1);
+        assert_eq!(json["length"], 14);
+    }
+}
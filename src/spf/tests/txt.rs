@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::spf::{Spf, SpfError};
+
+    #[test]
+    fn single_chunk() {
+        let spf = Spf::from_txt_strings(&["v=spf1 a mx -all"]).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a mx -all");
+    }
+    #[test]
+    fn chunks_split_on_whitespace() {
+        let spf =
+            Spf::from_txt_strings(&["v=spf1 ip4:192.0.2.0/24", " include:_spf.example.com -all"])
+                .unwrap();
+        assert_eq!(spf.ip4().unwrap().len(), 1);
+        assert_eq!(spf.includes().unwrap()[0].raw(), "_spf.example.com");
+        assert_eq!(
+            spf.source(),
+            "v=spf1 ip4:192.0.2.0/24 include:_spf.example.com -all"
+        );
+    }
+    #[test]
+    fn chunks_split_mid_term() {
+        let spf = Spf::from_txt_strings(&["v=spf1 ip4:192.0", ".2.0/24 -all"]).unwrap();
+        assert_eq!(spf.ip4().unwrap()[0].raw(), "192.0.2.0/24");
+    }
+    #[test]
+    fn empty_chunk() {
+        let err = Spf::from_txt_strings(&["v=spf1 a", "", " -all"]).unwrap_err();
+        assert_eq!(err.inner(), &SpfError::EmptyTxtChunk { index: 1 });
+    }
+    #[test]
+    fn joined_record_longer_than_255() {
+        let first = format!("v=spf1 {}", "ip4:192.0.2.0/24 ".repeat(14));
+        let second = " include:_spf.example.com -all";
+        assert_eq!(first.len() + second.len() > 255, true);
+
+        let spf = Spf::from_txt_strings(&[first.trim_end(), second]).unwrap();
+        assert_eq!(spf.ip4().unwrap().len(), 14);
+    }
+    #[test]
+    fn chunk_longer_than_255() {
+        let first = format!("v=spf1 {}", "ip4:192.0.2.0/24 ".repeat(16));
+        let err = Spf::from_txt_strings(&[first.trim_end(), " -all"]).unwrap_err();
+        assert_eq!(err.inner(), &SpfError::SourceLengthExceeded);
+    }
+}
+#[cfg(test)]
+mod chunks {
+
+    use crate::spf::{Spf, SpfError};
+
+    // A record of `terms` terms whose lengths vary with `seed`.
+    fn record(terms: usize, seed: usize) -> Spf {
+        let mut spf = Spf::new_v1();
+        spf.set_max_length(usize::MAX);
+        for i in 0..terms {
+            let n = (i * 7 + seed * 13) % 29;
+            let term = match n % 4 {
+                0 => format!("include:_spf{}.{}example.com", i, "sub.".repeat(n % 5)),
+                1 => format!("ip4:192.0.{}.{}", n, i % 256),
+                2 => format!("-ip6:2001:db8:{:x}::/48", i + seed),
+                _ => format!("a:host{}.example.net/{}", i, 24 + n % 8),
+            };
+            spf.append_str(&term).unwrap();
+        }
+        spf.append_str("~all").unwrap();
+        spf
+    }
+
+    #[test]
+    fn concatenated_chunks_reparse() {
+        for seed in 0..8 {
+            for terms in [0, 1, 5, 12, 30] {
+                let spf = record(terms, seed);
+                for max in [45, 64, 100, 180, 255] {
+                    let chunks = spf.to_txt_chunks(max).unwrap();
+                    for (index, chunk) in chunks.iter().enumerate() {
+                        assert_eq!(chunk.len() <= max, true);
+                        assert_eq!(chunk.ends_with(' '), false);
+                        assert_eq!(chunk.starts_with(' '), index > 0);
+                    }
+                    assert_eq!(chunks.concat(), spf.to_string());
+                    let chunks: Vec<&str> = chunks.iter().map(String::as_str).collect();
+                    assert_eq!(Spf::from_txt_strings(&chunks).unwrap(), spf);
+                }
+            }
+        }
+    }
+    #[test]
+    fn fills_each_chunk() {
+        let spf: Spf = "v=spf1 a mx ptr -all".parse().unwrap();
+        assert_eq!(
+            spf.to_txt_chunks(11).unwrap(),
+            vec!["v=spf1 a mx", " ptr -all"]
+        );
+        assert_eq!(
+            spf.to_txt_chunks(5).unwrap_err(),
+            SpfError::SourceLengthExceeded
+        );
+    }
+    #[test]
+    fn term_too_long() {
+        let spf: Spf = "v=spf1 include:_spf.example.com -all".parse().unwrap();
+        assert_eq!(
+            spf.to_txt_chunks(24).unwrap_err(),
+            SpfError::SourceLengthExceeded
+        );
+        assert_eq!(spf.to_txt_chunks(25).unwrap().len(), 3);
+    }
+    #[test]
+    fn longer_than_255() {
+        let spf = record(40, 1);
+        assert_eq!(spf.to_string().len() > 255, true);
+        let chunks = spf.to_txt_chunks(1000).unwrap();
+        assert_eq!(chunks.len() > 1, true);
+        assert_eq!(chunks.iter().all(|chunk| chunk.len() <= 255), true);
+    }
+    #[test]
+    fn no_version() {
+        assert_eq!(Spf::new().to_txt_chunks(255).unwrap().is_empty(), true);
+    }
+}
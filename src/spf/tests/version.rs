@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::spf::{ParseOptions, Spf, SpfError};
+
+    #[test]
+    fn version_not_at_start() {
+        let err = "a mx v=spf1 -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::VersionNotAtStart { position: 2 });
+    }
+    #[test]
+    fn no_version() {
+        let err = "a mx -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::InvalidSource);
+    }
+    #[test]
+    fn version_twice() {
+        let err = "v=spf1 a v=spf1 -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::DuplicateVersion { position: 2 });
+        assert_eq!(err.is_duplicate_version(), true);
+    }
+    #[test]
+    fn version_twice_strict() {
+        let err = Spf::from_str_strict("v=spf1 a mx V=SPF1").unwrap_err();
+        assert_eq!(err.inner(), &SpfError::DuplicateVersion { position: 3 });
+    }
+    #[test]
+    fn version_in_domain_is_not_version() {
+        let input = "v=spf1 include:v=spf1.example.com -all";
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.version(), "v=spf1");
+        assert_eq!(spf.includes().unwrap()[0].raw(), "v=spf1.example.com");
+    }
+    #[test]
+    fn version_with_suffix() {
+        for input in ["v=spf1a mx -all", "v=spf10 -all", "v=spf1.example.com -all"] {
+            let err = input.parse::<Spf>().unwrap_err();
+            assert_eq!(err.is_invalid_version(), true);
+        }
+    }
+    #[test]
+    fn uppercase_version() {
+        let spf: Spf = "V=SPF1 a -all".parse().unwrap();
+        assert_eq!(spf.is_v1(), true);
+        assert_eq!(spf.version(), "v=spf1");
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+
+        let spf: Spf = "SPF2.0/PRA a".parse().unwrap();
+        assert_eq!(spf.version(), "spf2.0/pra");
+    }
+    #[test]
+    fn uppercase_version_preserved() {
+        let options = ParseOptions::new().preserve_case(true);
+        let spf = Spf::from_str_with_options("V=SPF1 a -all", &options).unwrap();
+        assert_eq!(spf.is_v1(), true);
+        assert_eq!(spf.to_string(), "V=SPF1 a -all");
+    }
+    #[test]
+    fn near_miss_versions() {
+        for version in ["v=spf2", "v=spf1.0", "spf1", "V=SPF", "spf2.0pra"] {
+            let err = format!("{} a -all", version).parse::<Spf>().unwrap_err();
+            assert_eq!(err, SpfError::InvalidVersion(version.to_string()));
+        }
+    }
+    #[test]
+    fn not_a_version() {
+        let err = "a mx -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err, SpfError::InvalidSource);
+    }
+    #[test]
+    fn malformed_spf2_scope() {
+        for input in ["spf2.0/ a", "spf2.0/pra, a", "spf2.0/pra,,mfrom a"] {
+            let err = input.parse::<Spf>().unwrap_err();
+            assert_eq!(err, SpfError::InvalidScope("".to_string()));
+        }
+        let spf: Spf = "spf2.0/mfrom,pra a".parse().unwrap();
+        assert_eq!(spf.is_v2(), true);
+    }
+    #[test]
+    fn terms_containing_version() {
+        let input = "v=spf1 a:v=spf1.example.com exists:spf2.0.example.com -all x-v=v=spf1";
+        let spf: Spf = input.parse().unwrap();
+        assert_eq!(spf.version(), "v=spf1");
+        assert_eq!(spf.a().unwrap()[0].raw(), "v=spf1.example.com");
+        assert_eq!(spf.exists().unwrap()[0].raw(), "spf2.0.example.com");
+        assert_eq!(
+            spf.unknown_modifiers().unwrap(),
+            &vec![("x-v".to_string(), "v=spf1".to_string())]
+        );
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn spf2_version_term_later() {
+        let err = "spf2.0/pra a spf2.0/mfrom".parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::DuplicateVersion { position: 2 });
+    }
+}
+#[cfg(test)]
+mod typed {
+
+    use crate::spf::{ParseOptions, Scope, Spf, SpfError, Version};
+
+    fn every_version() -> Vec<(&'static str, Version)> {
+        vec![
+            ("v=spf1", Version::V1),
+            ("spf2.0/pra", Version::V2(vec![Scope::Pra])),
+            ("spf2.0/mfrom", Version::V2(vec![Scope::MFrom])),
+            (
+                "spf2.0/pra,mfrom",
+                Version::V2(vec![Scope::Pra, Scope::MFrom]),
+            ),
+            (
+                "spf2.0/mfrom,pra",
+                Version::V2(vec![Scope::MFrom, Scope::Pra]),
+            ),
+        ]
+    }
+
+    #[test]
+    fn round_trip() {
+        for (text, version) in every_version() {
+            let input = format!("{} a -all", text);
+            let spf: Spf = input.parse().unwrap();
+            assert_eq!(spf.version_kind(), Some(&version));
+            assert_eq!(spf.version(), text);
+            assert_eq!(spf.to_string(), input);
+            let reparsed: Spf = spf.to_string().parse().unwrap();
+            assert_eq!(reparsed, spf);
+        }
+    }
+    #[test]
+    fn version_round_trip() {
+        for (text, version) in every_version() {
+            assert_eq!(version.to_string(), text);
+            assert_eq!(text.parse::<Version>().unwrap(), version);
+            assert_eq!(
+                text.to_ascii_uppercase().parse::<Version>().unwrap(),
+                version
+            );
+        }
+    }
+    #[test]
+    fn set_version() {
+        for (text, version) in every_version() {
+            let mut spf: Spf = "v=spf1 mx -all".parse().unwrap();
+            spf.set_version(version.clone()).unwrap();
+            assert_eq!(spf.version_kind(), Some(&version));
+            assert_eq!(spf.is_v1(), version.is_v1());
+            assert_eq!(spf.is_v2(), version.is_v2());
+            assert_eq!(spf.to_string(), format!("{} mx -all", text));
+        }
+    }
+    #[test]
+    fn set_helpers() {
+        let mut spf = Spf::new();
+        for (text, version) in every_version() {
+            match text {
+                "v=spf1" => spf.set_v1(),
+                "spf2.0/pra" => spf.set_v2_pra(),
+                "spf2.0/mfrom" => spf.set_v2_mfrom(),
+                "spf2.0/pra,mfrom" => spf.set_v2_pra_mfrom(),
+                _ => spf.set_v2_mfrom_pra(),
+            }
+            assert_eq!(spf.version_kind(), Some(&version));
+            assert_eq!(spf.version(), text);
+        }
+    }
+    #[test]
+    fn set_invalid_version() {
+        let mut spf = Spf::new_v1();
+        assert_eq!(
+            spf.set_version(Version::V2(vec![])).unwrap_err(),
+            SpfError::InvalidScope("".to_string())
+        );
+        assert_eq!(
+            spf.set_version(Version::V2(vec![Scope::MFrom, Scope::MFrom]))
+                .unwrap_err(),
+            SpfError::InvalidScope("mfrom".to_string())
+        );
+        assert_eq!(spf.version_kind(), Some(&Version::V1));
+    }
+    #[test]
+    fn no_version() {
+        let spf = Spf::new();
+        assert_eq!(spf.version_kind(), None);
+        assert_eq!(spf.version(), "");
+        assert_eq!(spf.is_v1(), false);
+        assert_eq!(spf.is_v2(), false);
+        assert_eq!(spf.scopes(), None);
+    }
+    #[test]
+    fn preserved_case() {
+        let options = ParseOptions::new().preserve_case(true);
+        let spf = Spf::from_str_with_options("SPF2.0/MFrom,PRA a -all", &options).unwrap();
+        assert_eq!(
+            spf.version_kind(),
+            Some(&Version::V2(vec![Scope::MFrom, Scope::Pra]))
+        );
+        assert_eq!(spf.to_string(), "SPF2.0/MFrom,PRA a -all");
+        let lower: Spf = "spf2.0/mfrom,pra a -all".parse().unwrap();
+        assert_eq!(spf, lower);
+    }
+}
@@ -130,8 +130,7 @@ fn invalid_exists() {
 #[test]
 fn multiple_errors() {
     let input = "v=spf1 a:ex.t/23 mx:test.e -all";
-    let err = vec!["ex.t/23", "test.e"];
-    let mut i = 0;
+    let err = ["ex.t/23", "test.e"];
 
     let spf: Spf = input.parse().unwrap();
 
@@ -139,9 +138,8 @@ fn multiple_errors() {
     assert_eq!(spf.has_warnings(), true);
     assert_eq!(spf.warnings().unwrap().len(), 2);
     let warnings = spf.warnings().unwrap().iter();
-    for warning in warnings {
+    for (i, warning) in warnings.enumerate() {
         assert_eq!(warning, err[i]);
-        i = i + 1;
     }
     assert_eq!(spf.warnings.unwrap()[0], "ex.t/23");
 }
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod parse {
+
+    use crate::spf::{ParseOptions, Spf, SpfError, Warning};
+
+    #[test]
+    fn default_rejects_extra_whitespace() {
+        let err = "v=spf1  a -all".parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::WhiteSpaceSyntaxError);
+        let err = "v=spf1 a -all ".parse::<Spf>().unwrap_err();
+        assert_eq!(err.inner(), &SpfError::WhiteSpaceSyntaxError);
+    }
+    #[test]
+    fn lenient_consecutive() {
+        let options = ParseOptions::new().lenient_whitespace(true);
+        let spf = Spf::from_str_with_options("v=spf1 a  mx   -all", &options).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a mx -all");
+        assert_eq!(
+            spf.parse_warnings().unwrap(),
+            &vec![
+                Warning::ExtraWhitespace { position: 8 },
+                Warning::ExtraWhitespace { position: 12 }
+            ]
+        );
+    }
+    #[test]
+    fn lenient_leading_and_trailing() {
+        let options = ParseOptions::new().lenient_whitespace(true);
+        let spf = Spf::from_str_with_options("  v=spf1 a -all\t", &options).unwrap();
+        assert_eq!(spf.version(), "v=spf1");
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+        let warnings = spf.parse_warnings().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0], Warning::ExtraWhitespace { position: 0 });
+        assert_eq!(warnings[1], Warning::ExtraWhitespace { position: 15 });
+    }
+    #[test]
+    fn lenient_clean_record_has_no_warnings() {
+        let options = ParseOptions::new().lenient_whitespace(true);
+        let spf = Spf::from_str_with_options("v=spf1 a -all", &options).unwrap();
+        assert_eq!(spf.parse_warnings().is_none(), true);
+    }
+}
@@ -1,9 +1,18 @@
 mod tests;
 use crate::helpers;
-use crate::spf::{Spf, SpfError};
-#[allow(dead_code)]
+use crate::mechanism::{MacroContext, MacroError, Mechanism};
+use crate::spf::{Spf, SpfError, Warning};
+use std::net::{IpAddr, Ipv4Addr};
+
+/// The rules an Spf record is validated against. See [`Spf::validate()`](crate::Spf::validate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SpfRfcStandard {
+    /// RFC 4408, the original experimental standard. `redirect=` may not be used with `all`.
     Rfc4408,
+    /// RFC 7208, the current standard. `redirect=` may be used with `all`, and is then
+    /// ignored. The macros of each domain-spec, including that of `exp=`, are checked.
+    /// Both standards limit a record to 10 DNS lookups and accept `ptr`.
+    Rfc7208,
 }
 
 #[derive(Debug)]
@@ -23,57 +32,114 @@ impl<'a> std::fmt::Display for SpfValidationResult<'a> {
 
 #[allow(dead_code)]
 pub(crate) fn check_start_of_spf(spf_string: &str) -> Result<(), SpfError> {
-    if !spf_string.starts_with("v=spf1") && !spf_string.starts_with("spf2.0") {
+    let first = spf_string
+        .split(char::is_whitespace)
+        .next()
+        .unwrap_or_default();
+    if !helpers::is_version_term(first) {
         return Err(SpfError::InvalidSource);
     };
     Ok(())
 }
 
-pub(crate) fn check_lookup_count(spf: &Spf) -> usize {
-    let mut lookup_count: usize = 0;
+// A parsed record may have contained more than one `all` mechanism.
+pub(crate) fn check_duplicate_all(spf: &Spf) -> Result<(), SpfError> {
+    if let Some(warnings) = spf.parse_warnings() {
+        for warning in warnings {
+            if let Warning::DuplicateAll { first, second } = warning {
+                return Err(SpfError::DuplicateAll {
+                    first: *first,
+                    second: *second,
+                });
+            }
+        }
+    }
+    Ok(())
+}
 
-    if spf.redirect().is_some() {
-        lookup_count += 1;
+// A modifier repeated in the source. Only recorded when parsing with lenient modifiers.
+pub(crate) fn check_duplicate_modifier(spf: &Spf) -> Result<(), SpfError> {
+    for warning in spf.parse_warnings().into_iter().flatten() {
+        if let Warning::DuplicateModifier { kind, .. } = warning {
+            return Err(SpfError::ModifierMayOccurOnlyOnce(*kind));
+        }
     }
-    if let Some(a) = spf.a() {
-        lookup_count += a.len();
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub(crate) fn validate_rfc4408(spf: &mut Spf) -> Result<&Spf, SpfError> {
+    check_duplicate_all(spf)?;
+    check_duplicate_modifier(spf)?;
+    if spf.redirect().is_some() && spf.all().is_some() {
+        return Err(SpfError::RedirectWithAllMechanism);
     }
-    if let Some(mx) = spf.mx() {
-        lookup_count += mx.len();
+    if spf.from_src {
+        if spf.source.len() > helpers::MAX_SPF_STRING_LENGTH {
+            return Err(SpfError::SourceLengthExceeded);
+        };
+        if helpers::spf_check_whitespace(&spf.source) {
+            return Err(SpfError::WhiteSpaceSyntaxError);
+        }
+    } else if spf.version_kind().is_none() {
+        return Err(SpfError::InvalidSource);
     }
-    if let Some(includes) = spf.includes() {
-        lookup_count += includes.len();
+    // RFC 4408 section 10.1 applies to every record, however it was created.
+    if spf.lookup_count() > 10 {
+        return Err(SpfError::LookupLimitExceeded);
     }
-    lookup_count
+    spf.was_validated = true;
+    spf.is_valid = true;
+    Ok(spf)
 }
 
-#[allow(dead_code)]
-pub(crate) fn validate_rfc4408(spf: &mut Spf) -> Result<&Spf, SpfError> {
-    if spf.from_src && spf.was_parsed {
-        return Ok(spf);
-    };
+pub(crate) fn validate_rfc7208(spf: &mut Spf) -> Result<&Spf, SpfError> {
+    check_duplicate_all(spf)?;
+    check_duplicate_modifier(spf)?;
     if spf.from_src {
         if spf.source.len() > helpers::MAX_SPF_STRING_LENGTH {
             return Err(SpfError::SourceLengthExceeded);
         };
-        if !helpers::spf_check_whitespace(&spf.to_string()) {
+        if helpers::spf_check_whitespace(&spf.source) {
             return Err(SpfError::WhiteSpaceSyntaxError);
         }
     } else {
-        if !spf.is_v1() && !spf.is_v2() {
+        if spf.version_kind().is_none() {
             return Err(SpfError::InvalidSource);
         }
-        // Rediect should be the only mechanism present. Any additional values are not permitted.
-        // This is wrong need to re-read rfc
-        if spf.redirect().is_some() && spf.all().is_some() {
-            return Err(SpfError::RedirectWithAllMechanism);
-        }
-        // Basic check of lookup limit
-        if check_lookup_count(spf) > 10 {
-            return Err(SpfError::LookupLimitExceeded);
-        }
     }
+    // RFC 7208 section 6.1 permits `all` with `redirect=`, which is then ignored.
+    // RFC 7208 section 4.6.4 applies to every record, however it was created.
+    if spf.lookup_count() > 10 {
+        return Err(SpfError::LookupLimitExceeded);
+    }
+    // RFC 7208 section 5.5 says `ptr` should not be used, but a record with it is still valid.
+    // See `validation_report()`.
+    check_macros(spf)?;
     spf.was_validated = true;
     spf.is_valid = true;
     Ok(spf)
 }
+
+// Each domain-spec with macros, including that of `exp=`, must expand. `%{c}`, `%{r}` and
+// `%{t}` may only be used in the explanation string. RFC 7208 section 7.3
+fn check_macros(spf: &Spf) -> Result<(), SpfError> {
+    match invalid_macros(spf).into_iter().next() {
+        Some((_, error)) => Err(SpfError::InvalidMacro(error)),
+        None => Ok(()),
+    }
+}
+
+// Each term whose domain-spec can not be expanded, with the reason.
+pub(crate) fn invalid_macros(spf: &Spf) -> Vec<(&Mechanism<String>, MacroError)> {
+    let context = MacroContext::new(
+        "postmaster@example.com",
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        "example.com",
+    );
+    let mechanisms = spf.iter().filter_map(|m| m.txt());
+    mechanisms
+        .filter(|m| m.has_macros())
+        .filter_map(|m| m.expand_macros(&context).err().map(|error| (m, error)))
+        .collect()
+}
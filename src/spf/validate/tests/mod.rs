@@ -2,7 +2,9 @@
 mod validate {
     use crate::mechanism::{Mechanism, Qualifier};
     use crate::spf::Spf;
+    use crate::spf::SpfError;
     use crate::spf::SpfRfcStandard;
+    use crate::spf::{FindingCode, ValidationOptions};
 
     #[test]
     fn validate() {
@@ -22,10 +24,9 @@ mod validate {
         );
         let res = spf.validate(SpfRfcStandard::Rfc4408);
         assert_eq!(res.is_ok(), true);
-        let res2 = res.unwrap();
-        assert_eq!(res2.is_valid(), true);
+        assert_eq!(spf.is_valid(), true);
         assert_eq!(
-            res2.to_string(),
+            spf.to_string(),
             "v=spf1 ip4:203.32.160.0/23 ip6:2001:5160:4000::/36".to_string()
         );
     }
@@ -50,4 +51,90 @@ mod validate {
         let res2 = res.unwrap_err();
         assert_eq!(res2.to_string(), "Source string not valid.".to_string());
     }
+    #[test]
+    fn duplicate_all() {
+        let mut spf: Spf = "v=spf1 a ~all -all".parse().unwrap();
+        let err = spf.validate(SpfRfcStandard::Rfc4408).unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::DuplicateAll {
+                first: 2,
+                second: 3
+            }
+        );
+        #[allow(deprecated)]
+        let err = spf.try_validate().unwrap_err();
+        assert_eq!(err.is_duplicate_all(), true);
+    }
+    #[test]
+    fn duplicate_all_forgotten_once_modified() {
+        let mut spf: Spf = "v=spf1 a ~all -all".parse().unwrap();
+        spf.set_all(Mechanism::all(Qualifier::Fail)).unwrap();
+        assert_eq!(spf.parse_warnings(), None);
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+        let mut spf: Spf = "v=spf1 a ~all -all".parse().unwrap();
+        spf.take_all();
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+        let report = spf.validation_report(&ValidationOptions::new());
+        assert_eq!(report.codes().contains(&FindingCode::DuplicateAll), false);
+    }
+    #[test]
+    fn redirect_with_all_under_each_standard() {
+        let mut spf: Spf = "v=spf1 a -all redirect=_spf.example.com".parse().unwrap();
+        assert_eq!(
+            spf.validate(SpfRfcStandard::Rfc4408),
+            Err(SpfError::RedirectWithAllMechanism)
+        );
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+        let mut spf = Spf::new_v1();
+        spf.append_mechanism(Mechanism::all(Qualifier::Fail));
+        spf.append_mechanism(Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap());
+        let err = spf.validate(SpfRfcStandard::Rfc4408).unwrap_err();
+        assert_eq!(err.is_redirect_with_all_mechanism(), true);
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+    }
+    #[test]
+    fn ptr_under_each_standard() {
+        let mut spf: Spf = "v=spf1 ptr ptr:example.com mx -all".parse().unwrap();
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc4408), Ok(()));
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+        assert_eq!(spf.is_valid(), true);
+        let mut spf: Spf = "v=spf1 exists:%{p}.example.com -all".parse().unwrap();
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc4408), Ok(()));
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+    }
+    #[test]
+    fn lookup_limit_of_parsed_record() {
+        let record = "v=spf1 a mx include:a.example.com include:b.example.com \
+                      include:c.example.com include:d.example.com include:e.example.com \
+                      include:f.example.com include:g.example.com include:h.example.com \
+                      include:i.example.com -all";
+        let mut spf: Spf = record.parse().unwrap();
+        assert_eq!(spf.lookup_count(), 11);
+        assert_eq!(
+            spf.validate(SpfRfcStandard::Rfc4408),
+            Err(SpfError::LookupLimitExceeded)
+        );
+        assert_eq!(
+            spf.validate(SpfRfcStandard::Rfc7208),
+            Err(SpfError::LookupLimitExceeded)
+        );
+        assert_eq!(spf.is_valid(), false);
+    }
+    #[test]
+    fn macros_of_exp() {
+        let mut spf: Spf = "v=spf1 mx -all exp=%{c}.example.com".parse().unwrap();
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc4408), Ok(()));
+        let err = spf.validate(SpfRfcStandard::Rfc7208).unwrap_err();
+        assert_eq!(err.is_invalid_macro(), true);
+        let mut spf: Spf = "v=spf1 mx -all exp=explain.%{d}".parse().unwrap();
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208), Ok(()));
+        assert_eq!(spf.is_valid(), true);
+    }
+    #[test]
+    fn invalid_is_not_marked_valid() {
+        let mut spf: Spf = "v=spf1 exists:%{c}.example.com -all".parse().unwrap();
+        assert_eq!(spf.validate(SpfRfcStandard::Rfc7208).is_err(), true);
+        assert_eq!(spf.is_valid(), false);
+    }
 }
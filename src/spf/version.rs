@@ -0,0 +1,119 @@
+//! The version of an Spf record. See RFC 7208 and RFC 4406.
+use crate::helpers;
+use crate::spf::scope::{self, Scope};
+use crate::spf::SpfError;
+use std::str::FromStr;
+
+/// The version which starts an Spf record. `v=spf1` or `spf2.0/` followed by its scopes.
+///
+/// # Example:
+///```rust
+/// use decon_spf::{Scope, Version};
+/// let version: Version = "spf2.0/mfrom,pra".parse().unwrap();
+/// assert_eq!(version, Version::V2(vec![Scope::MFrom, Scope::Pra]));
+/// assert_eq!(version.to_string(), "spf2.0/mfrom,pra");
+/// assert_eq!("V=SPF1".parse::<Version>().unwrap(), Version::V1);
+///```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Version {
+    /// `v=spf1`
+    V1,
+    /// `spf2.0/` with its scopes in the order they are written.
+    V2(Vec<Scope>),
+}
+
+impl Version {
+    /// Returns `true` for `v=spf1`
+    pub fn is_v1(&self) -> bool {
+        matches!(self, Version::V1)
+    }
+    /// Returns `true` for `spf2.0`
+    pub fn is_v2(&self) -> bool {
+        matches!(self, Version::V2(_))
+    }
+    /// Returns the scopes of `spf2.0`. Returns `None` for `v=spf1`.
+    pub fn scopes(&self) -> Option<&[Scope]> {
+        match self {
+            Version::V1 => None,
+            Version::V2(scopes) => Some(scopes),
+        }
+    }
+    // An `spf2.0` version needs at least one scope and may hold each scope only once.
+    pub(crate) fn check(&self) -> Result<(), SpfError> {
+        let scopes = match self {
+            Version::V1 => return Ok(()),
+            Version::V2(scopes) => scopes,
+        };
+        if scopes.is_empty() {
+            return Err(SpfError::InvalidScope(String::new()));
+        }
+        for (index, scope) in scopes.iter().enumerate() {
+            if scopes[..index].contains(scope) {
+                return Err(SpfError::InvalidScope(scope.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Version::V1 => write!(f, "v=spf1"),
+            Version::V2(scopes) => {
+                let scopes: Vec<&str> = scopes.iter().map(Scope::as_str).collect();
+                write!(f, "spf2.0/{}", scopes.join(","))
+            }
+        }
+    }
+}
+
+/// The version is case-insensitive.
+///
+/// # Errors:
+/// - [`InvalidScope`](SpfError::InvalidScope) if a scope of `spf2.0` is not known or is
+///   repeated.
+/// - [`InvalidVersion`](SpfError::InvalidVersion) for anything else which is not a version.
+impl FromStr for Version {
+    type Err = SpfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("v=spf1") {
+            return Ok(Version::V1);
+        }
+        match helpers::strip_prefix_ignore_case(s, "spf2.0/") {
+            Some(scopes) => Ok(Version::V2(scope::parse_scopes(scopes)?)),
+            None => Err(SpfError::InvalidVersion(s.to_string())),
+        }
+    }
+}
+
+#[test]
+fn version_from_str() {
+    assert_eq!("v=spf1".parse::<Version>().unwrap(), Version::V1);
+    assert_eq!(
+        "SPF2.0/PRA".parse::<Version>().unwrap(),
+        Version::V2(vec![Scope::Pra])
+    );
+    assert_eq!(
+        "spf2.0/helo".parse::<Version>().unwrap_err(),
+        SpfError::InvalidScope("helo".to_string())
+    );
+    assert_eq!(
+        "v=spf10".parse::<Version>().unwrap_err(),
+        SpfError::InvalidVersion("v=spf10".to_string())
+    );
+}
+#[test]
+fn version_check() {
+    assert_eq!(Version::V1.check(), Ok(()));
+    assert_eq!(Version::V2(vec![Scope::MFrom, Scope::Pra]).check(), Ok(()));
+    assert_eq!(
+        Version::V2(vec![]).check(),
+        Err(SpfError::InvalidScope("".to_string()))
+    );
+    assert_eq!(
+        Version::V2(vec![Scope::Pra, Scope::Pra]).check(),
+        Err(SpfError::InvalidScope("pra".to_string()))
+    );
+}
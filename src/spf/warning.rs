@@ -0,0 +1,206 @@
+//! Structured warnings recorded while parsing an Spf record.
+use crate::mechanism::Kind;
+use ipnetwork::IpNetwork;
+
+/// A problem found while parsing which did not prevent the [`Spf`](crate::Spf) from being
+/// created. See [`Spf::parse_warnings()`](crate::Spf::parse_warnings)
+///
+/// Unless stated otherwise, positions are the zero based index of a term within the source
+/// record. The version string is always at position `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// More than one `all` mechanism is present. Only the first `all` is kept.
+    DuplicateAll {
+        /// Position of the first `all` term.
+        first: usize,
+        /// Position of the repeated `all` term.
+        second: usize,
+    },
+    /// `redirect=` or `exp=` appears more than once. Only the first is kept. Only recorded
+    /// when parsing with
+    /// [`ParseOptions::lenient_modifiers`](crate::ParseOptions::lenient_modifiers)
+    DuplicateModifier {
+        /// The kind of modifier.
+        kind: Kind,
+        /// Position of the first modifier.
+        first: usize,
+        /// Position of the repeated modifier.
+        second: usize,
+    },
+    /// A mechanism follows the `all` mechanism and can never be evaluated.
+    MechanismAfterAll {
+        /// Position of the `all` term.
+        all: usize,
+        /// Position of the mechanism which follows `all`.
+        position: usize,
+    },
+    /// Whitespace other than a single space between terms. Only recorded when parsing with
+    /// [`ParseOptions::lenient_whitespace`](crate::ParseOptions::lenient_whitespace)
+    ExtraWhitespace {
+        /// Byte offset of the start of the whitespace within the source.
+        position: usize,
+    },
+    /// A modifier was written with a qualifier. The qualifier has been removed.
+    QualifierOnModifier {
+        /// The kind of modifier.
+        kind: Kind,
+        /// Position of the modifier.
+        position: usize,
+    },
+    /// An `ip4` or `ip6` network has bits set below its prefix length. e.g. `192.168.1.17/24`
+    /// The network is rewritten when parsing with
+    /// [`ParseOptions::normalize_networks`](crate::ParseOptions::normalize_networks)
+    HostBitsSet {
+        /// The network which is actually matched.
+        network: IpNetwork,
+        /// Position of the mechanism.
+        position: usize,
+    },
+    /// A record made by [`Spf::flatten()`](crate::Spf::flatten) is longer than 255
+    /// characters. It must be published as more than one character-string. See
+    /// [`Spf::to_txt_chunks()`](crate::Spf::to_txt_chunks)
+    RecordTooLong {
+        /// The length of the record.
+        length: usize,
+    },
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::DuplicateAll { first, second } => write!(
+                f,
+                "Duplicate 'all' mechanism at term {}. First seen at term {}.",
+                second, first
+            ),
+            Warning::DuplicateModifier {
+                kind,
+                first,
+                second,
+            } => write!(
+                f,
+                "Duplicate modifier {} at term {}. First seen at term {}.",
+                kind.as_str(),
+                second,
+                first
+            ),
+            Warning::MechanismAfterAll { all, position } => write!(
+                f,
+                "Mechanism at term {} follows 'all' at term {} and can never be evaluated.",
+                position, all
+            ),
+            Warning::ExtraWhitespace { position } => {
+                write!(f, "Extra whitespace at offset {}.", position)
+            }
+            Warning::QualifierOnModifier { kind, position } => write!(
+                f,
+                "Modifier {} at term {} may not have a qualifier. The qualifier was removed.",
+                kind.as_str(),
+                position
+            ),
+            Warning::HostBitsSet { network, position } => write!(
+                f,
+                "Network at term {} has host bits set. The effective network is {}.",
+                position, network
+            ),
+            Warning::RecordTooLong { length } => {
+                write!(f, "Record is {} characters, longer than 255.", length)
+            }
+        }
+    }
+}
+
+impl Warning {
+    /// Returns `true` if the warning indicates more than one `all` mechanism.
+    pub fn is_duplicate_all(&self) -> bool {
+        matches!(self, Self::DuplicateAll { .. })
+    }
+    /// Returns `true` if the warning indicates a repeated `redirect=` or `exp=`.
+    pub fn is_duplicate_modifier(&self) -> bool {
+        matches!(self, Self::DuplicateModifier { .. })
+    }
+    /// Returns `true` if the warning indicates a mechanism follows `all`.
+    pub fn is_mechanism_after_all(&self) -> bool {
+        matches!(self, Self::MechanismAfterAll { .. })
+    }
+    /// Returns `true` if the warning indicates extra whitespace.
+    pub fn is_extra_whitespace(&self) -> bool {
+        matches!(self, Self::ExtraWhitespace { .. })
+    }
+    /// Returns `true` if the warning indicates a qualifier was removed from a modifier.
+    pub fn is_qualifier_on_modifier(&self) -> bool {
+        matches!(self, Self::QualifierOnModifier { .. })
+    }
+    /// Returns `true` if the warning indicates a network with host bits set.
+    pub fn is_host_bits_set(&self) -> bool {
+        matches!(self, Self::HostBitsSet { .. })
+    }
+}
+
+#[test]
+fn duplicate_all() {
+    let warning = Warning::DuplicateAll {
+        first: 2,
+        second: 3,
+    };
+    assert_eq!(warning.is_duplicate_all(), true);
+    assert_eq!(
+        warning.to_string(),
+        "Duplicate 'all' mechanism at term 3. First seen at term 2."
+    );
+}
+#[test]
+fn duplicate_modifier() {
+    let warning = Warning::DuplicateModifier {
+        kind: Kind::Exp,
+        first: 2,
+        second: 3,
+    };
+    assert_eq!(warning.is_duplicate_modifier(), true);
+    assert_eq!(
+        warning.to_string(),
+        "Duplicate modifier exp= at term 3. First seen at term 2."
+    );
+}
+#[test]
+fn extra_whitespace() {
+    let warning = Warning::ExtraWhitespace { position: 6 };
+    assert_eq!(warning.is_extra_whitespace(), true);
+    assert_eq!(warning.to_string(), "Extra whitespace at offset 6.");
+}
+#[test]
+fn mechanism_after_all() {
+    let warning = Warning::MechanismAfterAll {
+        all: 1,
+        position: 2,
+    };
+    assert_eq!(warning.is_mechanism_after_all(), true);
+    assert_eq!(
+        warning.to_string(),
+        "Mechanism at term 2 follows 'all' at term 1 and can never be evaluated."
+    );
+}
+#[test]
+fn qualifier_on_modifier() {
+    let warning = Warning::QualifierOnModifier {
+        kind: Kind::Redirect,
+        position: 2,
+    };
+    assert_eq!(warning.is_qualifier_on_modifier(), true);
+    assert_eq!(
+        warning.to_string(),
+        "Modifier redirect= at term 2 may not have a qualifier. The qualifier was removed."
+    );
+}
+#[test]
+fn host_bits_set() {
+    let warning = Warning::HostBitsSet {
+        network: "192.168.1.0/24".parse().unwrap(),
+        position: 1,
+    };
+    assert_eq!(warning.is_host_bits_set(), true);
+    assert_eq!(
+        warning.to_string(),
+        "Network at term 1 has host bits set. The effective network is 192.168.1.0/24."
+    );
+}